@@ -69,7 +69,7 @@ async fn main() -> Result<(), anyhow::Error> {
     };
 
     info!("connecting to database");
-    let database = Database::connect(&config.database.url).await?;
+    let database = Database::connect(config.database.connect_options()).await?;
     info!("database connection established");
 
     match cli.command {
@@ -4,14 +4,16 @@ use bollard::Docker;
 use common::{config, hash, s3};
 use db::{
     build_session::{self, ProcessedBuildSession},
-    build_session_token, code,
+    build_session_cancellation, build_session_token, code,
     sea_query::{LockBehavior, LockType, OnConflict},
-    source_code, ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr,
-    EntityTrait, QueryFilter, QuerySelect, TransactionErrorExt, TransactionTrait,
+    source_code, ActiveValue, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, DbErr, EntityTrait, QueryFilter, QuerySelect, SelectExt,
+    TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
 use futures_util::{pin_mut, StreamExt, TryFutureExt};
 use itertools::Itertools;
+use serde::de::IgnoredAny;
 use tokio::{sync::mpsc::UnboundedSender, time::timeout};
 use tracing::{debug, error, instrument};
 
@@ -28,6 +30,14 @@ use super::{
 /// [`Duration`] between each failed build session fetch attempt.
 const UPDATE_PERIOD: Duration = Duration::from_secs(5);
 
+/// Duration after which a claimed build session that is not
+/// locked by any build worker can be claimed again.
+const CLAIM_LIFESPAN: db::Duration = db::Duration::minutes(1);
+
+/// Text appended to the last stored log entry of a build
+/// session that exceeded the log size limit.
+const LOG_TRUNCATION_MARKER: &str = "\n[Log size limit exceeded, further output is truncated]\n";
+
 /// Worker errors, which are usually caused by the deployment environment itself.
 ///
 /// Such errors indicate that an error is not constrained to a single build session,
@@ -55,6 +65,18 @@ pub(crate) async fn spawn(
     log_sender: UnboundedSender<LogEntry>,
 ) {
     loop {
+        let id = match claim(&db).await {
+            Ok(Some(id)) => id,
+            Ok(None) => {
+                tokio::time::sleep(UPDATE_PERIOD).await;
+                continue;
+            }
+            Err(error) => {
+                error!(%error, "worker error");
+                continue;
+            }
+        };
+
         let outcome = db
             .transaction::<_, _, WorkerError>(|txn| {
                 let builder_config = builder_config.clone();
@@ -70,11 +92,14 @@ pub(crate) async fn spawn(
                             build_session::Column::Id,
                             build_session::Column::SourceCodeId,
                             build_session::Column::CargoContractVersion,
+                            build_session::Column::ExpectedCodeHash,
+                            build_session::Column::DryRun,
                         ])
+                        .filter(build_session::Column::Id.eq(id))
                         .filter(build_session::Column::Status.eq(build_session::Status::New));
 
-                    // Skip any locked build sessions to handle the build session
-                    // table as a queue.
+                    // Build session may be cancelled or claimed again by another
+                    // build worker in the meantime.
                     QuerySelect::query(&mut session_query)
                         .lock_with_behavior(LockType::NoKeyUpdate, LockBehavior::SkipLocked);
 
@@ -85,6 +110,7 @@ pub(crate) async fn spawn(
                     {
                         let mut wasm_buf = vec![0; builder_config.wasm_size_limit];
                         let mut metadata_buf = vec![0; builder_config.metadata_size_limit];
+                        let verification_log_sender = log_sender.clone();
 
                         let val = |wasm_buf, metadata_buf| async {
                             Instance::new(
@@ -96,71 +122,207 @@ pub(crate) async fn spawn(
                             )
                             .unarchive()
                             .await?
+                            .ensure_not_cancelled(txn)
+                            .await?
                             .build(log_sender, &supported_cargo_contract_versions)
                             .await?
+                            .ensure_not_cancelled(txn)
+                            .await?
                             .get_files(wasm_buf, metadata_buf)
                             .await
                         };
 
-                        match val(&mut wasm_buf, &mut metadata_buf).await {
-                            Ok((wasm, metadata)) => {
-                                let code_hash = hash::blake2(wasm);
-
-                                build_session::Entity::update_many()
-                                    .filter(build_session::Column::Id.eq(build_session.id))
-                                    .col_expr(
-                                        build_session::Column::Status,
-                                        build_session::Status::Completed.into(),
-                                    )
-                                    .col_expr(
-                                        build_session::Column::CodeHash,
-                                        (&code_hash[..]).into(),
-                                    )
-                                    .col_expr(build_session::Column::Metadata, metadata.into())
-                                    .exec(txn)
-                                    .await?;
-
-                                code::Entity::insert(code::ActiveModel {
-                                    hash: ActiveValue::Set(code_hash.to_vec()),
-                                    code: ActiveValue::Set(wasm.to_vec()),
-                                })
-                                .on_conflict(
-                                    OnConflict::column(code::Column::Hash)
-                                        .do_nothing()
-                                        .to_owned(),
-                                )
-                                .exec_without_returning(txn)
-                                .await?;
-                            }
-                            Err(_) => {
-                                build_session::Entity::update_many()
-                                    .filter(build_session::Column::Id.eq(build_session.id))
-                                    .col_expr(
-                                        build_session::Column::Status,
-                                        build_session::Status::Failed.into(),
-                                    )
-                                    .exec(txn)
-                                    .await?;
-                            }
-                        }
-
-                        Ok(false)
-                    } else {
-                        Ok(true)
+                        let files = val(&mut wasm_buf, &mut metadata_buf).await;
+
+                        save_result(txn, &build_session, files, &verification_log_sender).await?;
                     }
+
+                    Ok(())
                 })
             })
             .await
             .into_raw_result();
 
-        match outcome {
-            Ok(empty) if empty => tokio::time::sleep(UPDATE_PERIOD).await,
-            Err(error) => error!(%error, "worker error"),
-            _ => {}
+        if let Err(error) = outcome {
+            error!(%error, "worker error");
         }
     }
 }
 
+/// Claim a new build session by persisting its start time.
+///
+/// Claimed build sessions are locked by a build worker for the duration of
+/// their processing, thus unlocked build sessions that were claimed
+/// more than [`CLAIM_LIFESPAN`] ago are claimed again to recover
+/// from build worker failures.
+async fn claim(db: &DatabaseConnection) -> Result<Option<i64>, DbErr> {
+    db.transaction(|txn| {
+        Box::pin(async move {
+            let mut unclaimed = build_session::Entity::find()
+                .select_only()
+                .column(build_session::Column::Id)
+                .filter(build_session::Column::Status.eq(build_session::Status::New))
+                .filter(
+                    Condition::any()
+                        .add(build_session::Column::StartedAt.is_null())
+                        .add(build_session::Column::StartedAt.lt(db::now() - CLAIM_LIFESPAN)),
+                );
+
+            // Skip any locked build sessions to handle the build session
+            // table as a queue.
+            QuerySelect::query(&mut unclaimed)
+                .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+            let Some(id) = unclaimed.into_tuple::<i64>().one(txn).await? else {
+                return Ok(None);
+            };
+
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(id))
+                .col_expr(build_session::Column::StartedAt, db::now().into())
+                .exec(txn)
+                .await?;
+
+            Ok(Some(id))
+        })
+    })
+    .await
+    .into_raw_result()
+}
+
+/// Store the result of the build session processing.
+///
+/// Completed regular build sessions attach the resulting code to the code table,
+/// while dry-run build sessions only store the computed code hash.
+async fn save_result<C: ConnectionTrait>(
+    db: &C,
+    build_session: &ProcessedBuildSession,
+    files: Result<(&[u8], &[u8]), SessionError>,
+    log_sender: &UnboundedSender<LogEntry>,
+) -> Result<(), DbErr> {
+    let build_result = files.and_then(|(wasm, metadata)| {
+        let code_hash = hash::blake2(wasm);
+
+        // Dry-run build sessions report mismatches via the computed code hash.
+        if !build_session.dry_run {
+            verify_code_hash(build_session, &code_hash, log_sender)?;
+        }
+
+        Ok((wasm, metadata, code_hash))
+    });
+
+    match build_result {
+        Ok((_, _, code_hash)) if build_session.dry_run => {
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(build_session.id))
+                .col_expr(
+                    build_session::Column::Status,
+                    build_session::Status::Completed.into(),
+                )
+                .col_expr(
+                    build_session::Column::ComputedCodeHash,
+                    (&code_hash[..]).into(),
+                )
+                .exec(db)
+                .await?;
+        }
+        Ok((wasm, metadata, code_hash)) => {
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(build_session.id))
+                .col_expr(
+                    build_session::Column::Status,
+                    build_session::Status::Completed.into(),
+                )
+                .col_expr(build_session::Column::CodeHash, (&code_hash[..]).into())
+                .col_expr(build_session::Column::Metadata, metadata.into())
+                .exec(db)
+                .await?;
+
+            // Only valid JSON metadata is attached to the code,
+            // since it's served to clients as-is.
+            let code_metadata = serde_json::from_slice::<IgnoredAny>(metadata)
+                .ok()
+                .and_then(|_| String::from_utf8(metadata.to_vec()).ok());
+
+            let mut on_conflict = OnConflict::column(code::Column::Hash);
+
+            if code_metadata.is_some() {
+                // Update time is taken from the default value of the inserted row.
+                on_conflict.update_columns([code::Column::Metadata, code::Column::UpdatedAt]);
+            } else {
+                on_conflict.do_nothing();
+            }
+
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(code_hash.to_vec()),
+                code: ActiveValue::Set(wasm.to_vec()),
+                metadata: ActiveValue::Set(code_metadata),
+                ..Default::default()
+            })
+            .on_conflict(on_conflict)
+            .exec_without_returning(db)
+            .await?;
+        }
+        Err(SessionError::Cancelled) => {
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(build_session.id))
+                .col_expr(
+                    build_session::Column::Status,
+                    build_session::Status::Cancelled.into(),
+                )
+                .exec(db)
+                .await?;
+        }
+        Err(_) => {
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(build_session.id))
+                .col_expr(
+                    build_session::Column::Status,
+                    build_session::Status::Failed.into(),
+                )
+                .exec(db)
+                .await?;
+        }
+    }
+
+    // Cancellation may be requested after the last check.
+    build_session_cancellation::Entity::delete_by_id(build_session.id)
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Verify the resulting code hash against the one
+/// expected by the build session, if any.
+///
+/// Verification failure reason is reported to the build session logs.
+fn verify_code_hash(
+    build_session: &ProcessedBuildSession,
+    code_hash: &[u8],
+    log_sender: &UnboundedSender<LogEntry>,
+) -> Result<(), SessionError> {
+    match &build_session.expected_code_hash {
+        Some(expected_code_hash) if expected_code_hash.as_slice() != code_hash => {
+            let result = log_sender.send(LogEntry {
+                build_session_id: build_session.id,
+                text: format!(
+                    "Resulting code hash 0x{} doesn't match the on-chain code hash 0x{}.\n",
+                    hex::encode(code_hash),
+                    hex::encode(expected_code_hash)
+                ),
+            });
+
+            if let Err(e) = result {
+                error!(%e, "unable to send log entry")
+            }
+
+            Err(SessionError::CodeHashMismatch)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Build session errors, which are constrained down to a single container
 /// and are usually caused by an incorrect user input.
 #[derive(Debug, Display, Error, From)]
@@ -202,6 +364,37 @@ enum SessionError {
     /// Unsupported cargo-contract version.
     #[display(fmt = "unsupported cargo-contract version")]
     UnsupportedCargoContractVersion,
+
+    /// Resulting code hash doesn't match the expected on-chain code hash.
+    #[display(fmt = "code hash mismatch")]
+    CodeHashMismatch,
+
+    /// Build session was cancelled by a user.
+    #[display(fmt = "build session cancelled")]
+    Cancelled,
+}
+
+/// Ensure that the cancellation of the provided build session was not requested.
+///
+/// If it was, the provided [`Volume`] is closed to remove any partial build artifacts.
+async fn ensure_not_cancelled(
+    txn: &DatabaseTransaction,
+    build_session_id: i64,
+    volume: Volume,
+) -> Result<Volume, SessionError> {
+    let cancelled = build_session_cancellation::Entity::find_by_id(build_session_id)
+        .select_only()
+        .exists(txn)
+        .await?;
+
+    if cancelled {
+        debug!("build session was cancelled");
+
+        volume.close().await?;
+        return Err(SessionError::Cancelled);
+    }
+
+    Ok(volume)
 }
 
 /// Archived build session instance.
@@ -320,6 +513,14 @@ struct UnarchivedInstance<'a> {
 }
 
 impl<'a> UnarchivedInstance<'a> {
+    /// Stop processing the current build session instance, if its cancellation was requested.
+    async fn ensure_not_cancelled(self, txn: &DatabaseTransaction) -> Result<Self, SessionError> {
+        Ok(Self {
+            volume: ensure_not_cancelled(txn, self.build_session.id, self.volume).await?,
+            ..self
+        })
+    }
+
     /// Start build process for the current build session instance.
     #[instrument(skip(self, log_sender), fields(id = %self.build_session.id), err(level = "info"))]
     pub async fn build(
@@ -406,6 +607,14 @@ struct BuiltInstance<'a> {
 }
 
 impl<'a> BuiltInstance<'a> {
+    /// Stop processing the current build session instance, if its cancellation was requested.
+    async fn ensure_not_cancelled(self, txn: &DatabaseTransaction) -> Result<Self, SessionError> {
+        Ok(Self {
+            volume: ensure_not_cancelled(txn, self.build_session.id, self.volume).await?,
+            ..self
+        })
+    }
+
     /// Rename artifacts files and write them into the provided buffers.
     ///
     /// This methods returns an [`Err`] if the provided buffers are insufficient in size to write
@@ -521,14 +730,34 @@ async fn handle_session<'a>(
 
     pin_mut!(wait_future);
 
+    let mut log_size = 0;
+
     loop {
         tokio::select! {
             Some(chunk) = logs.next() => {
-                let text = chunk.into_iter()
+                // Keep draining container logs, even if they are not stored anymore.
+                if log_size > builder_config.log_size_limit {
+                    continue;
+                }
+
+                let mut text = chunk.into_iter()
                     .try_collect::<_, Vec<_>, _>()?
                     .into_iter()
                     .join("");
 
+                log_size += text.len();
+
+                if log_size > builder_config.log_size_limit {
+                    let mut remaining = builder_config.log_size_limit - (log_size - text.len());
+
+                    while !text.is_char_boundary(remaining) {
+                        remaining -= 1;
+                    }
+
+                    text.truncate(remaining);
+                    text.push_str(LOG_TRUNCATION_MARKER);
+                }
+
                 let result = log_sender.send(LogEntry {
                     build_session_id,
                     text
@@ -544,3 +773,103 @@ async fn handle_session<'a>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common::hash;
+    use db::{
+        build_session::{self, ProcessedBuildSession},
+        build_session_cancellation, code,
+        sea_orm::Schema,
+        source_code, user, ActiveValue, ConnectionTrait, Database, DatabaseConnection, EntityTrait,
+    };
+    use tokio::sync::mpsc;
+
+    async fn create_database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let builder = db.get_database_backend();
+        let schema = Schema::new(builder);
+
+        for statement in [
+            schema.create_table_from_entity(user::Entity),
+            schema.create_table_from_entity(code::Entity),
+            schema.create_table_from_entity(source_code::Entity),
+            schema.create_table_from_entity(build_session::Entity),
+            schema.create_table_from_entity(build_session_cancellation::Entity),
+        ] {
+            db.execute(builder.build(&statement)).await.unwrap();
+        }
+
+        db
+    }
+
+    #[tokio::test]
+    async fn dry_run() {
+        let db = create_database().await;
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            created_at: ActiveValue::Set(db::now()),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .expect("unable to create source code")
+        .last_insert_id;
+
+        let id = build_session::Entity::insert(build_session::ActiveModel {
+            source_code_id: ActiveValue::Set(source_code_id),
+            status: ActiveValue::Set(build_session::Status::New),
+            cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+            expected_code_hash: ActiveValue::Set(Some(vec![0; 32])),
+            dry_run: ActiveValue::Set(true),
+            created_at: ActiveValue::Set(db::now()),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .expect("unable to insert build session")
+        .last_insert_id;
+
+        let build_session = ProcessedBuildSession {
+            id,
+            source_code_id,
+            cargo_contract_version: String::from("3.0.0"),
+            expected_code_hash: Some(vec![0; 32]),
+            dry_run: true,
+        };
+
+        let (log_sender, mut log_receiver) = mpsc::unbounded_channel();
+
+        super::save_result(
+            &db,
+            &build_session,
+            Ok((&b"wasm"[..], &b"{}"[..])),
+            &log_sender,
+        )
+        .await
+        .expect("unable to save build result");
+
+        let model = build_session::Entity::find_by_id(id)
+            .one(&db)
+            .await
+            .unwrap()
+            .expect("build session is missing");
+
+        // Code hash mismatch doesn't fail dry-run build sessions.
+        assert_eq!(model.status, build_session::Status::Completed);
+        assert_eq!(
+            model.computed_code_hash,
+            Some(hash::blake2(b"wasm").to_vec())
+        );
+        assert_eq!(model.code_hash, None);
+        assert_eq!(model.metadata, None);
+
+        // Dry-run build sessions don't publish their code.
+        assert!(code::Entity::find().one(&db).await.unwrap().is_none());
+        assert!(log_receiver.try_recv().is_err());
+    }
+}
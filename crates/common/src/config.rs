@@ -1,12 +1,25 @@
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    net::{IpAddr, SocketAddr},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+};
 
 use byte_unit::{n_gib_bytes, n_mib_bytes};
 use figment::{
     providers::{Env, Format, Toml},
     Figment,
 };
+use ipnet::IpNet;
 use serde::Deserialize;
 
+#[cfg(feature = "database")]
+use std::time::Duration;
+
+#[cfg(feature = "database")]
+use db::ConnectOptions;
+
 #[cfg(feature = "logging")]
 use tracing_subscriber::filter::LevelFilter;
 
@@ -15,13 +28,250 @@ use tracing_subscriber::filter::LevelFilter;
 pub struct Database {
     /// Database URL string.
     pub url: String,
+
+    /// Read replica database URL string.
+    ///
+    /// Read-only API server routes are served using the primary database
+    /// if no read replica is configured.
+    #[serde(default)]
+    pub read_replica_url: Option<String>,
+
+    /// Maximum count of pooled database connections.
+    #[serde(default = "default_max_connections")]
+    pub max_connections: u32,
+
+    /// Minimum count of idle database connections kept in a pool.
+    #[serde(default = "default_min_connections")]
+    pub min_connections: u32,
+
+    /// Database connection timeout, in seconds.
+    #[serde(default = "default_connect_timeout")]
+    pub connect_timeout: u64,
+
+    /// Duration after which idle database connections are closed, in seconds.
+    #[serde(default = "default_idle_timeout")]
+    pub idle_timeout: u64,
+}
+
+#[cfg(feature = "database")]
+impl Database {
+    /// Create database [`ConnectOptions`] with the configured pool settings.
+    pub fn connect_options(&self) -> ConnectOptions {
+        self.pool_options(&self.url)
+    }
+
+    /// Create read replica database [`ConnectOptions`] with the configured pool settings.
+    ///
+    /// [`None`] if no read replica is configured.
+    pub fn read_replica_connect_options(&self) -> Option<ConnectOptions> {
+        self.read_replica_url
+            .as_ref()
+            .map(|url| self.pool_options(url))
+    }
+
+    /// Create [`ConnectOptions`] for the provided URL with the configured pool settings.
+    fn pool_options(&self, url: &str) -> ConnectOptions {
+        let mut options = ConnectOptions::new(url.to_owned());
+
+        options
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .connect_timeout(Duration::from_secs(self.connect_timeout))
+            .idle_timeout(Duration::from_secs(self.idle_timeout));
+
+        options
+    }
+}
+
+// Pool defaults are picked to handle event client bursts alongside API server load
+// on a single PostgreSQL instance with the default `max_connections` setting.
+fn default_max_connections() -> u32 {
+    20
+}
+
+fn default_min_connections() -> u32 {
+    1
+}
+
+fn default_connect_timeout() -> u64 {
+    10
+}
+
+fn default_idle_timeout() -> u64 {
+    600
 }
 
 /// HTTP server configuration.
 #[derive(Deserialize)]
 pub struct Server {
     /// Address, that HTTP server will listen on.
-    pub address: SocketAddr,
+    ///
+    /// Can not be combined with `host`, `port` or `socket_path`.
+    #[serde(default)]
+    pub address: Option<SocketAddr>,
+
+    /// IPv4 or IPv6 address, that HTTP server will listen on.
+    ///
+    /// Defaults to the IPv4 loopback address if only `port` is provided.
+    #[serde(default)]
+    pub host: Option<String>,
+
+    /// Port, that HTTP server will listen on.
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    /// Path to a unix socket, that HTTP server will listen on instead of a TCP address.
+    #[serde(default)]
+    pub socket_path: Option<PathBuf>,
+
+    /// Address, that Prometheus metrics will be served on.
+    ///
+    /// If not provided, metrics are served on the main address.
+    #[serde(default)]
+    pub metrics_address: Option<SocketAddr>,
+
+    /// Enable the `/graphql` endpoint.
+    #[serde(default = "default_graphql")]
+    pub graphql: bool,
+
+    /// Name of a header used to propagate request identifiers.
+    #[serde(default = "default_request_id_header")]
+    pub request_id_header: String,
+
+    /// Require at least one reachable RPC node for the `/ready` endpoint to succeed.
+    #[serde(default)]
+    pub ready_check_nodes: bool,
+
+    /// Run the event client inside of the API server process.
+    ///
+    /// Node watchers and event backfill are started for all registered nodes,
+    /// thus standalone event client processes are not required.
+    #[serde(default)]
+    pub embedded_event_client: bool,
+
+    /// Serve the API documentation page and the OpenAPI document.
+    #[serde(default = "default_docs")]
+    pub docs: bool,
+
+    /// Public URLs of the API server, which are listed in the OpenAPI document.
+    #[serde(default)]
+    pub openapi_servers: Vec<String>,
+
+    /// Duration for which build session creation requests with the same
+    /// `Idempotency-Key` header return the same build session, in seconds.
+    #[serde(default = "default_idempotency_key_lifespan")]
+    pub idempotency_key_lifespan: u64,
+
+    /// Allow webhooks to target loopback, private and link-local addresses.
+    ///
+    /// Webhook hosts must resolve to public addresses only by default.
+    #[serde(default)]
+    pub webhook_private_addresses: bool,
+
+    /// Apply pending database migrations on startup.
+    ///
+    /// If disabled, the API server refuses to start while migrations are pending.
+    #[serde(default)]
+    pub run_migrations: bool,
+}
+
+impl Server {
+    /// Determine the address, that HTTP server will listen on.
+    pub fn listen_address(&self) -> Result<ListenAddress, ListenAddressError> {
+        let tcp_options = self.host.is_some() || self.port.is_some();
+
+        if let Some(path) = &self.socket_path {
+            if self.address.is_some() || tcp_options {
+                return Err(ListenAddressError::Conflicting);
+            }
+
+            return Ok(ListenAddress::Unix(path.clone()));
+        }
+
+        if let Some(address) = self.address {
+            if tcp_options {
+                return Err(ListenAddressError::Conflicting);
+            }
+
+            return Ok(ListenAddress::Tcp(address));
+        }
+
+        let port = self.port.ok_or(ListenAddressError::Missing)?;
+
+        let ip = match &self.host {
+            // Allow IPv6 addresses to be enclosed in square brackets.
+            Some(host) => host
+                .strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host)
+                .parse()
+                .map_err(|_| ListenAddressError::InvalidHost(host.clone()))?,
+            None => IpAddr::from([127, 0, 0, 1]),
+        };
+
+        Ok(ListenAddress::Tcp(SocketAddr::new(ip, port)))
+    }
+}
+
+/// Address, that HTTP server will listen on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListenAddress {
+    /// TCP socket address.
+    Tcp(SocketAddr),
+
+    /// Unix socket path.
+    Unix(PathBuf),
+}
+
+/// Errors that may occur while determining the HTTP server listen address.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ListenAddressError {
+    /// Neither `address`, `port` nor `socket_path` were provided.
+    Missing,
+
+    /// Unix socket path was combined with a TCP address,
+    /// or `address` was combined with `host` and `port`.
+    Conflicting,
+
+    /// Provided host is not a valid IPv4 or IPv6 address.
+    InvalidHost(String),
+}
+
+impl Display for ListenAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(
+                f,
+                "server listen address is not configured, provide either address, port or socket_path"
+            ),
+            Self::Conflicting => write!(
+                f,
+                "conflicting server listen address options, use only one of address, host and port or socket_path"
+            ),
+            Self::InvalidHost(host) => {
+                write!(f, "server host \"{host}\" is not a valid IPv4 or IPv6 address")
+            }
+        }
+    }
+}
+
+impl Error for ListenAddressError {}
+
+fn default_graphql() -> bool {
+    true
+}
+
+fn default_docs() -> bool {
+    true
+}
+
+fn default_idempotency_key_lifespan() -> u64 {
+    // 24 hours.
+    86400
+}
+
+fn default_request_id_header() -> String {
+    String::from("x-request-id")
 }
 
 /// Implementation of [`serde`]'s deserializer for [`FromStr`] types.
@@ -43,6 +293,14 @@ pub struct Logging {
     /// Log level.
     #[serde(deserialize_with = "deserialize_from_str")]
     pub level: LevelFilter,
+
+    /// Log message format.
+    #[serde(default)]
+    pub format: LogFormat,
+
+    /// Whether to emit a single access log line for every handled API server request.
+    #[serde(default)]
+    pub access_log: bool,
 }
 
 #[cfg(feature = "logging")]
@@ -50,10 +308,394 @@ impl Default for Logging {
     fn default() -> Self {
         Self {
             level: LevelFilter::WARN,
+            format: LogFormat::default(),
+            access_log: false,
         }
     }
 }
 
+/// Log message format.
+#[cfg(feature = "logging")]
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Compact human-readable log lines.
+    #[default]
+    Compact,
+
+    /// Single JSON object per log line.
+    Json,
+}
+
+/// Authentication configuration.
+#[derive(Deserialize)]
+pub struct Authentication {
+    /// Authentication token lifespan, in seconds.
+    #[serde(default = "default_token_lifespan")]
+    pub token_lifespan: u64,
+
+    /// Interval between expired authentication token and login challenge cleanups, in seconds.
+    ///
+    /// Interval must be positive, which is checked during configuration loading.
+    #[serde(default = "default_token_cleanup_interval")]
+    pub token_cleanup_interval: NonZeroU64,
+
+    /// Keep deleted public keys as an audit trail instead of removing them.
+    ///
+    /// Soft-deleted public keys can not be used for authentication,
+    /// and their addresses can be verified again.
+    #[serde(default)]
+    pub soft_delete_public_keys: bool,
+
+    /// Groups of normally public API server routes that require authentication.
+    #[serde(default)]
+    pub protected_routes: Vec<RouteGroup>,
+}
+
+impl Default for Authentication {
+    fn default() -> Self {
+        Self {
+            token_lifespan: default_token_lifespan(),
+            token_cleanup_interval: default_token_cleanup_interval(),
+            soft_delete_public_keys: false,
+            protected_routes: Vec::new(),
+        }
+    }
+}
+
+/// Group of API server routes that are public by default.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteGroup {
+    /// Public contract information routes, nested under `/contracts`.
+    Contracts,
+
+    /// Single event routes, nested under `/events`.
+    Events,
+
+    /// Contract code routes, nested under `/codes`.
+    Codes,
+
+    /// Source code file routes, nested under `/files`.
+    Files,
+
+    /// Event subscription routes.
+    Subscriptions,
+
+    /// GraphQL API route.
+    Graphql,
+}
+
+fn default_token_lifespan() -> u64 {
+    // 12 weeks.
+    7_257_600
+}
+
+fn default_token_cleanup_interval() -> NonZeroU64 {
+    NonZeroU64::new(3600).unwrap()
+}
+
+/// Rate limiting configuration.
+#[derive(Deserialize)]
+pub struct RateLimit {
+    /// Maximum count of requests allowed during a single window.
+    #[serde(default = "default_rate_limit_requests")]
+    pub requests: u32,
+
+    /// Rate limiting window duration, in seconds.
+    #[serde(default = "default_rate_limit_window")]
+    pub window: u64,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            requests: default_rate_limit_requests(),
+            window: default_rate_limit_window(),
+        }
+    }
+}
+
+fn default_rate_limit_requests() -> u32 {
+    30
+}
+
+fn default_rate_limit_window() -> u64 {
+    60
+}
+
+/// Event retention configuration.
+#[derive(Deserialize)]
+pub struct EventRetention {
+    /// Maximum age of stored events, in days.
+    ///
+    /// If not provided, events are never pruned automatically.
+    #[serde(default)]
+    pub max_age: Option<u64>,
+
+    /// Interval between automatic event prunes, in seconds.
+    ///
+    /// Interval must be positive, which is checked during configuration loading.
+    #[serde(default = "default_event_prune_interval")]
+    pub prune_interval: NonZeroU64,
+
+    /// Maximum count of events deleted by a single statement.
+    #[serde(default = "default_event_prune_batch_size")]
+    pub batch_size: u64,
+}
+
+impl Default for EventRetention {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            prune_interval: default_event_prune_interval(),
+            batch_size: default_event_prune_batch_size(),
+        }
+    }
+}
+
+fn default_event_prune_interval() -> NonZeroU64 {
+    NonZeroU64::new(3600).unwrap()
+}
+
+fn default_event_prune_batch_size() -> u64 {
+    1000
+}
+
+/// Cross-origin resource sharing configuration.
+///
+/// Cross-origin requests are rejected unless at least one origin is allowed.
+/// A single `"*"` value can be used in any list to allow all values.
+#[derive(Deserialize)]
+pub struct Cors {
+    /// Origins allowed to make cross-origin requests.
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+
+    /// HTTP methods allowed for cross-origin requests.
+    #[serde(default = "default_cors_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+
+    /// HTTP headers allowed for cross-origin requests.
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+}
+
+impl Default for Cors {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: default_cors_allowed_methods(),
+            allowed_headers: default_cors_allowed_headers(),
+        }
+    }
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    ["GET", "POST", "PATCH", "DELETE"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    ["authorization", "content-type", "idempotency-key"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// HTTP response compression configuration.
+///
+/// Responses are compressed only if requested by a client with the `Accept-Encoding` header.
+#[derive(Deserialize)]
+pub struct Compression {
+    /// Compression level passed to the negotiated algorithm.
+    ///
+    /// If not provided, the default level of each algorithm is used.
+    /// Levels exceeding the maximum level of an algorithm are clamped.
+    #[serde(default)]
+    pub level: Option<u32>,
+
+    /// Minimum response body size to compress, in bytes.
+    #[serde(default = "default_compression_min_size")]
+    pub min_size: u16,
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self {
+            level: None,
+            min_size: default_compression_min_size(),
+        }
+    }
+}
+
+fn default_compression_min_size() -> u16 {
+    // Smaller responses do not benefit from compression overhead.
+    1024
+}
+
+/// RPC node retry configuration.
+///
+/// Failed RPC calls are retried with an exponential backoff, delays of which
+/// are partially randomized to spread retries of multiple clients.
+#[derive(Clone, Deserialize)]
+pub struct RpcRetry {
+    /// Delay before the first retry attempt, in milliseconds.
+    #[serde(default = "default_rpc_retry_initial_delay")]
+    pub initial_delay: u64,
+
+    /// Maximum delay between retry attempts, in milliseconds.
+    #[serde(default = "default_rpc_retry_max_delay")]
+    pub max_delay: u64,
+
+    /// Maximum count of attempts of a single RPC call.
+    ///
+    /// Reconnection attempts are not limited.
+    #[serde(default = "default_rpc_retry_max_attempts")]
+    pub max_attempts: u32,
+}
+
+impl Default for RpcRetry {
+    fn default() -> Self {
+        Self {
+            initial_delay: default_rpc_retry_initial_delay(),
+            max_delay: default_rpc_retry_max_delay(),
+            max_attempts: default_rpc_retry_max_attempts(),
+        }
+    }
+}
+
+fn default_rpc_retry_initial_delay() -> u64 {
+    500
+}
+
+fn default_rpc_retry_max_delay() -> u64 {
+    30000
+}
+
+fn default_rpc_retry_max_attempts() -> u32 {
+    5
+}
+
+/// Event client indexing configuration.
+///
+/// New blocks are received from an RPC node subscription as soon as they are
+/// finalized, thus these values only affect backfill jobs, which traverse
+/// historical block ranges.
+///
+/// Backfill jobs are checked every `poll_interval` seconds. Once a job is found,
+/// its block range is traversed in batches of up to `blocks_per_batch` blocks,
+/// events of which are inserted with a single database transaction. A batch is
+/// committed earlier if it already contains `max_events_per_batch` events,
+/// which bounds the transaction size for blocks with many contract events.
+///
+/// Low-traffic chains may use a longer poll interval, while busy chains
+/// may increase batch sizes to reduce the count of database transactions.
+///
+/// The `watch-all` subcommand and the embedded event client watch all registered nodes
+/// at the same time, and refuse to start if more than `max_concurrent_nodes` nodes are registered.
+///
+/// All values must be positive, which is checked during configuration loading.
+#[derive(Deserialize)]
+pub struct EventClient {
+    /// Delay between checks for new backfill jobs, in seconds.
+    #[serde(default = "default_event_client_poll_interval")]
+    pub poll_interval: NonZeroU64,
+
+    /// Maximum count of blocks traversed before discovered events are committed.
+    #[serde(default = "default_event_client_blocks_per_batch")]
+    pub blocks_per_batch: NonZeroU32,
+
+    /// Maximum count of events committed with a single database transaction.
+    ///
+    /// Events of a single block are never split between transactions,
+    /// thus this limit may be exceeded by a single block.
+    #[serde(default = "default_event_client_max_events_per_batch")]
+    pub max_events_per_batch: NonZeroUsize,
+
+    /// Maximum count of nodes watched concurrently by a single event client process.
+    #[serde(default = "default_event_client_max_concurrent_nodes")]
+    pub max_concurrent_nodes: NonZeroUsize,
+}
+
+impl Default for EventClient {
+    fn default() -> Self {
+        Self {
+            poll_interval: default_event_client_poll_interval(),
+            blocks_per_batch: default_event_client_blocks_per_batch(),
+            max_events_per_batch: default_event_client_max_events_per_batch(),
+            max_concurrent_nodes: default_event_client_max_concurrent_nodes(),
+        }
+    }
+}
+
+fn default_event_client_poll_interval() -> NonZeroU64 {
+    NonZeroU64::new(10).unwrap()
+}
+
+fn default_event_client_blocks_per_batch() -> NonZeroU32 {
+    NonZeroU32::new(100).unwrap()
+}
+
+fn default_event_client_max_events_per_batch() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+fn default_event_client_max_concurrent_nodes() -> NonZeroUsize {
+    NonZeroUsize::new(16).unwrap()
+}
+
+/// List endpoint pagination configuration.
+///
+/// Lists return `default_page_size` items unless a client requests
+/// a different limit, which is capped to `max_page_size` instead of
+/// being rejected. Routes that paginate using page numbers always return
+/// `default_page_size` items per page.
+#[derive(Deserialize)]
+pub struct Pagination {
+    /// Count of items returned when a client doesn't provide a limit.
+    #[serde(default = "default_page_size")]
+    pub default_page_size: NonZeroU64,
+
+    /// Maximum count of items returned by a single request.
+    #[serde(default = "default_max_page_size")]
+    pub max_page_size: NonZeroU64,
+}
+
+impl Pagination {
+    /// Get the count of items to return for the provided client-supplied limit.
+    pub fn limit(&self, requested: Option<u64>) -> u64 {
+        requested
+            .unwrap_or(self.default_page_size.get())
+            .min(self.max_page_size.get())
+    }
+
+    /// Get the count of items returned per page by routes
+    /// that paginate using page numbers.
+    pub fn page_size(&self) -> u64 {
+        self.limit(None)
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            default_page_size: default_page_size(),
+            max_page_size: default_max_page_size(),
+        }
+    }
+}
+
+fn default_page_size() -> NonZeroU64 {
+    NonZeroU64::new(25).unwrap()
+}
+
+fn default_max_page_size() -> NonZeroU64 {
+    NonZeroU64::new(100).unwrap()
+}
+
 /// Smart contract builder configuration.
 #[derive(Deserialize)]
 pub struct Builder {
@@ -79,6 +721,10 @@ pub struct Builder {
     #[serde(default = "default_metadata_size_limit")]
     pub metadata_size_limit: usize,
 
+    /// Max stored log size per build, in bytes.
+    #[serde(default = "default_log_size_limit")]
+    pub log_size_limit: usize,
+
     /// Memory limit per build.
     #[serde(default = "default_memory_limit")]
     pub memory_limit: i64,
@@ -113,6 +759,10 @@ fn default_metadata_size_limit() -> usize {
     n_mib_bytes!(1) as usize
 }
 
+fn default_log_size_limit() -> usize {
+    n_mib_bytes!(1) as usize
+}
+
 fn default_memory_limit() -> i64 {
     n_gib_bytes!(4) as i64
 }
@@ -159,6 +809,45 @@ pub struct Config {
     #[serde(default)]
     pub logging: Logging,
 
+    /// Authentication configuration.
+    #[serde(default)]
+    pub authentication: Authentication,
+
+    /// Rate limiting configuration.
+    #[serde(default)]
+    pub rate_limit: RateLimit,
+
+    /// Network ranges of reverse proxies, which are trusted to provide client IP
+    /// addresses using `Forwarded` or `X-Forwarded-For` headers.
+    ///
+    /// Forwarded headers are ignored for other peers, and the peer address is used instead.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpNet>,
+
+    /// Event retention configuration.
+    #[serde(default)]
+    pub event_retention: EventRetention,
+
+    /// Cross-origin resource sharing configuration.
+    #[serde(default)]
+    pub cors: Cors,
+
+    /// HTTP response compression configuration.
+    #[serde(default)]
+    pub compression: Compression,
+
+    /// RPC node retry configuration.
+    #[serde(default)]
+    pub rpc_retry: RpcRetry,
+
+    /// Event client indexing configuration.
+    #[serde(default)]
+    pub event_client: EventClient,
+
+    /// List endpoint pagination configuration.
+    #[serde(default)]
+    pub pagination: Pagination,
+
     /// Contract builder configuration.
     #[serde(default)]
     pub builder: Option<Builder>,
@@ -175,6 +864,15 @@ pub struct Config {
     /// Enable payments support.
     #[serde(default = "default_payments")]
     pub payments: bool,
+
+    /// SS58 address prefix used to encode accounts in API responses.
+    ///
+    /// Prefix must not exceed [`MAX_SS58_PREFIX`], which is checked during configuration loading.
+    #[serde(
+        default = "default_ss58_prefix",
+        deserialize_with = "deserialize_ss58_prefix"
+    )]
+    pub ss58_prefix: u16,
 }
 
 fn default_supported_cargo_contract_versions() -> Vec<String> {
@@ -185,6 +883,29 @@ fn default_payments() -> bool {
     false
 }
 
+/// Maximum SS58 address prefix, as prefixes are encoded using 14 bits.
+pub const MAX_SS58_PREFIX: u16 = 16383;
+
+fn default_ss58_prefix() -> u16 {
+    // Generic Substrate prefix.
+    42
+}
+
+fn deserialize_ss58_prefix<'de, D>(deserializer: D) -> Result<u16, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let prefix = u16::deserialize(deserializer)?;
+
+    if prefix > MAX_SS58_PREFIX {
+        return Err(serde::de::Error::custom(format!(
+            "ss58 prefix must not exceed {MAX_SS58_PREFIX}"
+        )));
+    }
+
+    Ok(prefix)
+}
+
 impl Config {
     /// Create new config using default configuration file or environment variables.
     ///
@@ -204,11 +925,38 @@ impl Config {
         Self {
             database: Database {
                 url: String::from("sqlite::memory:"),
+                read_replica_url: None,
+                max_connections: default_max_connections(),
+                min_connections: default_min_connections(),
+                connect_timeout: default_connect_timeout(),
+                idle_timeout: default_idle_timeout(),
             },
             server: Some(Server {
-                address: "127.0.0.1:3000".parse().unwrap(),
+                address: Some("127.0.0.1:3000".parse().unwrap()),
+                host: None,
+                port: None,
+                socket_path: None,
+                metrics_address: None,
+                graphql: true,
+                request_id_header: default_request_id_header(),
+                ready_check_nodes: false,
+                embedded_event_client: false,
+                docs: true,
+                openapi_servers: Vec::new(),
+                idempotency_key_lifespan: default_idempotency_key_lifespan(),
+                webhook_private_addresses: true,
+                run_migrations: false,
             }),
             logging: Logging::default(),
+            authentication: Authentication::default(),
+            rate_limit: RateLimit::default(),
+            trusted_proxies: Vec::new(),
+            event_retention: EventRetention::default(),
+            cors: Cors::default(),
+            compression: Compression::default(),
+            rpc_retry: RpcRetry::default(),
+            event_client: EventClient::default(),
+            pagination: Pagination::default(),
             builder: None,
             storage: Storage {
                 access_key_id: String::new(),
@@ -219,6 +967,198 @@ impl Config {
             },
             supported_cargo_contract_versions: default_supported_cargo_contract_versions(),
             payments: false,
+            ss58_prefix: default_ss58_prefix(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use figment::{
+        providers::{Format, Toml},
+        Figment,
+    };
+    use serde::Deserialize;
+
+    use super::{
+        Authentication, EventClient, EventRetention, ListenAddress, ListenAddressError, Pagination,
+        Server,
+    };
+
+    #[cfg(feature = "database")]
+    use super::Database;
+
+    #[test]
+    #[cfg(feature = "database")]
+    fn connect_options() {
+        use std::time::Duration;
+
+        let database: Database = Figment::from(Toml::string(
+            r#"
+            url = "postgres://patron@127.0.0.1/patron"
+            max_connections = 50
+            idle_timeout = 60
+            "#,
+        ))
+        .extract()
+        .unwrap();
+
+        let options = database.connect_options();
+
+        assert_eq!(options.get_url(), "postgres://patron@127.0.0.1/patron");
+        assert_eq!(options.get_max_connections(), Some(50));
+        assert_eq!(options.get_min_connections(), Some(1));
+        assert_eq!(options.get_connect_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(60)));
+        assert!(database.read_replica_connect_options().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "database")]
+    fn read_replica_connect_options() {
+        let database: Database = Figment::from(Toml::string(
+            r#"
+            url = "postgres://patron@127.0.0.1/patron"
+            read_replica_url = "postgres://patron@127.0.0.2/patron"
+            max_connections = 50
+            "#,
+        ))
+        .extract()
+        .unwrap();
+
+        let options = database
+            .read_replica_connect_options()
+            .expect("read replica options expected");
+
+        assert_eq!(options.get_url(), "postgres://patron@127.0.0.2/patron");
+        assert_eq!(options.get_max_connections(), Some(50));
+    }
+
+    #[test]
+    fn event_client() {
+        let event_client: EventClient = Figment::from(Toml::string(
+            r#"
+            poll_interval = 60
+            blocks_per_batch = 500
+            max_concurrent_nodes = 4
+            "#,
+        ))
+        .extract()
+        .unwrap();
+
+        assert_eq!(event_client.poll_interval.get(), 60);
+        assert_eq!(event_client.blocks_per_batch.get(), 500);
+        assert_eq!(event_client.max_events_per_batch.get(), 1000);
+        assert_eq!(event_client.max_concurrent_nodes.get(), 4);
+
+        let result = Figment::from(Toml::string("blocks_per_batch = 0")).extract::<EventClient>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn authentication() {
+        let authentication: Authentication =
+            Figment::from(Toml::string("token_cleanup_interval = 60"))
+                .extract()
+                .unwrap();
+
+        assert_eq!(authentication.token_cleanup_interval.get(), 60);
+
+        let result =
+            Figment::from(Toml::string("token_cleanup_interval = 0")).extract::<Authentication>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn event_retention() {
+        let event_retention: EventRetention = Figment::from(Toml::string("prune_interval = 60"))
+            .extract()
+            .unwrap();
+
+        assert_eq!(event_retention.prune_interval.get(), 60);
+
+        let result = Figment::from(Toml::string("prune_interval = 0")).extract::<EventRetention>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn pagination() {
+        let pagination: Pagination = Figment::from(Toml::string("max_page_size = 50"))
+            .extract()
+            .unwrap();
+
+        assert_eq!(pagination.limit(None), 25);
+        assert_eq!(pagination.limit(Some(10)), 10);
+        assert_eq!(pagination.limit(Some(1000)), 50);
+        assert_eq!(pagination.page_size(), 25);
+    }
+
+    #[test]
+    fn ss58_prefix() {
+        #[derive(Deserialize)]
+        struct Prefix {
+            #[serde(deserialize_with = "super::deserialize_ss58_prefix")]
+            ss58_prefix: u16,
+        }
+
+        let prefix: Prefix = Figment::from(Toml::string("ss58_prefix = 16383"))
+            .extract()
+            .unwrap();
+
+        assert_eq!(prefix.ss58_prefix, super::MAX_SS58_PREFIX);
+
+        let result = Figment::from(Toml::string("ss58_prefix = 16384")).extract::<Prefix>();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn listen_address() {
+        for (config, expected) in [
+            (
+                r#"address = "127.0.0.1:3000""#,
+                Ok(ListenAddress::Tcp("127.0.0.1:3000".parse().unwrap())),
+            ),
+            (
+                "port = 3000",
+                Ok(ListenAddress::Tcp("127.0.0.1:3000".parse().unwrap())),
+            ),
+            (
+                "host = \"0.0.0.0\"\nport = 8080",
+                Ok(ListenAddress::Tcp("0.0.0.0:8080".parse().unwrap())),
+            ),
+            (
+                "host = \"[::1]\"\nport = 8080",
+                Ok(ListenAddress::Tcp("[::1]:8080".parse().unwrap())),
+            ),
+            (
+                "host = \"::\"\nport = 8080",
+                Ok(ListenAddress::Tcp("[::]:8080".parse().unwrap())),
+            ),
+            (
+                r#"socket_path = "/run/patron.sock""#,
+                Ok(ListenAddress::Unix("/run/patron.sock".into())),
+            ),
+            ("", Err(ListenAddressError::Missing)),
+            (
+                "host = \"localhost\"\nport = 8080",
+                Err(ListenAddressError::InvalidHost(String::from("localhost"))),
+            ),
+            (
+                "address = \"127.0.0.1:3000\"\nport = 8080",
+                Err(ListenAddressError::Conflicting),
+            ),
+            (
+                "socket_path = \"/run/patron.sock\"\nport = 8080",
+                Err(ListenAddressError::Conflicting),
+            ),
+        ] {
+            let server: Server = Figment::from(Toml::string(config)).extract().unwrap();
+
+            assert_eq!(server.listen_address(), expected, "{config}");
         }
     }
 }
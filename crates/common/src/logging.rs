@@ -1,23 +1,58 @@
-use tracing_core::Level;
-use tracing_subscriber::{filter::Targets, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_core::{Level, Subscriber};
+use tracing_subscriber::{
+    filter::Targets,
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+    Layer,
+};
 
-use crate::config::Config;
+use crate::config::{Config, LogFormat, Logging};
+
+/// Target used by API server access log events.
+pub const ACCESS_LOG_TARGET: &str = "access_log";
 
 /// Initialize [`tracing_subscriber`] with the provided [`Config`] struct.
 ///
 /// Besides using the provided configuration to determine the minimal log level,
 /// this function also sets `sqlx` target log level to "warn" and makes log messages
-/// more compact.
+/// more compact, unless JSON log format is configured.
 pub fn init(config: &Config) {
-    let fmt = fmt::format().with_target(false).compact();
+    tracing_subscriber::registry()
+        .with(layer(&config.logging, std::io::stdout))
+        .init();
+}
 
-    let target_filters = Targets::new()
+/// Create a formatting [`Layer`] that writes filtered log messages into the provided writer.
+///
+/// Access log events are enabled at the "info" level if configured, regardless
+/// of the minimal log level.
+pub fn layer<S, W>(config: &Logging, writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let mut target_filters = Targets::new()
         .with_target("sqlx", Level::WARN)
         .with_target("substrate_api_client", Level::WARN)
-        .with_default(config.logging.level);
+        .with_default(config.level);
 
-    tracing_subscriber::registry()
-        .with(fmt::layer().event_format(fmt))
-        .with(target_filters)
-        .init();
+    if config.access_log {
+        target_filters = target_filters.with_target(ACCESS_LOG_TARGET, Level::INFO);
+    }
+
+    let fmt = match config.format {
+        LogFormat::Compact => fmt::layer()
+            .event_format(fmt::format().with_target(false).compact())
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_writer(writer)
+            .boxed(),
+    };
+
+    fmt.with_filter(target_filters)
 }
@@ -37,6 +37,7 @@ use substrate_api_client::{
 
 pub use parity_scale_codec;
 pub use sp_core;
+pub use sp_version;
 pub use substrate_api_client;
 
 /// Default page size for fetching data by storage key prefix.
@@ -191,6 +192,17 @@ pub async fn call_contract<C: Request + Subscribe>(
     Ok(result)
 }
 
+/// Get a [`RuntimeVersion`] of a node runtime at the provided block hash.
+pub async fn runtime_version<C: Request>(
+    api: &Api<PolkadotConfig, C>,
+    at: H256,
+) -> Result<RuntimeVersion, Error> {
+    Ok(api
+        .client()
+        .request("state_getRuntimeVersion", rpc_params![at])
+        .await?)
+}
+
 /// Node metadata cache.
 #[derive(Debug)]
 pub struct MetadataCache {
@@ -212,15 +224,26 @@ impl MetadataCache {
         api: &Api<PolkadotConfig, C>,
         at: H256,
     ) -> Result<&'a Metadata, Error> {
+        self.versioned_metadata(api, at)
+            .await
+            .map(|(_, metadata)| metadata)
+    }
+
+    /// Get metadata associated with the provided block hash
+    /// along with the corresponding [`RuntimeVersion`].
+    pub async fn versioned_metadata<'a, C: Request>(
+        &'a mut self,
+        api: &Api<PolkadotConfig, C>,
+        at: H256,
+    ) -> Result<(RuntimeVersion, &'a Metadata), Error> {
+        let runtime_version = runtime_version(api, at).await?;
+
         let RuntimeVersion {
             authoring_version,
             spec_version,
             impl_version,
             ..
-        } = api
-            .client()
-            .request("state_getRuntimeVersion", rpc_params![at])
-            .await?;
+        } = runtime_version;
 
         if !self
             .cache
@@ -246,7 +269,7 @@ impl MetadataCache {
             .get(&(authoring_version, spec_version, impl_version))
             .unwrap();
 
-        Ok(metadata)
+        Ok((runtime_version, metadata))
     }
 }
 
@@ -334,6 +357,23 @@ impl StaticEvent for Terminated {
     const EVENT: &'static str = "Terminated";
 }
 
+/// Custom event emitted by a contract.
+///
+/// Indexed topics of this event are stored separately in the event record.
+#[derive(Decode)]
+pub struct ContractEmitted {
+    /// [`AccountId32`] value of a contract that emitted the event.
+    pub contract: AccountId32,
+
+    /// Raw SCALE-encoded event data.
+    pub data: Vec<u8>,
+}
+
+impl StaticEvent for ContractEmitted {
+    const PALLET: &'static str = "Contracts";
+    const EVENT: &'static str = "ContractEmitted";
+}
+
 async fn get_ty_storage_by_key<C: Request, K: Encode, V: DecodeAsType>(
     api: &Api<PolkadotConfig, C>,
     pallet: &'static str,
@@ -0,0 +1,164 @@
+//! Historical event backfill job.
+//!
+//! Backfill jobs are created by an API server and processed by a separate
+//! event client server, which rescans the requested block range for
+//! events related to a single contract account.
+//!
+//! Progress of a backfill job is saved after every processed batch of blocks,
+//! thus an interrupted backfill job can be resumed from the last checkpoint.
+
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+/// Backfill job model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "backfill_jobs")]
+pub struct Model {
+    /// Unique backfill job identifier.
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// Identifier of a node that has to be rescanned.
+    pub node_id: i64,
+
+    /// Smart contract account identifier.
+    pub account: Vec<u8>,
+
+    /// First block of the rescanned range, inclusive.
+    pub from_block: i64,
+
+    /// Last block of the rescanned range, inclusive.
+    pub to_block: i64,
+
+    /// Last block of the rescanned range that was already processed.
+    ///
+    /// [`None`] if no blocks were processed yet.
+    pub last_processed_block: Option<i64>,
+
+    /// Current backfill job [`Status`].
+    pub status: Status,
+
+    /// Backfill job creation time.
+    pub created_at: TimeDateTime,
+}
+
+/// Backfill job status.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, JsonSchema)]
+#[sea_orm(rs_type = "i16", db_type = "Integer")]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Backfill job has not started yet or is in progress
+    /// if the related row is locked.
+    #[sea_orm(num_value = 0)]
+    New,
+
+    /// An attempt to rescan the block range failed.
+    #[sea_orm(num_value = 1)]
+    Failed,
+
+    /// Backfill job finished successfully.
+    #[sea_orm(num_value = 2)]
+    Completed,
+}
+
+/// Backfill job relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::node::Entity",
+        from = "Column::NodeId",
+        to = "super::node::Column::Id"
+    )]
+    Node,
+}
+
+impl Related<super::node::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Node.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Get the first block that has to be rescanned, taking the last checkpoint into account.
+    pub fn resume_block(&self) -> i64 {
+        self.last_processed_block
+            .map_or(self.from_block, |block| block + 1)
+    }
+}
+
+/// Save the last processed block of a backfill job.
+///
+/// This function should be called inside of the same transaction
+/// that inserts the discovered events.
+pub async fn checkpoint<C: ConnectionTrait>(db: &C, id: i64, block: i64) -> Result<(), DbErr> {
+    Entity::update_many()
+        .filter(Column::Id.eq(id))
+        .col_expr(Column::LastProcessedBlock, block.into())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ActiveValue, ConnectionTrait, Database, EntityTrait, Schema};
+    use time::OffsetDateTime;
+
+    use super::{checkpoint, ActiveModel, Entity, Status};
+
+    #[tokio::test]
+    async fn resume_from_checkpoint() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let builder = db.get_database_backend();
+        let schema = Schema::new(builder);
+
+        for statement in [
+            schema.create_table_from_entity(crate::node::Entity),
+            schema.create_table_from_entity(Entity),
+        ] {
+            db.execute(builder.build(&statement)).await.unwrap();
+        }
+
+        crate::node::Entity::insert(crate::node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .unwrap();
+
+        let now = OffsetDateTime::now_utc();
+        let now = time::PrimitiveDateTime::new(now.date(), now.time());
+
+        let job = Entity::insert(ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![0; 32]),
+            from_block: ActiveValue::Set(100),
+            to_block: ActiveValue::Set(200),
+            status: ActiveValue::Set(Status::New),
+            created_at: ActiveValue::Set(now),
+            ..Default::default()
+        })
+        .exec_with_returning(&db)
+        .await
+        .unwrap();
+
+        assert_eq!(job.resume_block(), 100);
+
+        checkpoint(&db, job.id, 150).await.unwrap();
+
+        let job = Entity::find_by_id(job.id).one(&db).await.unwrap().unwrap();
+
+        assert_eq!(job.last_processed_block, Some(150));
+        assert_eq!(job.resume_block(), 151);
+    }
+}
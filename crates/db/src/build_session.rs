@@ -27,6 +27,12 @@ pub struct Model {
     /// Related contract source code identifier.
     pub source_code_id: i64,
 
+    /// Hash of the related source code archive.
+    ///
+    /// Stored separately from the source code to find build sessions
+    /// of identical source code archives uploaded by different users.
+    pub archive_hash: Option<Vec<u8>>,
+
     /// Current build session [`Status`].
     pub status: Status,
 
@@ -39,8 +45,28 @@ pub struct Model {
     /// JSON metadata value, if the contract build was successful.
     pub metadata: Option<Vec<u8>>,
 
+    /// Code hash of an on-chain WASM blob, against which
+    /// the build result has to be verified.
+    ///
+    /// [`None`] if no verification was requested.
+    pub expected_code_hash: Option<Vec<u8>>,
+
+    /// Whether the build session is a dry-run verification.
+    ///
+    /// Results of dry-run build sessions are not persisted as an official verification,
+    /// thus `code_hash` and `metadata` values are never set for them.
+    pub dry_run: bool,
+
+    /// WASM blob code hash, computed by a successful dry-run build session.
+    pub computed_code_hash: Option<Vec<u8>>,
+
     /// Build session creation time.
     pub created_at: TimeDateTime,
+
+    /// Time at which a build worker claimed the build session.
+    ///
+    /// [`None`] if the build session is still waiting for an available build worker.
+    pub started_at: Option<TimeDateTime>,
 }
 
 /// Build session status.
@@ -62,6 +88,10 @@ pub enum Status {
     /// Build session finished successfully.
     #[sea_orm(num_value = 2)]
     Completed,
+
+    /// Build session was cancelled by a user.
+    #[sea_orm(num_value = 3)]
+    Cancelled,
 }
 
 /// Build session relations.
@@ -116,4 +146,6 @@ pub struct ProcessedBuildSession {
     pub id: i64,
     pub source_code_id: i64,
     pub cargo_contract_version: String,
+    pub expected_code_hash: Option<Vec<u8>>,
+    pub dry_run: bool,
 }
@@ -0,0 +1,38 @@
+//! Build session cancellation request.
+//!
+//! Build sessions that are being processed are locked by a build worker,
+//! thus cancellation requests are stored separately and are checked
+//! by a worker in between build steps.
+
+use sea_orm::entity::prelude::*;
+
+/// Build session cancellation request model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "build_session_cancellations")]
+pub struct Model {
+    /// Identifier of a build session to cancel.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub build_session_id: i64,
+
+    /// Cancellation request time.
+    pub created_at: TimeDateTime,
+}
+
+/// Build session cancellation request relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::build_session::Entity",
+        from = "Column::BuildSessionId",
+        to = "super::build_session::Column::Id"
+    )]
+    BuildSession,
+}
+
+impl Related<super::build_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BuildSession.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -2,18 +2,27 @@
 //!
 //! This model stores the information about WASM blobs and their code hashes.
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, ActiveValue};
 
 /// WASM blob info model.
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "codes")]
 pub struct Model {
     /// Unique code hash.
-    #[sea_orm(primary_key)]
+    #[sea_orm(primary_key, auto_increment = false)]
     pub hash: Vec<u8>,
 
     /// WASM blob.
     pub code: Vec<u8>,
+
+    /// JSON metadata value, if the code was verified using a build session.
+    pub metadata: Option<String>,
+
+    /// Code discovery time.
+    pub created_at: TimeDateTime,
+
+    /// Last code update time.
+    pub updated_at: TimeDateTime,
 }
 
 /// Code model relations.
@@ -38,4 +47,17 @@ impl Related<super::build_session::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        // Insertion time is set by the database.
+        if !insert {
+            self.updated_at = ActiveValue::Set(crate::now());
+        }
+
+        Ok(self)
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This model is used to store information about discovered contracts.
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, ActiveValue};
 
 /// Smart contract information model.
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -24,6 +24,21 @@ pub struct Model {
     /// Contract owner, if the contract was
     /// discovered via propagated node events.
     pub owner: Option<Vec<u8>>,
+    /// Number of a block in which the contract was instantiated.
+    ///
+    /// [`None`] if the contract was discovered without an instantiation event.
+    pub instantiation_block: Option<i64>,
+
+    /// Timestamp of a block in which the contract was instantiated.
+    ///
+    /// [`None`] if the contract was discovered without an instantiation event.
+    pub instantiated_at: Option<TimeDateTime>,
+
+    /// Contract discovery time.
+    pub created_at: TimeDateTime,
+
+    /// Last contract update time.
+    pub updated_at: TimeDateTime,
 }
 
 /// Smart contract model relations.
@@ -56,4 +71,17 @@ impl Related<super::node::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        // Insertion time is set by the database.
+        if !insert {
+            self.updated_at = ActiveValue::Set(crate::now());
+        }
+
+        Ok(self)
+    }
+}
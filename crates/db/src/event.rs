@@ -2,8 +2,11 @@
 //!
 //! These events are discovered by a separate event client server (also known as a sync server).
 
-use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use schemars::JsonSchema;
+use sea_orm::{entity::prelude::*, sea_query::OnConflict, ActiveValue, QuerySelect, QueryTrait};
+use serde::{Deserialize, Serialize};
 
 /// Event model.
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -25,11 +28,24 @@ pub struct Model {
     /// Raw event body value, instantiated from a JSON serialization of a [`EventBody`] enum.
     pub body: String,
 
+    /// SCALE-encoded fields of a chain event from which the current event was derived.
+    ///
+    /// [`None`] if raw event fields were not available during event discovery.
+    pub raw_body: Option<Vec<u8>>,
+
     /// Timestamp of a block during which the event occured.
     pub block_timestamp: TimeDateTime,
+
+    /// Number of a block during which the event occured.
+    pub block_number: i64,
+
+    /// Index of the event inside of a block.
+    ///
+    /// [`None`] for events discovered before event indices were recorded.
+    pub event_index: Option<i32>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, JsonSchema)]
 #[sea_orm(rs_type = "i16", db_type = "Integer")]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
@@ -44,9 +60,17 @@ pub enum EventType {
     /// A contract was terminated.
     #[sea_orm(num_value = 2)]
     Termination,
+
+    /// A contract emitted a custom event.
+    #[sea_orm(num_value = 3)]
+    ContractEmission,
+
+    /// Contract's owner was changed.
+    #[sea_orm(num_value = 4)]
+    OwnershipChange,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub enum EventBody {
     /// A contract was instantiated.
     Instantiation,
@@ -59,9 +83,359 @@ pub enum EventBody {
 
     /// A contract was terminated.
     Termination,
+
+    /// A contract emitted a custom event.
+    ContractEmission {
+        /// Raw SCALE-encoded event data, stored as a hex value.
+        data: String,
+    },
+
+    /// Contract's owner was changed.
+    ///
+    /// Recorded along with each contract instantiation, since the deployer
+    /// of a contract becomes its owner.
+    OwnershipChange {
+        /// New owner account, stored as a hex value.
+        new_owner: String,
+    },
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Create an [`OnConflict`] clause, which skips insertion of already discovered events.
+pub fn skip_duplicates() -> OnConflict {
+    OnConflict::columns([
+        Column::NodeId,
+        Column::Account,
+        Column::BlockNumber,
+        Column::EventIndex,
+        Column::EventType,
+    ])
+    .do_nothing()
+    .to_owned()
+}
+
+/// Event discovered inside of a block, which has to be inserted with [`insert_block_events`].
+#[derive(Clone)]
+pub struct BlockEvent {
+    /// Related smart contract account identifier.
+    pub account: Vec<u8>,
+
+    /// Type of the discovered event.
+    pub event_type: EventType,
+
+    /// Raw event body value, instantiated from a JSON serialization of a [`EventBody`] enum.
+    pub body: String,
+
+    /// SCALE-encoded fields of a chain event from which the event was derived, if available.
+    pub raw_body: Option<Vec<u8>>,
+
+    /// Index of the event inside of a block.
+    pub event_index: i32,
+
+    /// Indexed event topics.
+    pub topics: Vec<Vec<u8>>,
+}
+
+/// Insert events discovered inside of a single block.
+///
+/// Events that were already discovered previously are skipped. New events, their topics
+/// and webhook deliveries are inserted with a single query each.
+///
+/// Returns the count of inserted events.
+pub async fn insert_block_events<C: ConnectionTrait>(
+    db: &C,
+    node_id: i64,
+    block_number: i64,
+    block_timestamp: TimeDateTime,
+    events: Vec<BlockEvent>,
+) -> Result<usize, DbErr> {
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    // Multiple events (e.g. instantiation and ownership change) can be derived
+    // from a single chain event, thus sharing the same index.
+    let existing: HashSet<(i32, EventType)> = Entity::find()
+        .select_only()
+        .columns([Column::EventIndex, Column::EventType])
+        .filter(Column::NodeId.eq(node_id))
+        .filter(Column::BlockNumber.eq(block_number))
+        .filter(Column::EventIndex.is_not_null())
+        .into_tuple::<(i32, EventType)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let events: Vec<_> = events
+        .into_iter()
+        .filter(|event| !existing.contains(&(event.event_index, event.event_type.clone())))
+        .collect();
+
+    if events.is_empty() {
+        return Ok(0);
+    }
+
+    Entity::insert_many(events.iter().map(|event| ActiveModel {
+        node_id: ActiveValue::Set(node_id),
+        account: ActiveValue::Set(event.account.clone()),
+        event_type: ActiveValue::Set(event.event_type.clone()),
+        body: ActiveValue::Set(event.body.clone()),
+        raw_body: ActiveValue::Set(event.raw_body.clone()),
+        block_timestamp: ActiveValue::Set(block_timestamp),
+        block_number: ActiveValue::Set(block_number),
+        event_index: ActiveValue::Set(Some(event.event_index)),
+        ..Default::default()
+    }))
+    .on_conflict(skip_duplicates())
+    .exec_without_returning(db)
+    .await?;
+
+    let ids: HashMap<(i32, EventType), i64> = Entity::find()
+        .select_only()
+        .columns([Column::EventIndex, Column::EventType, Column::Id])
+        .filter(Column::NodeId.eq(node_id))
+        .filter(Column::BlockNumber.eq(block_number))
+        .filter(Column::EventIndex.is_in(events.iter().map(|event| event.event_index)))
+        .into_tuple::<(i32, EventType, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|(event_index, event_type, id)| ((event_index, event_type), id))
+        .collect();
+
+    let inserted: Vec<_> = events
+        .iter()
+        .filter_map(|event| {
+            let id = ids.get(&(event.event_index, event.event_type.clone()))?;
+
+            Some((*id, event))
+        })
+        .collect();
+
+    let topics: Vec<_> = inserted
+        .iter()
+        .flat_map(|(event_id, event)| {
+            event
+                .topics
+                .iter()
+                .map(|topic| super::event_topic::ActiveModel {
+                    event_id: ActiveValue::Set(*event_id),
+                    topic: ActiveValue::Set(topic.clone()),
+                    ..Default::default()
+                })
+        })
+        .collect();
+
+    if !topics.is_empty() {
+        super::event_topic::Entity::insert_many(topics)
+            .exec_without_returning(db)
+            .await?;
+    }
+
+    let deliveries: Vec<_> = inserted
+        .iter()
+        .map(|(event_id, event)| (*event_id, &event.account[..]))
+        .collect();
+
+    super::webhook_delivery::enqueue(db, &deliveries).await?;
+
+    Ok(inserted.len())
+}
+
+/// Delete events that occured before the provided timestamp.
+///
+/// Events are deleted in batches of at most `batch_size` events, each with a separate
+/// statement, to avoid locking the table for a long time. Related event topics and
+/// webhook deliveries are deleted along with events.
+///
+/// Returns the count of deleted events.
+pub async fn prune<C: ConnectionTrait>(
+    db: &C,
+    before: TimeDateTime,
+    batch_size: u64,
+) -> Result<u64, DbErr> {
+    let batch_size = batch_size.max(1);
+    let mut deleted = 0;
+
+    loop {
+        let batch = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::BlockTimestamp.lt(before))
+            .limit(batch_size)
+            .into_query();
+
+        let result = Entity::delete_many()
+            .filter(Column::Id.in_subquery(batch))
+            .exec(db)
+            .await?;
+
+        deleted += result.rows_affected;
+
+        if result.rows_affected < batch_size {
+            return Ok(deleted);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{
+        sea_query::Index, ConnectionTrait, Database, DatabaseConnection, EntityTrait,
+        PaginatorTrait, Schema,
+    };
+    use time::OffsetDateTime;
+
+    use super::{BlockEvent, Column, Entity, EventType};
+
+    async fn create_database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let builder = db.get_database_backend();
+        let schema = Schema::new(builder);
+
+        for statement in [
+            schema.create_table_from_entity(Entity),
+            schema.create_table_from_entity(crate::event_topic::Entity),
+            schema.create_table_from_entity(crate::webhook::Entity),
+            schema.create_table_from_entity(crate::webhook_delivery::Entity),
+        ] {
+            db.execute(builder.build(&statement)).await.unwrap();
+        }
+
+        let index = Index::create()
+            .table(Entity)
+            .col(Column::NodeId)
+            .col(Column::Account)
+            .col(Column::BlockNumber)
+            .col(Column::EventIndex)
+            .col(Column::EventType)
+            .unique()
+            .name("idx_events_unique")
+            .to_owned();
+
+        db.execute(builder.build(&index)).await.unwrap();
+
+        db
+    }
+
+    fn block_events(count: i32) -> Vec<BlockEvent> {
+        (0..count)
+            .map(|event_index| BlockEvent {
+                account: vec![1; 32],
+                event_type: EventType::ContractEmission,
+                body: String::from("{}"),
+                raw_body: None,
+                event_index,
+                topics: vec![vec![2; 32]],
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn insert_block_events() {
+        let db = create_database().await;
+
+        let now = OffsetDateTime::now_utc();
+        let now = time::PrimitiveDateTime::new(now.date(), now.time());
+
+        let inserted = super::insert_block_events(&db, 1, 1, now, block_events(100))
+            .await
+            .unwrap();
+
+        assert_eq!(inserted, 100);
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 100);
+        assert_eq!(
+            crate::event_topic::Entity::find().count(&db).await.unwrap(),
+            100
+        );
+
+        // Already discovered events must be skipped.
+        let inserted = super::insert_block_events(&db, 1, 1, now, block_events(150))
+            .await
+            .unwrap();
+
+        assert_eq!(inserted, 50);
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 150);
+    }
+
+    #[tokio::test]
+    async fn insert_shared_index() {
+        let db = create_database().await;
+
+        let now = OffsetDateTime::now_utc();
+        let now = time::PrimitiveDateTime::new(now.date(), now.time());
+
+        let events = || {
+            [EventType::Instantiation, EventType::OwnershipChange]
+                .into_iter()
+                .map(|event_type| BlockEvent {
+                    account: vec![1; 32],
+                    event_type,
+                    body: String::from("{}"),
+                    raw_body: None,
+                    event_index: 0,
+                    topics: Vec::new(),
+                })
+                .collect()
+        };
+
+        let inserted = super::insert_block_events(&db, 1, 1, now, events())
+            .await
+            .unwrap();
+
+        assert_eq!(inserted, 2);
+
+        let inserted = super::insert_block_events(&db, 1, 1, now, events())
+            .await
+            .unwrap();
+
+        assert_eq!(inserted, 0);
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn prune() {
+        let db = create_database().await;
+
+        let now = OffsetDateTime::now_utc();
+        let now = time::PrimitiveDateTime::new(now.date(), now.time());
+        let old = now - time::Duration::days(100);
+
+        super::insert_block_events(&db, 1, 1, old, block_events(25))
+            .await
+            .unwrap();
+        super::insert_block_events(&db, 1, 2, now, block_events(10))
+            .await
+            .unwrap();
+
+        let deleted = super::prune(&db, now - time::Duration::days(90), 10)
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 25);
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 10);
+    }
+
+    #[tokio::test]
+    async fn insert_empty_block() {
+        let db = create_database().await;
+
+        let now = OffsetDateTime::now_utc();
+        let now = time::PrimitiveDateTime::new(now.date(), now.time());
+
+        let inserted = super::insert_block_events(&db, 1, 1, now, Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(inserted, 0);
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 0);
+    }
+}
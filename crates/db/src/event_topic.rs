@@ -0,0 +1,41 @@
+//! Indexed topic of a discovered smart contract event.
+//!
+//! Topics are emitted by smart contracts alongside event data
+//! and represent hashes of event fields marked as indexed.
+
+use sea_orm::entity::prelude::*;
+
+/// Event topic model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "event_topics")]
+pub struct Model {
+    /// Unique event topic identifier.
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// Related event identifier.
+    pub event_id: i64,
+
+    /// Topic hash value.
+    pub topic: Vec<u8>,
+}
+
+/// Event topic model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::event::Entity",
+        from = "Column::EventId",
+        to = "super::event::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Event,
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,64 @@
+//! Idempotency key of a build session creation request.
+//!
+//! Clients may provide an idempotency key to safely retry build session creation
+//! requests, in which case the originally created build session is returned
+//! instead of creating a new one.
+//!
+//! Keys are scoped to a single user and have their length limited to [`KEY_MAX_LENGTH`].
+
+use sea_orm::entity::prelude::*;
+
+pub const KEY_MAX_LENGTH: usize = 255;
+
+/// Idempotency key model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "idempotency_keys")]
+pub struct Model {
+    /// Unique idempotency key identifier.
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// Identifier of a user that provided the key.
+    pub user_id: i64,
+
+    /// Client-provided key value.
+    pub key: String,
+
+    /// Identifier of a build session created with the key.
+    pub build_session_id: i64,
+
+    /// Idempotency key creation time.
+    pub created_at: TimeDateTime,
+}
+
+/// Idempotency key model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+
+    #[sea_orm(
+        belongs_to = "super::build_session::Entity",
+        from = "Column::BuildSessionId",
+        to = "super::build_session::Column::Id"
+    )]
+    BuildSession,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::build_session::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BuildSession.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
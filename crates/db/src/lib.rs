@@ -3,33 +3,51 @@
 //! This crate provides definitions for database models that can be used, in conjunction
 //! with [`sea_orm`], to interact with the database in a typed manner.
 //!
-//! Additionally, this crate provides with utilities to map transaction errors ([`TransactionErrorExt::into_raw_result`])
+//! Additionally, this crate provides with utilities to map transaction errors ([`TransactionErrorExt::into_raw_result`]),
+//! to retry transactions that failed due to concurrent writes ([`RetryTransactionExt::retry_transaction`])
 //! and to provide other crates with commonly used `SELECT` query utilities [`SelectExt`].
 
+pub mod backfill_job;
 pub mod build_session;
+pub mod build_session_cancellation;
 pub mod build_session_token;
 pub mod cli_token;
 pub mod code;
 pub mod contract;
 pub mod event;
+pub mod event_topic;
 pub mod file;
+pub mod idempotency_key;
 pub mod log;
+pub mod login_challenge;
 pub mod node;
 pub mod public_key;
+pub mod public_key_nonce;
 pub mod source_code;
 pub mod token;
 pub mod user;
+pub mod webhook;
+pub mod webhook_delivery;
 
-use std::error::Error;
+use std::{error::Error, future::Future, pin::Pin};
 
 use async_trait::async_trait;
+use rand::Rng;
+use sea_orm::RuntimeErr;
 pub use sea_orm::{
-    self, sea_query, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, Database,
-    DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, FromQueryResult, QueryFilter,
-    QueryOrder, QuerySelect, QueryTrait, StatementBuilder, TransactionError, TransactionTrait,
-    TryGetableMany,
+    self, sea_query, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectOptions,
+    ConnectionTrait, Database, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+    StatementBuilder, TransactionError, TransactionTrait, TryGetableMany,
 };
-pub use time::{OffsetDateTime, PrimitiveDateTime};
+pub use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+/// Get current UTC time as a [`PrimitiveDateTime`].
+pub fn now() -> PrimitiveDateTime {
+    let now = OffsetDateTime::now_utc();
+
+    PrimitiveDateTime::new(now.date(), now.time())
+}
 
 /// Utility methods for operating with transaction errors.
 pub trait TransactionErrorExt<T, E> {
@@ -97,6 +115,124 @@ where
     }
 }
 
+/// Default count of transaction retries used by [`RetryTransactionExt::retry_transaction`] callers.
+pub const TRANSACTION_RETRIES: u32 = 3;
+
+/// Delay before the first transaction retry, doubled after each subsequent attempt.
+const TRANSACTION_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// PostgreSQL `serialization_failure` error code.
+const SERIALIZATION_FAILURE: &str = "40001";
+
+/// PostgreSQL `deadlock_detected` error code.
+const DEADLOCK_DETECTED: &str = "40P01";
+
+/// Utility methods for retrying transactions.
+#[async_trait]
+pub trait RetryTransactionExt {
+    /// Execute the provided callback inside of a transaction, retrying the whole transaction
+    /// up to `retries` times if it fails due to a serialization failure or a deadlock.
+    ///
+    /// Retries are delayed with an exponential backoff. Unlike [`TransactionTrait::transaction`],
+    /// the callback may be called multiple times, thus it must not consume any captured values.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let result = db
+    ///     .retry_transaction(TRANSACTION_RETRIES, |txn| {
+    ///         Box::pin(async move {
+    ///             Entity::delete_many().exec(txn).await?;
+    ///
+    ///             Ok::<_, DbErr>(())
+    ///         })
+    ///     })
+    ///     .await
+    ///     .into_raw_result()?;
+    /// ```
+    async fn retry_transaction<F, T, E>(
+        &self,
+        retries: u32,
+        callback: F,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> Fn(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send
+            + Sync,
+        T: Send,
+        E: Error + Send + 'static;
+}
+
+#[async_trait]
+impl<C> RetryTransactionExt for C
+where
+    C: TransactionTrait + Sync,
+{
+    async fn retry_transaction<F, T, E>(
+        &self,
+        retries: u32,
+        callback: F,
+    ) -> Result<T, TransactionError<E>>
+    where
+        F: for<'c> Fn(
+                &'c DatabaseTransaction,
+            ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+            + Send
+            + Sync,
+        T: Send,
+        E: Error + Send + 'static,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let result = self.transaction(|txn| callback(txn)).await;
+
+            let retriable = match &result {
+                Err(TransactionError::Connection(err)) => is_retriable(err),
+                Err(TransactionError::Transaction(err)) => is_retriable(err),
+                Ok(_) => false,
+            };
+
+            if !retriable || attempt >= retries {
+                return result;
+            }
+
+            let delay = TRANSACTION_RETRY_DELAY * 2u32.pow(attempt);
+            let jitter =
+                rand::thread_rng().gen_range(0..=TRANSACTION_RETRY_DELAY.as_millis() as u64);
+
+            tokio::time::sleep(delay + std::time::Duration::from_millis(jitter)).await;
+
+            attempt += 1;
+        }
+    }
+}
+
+/// Check if the provided error, or any of its sources, is caused by a serialization failure or a deadlock.
+fn is_retriable(err: &(dyn Error + 'static)) -> bool {
+    let mut current = Some(err);
+
+    while let Some(err) = current {
+        if let Some(
+            DbErr::Conn(RuntimeErr::SqlxError(sqlx::Error::Database(err)))
+            | DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(err)))
+            | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(err))),
+        ) = err.downcast_ref::<DbErr>()
+        {
+            return matches!(
+                err.code().as_deref(),
+                Some(SERIALIZATION_FAILURE | DEADLOCK_DETECTED)
+            );
+        }
+
+        current = err.source();
+    }
+
+    false
+}
+
 /// Utility methods for SELECT queries.
 #[async_trait]
 pub trait SelectExt {
@@ -138,13 +274,20 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        borrow::Cow,
+        error::Error,
+        fmt::{self, Display},
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
     use sea_orm::{
         prelude::*,
         sea_query::{self, ColumnDef, Iden, Table},
-        Database, QuerySelect,
+        Database, DatabaseConnection, QuerySelect, RuntimeErr,
     };
 
-    use crate::SelectExt;
+    use crate::{RetryTransactionExt, SelectExt, TransactionErrorExt};
 
     #[derive(Iden)]
     enum TestVals {
@@ -164,8 +307,40 @@ mod tests {
 
     impl ActiveModelBehavior for ActiveModel {}
 
-    #[tokio::test]
-    async fn exists() {
+    #[derive(Debug)]
+    struct SerializationFailure;
+
+    impl Display for SerializationFailure {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "could not serialize access due to concurrent update")
+        }
+    }
+
+    impl Error for SerializationFailure {}
+
+    impl sqlx::error::DatabaseError for SerializationFailure {
+        fn message(&self) -> &str {
+            "could not serialize access due to concurrent update"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(super::SERIALIZATION_FAILURE))
+        }
+
+        fn as_error(&self) -> &(dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
+            self
+        }
+    }
+
+    async fn create_test_env() -> DatabaseConnection {
         let db = Database::connect("sqlite::memory:")
             .await
             .expect("unable to create test database");
@@ -184,6 +359,13 @@ mod tests {
         let builder = db.get_database_backend();
         db.execute(builder.build(&table)).await.unwrap();
 
+        db
+    }
+
+    #[tokio::test]
+    async fn exists() {
+        let db = create_test_env().await;
+
         let exists = Entity::find().select_only().exists(&db).await.unwrap();
 
         assert!(!exists);
@@ -197,4 +379,53 @@ mod tests {
 
         assert!(exists);
     }
+
+    #[tokio::test]
+    async fn retry_transaction() {
+        let db = create_test_env().await;
+
+        let attempts = AtomicU32::new(0);
+
+        db.retry_transaction(crate::TRANSACTION_RETRIES, |txn| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                Entity::insert(<ActiveModel as std::default::Default>::default())
+                    .exec_without_returning(txn)
+                    .await?;
+
+                if attempt == 0 {
+                    return Err(DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(
+                        Box::new(SerializationFailure),
+                    ))));
+                }
+
+                Ok(())
+            })
+        })
+        .await
+        .into_raw_result()
+        .expect("transaction should succeed on retry");
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // Changes of the failed attempt must be rolled back.
+        assert_eq!(Entity::find().count(&db).await.unwrap(), 1);
+
+        let attempts = AtomicU32::new(0);
+
+        let result = db
+            .retry_transaction(crate::TRANSACTION_RETRIES, |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+
+                Box::pin(async move { Err::<(), _>(DbErr::Custom(String::from("test"))) })
+            })
+            .await
+            .into_raw_result();
+
+        assert!(result.is_err());
+
+        // Non-retriable errors are returned immediately.
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,137 @@
+//! Login challenge.
+//!
+//! To authenticate without an existing authentication token, a user has to request
+//! a challenge nonce bound to the claimed account and sign it with
+//! the corresponding private key.
+//!
+//! Each challenge can be used only once, and has its lifespan limited
+//! to [`CHALLENGE_LIFESPAN`] [`Duration`] value.
+
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    thread_rng,
+};
+use sea_orm::{entity::prelude::*, ActiveValue};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+pub const NONCE_LENGTH: usize = 32;
+pub const CHALLENGE_LIFESPAN: Duration = Duration::minutes(5);
+
+/// Login challenge model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "login_challenges")]
+pub struct Model {
+    /// Unique nonce string value.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub nonce: String,
+
+    /// Address of an account, for which the challenge was issued.
+    pub address: Vec<u8>,
+
+    /// Challenge creation timestamp.
+    pub created_at: TimeDateTime,
+}
+
+/// Login challenge model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Check if the current challenge is expired.
+    pub fn is_expired(&self) -> bool {
+        let now = OffsetDateTime::now_utc();
+
+        self.created_at + CHALLENGE_LIFESPAN < PrimitiveDateTime::new(now.date(), now.time())
+    }
+}
+
+/// Generate new login challenge for the provided account address.
+///
+/// This function returns both an [`ActiveModel`] of a challenge
+/// and its nonce value.
+///
+/// ## Example
+///
+/// ```
+/// use db::login_challenge::{NONCE_LENGTH, generate_challenge};
+///
+/// let (_, nonce) = generate_challenge(vec![0; 32]);
+/// assert_eq!(nonce.len(), NONCE_LENGTH);
+/// ```
+pub fn generate_challenge(address: Vec<u8>) -> (ActiveModel, String) {
+    let nonce = Alphanumeric.sample_string(&mut thread_rng(), NONCE_LENGTH);
+
+    let now = OffsetDateTime::now_utc();
+
+    let created_at = PrimitiveDateTime::new(now.date(), now.time());
+
+    (
+        ActiveModel {
+            nonce: ActiveValue::Set(nonce.clone()),
+            address: ActiveValue::Set(address),
+            created_at: ActiveValue::Set(created_at),
+        },
+        nonce,
+    )
+}
+
+/// Delete login challenges that are expired.
+///
+/// Returns the count of deleted challenges.
+pub async fn delete_expired<C: ConnectionTrait>(db: &C) -> Result<u64, DbErr> {
+    Entity::delete_many()
+        .filter(Column::CreatedAt.lt(crate::now() - CHALLENGE_LIFESPAN))
+        .exec(db)
+        .await
+        .map(|result| result.rows_affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{
+        ActiveValue, ConnectionTrait, Database, DatabaseConnection, EntityTrait, Schema,
+    };
+    use time::Duration;
+
+    use super::{generate_challenge, ActiveModel, Entity, CHALLENGE_LIFESPAN};
+
+    async fn create_database() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let builder = db.get_database_backend();
+        let statement = Schema::new(builder).create_table_from_entity(Entity);
+
+        db.execute(builder.build(&statement)).await.unwrap();
+
+        db
+    }
+
+    #[tokio::test]
+    async fn delete_expired() {
+        let db = create_database().await;
+
+        let (active, _) = generate_challenge(vec![1; 32]);
+        let (expired, _) = generate_challenge(vec![2; 32]);
+
+        let expired = ActiveModel {
+            created_at: ActiveValue::Set(crate::now() - CHALLENGE_LIFESPAN - Duration::seconds(1)),
+            ..expired
+        };
+
+        Entity::insert_many([active, expired])
+            .exec_without_returning(&db)
+            .await
+            .unwrap();
+
+        assert_eq!(super::delete_expired(&db).await.unwrap(), 1);
+
+        let remaining = Entity::find().all(&db).await.unwrap();
+
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].address, vec![1; 32]);
+    }
+}
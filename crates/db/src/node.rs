@@ -4,7 +4,8 @@
 //! last confirmed block for event client and optionally a payment contract
 //! that can be used to acquire membership fees.
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, DatabaseConnection};
+use sqlx::{pool::PoolConnection, Postgres};
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "nodes")]
@@ -29,6 +30,16 @@ pub struct Model {
     /// `confirmed_block` value is used to catch-up to missed blocks if
     /// any such blocks are present.
     pub confirmed_block: i64,
+
+    /// Runtime specification name of the node chain.
+    ///
+    /// [`None`] if the node was not yet watched by an event client.
+    pub spec_name: Option<String>,
+
+    /// Runtime specification version of the node chain.
+    ///
+    /// Updated by an event client whenever a runtime upgrade is detected.
+    pub spec_version: Option<i64>,
 }
 
 /// Node model relations.
@@ -45,3 +56,118 @@ impl Related<super::contract::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Advisory lock of a single node, which ensures that only one event client
+/// instance advances the confirmed block of a node at a time.
+///
+/// The lock is bound to a dedicated database connection and is released either
+/// explicitly with [`NodeLock::release`], or by closing the connection when dropped.
+pub struct NodeLock {
+    /// Connection that holds the lock.
+    ///
+    /// [`None`] if the database backend doesn't support advisory locks.
+    connection: Option<PoolConnection<Postgres>>,
+
+    /// Locked node identifier.
+    node_id: i64,
+}
+
+impl NodeLock {
+    /// Release the lock and return its connection back to the pool.
+    pub async fn release(mut self) -> Result<(), DbErr> {
+        let Some(mut connection) = self.connection.take() else {
+            return Ok(());
+        };
+
+        let result = sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(self.node_id)
+            .execute(&mut *connection)
+            .await;
+
+        if let Err(err) = result {
+            // Ensure that a possibly locked connection is not reused.
+            drop(connection.detach());
+            return Err(DbErr::Custom(err.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for NodeLock {
+    fn drop(&mut self) {
+        // Closing the connection releases all of its session-level locks.
+        if let Some(connection) = self.connection.take() {
+            drop(connection.detach());
+        }
+    }
+}
+
+/// Attempt to acquire a Postgres advisory lock keyed by the provided node identifier.
+///
+/// Returns [`None`] without waiting if the node is already locked by another connection.
+/// Databases other than Postgres do not support advisory locks, thus locks are
+/// always acquired for them.
+pub async fn try_lock(db: &DatabaseConnection, node_id: i64) -> Result<Option<NodeLock>, DbErr> {
+    let DatabaseConnection::SqlxPostgresPoolConnection(_) = db else {
+        return Ok(Some(NodeLock {
+            connection: None,
+            node_id,
+        }));
+    };
+
+    let mut connection = db
+        .get_postgres_connection_pool()
+        .acquire()
+        .await
+        .map_err(|_| DbErr::ConnectionAcquire)?;
+
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+        .bind(node_id)
+        .fetch_one(&mut *connection)
+        .await
+        .map_err(|err| DbErr::Custom(err.to_string()))?;
+
+    Ok(locked.then_some(NodeLock {
+        connection: Some(connection),
+        node_id,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::Database;
+
+    use super::try_lock;
+
+    #[tokio::test]
+    async fn unsupported_backend() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let lock = try_lock(&db, 1).await.unwrap().expect("lock expected");
+
+        assert!(try_lock(&db, 1).await.unwrap().is_some());
+
+        lock.release().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database provided with the TEST_DATABASE_URL variable"]
+    async fn second_acquisition() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is not set");
+        let db = Database::connect(url)
+            .await
+            .expect("unable to connect to test database");
+
+        let lock = try_lock(&db, 1).await.unwrap().expect("lock expected");
+
+        assert!(try_lock(&db, 1).await.unwrap().is_none());
+        assert!(try_lock(&db, 2).await.unwrap().is_some());
+
+        lock.release().await.unwrap();
+
+        assert!(try_lock(&db, 1).await.unwrap().is_some());
+    }
+}
@@ -6,7 +6,9 @@
 //! Public key verification is done by signing some generated message
 //! and verifying that the signature corresponds to the requested public key value.
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, ActiveValue};
+
+pub const NAME_MAX_LENGTH: usize = 64;
 
 /// Public key model.
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -17,6 +19,19 @@ pub struct Model {
     pub user_id: i64,
     pub address: Vec<u8>,
     pub created_at: TimeDateTime,
+    pub name: Option<String>,
+
+    /// Timestamp of a public key deletion.
+    ///
+    /// [`None`] for active public keys, deleted keys are kept only
+    /// if soft deletion is enabled.
+    pub deleted_at: Option<TimeDateTime>,
+
+    /// Last public key update time.
+    pub updated_at: TimeDateTime,
+
+    /// Public key version, which is incremented on each update.
+    pub version: i64,
 }
 
 /// Public key model relations.
@@ -36,4 +51,17 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        // Insertion time is set by the database.
+        if !insert {
+            self.updated_at = ActiveValue::Set(crate::now());
+        }
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,90 @@
+//! Public key verification nonce.
+//!
+//! To attach a public key to an account, a user has to request a nonce
+//! from an API server and sign it with the corresponding private key.
+//!
+//! Each nonce can be used only once, and has its lifespan limited
+//! to [`NONCE_LIFESPAN`] [`Duration`] value.
+
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    thread_rng,
+};
+use sea_orm::{entity::prelude::*, ActiveValue};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime};
+
+pub const NONCE_LENGTH: usize = 32;
+pub const NONCE_LIFESPAN: Duration = Duration::minutes(10);
+
+/// Public key verification nonce model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "public_key_nonces")]
+pub struct Model {
+    /// Unique nonce string value.
+    #[sea_orm(primary_key)]
+    pub nonce: String,
+
+    /// Identifier of a user that requested the nonce.
+    pub user_id: i64,
+
+    /// Nonce creation timestamp.
+    pub created_at: TimeDateTime,
+}
+
+/// Public key verification nonce model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Check if the current nonce is expired.
+    pub fn is_expired(&self) -> bool {
+        let now = OffsetDateTime::now_utc();
+
+        self.created_at + NONCE_LIFESPAN < PrimitiveDateTime::new(now.date(), now.time())
+    }
+}
+
+/// Generate new public key verification nonce for the provided user identifier.
+///
+/// This function returns both an [`ActiveModel`] of a nonce
+/// and its string value.
+///
+/// ## Example
+///
+/// ```
+/// use db::public_key_nonce::{NONCE_LENGTH, generate_nonce};
+///
+/// let (_, nonce) = generate_nonce(1);
+/// assert_eq!(nonce.len(), NONCE_LENGTH);
+/// ```
+pub fn generate_nonce(user_id: i64) -> (ActiveModel, String) {
+    let nonce = Alphanumeric.sample_string(&mut thread_rng(), NONCE_LENGTH);
+
+    let now = OffsetDateTime::now_utc();
+
+    let created_at = PrimitiveDateTime::new(now.date(), now.time());
+
+    (
+        ActiveModel {
+            nonce: ActiveValue::Set(nonce.clone()),
+            user_id: ActiveValue::Set(user_id),
+            created_at: ActiveValue::Set(created_at),
+        },
+        nonce,
+    )
+}
@@ -3,14 +3,20 @@
 //! Authentication token is passed to an API server to identify
 //! a user that executes the request.
 //!
-//! Authentication tokens have their lifespan limited to [`TOKEN_LIFESPAN`] [`Duration`]
-//! value, and are to have their length equal to the [`TOKEN_LENGTH`] value.
+//! Authentication tokens have their lifespan limited to a configurable [`Duration`]
+//! value (with [`TOKEN_LIFESPAN`] being the default one), and are to have
+//! their length equal to the [`TOKEN_LENGTH`] value.
+//!
+//! Only SHA-256 hashes of authentication tokens are stored, thus a database leak
+//! does not expose tokens that can be used to authenticate. Plaintext tokens are
+//! returned once, during the token generation.
 
 use rand::{
     distributions::{Alphanumeric, DistString},
     thread_rng,
 };
 use sea_orm::{entity::prelude::*, ActiveValue};
+use sha2::{Digest, Sha256};
 use time::{Duration, OffsetDateTime, PrimitiveDateTime};
 
 pub const TOKEN_LENGTH: usize = 64;
@@ -27,11 +33,14 @@ pub struct Model {
     /// Related user identifier.
     pub user_id: i64,
 
-    /// Authentication token string value.
+    /// Hex-encoded SHA-256 hash of an authentication token string value.
     pub token: String,
 
     /// Authentication token creation timestamp.
     pub created_at: TimeDateTime,
+
+    /// Authentication token expiration timestamp.
+    pub expires_at: TimeDateTime,
 }
 
 /// Authentication token model relations.
@@ -53,21 +62,35 @@ impl Related<super::user::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
+/// Hash the provided authentication token string value for storage and lookup.
+///
+/// Since stored hashes are compared instead of plaintext tokens,
+/// lookup timing does not leak any information about valid tokens.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token))
+}
+
+/// Generate a random authentication token string value.
+fn generate_value() -> String {
+    Alphanumeric.sample_string(&mut thread_rng(), TOKEN_LENGTH)
+}
+
 /// Generate new authentication token for the provided user identifier.
 ///
 /// This function returns both an [`ActiveModel`] of an authentication token
-/// and its string value.
+/// and its string value, only the hash of which is stored in the model.
+/// The generated token expires after the provided `lifespan`.
 ///
 /// ## Example
 ///
 /// ```
-/// use db::token::{TOKEN_LENGTH, generate_token};
+/// use db::token::{TOKEN_LENGTH, TOKEN_LIFESPAN, generate_token};
 ///
-/// let (_, token_string) = generate_token(1);
+/// let (_, token_string) = generate_token(1, TOKEN_LIFESPAN);
 /// assert_eq!(token_string.len(), TOKEN_LENGTH);
 /// ```
-pub fn generate_token(user_id: i64) -> (ActiveModel, String) {
-    let token = Alphanumeric.sample_string(&mut thread_rng(), TOKEN_LENGTH);
+pub fn generate_token(user_id: i64, lifespan: Duration) -> (ActiveModel, String) {
+    let token = generate_value();
 
     let now = OffsetDateTime::now_utc();
 
@@ -76,8 +99,26 @@ pub fn generate_token(user_id: i64) -> (ActiveModel, String) {
     (
         ActiveModel {
             user_id: ActiveValue::Set(user_id),
-            token: ActiveValue::Set(token.clone()),
+            token: ActiveValue::Set(hash_token(&token)),
             created_at: ActiveValue::Set(created_at),
+            expires_at: ActiveValue::Set(created_at + lifespan),
+            ..Default::default()
+        },
+        token,
+    )
+}
+
+/// Replace the string value of an existing authentication token with the provided identifier.
+///
+/// This function returns both an [`ActiveModel`] suitable for an update
+/// and the new string value, which is otherwise unrecoverable from the database.
+pub fn regenerate_token(id: i64) -> (ActiveModel, String) {
+    let token = generate_value();
+
+    (
+        ActiveModel {
+            id: ActiveValue::Unchanged(id),
+            token: ActiveValue::Set(hash_token(&token)),
             ..Default::default()
         },
         token,
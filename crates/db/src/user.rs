@@ -5,7 +5,7 @@
 //! to seamlessly register new users and automatically attach public keys to them
 //! for later authentications.
 
-use sea_orm::entity::prelude::*;
+use sea_orm::{entity::prelude::*, ActiveValue};
 
 /// User model.
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -14,7 +14,11 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i64,
     pub paid: bool,
+    pub is_admin: bool,
     pub created_at: TimeDateTime,
+
+    /// Last user update time.
+    pub updated_at: TimeDateTime,
 }
 
 /// User model relations.
@@ -31,6 +35,9 @@ pub enum Relation {
 
     #[sea_orm(has_many = "super::build_session::Entity")]
     BuildSessions,
+
+    #[sea_orm(has_many = "super::webhook::Entity")]
+    Webhooks,
 }
 
 impl Related<super::public_key::Entity> for Entity {
@@ -57,4 +64,23 @@ impl Related<super::build_session::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Related<super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhooks.def()
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        // Insertion time is set by the database.
+        if !insert {
+            self.updated_at = ActiveValue::Set(crate::now());
+        }
+
+        Ok(self)
+    }
+}
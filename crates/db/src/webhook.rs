@@ -0,0 +1,84 @@
+//! Contract event webhook.
+//!
+//! Webhooks are registered by users to receive HTTP notifications
+//! about newly discovered events of a single contract account.
+//!
+//! Each notification is signed with a webhook secret, which is generated
+//! by an API server and has its length equal to the [`SECRET_LENGTH`] value.
+
+use rand::{
+    distributions::{Alphanumeric, DistString},
+    thread_rng,
+};
+use sea_orm::entity::prelude::*;
+
+pub const SECRET_LENGTH: usize = 32;
+pub const URL_MAX_LENGTH: usize = 255;
+
+/// Webhook model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "webhooks")]
+pub struct Model {
+    /// Unique webhook identifier.
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// Identifier of a user that registered the webhook.
+    pub user_id: i64,
+
+    /// Target URL, to which event notifications are sent.
+    pub url: String,
+
+    /// Smart contract account identifier, events of which are sent.
+    pub account: Vec<u8>,
+
+    /// Secret used to sign event notifications.
+    pub secret: String,
+
+    /// Count of event notifications that were not delivered after all attempts.
+    pub dead_letter_count: i64,
+
+    /// Webhook creation time.
+    pub created_at: TimeDateTime,
+}
+
+/// Webhook model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+
+    #[sea_orm(has_many = "super::webhook_delivery::Entity")]
+    Deliveries,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::webhook_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Deliveries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Generate new webhook secret.
+///
+/// ## Example
+///
+/// ```
+/// use db::webhook::{SECRET_LENGTH, generate_secret};
+///
+/// assert_eq!(generate_secret().len(), SECRET_LENGTH);
+/// ```
+pub fn generate_secret() -> String {
+    Alphanumeric.sample_string(&mut thread_rng(), SECRET_LENGTH)
+}
@@ -0,0 +1,128 @@
+//! Queued webhook event notification.
+//!
+//! Deliveries are enqueued by an event client as soon as an event related to
+//! the webhook's contract account is discovered, and are sent
+//! by an API server, which retries failed attempts with a backoff.
+
+use sea_orm::{entity::prelude::*, ActiveValue, QuerySelect};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+/// Webhook delivery model.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "webhook_deliveries")]
+pub struct Model {
+    /// Unique webhook delivery identifier.
+    #[sea_orm(primary_key)]
+    pub id: i64,
+
+    /// Related webhook identifier.
+    pub webhook_id: i64,
+
+    /// Identifier of an event that has to be delivered.
+    pub event_id: i64,
+
+    /// Current webhook delivery [`Status`].
+    pub status: Status,
+
+    /// Count of failed delivery attempts.
+    pub attempts: i32,
+
+    /// Time after which the next delivery attempt can be made.
+    pub next_attempt_at: TimeDateTime,
+
+    /// Webhook delivery creation time.
+    pub created_at: TimeDateTime,
+}
+
+/// Webhook delivery status.
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "i16", db_type = "Integer")]
+pub enum Status {
+    /// Event notification was not delivered yet.
+    #[sea_orm(num_value = 0)]
+    New,
+
+    /// All delivery attempts failed.
+    #[sea_orm(num_value = 1)]
+    Failed,
+
+    /// Event notification was delivered successfully.
+    #[sea_orm(num_value = 2)]
+    Completed,
+}
+
+/// Webhook delivery model relations.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::webhook::Entity",
+        from = "Column::WebhookId",
+        to = "super::webhook::Column::Id"
+    )]
+    Webhook,
+
+    #[sea_orm(
+        belongs_to = "super::event::Entity",
+        from = "Column::EventId",
+        to = "super::event::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Event,
+}
+
+impl Related<super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl Related<super::event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Enqueue deliveries of the provided events to all webhooks of their contract accounts.
+///
+/// Events are provided as pairs of event identifiers and contract accounts.
+pub async fn enqueue<C: ConnectionTrait>(db: &C, events: &[(i64, &[u8])]) -> Result<(), DbErr> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let webhooks = super::webhook::Entity::find()
+        .select_only()
+        .columns([super::webhook::Column::Id, super::webhook::Column::Account])
+        .filter(super::webhook::Column::Account.is_in(events.iter().map(|(_, account)| *account)))
+        .into_tuple::<(i64, Vec<u8>)>()
+        .all(db)
+        .await?;
+
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let now = PrimitiveDateTime::new(now.date(), now.time());
+
+    let deliveries = events.iter().flat_map(|(event_id, account)| {
+        webhooks
+            .iter()
+            .filter(move |(_, webhook_account)| webhook_account == account)
+            .map(move |(webhook_id, _)| ActiveModel {
+                webhook_id: ActiveValue::Set(*webhook_id),
+                event_id: ActiveValue::Set(*event_id),
+                status: ActiveValue::Set(Status::New),
+                next_attempt_at: ActiveValue::Set(now),
+                ..Default::default()
+            })
+    });
+
+    Entity::insert_many(deliveries)
+        .exec_without_returning(db)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Derive macro for API server error responses.
+//!
+//! [`ErrorResponse`] implements `IntoResponse` for error enums, producing a JSON error envelope
+//! with a stable machine-readable error code:
+//!
+//! ```json
+//! { "error": { "code": "database_error", "message": "Internal server error" } }
+//! ```
+//!
+//! Error codes are snake_case names of enum variants. Response status codes default to
+//! `500 Internal Server Error` and can be overridden with a `#[status(...)]` attribute.
+//! Messages of server errors are hidden from clients and logged instead.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Expr, Fields, Ident};
+
+/// Derive `IntoResponse` and `Debug` implementations for an error enum.
+#[proc_macro_derive(ErrorResponse, attributes(status))]
+pub fn derive_error_response(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+
+    let result = match input.data {
+        Data::Enum(enum_data) => derive_for_enum(input.ident, enum_data),
+        _ => Err(syn::Error::new_spanned(
+            input.ident,
+            "ErrorResponse can only be derived for enums",
+        )),
+    };
+
+    result.unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Generate trait implementations for the provided error enum.
+fn derive_for_enum(ident: Ident, enum_data: DataEnum) -> syn::Result<TokenStream> {
+    let mut status_codes = Vec::new();
+    let mut error_codes = Vec::new();
+
+    for variant in enum_data.variants {
+        let variant_name = variant.ident;
+
+        let match_fields = match variant.fields {
+            Fields::Named(_) => quote!({ .. }),
+            Fields::Unnamed(_) => quote!((..)),
+            Fields::Unit => quote!(),
+        };
+
+        let status = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("status"))
+            .map(|attr| attr.parse_args::<Expr>())
+            .transpose()?
+            .map(|status| quote!(#status))
+            .unwrap_or_else(|| quote!(::axum::http::StatusCode::INTERNAL_SERVER_ERROR));
+
+        let error_code = snake_case(&variant_name.to_string());
+
+        status_codes.push(quote!(Self::#variant_name #match_fields => #status));
+        error_codes.push(quote!(Self::#variant_name #match_fields => #error_code));
+    }
+
+    Ok(quote! {
+        impl #ident {
+            /// Get an HTTP status code of the current error.
+            fn status_code(&self) -> ::axum::http::StatusCode {
+                match self {
+                    #(#status_codes,)*
+                }
+            }
+
+            /// Get a stable machine-readable code of the current error.
+            #[allow(dead_code)]
+            pub fn error_code(&self) -> &'static str {
+                match self {
+                    #(#error_codes,)*
+                }
+            }
+        }
+
+        impl ::axum::response::IntoResponse for #ident {
+            fn into_response(self) -> ::axum::response::Response {
+                let status = self.status_code();
+                let mut message = self.to_string();
+
+                if status.is_server_error() {
+                    ::tracing::error!(message, error_details = ?self, "internal server error");
+                    message = String::from("Internal server error");
+                }
+
+                let body = ::axum::Json(::serde_json::json!({
+                    "error": {
+                        "code": self.error_code(),
+                        "message": message,
+                    }
+                }));
+
+                ::axum::response::IntoResponse::into_response((status, body))
+            }
+        }
+
+        impl ::std::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                writeln!(f, "{}\n", self)?;
+                let mut current = ::std::error::Error::source(self);
+                while let Some(cause) = current {
+                    writeln!(f, "Caused by:\n\t{}", cause)?;
+                    current = ::std::error::Error::source(cause);
+                }
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Convert a PascalCase variant name into a snake_case error code.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 4);
+
+    for (index, &char) in chars.iter().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_lowercase = matches!(chars.get(index + 1), Some(next) if next.is_lowercase());
+
+            if !previous.is_uppercase() || next_lowercase {
+                result.push('_');
+            }
+        }
+
+        result.extend(char.to_lowercase());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::snake_case;
+
+    #[test]
+    fn error_codes() {
+        for (name, code) in [
+            ("DatabaseError", "database_error"),
+            ("NonExistentUser", "non_existent_user"),
+            ("InvalidSS58Address", "invalid_ss58_address"),
+            ("RpcError", "rpc_error"),
+        ] {
+            assert_eq!(snake_case(name), code);
+        }
+    }
+}
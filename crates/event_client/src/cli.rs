@@ -1,3 +1,6 @@
+/// `backfill` subcommand.
+mod backfill;
+
 /// `initialize` subcommand.
 mod initialize;
 
@@ -14,15 +17,18 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 
+pub use backfill::backfill;
 pub use initialize::initialize;
 pub use traverse::traverse;
 pub use update_contract::update_contract;
-pub use watch::watch;
+pub use watch::{watch, watch_all};
+
+pub(crate) use watch::load_nodes;
 
 /// Primary CLI configuration, serves as an entrypoint to [`clap`].
 #[derive(Parser)]
 #[command(about, version)]
-pub(crate) struct Cli {
+pub struct Cli {
     /// Selected subcommand.
     #[command(subcommand)]
     pub command: Command,
@@ -34,7 +40,13 @@ pub(crate) struct Cli {
 
 /// Supported subcommands.
 #[derive(Subcommand)]
-pub(crate) enum Command {
+pub enum Command {
+    /// Process contract event backfill jobs of the provided node.
+    Backfill {
+        /// Node name.
+        name: String,
+    },
+
     /// Initialize new node with the provided options.
     Initialize {
         /// Node name.
@@ -46,6 +58,12 @@ pub(crate) enum Command {
         /// Address of a contract that accepts membership payments.
         #[clap(long)]
         payment_address: Option<String>,
+
+        /// Block to start discovering events from.
+        ///
+        /// Defaults to the latest block.
+        #[clap(long)]
+        start_block: Option<u32>,
     },
 
     /// Traverse old blocks of the provided node for old events.
@@ -68,4 +86,7 @@ pub(crate) enum Command {
         /// Node name.
         name: String,
     },
+
+    /// Watch all registered nodes concurrently for new blocks to discover contract events.
+    WatchAll,
 }
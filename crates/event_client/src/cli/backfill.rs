@@ -0,0 +1,409 @@
+use std::{mem, time::Duration};
+
+use common::{
+    config::EventClient,
+    rpc::{
+        self,
+        parity_scale_codec::{self, Decode},
+        sp_core::{ByteArray, H256},
+        substrate_api_client::{
+            self,
+            ac_node_api::{Events, StaticEvent},
+            ac_primitives::PolkadotConfig,
+            rpc::{JsonrpseeClient, Request},
+            Api,
+        },
+        ContractCodeUpdated, ContractEmitted, Instantiated, MetadataCache, Terminated,
+    },
+};
+use db::{
+    backfill_job, event, node, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    PrimitiveDateTime, QueryFilter, QueryOrder, RetryTransactionExt, TransactionErrorExt,
+    TRANSACTION_RETRIES,
+};
+use derive_more::{Display, Error, From};
+use futures_util::{pin_mut, TryStreamExt};
+use tracing::{error, info, warn};
+
+use crate::{
+    retry::Recoverable,
+    utils::{block_mapping_stream, block_timestamp, decode_contract_emission},
+};
+
+/// Errors that may occur during the backfill process.
+#[derive(Debug, Display, Error, From)]
+pub enum BackfillError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Substrate RPC-related error.
+    #[display(fmt = "rpc error: {:?}", _0)]
+    RpcError(#[error(ignore)] substrate_api_client::Error),
+
+    /// JSON serialization error.
+    JsonError(serde_json::Error),
+
+    /// SCALE decoding error.
+    #[display(fmt = "unable to decode contract event: {}", _0)]
+    DecodeError(parity_scale_codec::Error),
+
+    /// The provided node name is incorrect.
+    #[display(fmt = "node not found")]
+    NodeNotFound,
+}
+
+impl Recoverable for BackfillError {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            BackfillError::DatabaseError(DbErr::ConnectionAcquire | DbErr::Conn(_)) => true,
+            BackfillError::RpcError(err) => err.is_recoverable(),
+            _ => false,
+        }
+    }
+}
+
+/// Contract event discovered during a block range rescan.
+struct DiscoveredEvent {
+    /// Index of the event inside of a block.
+    index: u32,
+
+    /// Event type.
+    event_type: event::EventType,
+
+    /// Event body.
+    body: event::EventBody,
+
+    /// SCALE-encoded fields of a chain event.
+    raw_body: Vec<u8>,
+
+    /// Indexed event topics.
+    topics: Vec<Vec<u8>>,
+}
+
+/// Discovered events of a single block, which are not yet inserted into the database.
+#[derive(Clone)]
+struct PendingBlock {
+    /// Block number.
+    number: i64,
+
+    /// Block timestamp.
+    timestamp: PrimitiveDateTime,
+
+    /// Events discovered inside of the block.
+    events: Vec<event::BlockEvent>,
+}
+
+/// Process backfill jobs of an RPC node.
+///
+/// # Details
+///
+/// [`backfill`] function periodically checks for new backfill jobs
+/// created by an API server and rescans the requested block ranges
+/// for events related to the job's contract account.
+///
+/// Discovered events are inserted only if they were not discovered previously,
+/// thus it's safe to rescan the same block range multiple times.
+///
+/// The last processed block is saved along with every inserted batch of events,
+/// thus jobs that were interrupted, for example by a restart or a recoverable error,
+/// are resumed from the last checkpoint instead of being rescanned from the start.
+/// Recoverable errors are returned without failing the current job.
+///
+/// See [`EventClient`] for details on how job polling and batching can be configured.
+pub async fn backfill(
+    database: DatabaseConnection,
+    name: String,
+    config: &EventClient,
+) -> Result<(), BackfillError> {
+    let node = node::Entity::find()
+        .filter(node::Column::Name.eq(&name))
+        .one(&database)
+        .await?
+        .ok_or(BackfillError::NodeNotFound)?;
+
+    let client = JsonrpseeClient::new(&node.url).map_err(substrate_api_client::Error::RpcClient)?;
+    let api = Api::<PolkadotConfig, _>::new(client).await?;
+
+    let mut metadata_cache = MetadataCache::new();
+
+    let poll_interval = Duration::from_secs(config.poll_interval.get());
+
+    info!("waiting for new backfill jobs");
+
+    loop {
+        let job = backfill_job::Entity::find()
+            .filter(backfill_job::Column::NodeId.eq(node.id))
+            .filter(backfill_job::Column::Status.eq(backfill_job::Status::New))
+            .order_by_asc(backfill_job::Column::Id)
+            .one(&database)
+            .await?;
+
+        let Some(job) = job else {
+            tokio::time::sleep(poll_interval).await;
+            continue;
+        };
+
+        info!(id = %job.id, "processing backfill job");
+
+        let result = process_job(&job, &database, &api, &mut metadata_cache, config).await;
+        let status = job_status(job.id, result)?;
+
+        backfill_job::Entity::update_many()
+            .filter(backfill_job::Column::Id.eq(job.id))
+            .col_expr(backfill_job::Column::Status, status.into())
+            .exec(&database)
+            .await?;
+    }
+}
+
+/// Get the status of a processed backfill job.
+///
+/// Jobs interrupted by a recoverable error are kept as new and the error is returned,
+/// thus such jobs are resumed from the last checkpoint once the backfill is restarted.
+fn job_status(
+    id: i64,
+    result: Result<(), BackfillError>,
+) -> Result<backfill_job::Status, BackfillError> {
+    match result {
+        Ok(()) => Ok(backfill_job::Status::Completed),
+        Err(err) if err.is_recoverable() => {
+            warn!(%id, %err, "backfill job interrupted");
+            Err(err)
+        }
+        Err(err) => {
+            error!(%id, %err, "backfill job failed");
+            Ok(backfill_job::Status::Failed)
+        }
+    }
+}
+
+/// Rescan the block range of the provided backfill job.
+async fn process_job<C: Request>(
+    job: &backfill_job::Model,
+    database: &DatabaseConnection,
+    api: &Api<PolkadotConfig, C>,
+    metadata_cache: &mut MetadataCache,
+    config: &EventClient,
+) -> Result<(), BackfillError> {
+    let from_block = job.resume_block();
+
+    if from_block > job.to_block {
+        return Ok(());
+    }
+
+    if job.last_processed_block.is_some() {
+        info!(id = %job.id, %from_block, "resuming backfill job from the last checkpoint");
+    }
+
+    let stream = block_mapping_stream(from_block as u32..=job.to_block as u32, api);
+
+    pin_mut!(stream);
+
+    let mut pending = Vec::new();
+    let mut pending_blocks = 0;
+    let mut pending_events = 0;
+    let mut last_block = None;
+
+    while let Some((block_number, block_hash)) = stream.try_next().await? {
+        let metadata = metadata_cache.metadata(api, block_hash).await?;
+        let events = rpc::events(api, block_hash, metadata.clone()).await?;
+
+        let discovered = account_events(&events, &job.account)?;
+
+        if !discovered.is_empty() {
+            let block_events = discovered
+                .into_iter()
+                .map(|discovered_event| {
+                    Ok(event::BlockEvent {
+                        account: job.account.clone(),
+                        event_type: discovered_event.event_type,
+                        body: serde_json::to_string(&discovered_event.body)?,
+                        raw_body: Some(discovered_event.raw_body),
+                        event_index: discovered_event.index as i32,
+                        topics: discovered_event.topics,
+                    })
+                })
+                .collect::<Result<Vec<_>, BackfillError>>()?;
+
+            pending_events += block_events.len();
+
+            pending.push(PendingBlock {
+                number: block_number.into(),
+                timestamp: block_timestamp(api, block_hash).await?,
+                events: block_events,
+            });
+        }
+
+        pending_blocks += 1;
+        last_block = Some(i64::from(block_number));
+
+        if pending_blocks >= config.blocks_per_batch.get()
+            || pending_events >= config.max_events_per_batch.get()
+        {
+            insert_pending(database, job, mem::take(&mut pending), last_block.take()).await?;
+            pending_blocks = 0;
+            pending_events = 0;
+        }
+    }
+
+    insert_pending(database, job, pending, last_block).await
+}
+
+/// Insert events of the provided blocks and save the backfill job checkpoint
+/// using a single database transaction.
+async fn insert_pending(
+    database: &DatabaseConnection,
+    job: &backfill_job::Model,
+    blocks: Vec<PendingBlock>,
+    last_block: Option<i64>,
+) -> Result<(), BackfillError> {
+    let Some(last_block) = last_block else {
+        return Ok(());
+    };
+
+    let (id, node_id) = (job.id, job.node_id);
+
+    database
+        .retry_transaction::<_, _, BackfillError>(TRANSACTION_RETRIES, |txn| {
+            let blocks = blocks.clone();
+
+            Box::pin(async move {
+                for block in blocks {
+                    event::insert_block_events(
+                        txn,
+                        node_id,
+                        block.number,
+                        block.timestamp,
+                        block.events,
+                    )
+                    .await?;
+                }
+
+                backfill_job::checkpoint(txn, id, last_block).await?;
+
+                Ok(())
+            })
+        })
+        .await
+        .into_raw_result()
+}
+
+/// Find all events related to the provided contract account.
+fn account_events(
+    events: &Events<H256>,
+    account: &[u8],
+) -> Result<Vec<DiscoveredEvent>, BackfillError> {
+    let mut discovered = Vec::new();
+
+    for details in events.iter() {
+        let details = details.map_err(substrate_api_client::Error::NodeApi)?;
+
+        if details.pallet_name() != Instantiated::PALLET {
+            continue;
+        }
+
+        let index = details.index();
+        let mut push = |event_type, body, topics| {
+            discovered.push(DiscoveredEvent {
+                index,
+                event_type,
+                body,
+                raw_body: details.field_bytes().to_vec(),
+                topics,
+            })
+        };
+
+        match details.variant_name() {
+            Instantiated::EVENT => {
+                let Instantiated { deployer, contract } =
+                    Instantiated::decode(&mut details.field_bytes())?;
+
+                if contract.as_slice() == account {
+                    push(
+                        event::EventType::Instantiation,
+                        event::EventBody::Instantiation,
+                        Vec::new(),
+                    );
+                    push(
+                        event::EventType::OwnershipChange,
+                        event::EventBody::OwnershipChange {
+                            new_owner: hex::encode(deployer.as_slice()),
+                        },
+                        Vec::new(),
+                    );
+                }
+            }
+            ContractCodeUpdated::EVENT => {
+                let ContractCodeUpdated {
+                    contract,
+                    new_code_hash,
+                    ..
+                } = ContractCodeUpdated::decode(&mut details.field_bytes())?;
+
+                if contract.as_slice() == account {
+                    push(
+                        event::EventType::CodeHashUpdate,
+                        event::EventBody::CodeHashUpdate {
+                            new_code_hash: hex::encode(new_code_hash),
+                        },
+                        Vec::new(),
+                    );
+                }
+            }
+            Terminated::EVENT => {
+                let Terminated { contract, .. } = Terminated::decode(&mut details.field_bytes())?;
+
+                if contract.as_slice() == account {
+                    push(
+                        event::EventType::Termination,
+                        event::EventBody::Termination,
+                        Vec::new(),
+                    );
+                }
+            }
+            ContractEmitted::EVENT => {
+                let emission = decode_contract_emission(details.field_bytes(), details.topics())?;
+
+                if emission.contract.as_slice() == account {
+                    push(
+                        event::EventType::ContractEmission,
+                        emission.body,
+                        emission.topics,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(discovered)
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{backfill_job, DbErr};
+
+    use super::BackfillError;
+
+    #[test]
+    fn job_status() {
+        assert_eq!(
+            super::job_status(1, Ok(())).unwrap(),
+            backfill_job::Status::Completed
+        );
+
+        assert_eq!(
+            super::job_status(1, Err(BackfillError::NodeNotFound)).unwrap(),
+            backfill_job::Status::Failed
+        );
+
+        // Jobs interrupted by a lost database connection must be resumed.
+        assert!(matches!(
+            super::job_status(
+                1,
+                Err(BackfillError::DatabaseError(DbErr::ConnectionAcquire))
+            ),
+            Err(BackfillError::DatabaseError(DbErr::ConnectionAcquire))
+        ));
+    }
+}
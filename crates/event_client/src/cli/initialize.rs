@@ -28,6 +28,10 @@ pub enum InitializeError {
     /// Invalid payment contract account id was provided.
     #[display(fmt = "invalid account id for payment contract")]
     InvalidPaymentAddress,
+
+    /// The provided start block is ahead of the latest block.
+    #[display(fmt = "start block is ahead of the chain head")]
+    StartBlockAhead,
 }
 
 /// Initialize an RPC node from the provided data.
@@ -42,11 +46,16 @@ pub enum InitializeError {
 /// since [`initialize`] function initializes node information too.
 ///
 /// No traversal of previous blocks is being done by this command.
+///
+/// If `start_block` is provided, the watcher will discover events starting from
+/// that block instead of the latest one. Events that occured before
+/// the start block are not archived.
 pub async fn initialize(
     database: DatabaseConnection,
     name: String,
     url: String,
     payment_address: Option<String>,
+    start_block: Option<u32>,
 ) -> Result<(), InitializeError> {
     let client = JsonrpseeClient::new(&url).map_err(substrate_api_client::Error::RpcClient)?;
     let api = Api::new(client).await?;
@@ -57,9 +66,17 @@ pub async fn initialize(
         .await?
         .expect("at least one block is expected");
 
+    let confirmed_block = match start_block {
+        Some(start_block) if start_block > latest_block.header.number => {
+            return Err(InitializeError::StartBlockAhead)
+        }
+        Some(start_block) => start_block.saturating_sub(1),
+        None => latest_block.header.number,
+    };
+
     let block_hash = latest_block.hash();
 
-    let metadata = metadata_cache.metadata(&api, block_hash).await?;
+    let (runtime_version, metadata) = metadata_cache.versioned_metadata(&api, block_hash).await?;
 
     let payment_address = payment_address
         .as_deref()
@@ -75,7 +92,9 @@ pub async fn initialize(
                     name: ActiveValue::Set(name),
                     url: ActiveValue::Set(url),
                     payment_contract: ActiveValue::Set(payment_address),
-                    confirmed_block: ActiveValue::Set(latest_block.header.number as i64),
+                    confirmed_block: ActiveValue::Set(confirmed_block as i64),
+                    spec_name: ActiveValue::Set(Some(runtime_version.spec_name.to_string())),
+                    spec_version: ActiveValue::Set(Some(runtime_version.spec_version.into())),
                     ..Default::default()
                 })
                 .on_conflict(
@@ -84,6 +103,8 @@ pub async fn initialize(
                             node::Column::Url,
                             node::Column::PaymentContract,
                             node::Column::ConfirmedBlock,
+                            node::Column::SpecName,
+                            node::Column::SpecVersion,
                         ])
                         .to_owned(),
                 )
@@ -106,6 +127,7 @@ pub async fn initialize(
                         code::ActiveModel {
                             hash: ActiveValue::Set(extract_code_hash(key)),
                             code: ActiveValue::Set(wasm),
+                            ..Default::default()
                         }
                     }))
                     .on_conflict(
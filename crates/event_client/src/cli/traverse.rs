@@ -10,8 +10,8 @@ use common::rpc::{
     Instantiated, MetadataCache,
 };
 use db::{
-    contract, node, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
-    TransactionErrorExt, TransactionTrait,
+    contract, node, sea_query::Expr, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, RetryTransactionExt, TransactionErrorExt, TRANSACTION_RETRIES,
 };
 use derive_more::{Display, Error, From};
 use futures_util::{pin_mut, TryStreamExt};
@@ -64,19 +64,33 @@ pub async fn traverse(database: DatabaseConnection, name: String) -> Result<(),
 
     while let Some((_, block_hash)) = stream.try_next().await? {
         if let Ok(block_data) = parse_block(&api, block_hash, &mut metadata_cache).await {
+            // Owned values are cloned for each transaction attempt.
+            let instantiations = block_data
+                .instantiations
+                .iter()
+                .map(|instantiation| {
+                    (
+                        instantiation.deployer.as_slice().to_vec(),
+                        instantiation.contract.as_slice().to_vec(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
             database
-                .transaction::<_, _, TraverseError>(|txn| {
+                .retry_transaction::<_, _, TraverseError>(TRANSACTION_RETRIES, |txn| {
+                    let instantiations = instantiations.clone();
+                    let node_id = node.id;
+
                     Box::pin(async move {
-                        for instantiation in block_data.instantiations {
+                        for (deployer, address) in instantiations {
                             contract::Entity::update_many()
+                                .col_expr(contract::Column::Owner, deployer.into())
                                 .col_expr(
-                                    contract::Column::Owner,
-                                    (instantiation.deployer.as_slice()).into(),
-                                )
-                                .filter(contract::Column::NodeId.eq(node.id))
-                                .filter(
-                                    contract::Column::Address.eq(instantiation.contract.as_slice()),
+                                    contract::Column::UpdatedAt,
+                                    Expr::current_timestamp().into(),
                                 )
+                                .filter(contract::Column::NodeId.eq(node_id))
+                                .filter(contract::Column::Address.eq(address))
                                 .exec(txn)
                                 .await?;
                         }
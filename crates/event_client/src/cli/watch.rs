@@ -1,28 +1,39 @@
-use std::{future::ready, iter};
+use std::{collections::HashMap, future::ready, num::NonZeroUsize, thread};
 
-use common::rpc::{
-    self,
-    sp_core::ByteArray,
-    substrate_api_client::{
+use common::{
+    config::RpcRetry,
+    rpc::{
         self,
-        ac_node_api::Metadata,
-        ac_primitives::{Block, Config, Header, PolkadotConfig},
-        rpc::{HandleSubscription, JsonrpseeClient, Request},
-        Api, GetChainInfo, SubscribeChain,
+        parity_scale_codec::{self, Decode},
+        sp_core::{ByteArray, H256},
+        sp_version::RuntimeVersion,
+        substrate_api_client::{
+            self,
+            ac_node_api::{Events, Metadata, StaticEvent},
+            ac_primitives::{Block, Config, Header, PolkadotConfig},
+            rpc::{HandleSubscription, JsonrpseeClient, Request},
+            Api, GetChainInfo, SubscribeChain,
+        },
+        CodeStored, ContractCodeUpdated, ContractEmitted, Instantiated, MetadataCache, Terminated,
     },
-    CodeStored, ContractCodeUpdated, Instantiated, MetadataCache, Terminated,
 };
 use db::{
-    code, contract, event, node, sea_query::OnConflict, ActiveModelTrait, ActiveValue, ColumnTrait,
-    DatabaseConnection, DbErr, EntityTrait, OffsetDateTime, PrimitiveDateTime, QueryFilter,
-    TransactionErrorExt, TransactionTrait,
+    code, contract, event, node,
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    QueryFilter, QuerySelect, RetryTransactionExt, TransactionErrorExt, TRANSACTION_RETRIES,
 };
 use derive_more::{Display, Error, From};
 use futures_util::{pin_mut, stream, TryStreamExt};
 use itertools::Itertools;
-use tracing::{debug, info};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
 
-use crate::utils::block_mapping_stream;
+use crate::{
+    retry::{retry, Backoff, Recoverable},
+    supervisor::supervise,
+    utils::{block_mapping_stream, block_timestamp, decode_contract_emission},
+};
 
 /// Errors that may occur during the watch process.
 #[derive(Debug, Display, Error, From)]
@@ -37,9 +48,44 @@ pub enum WatchError {
     /// JSON serialization error.
     JsonError(serde_json::Error),
 
+    /// SCALE decoding error.
+    #[display(fmt = "unable to decode contract event: {}", _0)]
+    DecodeError(parity_scale_codec::Error),
+
     /// The provided node name is incorrect.
     #[display(fmt = "node not found")]
     NodeNotFound,
+
+    /// The provided node is already watched by another event client instance.
+    #[display(fmt = "node is already watched by another event client")]
+    NodeLocked,
+
+    /// More nodes are registered than can be watched at the same time.
+    #[display(
+        fmt = "{} nodes are registered, which exceeds the limit of {} concurrently watched nodes",
+        count,
+        limit
+    )]
+    #[from(ignore)]
+    TooManyNodes {
+        /// Count of registered nodes.
+        count: usize,
+
+        /// Maximum count of concurrently watched nodes.
+        limit: NonZeroUsize,
+    },
+}
+
+impl Recoverable for WatchError {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            WatchError::DatabaseError(DbErr::ConnectionAcquire | DbErr::Conn(_)) => true,
+            WatchError::RpcError(err) => err.is_recoverable(),
+            // Node may be released by another event client at any time.
+            WatchError::NodeLocked => true,
+            _ => false,
+        }
+    }
 }
 
 /// Watch an RPC node for new smart contract-related events.
@@ -54,13 +100,128 @@ pub enum WatchError {
 ///
 /// As soon as all missed blocks are processed, [`watch`] will start listening
 /// and processing only new blocks from now on.
-pub async fn watch(database: DatabaseConnection, name: String) -> Result<(), WatchError> {
+///
+/// If the connection to an RPC node is lost, [`watch`] reconnects with an exponential
+/// backoff and resumes from the confirmed block. Permanent errors are returned immediately.
+///
+/// An advisory lock of the node is held while watching, to prevent multiple event client
+/// instances from advancing the same confirmed block. If the node is already locked,
+/// [`watch`] fails without waiting. The lock is released as soon as [`watch`] returns
+/// or is cancelled.
+pub async fn watch(
+    database: DatabaseConnection,
+    name: String,
+    config: &RpcRetry,
+) -> Result<(), WatchError> {
     let mut node = node::Entity::find()
         .filter(node::Column::Name.eq(&name))
         .one(&database)
         .await?
         .ok_or(WatchError::NodeNotFound)?;
 
+    let _lock = node::try_lock(&database, node.id)
+        .await?
+        .ok_or(WatchError::NodeLocked)?;
+
+    let backoff = Backoff::new(config);
+
+    let mut attempt = 0;
+
+    loop {
+        let confirmed_block = node.confirmed_block;
+
+        let result = follow(&mut node, &database, &backoff).await;
+
+        // Reset the backoff as soon as the previous connection made any progress.
+        if node.confirmed_block > confirmed_block {
+            attempt = 0;
+        }
+
+        match result {
+            Ok(()) => warn!("block subscription ended"),
+            Err(err) if err.is_recoverable() => warn!(%err, "connection to rpc node failed"),
+            Err(err) => return Err(err),
+        }
+
+        let delay = backoff.delay(attempt);
+        info!(?delay, "reconnecting to rpc node");
+        tokio::time::sleep(delay).await;
+        attempt = attempt.saturating_add(1);
+    }
+}
+
+/// Watch all registered RPC nodes for new smart contract-related events.
+///
+/// # Details
+///
+/// Each node is watched with a separate [`watch`] task, which maintains its own
+/// confirmed block and commits processed blocks using its own transactions,
+/// thus a slow or unavailable node does not block other nodes.
+///
+/// Watchers never stop on their own, thus all registered nodes have to be watched
+/// at the same time. [`watch_all`] refuses to start if more than `max_concurrent_nodes`
+/// nodes are registered.
+///
+/// Watchers that fail with a recoverable error, including nodes that are locked
+/// by another event client instance, are restarted with an exponential backoff.
+/// Watchers that fail with a permanent error are stopped without affecting other nodes,
+/// and the first of such errors is returned as soon as all watchers are stopped.
+///
+/// Nodes are loaded once, thus nodes that are initialized afterwards
+/// are picked up only after a restart.
+pub async fn watch_all(
+    database: DatabaseConnection,
+    config: &RpcRetry,
+    max_concurrent_nodes: NonZeroUsize,
+) -> Result<(), WatchError> {
+    let nodes = load_nodes(&database, max_concurrent_nodes).await?;
+
+    info!(count = nodes.len(), "watching all registered nodes");
+
+    let backoff = Backoff::new(config);
+    let config = config.clone();
+
+    supervise(nodes, max_concurrent_nodes, &backoff, move |name| {
+        let database = database.clone();
+        let config = config.clone();
+
+        async move { watch(database, name, &config).await }
+    })
+    .await
+}
+
+/// Load identifiers and names of all registered nodes,
+/// ensuring that there are no more than `limit` of them.
+pub(crate) async fn load_nodes(
+    database: &DatabaseConnection,
+    limit: NonZeroUsize,
+) -> Result<Vec<(i64, String)>, WatchError> {
+    let nodes = node::Entity::find()
+        .select_only()
+        .columns([node::Column::Id, node::Column::Name])
+        .into_tuple::<(i64, String)>()
+        .all(database)
+        .await?;
+
+    if nodes.len() > limit.get() {
+        return Err(WatchError::TooManyNodes {
+            count: nodes.len(),
+            limit,
+        });
+    }
+
+    Ok(nodes)
+}
+
+/// Connect to an RPC node and process blocks starting from the confirmed block.
+///
+/// The provided [`node::Model`] is kept up-to-date with the latest confirmed block,
+/// even if an error occurs.
+async fn follow(
+    node: &mut node::Model,
+    database: &DatabaseConnection,
+    backoff: &Backoff,
+) -> Result<(), WatchError> {
     let client = JsonrpseeClient::new(&node.url).map_err(substrate_api_client::Error::RpcClient)?;
     let api = Api::<PolkadotConfig, _>::new(client).await?;
 
@@ -70,39 +231,79 @@ pub async fn watch(database: DatabaseConnection, name: String) -> Result<(), Wat
 
     // Attempt to catch-up to the latest block.
     info!("attempting to catch-up to the latest block");
-    let latest = api
-        .get_block(None)
+    let latest = retry(backoff, || api.get_block(None))
         .await?
         .expect("at least one block is expected");
     let stream = block_mapping_stream(
         (node.confirmed_block + 1) as u32..=latest.header.number,
         &api,
     )
-    .try_filter_map(|(_, hash)| rpc::block(&api, Some(hash)));
+    .try_filter_map(|(_, hash)| {
+        let api = &api;
+        retry(backoff, move || rpc::block(api, Some(hash)))
+    });
 
     pin_mut!(stream);
 
     while let Some(block) = stream.try_next().await? {
         debug!(block_number = %block.header().number(), "found a block to catch-up to");
-        let metadata = metadata_cache.metadata(&api, block.hash()).await?;
-        node = process_block(node, &database, &api, block.header(), metadata).await?;
+        let (runtime_version, metadata) = metadata_cache
+            .versioned_metadata(&api, block.hash())
+            .await?;
+        *node = process_block(
+            node.clone(),
+            database,
+            &api,
+            backoff,
+            block.header(),
+            &runtime_version,
+            metadata,
+        )
+        .await?;
     }
 
     // Proceed with the subscription, since an attempt to traverse missed blocks was already made.
     info!("processing new blocks from now on");
 
+    // Block subscription can only be read synchronously, thus it's read from a dedicated thread
+    // to keep other tasks, including watchers of other nodes, running in the meantime.
+    //
+    // Unlike blocking tasks, dedicated threads are not awaited during the runtime shutdown,
+    // which would otherwise be delayed until the next block is finalized.
+    let (header_sender, mut headers) = mpsc::channel(1);
+
+    thread::spawn(move || {
+        while let Some(header) = subscription.next() {
+            // Headers are no longer received as soon as the block processing fails.
+            if header_sender.blocking_send(header).is_err() {
+                break;
+            }
+        }
+    });
+
     let confirmed_block = node.confirmed_block as u32;
-    let mut subscription_iter =
-        iter::from_fn(|| subscription.next()).filter_ok(|header| header.number() > confirmed_block);
-
-    while let Some(header) = subscription_iter
-        .next()
-        .transpose()
-        .map_err(substrate_api_client::Error::RpcClient)?
-    {
+
+    while let Some(header) = headers.recv().await {
+        let header = header.map_err(substrate_api_client::Error::RpcClient)?;
+
+        if header.number() <= confirmed_block {
+            continue;
+        }
+
         debug!(block_number = %header.number(), "found new block");
-        let metadata = metadata_cache.metadata(&api, header.hash()).await?;
-        node = process_block(node, &database, &api, &header, metadata).await?;
+        let (runtime_version, metadata) = metadata_cache
+            .versioned_metadata(&api, header.hash())
+            .await?;
+        *node = process_block(
+            node.clone(),
+            database,
+            &api,
+            backoff,
+            &header,
+            &runtime_version,
+            metadata,
+        )
+        .await?;
     }
 
     Ok(())
@@ -112,82 +313,208 @@ pub async fn watch(database: DatabaseConnection, name: String) -> Result<(), Wat
 /// block subscription.
 ///
 /// Returns new [`node::Model`], which represents an updated node
-/// with up-to-date confirmed block counter.
+/// with up-to-date confirmed block counter and runtime version.
 async fn process_block<C: Request>(
     node: node::Model,
     database: &DatabaseConnection,
     api: &Api<PolkadotConfig, C>,
+    backoff: &Backoff,
     block_header: &<PolkadotConfig as Config>::Header,
+    runtime_version: &RuntimeVersion,
     metadata: &Metadata,
 ) -> Result<node::Model, WatchError> {
     let mut active_node: node::ActiveModel = node.clone().into();
 
+    let spec_name = runtime_version.spec_name.to_string();
+    let spec_version = i64::from(runtime_version.spec_version);
+
+    if node.spec_name.as_ref() != Some(&spec_name) || node.spec_version != Some(spec_version) {
+        info!(%spec_name, spec_version, "detected node runtime version change");
+
+        active_node.spec_name = ActiveValue::Set(Some(spec_name));
+        active_node.spec_version = ActiveValue::Set(Some(spec_version));
+    }
+
     let block_hash = block_header.hash();
     let block_number = block_header.number();
 
-    let block_millis = rpc::block_timestamp_millis(api, block_hash).await?;
-    let raw_timestamp = unix_ts::Timestamp::from_millis(block_millis);
-    let offset_timestamp = OffsetDateTime::from_unix_timestamp(raw_timestamp.seconds())
-        .expect("invalid timestamp was provided");
-    let block_timestamp = PrimitiveDateTime::new(offset_timestamp.date(), offset_timestamp.time());
+    let block_timestamp = retry(backoff, || block_timestamp(api, block_hash)).await?;
 
-    let events = rpc::events(api, block_hash, metadata.clone()).await?;
+    let events = retry(backoff, || rpc::events(api, block_hash, metadata.clone())).await?;
 
     let code_uploads = stream::iter(events.find::<CodeStored>())
         .err_into()
         .and_then(|CodeStored { code_hash }| async move {
-            rpc::pristine_code(api, block_hash, code_hash, metadata)
-                .await
-                .map(|code| (code_hash.0, code))
+            retry(backoff, || {
+                rpc::pristine_code(api, block_hash, code_hash, metadata)
+            })
+            .await
+            .map(|code| (code_hash.0, code))
         })
         .try_filter_map(|(hash, code)| ready(Ok(code.map(|val| (hash, val)))))
         .map_ok(|(hash, code)| code::ActiveModel {
             hash: ActiveValue::Set(hash.to_vec()),
             code: ActiveValue::Set(code),
+            ..Default::default()
         })
         .try_collect::<Vec<_>>()
         .await?;
 
-    let instantiations = stream::iter(events.find::<Instantiated>())
-        .err_into()
-        .and_then(|Instantiated { deployer, contract }| async move {
-            rpc::contract_info_of(api, block_hash, &contract, metadata)
-                .await
-                .map(|info| (contract, deployer, info))
+    let instantiations = stream::iter(find_indexed::<Instantiated>(&events))
+        .and_then(|(index, Instantiated { deployer, contract })| async move {
+            retry(backoff, || {
+                rpc::contract_info_of(api, block_hash, &contract, metadata)
+            })
+            .await
+            .map(|info| (index, contract, deployer, info))
+            .map_err(WatchError::from)
         })
-        .try_filter_map(|(contract, deployer, info)| {
-            ready(Ok(info.map(|val| (contract, deployer, val))))
+        .try_filter_map(|(index, contract, deployer, info)| {
+            ready(Ok(info.map(|val| (index, contract, deployer, val))))
         })
-        .map_ok(|(contract, deployer, info)| contract::ActiveModel {
-            code_hash: ActiveValue::Set(info.code_hash.0.to_vec()),
-            node_id: ActiveValue::Set(node.id),
-            address: ActiveValue::Set(contract.as_slice().to_vec()),
-            owner: ActiveValue::Set(Some(deployer.as_slice().to_vec())),
-            ..Default::default()
+        .map_ok(|(index, contract, deployer, info)| {
+            (
+                index,
+                contract::ActiveModel {
+                    code_hash: ActiveValue::Set(info.code_hash.0.to_vec()),
+                    node_id: ActiveValue::Set(node.id),
+                    address: ActiveValue::Set(contract.as_slice().to_vec()),
+                    owner: ActiveValue::Set(Some(deployer.as_slice().to_vec())),
+                    instantiation_block: ActiveValue::Set(Some(block_number as i64)),
+                    instantiated_at: ActiveValue::Set(Some(block_timestamp)),
+                    ..Default::default()
+                },
+            )
         })
         .try_collect::<Vec<_>>()
         .await?;
 
-    let code_hash_updates: Vec<_> = events
-        .find::<ContractCodeUpdated>()
+    let code_hash_updates: Vec<_> = find_indexed::<ContractCodeUpdated>(&events)
         .map_ok(
-            |ContractCodeUpdated {
-                 contract,
-                 new_code_hash,
-                 ..
-             }| { (contract, new_code_hash) },
+            |(
+                index,
+                ContractCodeUpdated {
+                    contract,
+                    new_code_hash,
+                    ..
+                },
+            )| { (index, contract, new_code_hash) },
         )
-        .try_collect()
-        .map_err(substrate_api_client::Error::NodeApi)?;
+        .try_collect()?;
 
-    let terminations: Vec<_> = events
-        .find::<Terminated>()
-        .map_ok(|Terminated { contract, .. }| contract)
-        .try_collect()
-        .map_err(substrate_api_client::Error::NodeApi)?;
+    let emissions = events
+        .iter()
+        .filter_ok(|details| {
+            details.pallet_name() == ContractEmitted::PALLET
+                && details.variant_name() == ContractEmitted::EVENT
+        })
+        .map(|details| {
+            let details = details.map_err(substrate_api_client::Error::NodeApi)?;
+
+            Ok((
+                details.index(),
+                decode_contract_emission(details.field_bytes(), details.topics())?,
+            ))
+        })
+        .collect::<Result<Vec<_>, WatchError>>()?;
+
+    let terminations: Vec<_> = find_indexed::<Terminated>(&events)
+        .map_ok(|(index, Terminated { contract, .. })| (index, contract))
+        .try_collect()?;
+
+    // Raw event fields are stored along with decoded event bodies,
+    // which allows clients to decode events using their own metadata.
+    let raw_bodies: HashMap<u32, Vec<u8>> = events
+        .iter()
+        .filter_map(Result::ok)
+        .filter(|details| details.pallet_name() == Instantiated::PALLET)
+        .map(|details| (details.index(), details.field_bytes().to_vec()))
+        .collect();
+    let raw_body = |index: u32| raw_bodies.get(&index).cloned();
+
+    let mut block_events = Vec::new();
+
+    if !instantiations.is_empty() {
+        let instantiation_body = serde_json::to_string(&event::EventBody::Instantiation)?;
+
+        block_events.extend(
+            instantiations
+                .iter()
+                .map(|(index, model)| event::BlockEvent {
+                    account: model.address.clone().unwrap(),
+                    event_type: event::EventType::Instantiation,
+                    body: instantiation_body.clone(),
+                    raw_body: raw_body(*index),
+                    event_index: *index as i32,
+                    topics: Vec::new(),
+                }),
+        );
+
+        for (index, model) in &instantiations {
+            block_events.push(event::BlockEvent {
+                account: model.address.clone().unwrap(),
+                event_type: event::EventType::OwnershipChange,
+                body: serde_json::to_string(&event::EventBody::OwnershipChange {
+                    new_owner: hex::encode(model.owner.clone().unwrap().unwrap()),
+                })?,
+                raw_body: raw_body(*index),
+                event_index: *index as i32,
+                topics: Vec::new(),
+            });
+        }
+    }
+
+    for (index, contract, new_code_hash) in &code_hash_updates {
+        block_events.push(event::BlockEvent {
+            account: contract.as_slice().to_vec(),
+            event_type: event::EventType::CodeHashUpdate,
+            body: serde_json::to_string(&event::EventBody::CodeHashUpdate {
+                new_code_hash: hex::encode(new_code_hash),
+            })?,
+            raw_body: raw_body(*index),
+            event_index: *index as i32,
+            topics: Vec::new(),
+        });
+    }
+
+    for (index, emission) in emissions {
+        block_events.push(event::BlockEvent {
+            account: emission.contract.as_slice().to_vec(),
+            event_type: event::EventType::ContractEmission,
+            body: serde_json::to_string(&emission.body)?,
+            raw_body: raw_body(index),
+            event_index: index as i32,
+            topics: emission.topics,
+        });
+    }
+
+    if !terminations.is_empty() {
+        let termination_body = serde_json::to_string(&event::EventBody::Termination)?;
+
+        block_events.extend(
+            terminations
+                .iter()
+                .map(|(index, contract)| event::BlockEvent {
+                    account: contract.as_slice().to_vec(),
+                    event_type: event::EventType::Termination,
+                    body: termination_body.clone(),
+                    raw_body: raw_body(*index),
+                    event_index: *index as i32,
+                    topics: Vec::new(),
+                }),
+        );
+    }
 
     database
-        .transaction(|txn| {
+        .retry_transaction::<_, _, WatchError>(TRANSACTION_RETRIES, |txn| {
+            // Transaction may be retried, thus each attempt operates on its own copy of values.
+            let code_uploads = code_uploads.clone();
+            let block_events = block_events.clone();
+            let instantiations = instantiations.clone();
+            let code_hash_updates = code_hash_updates.clone();
+            let terminations = terminations.clone();
+            let mut active_node = active_node.clone();
+
             Box::pin(async move {
                 if !code_uploads.is_empty() {
                     code::Entity::insert_many(code_uploads)
@@ -200,54 +527,43 @@ async fn process_block<C: Request>(
                         .await?;
                 }
 
-                if !instantiations.is_empty() {
-                    let instantiation_body =
-                        serde_json::to_string(&event::EventBody::Instantiation)?;
-
-                    event::Entity::insert_many(instantiations.iter().map(|model| {
-                        event::ActiveModel {
-                            node_id: ActiveValue::Set(node.id),
-                            account: model.address.clone(),
-                            event_type: ActiveValue::Set(event::EventType::Instantiation),
-                            body: ActiveValue::Set(instantiation_body.clone()),
-                            block_timestamp: ActiveValue::Set(block_timestamp),
-                            ..Default::default()
-                        }
-                    }))
-                    .exec_without_returning(txn)
-                    .await?;
+                // Empty blocks only advance the confirmed block counter.
+                event::insert_block_events(
+                    txn,
+                    node.id,
+                    block_number as i64,
+                    block_timestamp,
+                    block_events,
+                )
+                .await?;
 
-                    contract::Entity::insert_many(instantiations)
-                        .on_conflict(
-                            OnConflict::columns([
-                                contract::Column::NodeId,
-                                contract::Column::Address,
+                if !instantiations.is_empty() {
+                    contract::Entity::insert_many(
+                        instantiations.into_iter().map(|(_, model)| model),
+                    )
+                    .on_conflict(
+                        OnConflict::columns([contract::Column::NodeId, contract::Column::Address])
+                            .update_columns([
+                                contract::Column::CodeHash,
+                                contract::Column::Owner,
+                                contract::Column::InstantiationBlock,
+                                contract::Column::InstantiatedAt,
+                                // Update time is taken from the default value of the inserted row.
+                                contract::Column::UpdatedAt,
                             ])
-                            .update_column(contract::Column::CodeHash)
                             .to_owned(),
-                        )
-                        .exec_without_returning(txn)
-                        .await?;
-                }
-
-                for (contract, new_code_hash) in code_hash_updates {
-                    event::ActiveModel {
-                        node_id: ActiveValue::Set(node.id),
-                        account: ActiveValue::Set(contract.as_slice().to_vec()),
-                        event_type: ActiveValue::Set(event::EventType::CodeHashUpdate),
-                        body: ActiveValue::Set(serde_json::to_string(
-                            &event::EventBody::CodeHashUpdate {
-                                new_code_hash: hex::encode(new_code_hash),
-                            },
-                        )?),
-                        block_timestamp: ActiveValue::Set(block_timestamp),
-                        ..Default::default()
-                    }
-                    .insert(txn)
+                    )
+                    .exec_without_returning(txn)
                     .await?;
+                }
 
+                for (_, contract, new_code_hash) in code_hash_updates {
                     contract::Entity::update_many()
                         .col_expr(contract::Column::CodeHash, (&new_code_hash[..]).into())
+                        .col_expr(
+                            contract::Column::UpdatedAt,
+                            Expr::current_timestamp().into(),
+                        )
                         .filter(contract::Column::NodeId.eq(node.id))
                         .filter(contract::Column::Address.eq(contract.as_slice()))
                         .exec(txn)
@@ -255,26 +571,11 @@ async fn process_block<C: Request>(
                 }
 
                 if !terminations.is_empty() {
-                    let termination_body = serde_json::to_string(&event::EventBody::Termination)?;
-
-                    event::Entity::insert_many(terminations.iter().map(|model| {
-                        event::ActiveModel {
-                            node_id: ActiveValue::Set(node.id),
-                            account: ActiveValue::Set(model.as_slice().to_vec()),
-                            event_type: ActiveValue::Set(event::EventType::Termination),
-                            body: ActiveValue::Set(termination_body.clone()),
-                            block_timestamp: ActiveValue::Set(block_timestamp),
-                            ..Default::default()
-                        }
-                    }))
-                    .exec_without_returning(txn)
-                    .await?;
-
                     contract::Entity::delete_many()
                         .filter(contract::Column::NodeId.eq(node.id))
                         .filter(
                             contract::Column::Address
-                                .is_in(terminations.iter().map(|val| val.as_slice())),
+                                .is_in(terminations.iter().map(|(_, val)| val.as_slice())),
                         )
                         .exec(txn)
                         .await?;
@@ -288,3 +589,61 @@ async fn process_block<C: Request>(
         .await
         .into_raw_result()
 }
+
+/// Find all events of the provided type along with their indices inside of a block.
+fn find_indexed<E: StaticEvent + Decode>(
+    events: &Events<H256>,
+) -> impl Iterator<Item = Result<(u32, E), WatchError>> + '_ {
+    events
+        .iter()
+        .filter_ok(|details| {
+            details.pallet_name() == E::PALLET && details.variant_name() == E::EVENT
+        })
+        .map(|details| {
+            let details = details.map_err(substrate_api_client::Error::NodeApi)?;
+
+            Ok((details.index(), E::decode(&mut details.field_bytes())?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use common::config::RpcRetry;
+    use db::{node, sea_orm::Schema, ActiveValue, ConnectionTrait, Database, EntityTrait};
+
+    use super::WatchError;
+
+    #[tokio::test]
+    async fn too_many_nodes() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        let backend = db.get_database_backend();
+
+        db.execute(backend.build(&Schema::new(backend).create_table_from_entity(node::Entity)))
+            .await
+            .expect("unable to create nodes table");
+
+        node::Entity::insert_many((1..=3).map(|id| node::ActiveModel {
+            name: ActiveValue::Set(format!("node-{id}")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        }))
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert nodes");
+
+        // Watchers never stop, thus the third node would never be watched.
+        let result =
+            super::watch_all(db, &RpcRetry::default(), NonZeroUsize::new(2).unwrap()).await;
+
+        assert!(matches!(
+            result,
+            Err(WatchError::TooManyNodes { count: 3, limit }) if limit.get() == 2
+        ));
+    }
+}
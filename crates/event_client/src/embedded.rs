@@ -0,0 +1,81 @@
+use std::{future::Future, sync::Arc};
+
+use common::config::Config;
+use db::DatabaseConnection;
+use futures_util::future::join;
+use tracing::info;
+
+use crate::{
+    cli::{backfill, load_nodes, watch},
+    retry::Backoff,
+    supervisor::supervise,
+};
+
+/// Run node watchers and event backfill for all registered nodes.
+///
+/// # Details
+///
+/// [`run_event_client`] is an alternative to running `watch` and `backfill` subcommands
+/// of a standalone event client for each node, which allows smaller deployments
+/// to operate a single process with a shared database connection pool.
+///
+/// Up to `max_concurrent_nodes` nodes can be registered, otherwise
+/// the embedded event client refuses to start.
+///
+/// Nodes are loaded once, thus nodes that are initialized afterwards
+/// are picked up only after a restart.
+///
+/// Tasks of each node are supervised separately. Tasks that fail with a recoverable error,
+/// including watchers of nodes that are locked by another event client instance,
+/// are restarted with an exponential backoff, thus another replica can take over
+/// as soon as the lock is released. Tasks that fail with a permanent error
+/// are stopped without affecting other tasks.
+///
+/// Each node task is spawned separately and block subscriptions are read from
+/// dedicated threads, thus a watcher waiting for new blocks blocks neither backfill
+/// jobs of other nodes nor API server worker threads.
+///
+/// All tasks are stopped as soon as the provided `shutdown` future completes.
+pub async fn run_event_client<F: Future<Output = ()>>(
+    database: DatabaseConnection,
+    config: Arc<Config>,
+    shutdown: F,
+) -> Result<(), anyhow::Error> {
+    let limit = config.event_client.max_concurrent_nodes;
+    let nodes = load_nodes(&database, limit).await?;
+
+    info!(count = nodes.len(), "starting embedded event client");
+
+    let backoff = Backoff::new(&config.rpc_retry);
+
+    let watchers = supervise(nodes.clone(), limit, &backoff, {
+        let database = database.clone();
+        let config = config.clone();
+
+        move |name| {
+            let database = database.clone();
+            let config = config.clone();
+
+            async move { watch(database, name, &config.rpc_retry).await }
+        }
+    });
+
+    let backfills = supervise(nodes, limit, &backoff, move |name| {
+        let database = database.clone();
+        let config = config.clone();
+
+        async move { backfill(database, name, &config.event_client).await }
+    });
+
+    tokio::select! {
+        (watchers, backfills) = join(watchers, backfills) => {
+            watchers?;
+            backfills?;
+            Ok(())
+        }
+        () = shutdown => {
+            info!("embedded event client stopped");
+            Ok(())
+        }
+    }
+}
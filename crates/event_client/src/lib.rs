@@ -0,0 +1,79 @@
+//! # Event client
+//!
+//! Event client is responsible for the background interaction with different RPC nodes
+//! attached to corresponding networks.
+//!
+//! The communication is done in order to keep the database with recent deployment events
+//! and provide users with information about existing smart contracts and uploaded WASM blobs.
+//!
+//! ## Node initialization
+//!
+//! Use the `initialize` subcommand to initialize a new node and add information
+//! about its deployed smart contracts and uploaded WASM blobs to the database.
+//!
+//! Refer to the [`initialize`] documentation for more details.
+//!
+//! ## Node watcher
+//!
+//! `watch` subcommand can be used to watch for new events from an RPC node.
+//! These events contain information about new smart contract deployments and code uploads.
+//!
+//! Refer to the [`watch`] documentation for more details.
+//!
+//! `watch-all` subcommand watches all registered nodes concurrently,
+//! refer to the [`watch_all`] documentation for more details.
+//!
+//! ## Node traversal
+//!
+//! `traverse` subcommand attempts to traverse previous blocks to collect info about
+//! previous smart contract events. Be aware, that this command is meant for **testing only**,
+//! as detailed info about previous blocks is usually available to fully-featured indexing
+//! servers.
+//!
+//! Refer to the [`traverse`] documentation for more details.
+//!
+//! ## Event backfill
+//!
+//! `backfill` subcommand processes backfill jobs created via an API server,
+//! rescanning the requested block ranges for previously missed contract events.
+//!
+//! Refer to the [`backfill`] documentation for more details.
+//!
+//! ## Payment contract update
+//!
+//! Using `update-contract` subcommand you can update the address of the payment
+//! contract for the specified node.
+//!
+//! Refer to the [`update_contract`] documentation for more details.
+//!
+//! ## Embedded event client
+//!
+//! Both node watchers and event backfill can be run inside of another process,
+//! such as an API server, with the [`run_event_client`] function.
+//!
+//! [`backfill`]: cli::backfill
+//! [`initialize`]: cli::initialize
+//! [`watch`]: cli::watch
+//! [`watch_all`]: cli::watch_all
+//! [`traverse`]: cli::traverse
+//! [`update_contract`]: cli::update_contract
+
+#![deny(missing_docs)]
+#![deny(clippy::missing_docs_in_private_items)]
+
+/// CLI general configuration and subcommands.
+pub mod cli;
+
+/// Event client task embeddable into other processes.
+mod embedded;
+
+/// Retry and backoff utilities for RPC calls.
+pub(crate) mod retry;
+
+/// Supervision of node tasks, which restarts failed tasks.
+pub(crate) mod supervisor;
+
+/// Various extraction and mapping utilities.
+pub(crate) mod utils;
+
+pub use embedded::run_event_client;
@@ -1,59 +1,15 @@
-//! # Event client
+//! # Event client binary
 //!
-//! Event client is responsible for the background interaction with different RPC nodes
-//! attached to corresponding networks.
-//!
-//! The communication is done in order to keep the database with recent deployment events
-//! and provide users with information about existing smart contracts and uploaded WASM blobs.
-//!
-//! ## Node initialization
-//!
-//! Use the `initialize` subcommand to initialize a new node and add information
-//! about its deployed smart contracts and uploaded WASM blobs to the database.
-//!
-//! Refer to the [`initialize`] documentation for more details.
-//!
-//! ## Node watcher
-//!
-//! `watch` subcommand can be used to watch for new events from an RPC node.
-//! These events contain information about new smart contract deployments and code uploads.
-//!
-//! Refer to the [`watch`] documentation for more details.
-//!
-//! ## Node traversal
-//!
-//! `traverse` subcommand attempts to traverse previous blocks to collect info about
-//! previous smart contract events. Be aware, that this command is meant for **testing only**,
-//! as detailed info about previous blocks is usually available to fully-featured indexing
-//! servers.
-//!
-//! Refer to the [`traverse`] documentation for more details.
-//!
-//! ## Payment contract update
-//!
-//! Using `update-contract` subcommand you can update the address of the payment
-//! contract for the specified node.
-//!
-//! Refer to the [`update_contract`] documentation for more details.
-//!
-//! [`initialize`]: cli::initialize
-//! [`watch`]: cli::watch
-//! [`traverse`]: cli::traverse
-//! [`update_contract`]: cli::update_contract
+//! Standalone entrypoint of the event client, see the library documentation
+//! for the list of supported subcommands.
 
 #![deny(missing_docs)]
 #![deny(clippy::missing_docs_in_private_items)]
 
-/// CLI general configuration and subcommands.
-mod cli;
-
-/// Various extraction and mapping utilities.
-pub(crate) mod utils;
-
 use clap::Parser;
-use cli::{Cli, Command};
 use common::{config::Config, logging};
 use db::Database;
+use event_client::cli::{self, Cli, Command};
 use tracing::info;
 
 /// Event client entrypoint.
@@ -66,21 +22,31 @@ async fn main() -> Result<(), anyhow::Error> {
     logging::init(&config);
 
     info!("connecting to database");
-    let database = Database::connect(&config.database.url).await?;
+    let database = Database::connect(config.database.connect_options()).await?;
     info!("database connection established");
 
     match cli.command {
+        Command::Backfill { name } => cli::backfill(database, name, &config.event_client).await?,
         Command::Initialize {
             name,
             url,
             payment_address,
-        } => cli::initialize(database, name, url, payment_address).await?,
+            start_block,
+        } => cli::initialize(database, name, url, payment_address, start_block).await?,
         Command::Traverse { name } => cli::traverse(database, name).await?,
         Command::UpdateContract {
             name,
             payment_address,
         } => cli::update_contract(database, name, payment_address).await?,
-        Command::Watch { name } => cli::watch(database, name).await?,
+        Command::Watch { name } => cli::watch(database, name, &config.rpc_retry).await?,
+        Command::WatchAll => {
+            cli::watch_all(
+                database,
+                &config.rpc_retry,
+                config.event_client.max_concurrent_nodes,
+            )
+            .await?
+        }
     }
 
     Ok(())
@@ -0,0 +1,178 @@
+use std::{fmt::Debug, future::Future, time::Duration};
+
+use common::{config::RpcRetry, rpc::substrate_api_client};
+use rand::Rng;
+use tracing::warn;
+
+/// Errors that can be distinguished between transient and permanent ones.
+pub(crate) trait Recoverable {
+    /// Check if an operation that failed with this error may succeed if retried.
+    fn is_recoverable(&self) -> bool;
+}
+
+impl Recoverable for substrate_api_client::Error {
+    fn is_recoverable(&self) -> bool {
+        // Other errors are caused by unexpected RPC node responses,
+        // which will not change on retry.
+        matches!(self, substrate_api_client::Error::RpcClient(_))
+    }
+}
+
+/// Exponential backoff parameters.
+#[derive(Clone, Copy)]
+pub(crate) struct Backoff {
+    /// Delay before the first retry attempt.
+    initial_delay: Duration,
+
+    /// Maximum delay between retry attempts.
+    max_delay: Duration,
+
+    /// Maximum count of attempts of a single call.
+    max_attempts: u32,
+}
+
+impl Backoff {
+    /// Create new [`Backoff`] from the provided configuration.
+    pub(crate) fn new(config: &RpcRetry) -> Self {
+        Self {
+            initial_delay: Duration::from_millis(config.initial_delay),
+            max_delay: Duration::from_millis(config.max_delay),
+            max_attempts: config.max_attempts.max(1),
+        }
+    }
+
+    /// Get a delay before the provided retry attempt, starting from zero.
+    ///
+    /// Up to a half of the delay is randomized to avoid synchronized retries.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+
+        delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+/// Call the provided function until it succeeds, fails with a permanent error
+/// or the maximum count of attempts is reached.
+pub(crate) async fn retry<T, E, F, Fut>(backoff: &Backoff, mut call: F) -> Result<T, E>
+where
+    E: Recoverable + Debug,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        match call().await {
+            Err(err) if err.is_recoverable() && attempt + 1 < backoff.max_attempts => {
+                let delay = backoff.delay(attempt);
+                warn!(?err, ?delay, "rpc call failed, retrying");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use common::config::RpcRetry;
+
+    use super::{Backoff, Recoverable};
+
+    #[derive(Debug, PartialEq)]
+    enum MockError {
+        Transient,
+        Permanent,
+    }
+
+    impl Recoverable for MockError {
+        fn is_recoverable(&self) -> bool {
+            *self == MockError::Transient
+        }
+    }
+
+    fn backoff(max_attempts: u32) -> Backoff {
+        Backoff::new(&RpcRetry {
+            initial_delay: 1,
+            max_delay: 4,
+            max_attempts,
+        })
+    }
+
+    /// Mock RPC call that fails with the provided error the first `failures` times.
+    async fn mock_rpc(
+        calls: &AtomicU32,
+        failures: u32,
+        error: MockError,
+    ) -> Result<u32, MockError> {
+        let call = calls.fetch_add(1, Ordering::SeqCst);
+
+        if call < failures {
+            Err(error)
+        } else {
+            Ok(call)
+        }
+    }
+
+    #[tokio::test]
+    async fn transient_failures() {
+        let calls = AtomicU32::new(0);
+
+        let result = super::retry(&backoff(5), || mock_rpc(&calls, 2, MockError::Transient)).await;
+
+        assert_eq!(result, Ok(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn attempts_exhausted() {
+        let calls = AtomicU32::new(0);
+
+        let result = super::retry(&backoff(2), || mock_rpc(&calls, 2, MockError::Transient)).await;
+
+        assert_eq!(result, Err(MockError::Transient));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn permanent_failure() {
+        let calls = AtomicU32::new(0);
+
+        let result = super::retry(&backoff(5), || mock_rpc(&calls, 2, MockError::Permanent)).await;
+
+        assert_eq!(result, Err(MockError::Permanent));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn delay() {
+        let backoff = Backoff::new(&RpcRetry {
+            initial_delay: 100,
+            max_delay: 1000,
+            max_attempts: 5,
+        });
+
+        for (attempt, max) in [
+            (0, 100),
+            (1, 200),
+            (2, 400),
+            (3, 800),
+            (4, 1000),
+            (10, 1000),
+        ] {
+            let delay = backoff.delay(attempt);
+
+            assert!(delay >= Duration::from_millis(max / 2));
+            assert!(delay <= Duration::from_millis(max));
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::{fmt::Display, future::Future, num::NonZeroUsize, panic, sync::Arc};
+
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing::{error, info, info_span, warn, Instrument};
+
+use crate::retry::{Backoff, Recoverable};
+
+/// Run the provided task for each of the provided nodes,
+/// with up to `limit` tasks running at the same time.
+///
+/// Each task is spawned separately and supervised on its own: tasks that fail
+/// with a recoverable error are restarted with an exponential backoff, while tasks
+/// that fail with a permanent error are stopped without affecting tasks of other nodes.
+///
+/// Returns the first permanent error as soon as all tasks are stopped.
+/// Tasks that are still running are aborted if the returned future is dropped.
+pub(crate) async fn supervise<F, Fut, E>(
+    nodes: Vec<(i64, String)>,
+    limit: NonZeroUsize,
+    backoff: &Backoff,
+    task: F,
+) -> Result<(), E>
+where
+    F: Fn(String) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: Recoverable + Display + Send + 'static,
+{
+    let task = Arc::new(task);
+    let semaphore = Arc::new(Semaphore::new(limit.get()));
+    let mut tasks = JoinSet::new();
+
+    for (id, name) in nodes {
+        let task = task.clone();
+        let semaphore = semaphore.clone();
+        let backoff = *backoff;
+
+        tasks.spawn(
+            async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+
+                restart(name, &backoff, &*task).await
+            }
+            .instrument(info_span!("node", id)),
+        );
+    }
+
+    let mut result = Ok(());
+
+    while let Some(outcome) = tasks.join_next().await {
+        let outcome = outcome.unwrap_or_else(|err| panic::resume_unwind(err.into_panic()));
+        result = result.and(outcome);
+    }
+
+    result
+}
+
+/// Run the provided task of a single node, restarting it after recoverable errors.
+async fn restart<F, Fut, E>(name: String, backoff: &Backoff, task: &F) -> Result<(), E>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: Recoverable + Display,
+{
+    let mut attempt = 0;
+
+    loop {
+        match task(name.clone()).await {
+            Ok(()) => {
+                info!("node task stopped");
+                return Ok(());
+            }
+            Err(err) if err.is_recoverable() => {
+                let delay = backoff.delay(attempt);
+                warn!(%err, ?delay, "node task failed, restarting");
+                tokio::time::sleep(delay).await;
+                attempt = attempt.saturating_add(1);
+            }
+            Err(err) => {
+                error!(%err, "node task failed");
+                return Err(err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        num::NonZeroUsize,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    };
+
+    use common::config::RpcRetry;
+    use derive_more::Display;
+
+    use crate::retry::{Backoff, Recoverable};
+
+    #[derive(Debug, Display, PartialEq)]
+    enum MockError {
+        Transient,
+        Permanent,
+    }
+
+    impl Recoverable for MockError {
+        fn is_recoverable(&self) -> bool {
+            *self == MockError::Transient
+        }
+    }
+
+    fn backoff() -> Backoff {
+        Backoff::new(&RpcRetry {
+            initial_delay: 1,
+            max_delay: 4,
+            max_attempts: 1,
+        })
+    }
+
+    #[tokio::test]
+    async fn independent_nodes() {
+        let nodes = vec![
+            (1, String::from("slow")),
+            (2, String::from("failing")),
+            (3, String::from("fast")),
+        ];
+
+        let confirmed_blocks = Arc::new(Mutex::new(HashMap::new()));
+        let stopped = Arc::new(Mutex::new(Vec::new()));
+
+        let result = super::supervise(nodes, NonZeroUsize::new(3).unwrap(), &backoff(), {
+            let confirmed_blocks = confirmed_blocks.clone();
+            let stopped = stopped.clone();
+
+            move |name| {
+                let confirmed_blocks = confirmed_blocks.clone();
+                let stopped = stopped.clone();
+
+                async move {
+                    let delay = match name.as_str() {
+                        "slow" => Duration::from_millis(50),
+                        "fast" => Duration::from_millis(1),
+                        _ => return Err(MockError::Permanent),
+                    };
+
+                    for block in 1..=3 {
+                        tokio::time::sleep(delay).await;
+                        confirmed_blocks.lock().unwrap().insert(name.clone(), block);
+                    }
+
+                    let slow_block = confirmed_blocks.lock().unwrap().get("slow").copied();
+                    stopped.lock().unwrap().push((name, slow_block));
+
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Err(MockError::Permanent));
+
+        let confirmed_blocks = confirmed_blocks.lock().unwrap();
+
+        assert_eq!(confirmed_blocks.get("slow"), Some(&3));
+        assert_eq!(confirmed_blocks.get("fast"), Some(&3));
+        assert_eq!(confirmed_blocks.get("failing"), None);
+
+        // Fast node must not wait for the slow node to advance.
+        assert_eq!(
+            *stopped.lock().unwrap(),
+            [
+                (String::from("fast"), None),
+                (String::from("slow"), Some(3)),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn blocking_node() {
+        let nodes = vec![(1, String::from("blocking")), (2, String::from("other"))];
+
+        let confirmed_blocks = Arc::new(AtomicU32::new(0));
+
+        let result = super::supervise(nodes, NonZeroUsize::new(2).unwrap(), &backoff(), {
+            let confirmed_blocks = confirmed_blocks.clone();
+
+            move |name| {
+                let confirmed_blocks = confirmed_blocks.clone();
+
+                async move {
+                    if name == "blocking" {
+                        // Block the current thread, just like a synchronous RPC call does,
+                        // until the other node advances.
+                        let started_at = Instant::now();
+
+                        while confirmed_blocks.load(Ordering::SeqCst) < 3 {
+                            if started_at.elapsed() > Duration::from_secs(5) {
+                                return Err(MockError::Permanent);
+                            }
+
+                            std::thread::sleep(Duration::from_millis(1));
+                        }
+                    } else {
+                        for _ in 0..3 {
+                            tokio::time::sleep(Duration::from_millis(1)).await;
+                            confirmed_blocks.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(confirmed_blocks.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn restarts() {
+        let nodes = vec![(1, String::from("locked")), (2, String::from("other"))];
+
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let result = super::supervise(nodes, NonZeroUsize::new(2).unwrap(), &backoff(), {
+            let calls = calls.clone();
+
+            move |name| {
+                let calls = calls.clone();
+
+                async move {
+                    // Node is released by another event client after two attempts.
+                    if name == "locked" && calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err(MockError::Transient)
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
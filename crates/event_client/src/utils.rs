@@ -1,7 +1,11 @@
 use common::rpc::{
-    sp_core::H256,
+    self,
+    parity_scale_codec::{self, Decode},
+    sp_core::{crypto::AccountId32, H256},
     substrate_api_client::{ac_primitives::PolkadotConfig, rpc::Request, Api, Error, GetChainInfo},
+    ContractEmitted,
 };
+use db::{event::EventBody, OffsetDateTime, PrimitiveDateTime};
 use futures_util::{stream, Stream, StreamExt, TryStreamExt};
 
 /// TwoX hash length.
@@ -32,6 +36,53 @@ pub(crate) fn extract_code_hash<T: AsRef<[u8]>>(key: T) -> Vec<u8> {
     key.as_ref()[STORAGE_PREFIX_LEN..].to_owned()
 }
 
+/// Decoded custom event emitted by a contract.
+pub(crate) struct ContractEmission {
+    /// [`AccountId32`] value of a contract that emitted the event.
+    pub contract: AccountId32,
+
+    /// Event body that can be stored in the database.
+    pub body: EventBody,
+
+    /// Indexed event topics.
+    pub topics: Vec<Vec<u8>>,
+}
+
+/// Decode the provided `ContractEmitted` event fields along with its topics.
+pub(crate) fn decode_contract_emission(
+    mut fields: &[u8],
+    topics: &[H256],
+) -> Result<ContractEmission, parity_scale_codec::Error> {
+    let ContractEmitted { contract, data } = ContractEmitted::decode(&mut fields)?;
+
+    Ok(ContractEmission {
+        contract,
+        body: EventBody::ContractEmission {
+            data: hex::encode(data),
+        },
+        topics: topics
+            .iter()
+            .map(|topic| topic.as_bytes().to_vec())
+            .collect(),
+    })
+}
+
+/// Get the timestamp of a block with the provided hash.
+pub(crate) async fn block_timestamp<C: Request>(
+    api: &Api<PolkadotConfig, C>,
+    block_hash: H256,
+) -> Result<PrimitiveDateTime, Error> {
+    let block_millis = rpc::block_timestamp_millis(api, block_hash).await?;
+    let raw_timestamp = unix_ts::Timestamp::from_millis(block_millis);
+    let offset_timestamp = OffsetDateTime::from_unix_timestamp(raw_timestamp.seconds())
+        .expect("invalid timestamp was provided");
+
+    Ok(PrimitiveDateTime::new(
+        offset_timestamp.date(),
+        offset_timestamp.time(),
+    ))
+}
+
 /// Get a mapping stream from block number to block hash.
 ///
 /// The stream may skip blocks, to which an RPC node did not provide a hash.
@@ -51,9 +102,12 @@ pub(crate) fn block_mapping_stream<'a, I: IntoIterator<Item = u32> + 'a, C: Requ
 
 #[cfg(test)]
 mod tests {
-    use common::rpc::sp_core::{
-        crypto::{AccountId32, Ss58Codec},
-        ByteArray,
+    use common::rpc::{
+        parity_scale_codec::Encode,
+        sp_core::{
+            crypto::{AccountId32, Ss58Codec},
+            ByteArray, H256,
+        },
     };
 
     #[test]
@@ -75,4 +129,29 @@ mod tests {
         let key = hex::decode(hex_key).unwrap();
         assert_eq!(super::extract_code_hash(&key), vec![0; 32]);
     }
+
+    #[test]
+    fn decode_contract_emission() {
+        let contract = AccountId32::new([1; 32]);
+        let topics = [H256::repeat_byte(2), H256::repeat_byte(3)];
+
+        let mut fields = contract.encode();
+        fields.extend(vec![0xde, 0xad, 0xbe, 0xef].encode());
+
+        let emission = super::decode_contract_emission(&fields, &topics).unwrap();
+
+        assert_eq!(emission.contract, contract);
+        assert_eq!(
+            serde_json::to_string(&emission.body).unwrap(),
+            r#"{"ContractEmission":{"data":"deadbeef"}}"#
+        );
+        assert_eq!(emission.topics, vec![vec![2; 32], vec![3; 32]]);
+    }
+
+    #[test]
+    fn decode_contract_emission_truncated() {
+        let fields = AccountId32::new([1; 32]).encode();
+
+        assert!(super::decode_contract_emission(&fields[..16], &[]).is_err());
+    }
 }
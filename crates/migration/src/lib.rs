@@ -15,12 +15,44 @@ mod m20220101_000012_create_cli_tokens_table;
 mod m20220101_000013_create_events_table;
 mod m20220101_000014_remove_node_schema;
 mod m20220101_000015_remove_rust_version;
+mod m20220101_000016_add_event_block_number;
+mod m20220101_000017_create_public_key_nonces_table;
+mod m20220101_000018_add_public_key_name;
+mod m20220101_000019_add_token_expiration;
+mod m20220101_000020_add_code_metadata;
+mod m20220101_000021_add_build_session_expected_code_hash;
+mod m20220101_000022_create_event_topics_table;
+mod m20220101_000023_add_event_index;
+mod m20220101_000024_create_backfill_jobs_table;
+mod m20220101_000025_remove_duplicate_events;
+mod m20220101_000026_add_event_body_search_index;
+mod m20220101_000027_create_webhooks_table;
+mod m20220101_000028_create_webhook_deliveries_table;
+mod m20220101_000029_create_idempotency_keys_table;
+mod m20220101_000030_add_ownership_change_events;
+mod m20220101_000031_add_public_key_deletion;
+mod m20220101_000032_hash_authentication_tokens;
+mod m20220101_000033_add_user_admin_flag;
+mod m20220101_000034_create_login_challenges_table;
+mod m20220101_000035_add_contract_instantiation;
+mod m20220101_000036_add_entity_timestamps;
+mod m20220101_000037_add_node_runtime_version;
+mod m20220101_000038_add_build_session_dry_run;
+mod m20220101_000039_create_build_session_cancellations_table;
+mod m20220101_000040_add_build_session_archive_hash;
+mod m20220101_000041_add_backfill_job_checkpoint;
+mod m20220101_000042_add_event_raw_body;
+mod m20220101_000043_add_active_public_key_index;
+mod m20220101_000044_add_public_key_version;
+mod m20220101_000045_add_build_session_start_time;
 
 pub(crate) use m20220101_000001_create_users_table::Users;
 pub(crate) use m20220101_000003_create_authentication_tokens_table::AuthenticationTokens;
 pub(crate) use m20220101_000004_create_nodes_table::Nodes;
 pub(crate) use m20220101_000007_create_source_codes_table::SourceCodes;
 pub(crate) use m20220101_000009_create_build_sessions_table::BuildSessions;
+pub(crate) use m20220101_000013_create_events_table::Events;
+pub(crate) use m20220101_000027_create_webhooks_table::Webhooks;
 
 pub struct Migrator;
 
@@ -43,6 +75,36 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000013_create_events_table::Migration),
             Box::new(m20220101_000014_remove_node_schema::Migration),
             Box::new(m20220101_000015_remove_rust_version::Migration),
+            Box::new(m20220101_000016_add_event_block_number::Migration),
+            Box::new(m20220101_000017_create_public_key_nonces_table::Migration),
+            Box::new(m20220101_000018_add_public_key_name::Migration),
+            Box::new(m20220101_000019_add_token_expiration::Migration),
+            Box::new(m20220101_000020_add_code_metadata::Migration),
+            Box::new(m20220101_000021_add_build_session_expected_code_hash::Migration),
+            Box::new(m20220101_000022_create_event_topics_table::Migration),
+            Box::new(m20220101_000023_add_event_index::Migration),
+            Box::new(m20220101_000024_create_backfill_jobs_table::Migration),
+            Box::new(m20220101_000025_remove_duplicate_events::Migration),
+            Box::new(m20220101_000026_add_event_body_search_index::Migration),
+            Box::new(m20220101_000027_create_webhooks_table::Migration),
+            Box::new(m20220101_000028_create_webhook_deliveries_table::Migration),
+            Box::new(m20220101_000029_create_idempotency_keys_table::Migration),
+            Box::new(m20220101_000030_add_ownership_change_events::Migration),
+            Box::new(m20220101_000031_add_public_key_deletion::Migration),
+            Box::new(m20220101_000032_hash_authentication_tokens::Migration),
+            Box::new(m20220101_000033_add_user_admin_flag::Migration),
+            Box::new(m20220101_000034_create_login_challenges_table::Migration),
+            Box::new(m20220101_000035_add_contract_instantiation::Migration),
+            Box::new(m20220101_000036_add_entity_timestamps::Migration),
+            Box::new(m20220101_000037_add_node_runtime_version::Migration),
+            Box::new(m20220101_000038_add_build_session_dry_run::Migration),
+            Box::new(m20220101_000039_create_build_session_cancellations_table::Migration),
+            Box::new(m20220101_000040_add_build_session_archive_hash::Migration),
+            Box::new(m20220101_000041_add_backfill_job_checkpoint::Migration),
+            Box::new(m20220101_000042_add_event_raw_body::Migration),
+            Box::new(m20220101_000043_add_active_public_key_index::Migration),
+            Box::new(m20220101_000044_add_public_key_version::Migration),
+            Box::new(m20220101_000045_add_build_session_start_time::Migration),
         ]
     }
 }
@@ -47,7 +47,7 @@ impl MigrationTrait for Migration {
 
 /// Learn more at https://docs.rs/sea-query#iden
 #[derive(Iden)]
-enum Events {
+pub(crate) enum Events {
     Table,
     Id,
     NodeId,
@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuthenticationTokens::Table)
+                    .add_column(
+                        ColumnDef::new(AuthenticationTokens::ExpiresAt)
+                            .timestamp()
+                            .not_null()
+                            .default("1970-01-01 00:00:00"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Preserve the previously hard-coded lifespan of 12 weeks for existing tokens.
+        let expires_at = match manager.get_database_backend() {
+            DbBackend::Postgres => "created_at + INTERVAL '84 days'",
+            DbBackend::MySql => "DATE_ADD(created_at, INTERVAL 84 DAY)",
+            DbBackend::Sqlite => "DATETIME(created_at, '+84 days')",
+        };
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(AuthenticationTokens::Table)
+                    .value(AuthenticationTokens::ExpiresAt, Expr::cust(expires_at))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AuthenticationTokens::Table)
+                    .drop_column(AuthenticationTokens::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum AuthenticationTokens {
+    Table,
+    ExpiresAt,
+}
@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventTopics::Table)
+                    .col(
+                        ColumnDef::new(EventTopics::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(EventTopics::EventId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(EventTopics::Topic).binary().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(EventTopics::Table, EventTopics::EventId)
+                            .to(crate::Events::Table, crate::Events::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_event_topics_topic")
+                    .table(EventTopics::Table)
+                    .col(EventTopics::Topic)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EventTopics::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum EventTopics {
+    Table,
+    Id,
+    EventId,
+    Topic,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Events::Table)
+                    .add_column(ColumnDef::new(Events::EventIndex).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Previously discovered events do not have an index, and since
+        // null values are considered distinct, they are not affected by this constraint.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .col(Events::NodeId)
+                    .col(Events::Account)
+                    .col(Events::BlockNumber)
+                    .col(Events::EventIndex)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Events::Table)
+                    .drop_column(Events::EventIndex)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Events {
+    Table,
+    NodeId,
+    Account,
+    BlockNumber,
+    EventIndex,
+}
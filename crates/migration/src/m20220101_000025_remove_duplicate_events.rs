@@ -0,0 +1,54 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Events discovered before event indices were recorded are not covered
+        // by the unique constraint, thus previously inserted duplicates are removed,
+        // preserving the earliest discovered event.
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Events::Table)
+                    .and_where(
+                        Expr::col(Events::Id).not_in_subquery(
+                            Query::select()
+                                .expr(Expr::col(Events::Id).min())
+                                .from(Events::Table)
+                                .group_by_columns([
+                                    Events::NodeId,
+                                    Events::Account,
+                                    Events::EventType,
+                                    Events::Body,
+                                    Events::BlockNumber,
+                                    Events::EventIndex,
+                                ])
+                                .to_owned(),
+                        ),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, _: &SchemaManager) -> Result<(), DbErr> {
+        // Removed duplicates can not be restored.
+        Ok(())
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Events {
+    Table,
+    Id,
+    NodeId,
+    Account,
+    EventType,
+    Body,
+    BlockNumber,
+    EventIndex,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+/// Numeric event type value of ownership change events.
+const OWNERSHIP_CHANGE: i16 = 4;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Ownership change events are derived from the same chain events
+        // as instantiations, thus share their indices.
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .col(Events::NodeId)
+                    .col(Events::Account)
+                    .col(Events::BlockNumber)
+                    .col(Events::EventIndex)
+                    .col(Events::EventType)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Events::Table)
+                    .and_where(Expr::col(Events::EventType).eq(OWNERSHIP_CHANGE))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_events_unique")
+                    .table(Events::Table)
+                    .col(Events::NodeId)
+                    .col(Events::Account)
+                    .col(Events::BlockNumber)
+                    .col(Events::EventIndex)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Events {
+    Table,
+    NodeId,
+    Account,
+    EventType,
+    BlockNumber,
+    EventIndex,
+}
@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PublicKeys::Table)
+                    .add_column(ColumnDef::new(PublicKeys::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Soft-deleted keys must not become active again.
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(PublicKeys::Table)
+                    .and_where(Expr::col(PublicKeys::DeletedAt).is_not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PublicKeys::Table)
+                    .drop_column(PublicKeys::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum PublicKeys {
+    Table,
+    DeletedAt,
+}
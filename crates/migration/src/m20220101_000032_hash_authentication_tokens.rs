@@ -0,0 +1,53 @@
+use sea_orm_migration::{prelude::*, sea_orm::ConnectionTrait};
+
+use crate::AuthenticationTokens;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let connection = manager.get_connection();
+        let builder = manager.get_database_backend();
+
+        let tokens = connection
+            .query_all(
+                builder.build(
+                    Query::select()
+                        .columns([AuthenticationTokens::Id, AuthenticationTokens::Token])
+                        .from(AuthenticationTokens::Table),
+                ),
+            )
+            .await?;
+
+        // Existing tokens are hashed in place to keep current sessions valid.
+        for row in tokens {
+            let id: i64 = row.try_get("", "id")?;
+            let token: String = row.try_get("", "token")?;
+
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(AuthenticationTokens::Table)
+                        .value(AuthenticationTokens::Token, db::token::hash_token(&token))
+                        .and_where(Expr::col(AuthenticationTokens::Id).eq(id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Hashed tokens can not be restored, thus all sessions are invalidated.
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(AuthenticationTokens::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .add_column(ColumnDef::new(Contracts::InstantiationBlock).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .add_column(ColumnDef::new(Contracts::InstantiatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Derive values of already discovered contracts from their first instantiation events.
+        let first_instantiation = |column| {
+            SimpleExpr::SubQuery(
+                None,
+                Box::new(
+                    Query::select()
+                        .expr(Expr::col((Events::Table, column)).min())
+                        .from(Events::Table)
+                        .and_where(
+                            Expr::col((Events::Table, Events::NodeId))
+                                .equals((Contracts::Table, Contracts::NodeId)),
+                        )
+                        .and_where(
+                            Expr::col((Events::Table, Events::Account))
+                                .equals((Contracts::Table, Contracts::Address)),
+                        )
+                        // Instantiation event type.
+                        .and_where(Expr::col((Events::Table, Events::EventType)).eq(0))
+                        .to_owned()
+                        .into_sub_query_statement(),
+                ),
+            )
+        };
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Contracts::Table)
+                    .value(
+                        Contracts::InstantiationBlock,
+                        first_instantiation(Events::BlockNumber),
+                    )
+                    .value(
+                        Contracts::InstantiatedAt,
+                        first_instantiation(Events::BlockTimestamp),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .drop_column(Contracts::InstantiatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Contracts::Table)
+                    .drop_column(Contracts::InstantiationBlock)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Contracts {
+    Table,
+    NodeId,
+    Address,
+    InstantiationBlock,
+    InstantiatedAt,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden, Clone, Copy)]
+enum Events {
+    Table,
+    NodeId,
+    Account,
+    EventType,
+    BlockNumber,
+    BlockTimestamp,
+}
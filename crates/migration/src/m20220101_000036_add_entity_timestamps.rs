@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Timestamp columns added by this migration.
+///
+/// Public keys and users already have creation timestamps.
+fn columns() -> [(DynIden, DynIden); 6] {
+    [
+        (
+            Contracts::Table.into_iden(),
+            Contracts::CreatedAt.into_iden(),
+        ),
+        (
+            Contracts::Table.into_iden(),
+            Contracts::UpdatedAt.into_iden(),
+        ),
+        (Codes::Table.into_iden(), Codes::CreatedAt.into_iden()),
+        (Codes::Table.into_iden(), Codes::UpdatedAt.into_iden()),
+        (
+            PublicKeys::Table.into_iden(),
+            PublicKeys::UpdatedAt.into_iden(),
+        ),
+        (Users::Table.into_iden(), Users::UpdatedAt.into_iden()),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite doesn't support multiple alterations in a single statement.
+        for (table, column) in columns() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(column)
+                                .timestamp()
+                                .not_null()
+                                .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (table, column) in columns().into_iter().rev() {
+            manager
+                .alter_table(Table::alter().table(table).drop_column(column).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Contracts {
+    Table,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Codes {
+    Table,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum PublicKeys {
+    Table,
+    UpdatedAt,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum Users {
+    Table,
+    UpdatedAt,
+}
@@ -0,0 +1,94 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BuildSessions::Table)
+                    .add_column(ColumnDef::new(BuildSessions::ArchiveHash).binary().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Copy archive hashes of already existing build sessions from their source codes.
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(BuildSessions::Table)
+                    .value(
+                        BuildSessions::ArchiveHash,
+                        SimpleExpr::SubQuery(
+                            None,
+                            Box::new(
+                                Query::select()
+                                    .column((SourceCodes::Table, SourceCodes::ArchiveHash))
+                                    .from(SourceCodes::Table)
+                                    .and_where(
+                                        Expr::col((SourceCodes::Table, SourceCodes::Id)).equals((
+                                            BuildSessions::Table,
+                                            BuildSessions::SourceCodeId,
+                                        )),
+                                    )
+                                    .to_owned()
+                                    .into_sub_query_statement(),
+                            ),
+                        ),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_build_sessions_archive_hash")
+                    .table(BuildSessions::Table)
+                    .col(BuildSessions::ArchiveHash)
+                    .col(BuildSessions::CargoContractVersion)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_build_sessions_archive_hash")
+                    .table(BuildSessions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(BuildSessions::Table)
+                    .drop_column(BuildSessions::ArchiveHash)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum BuildSessions {
+    Table,
+    SourceCodeId,
+    CargoContractVersion,
+    ArchiveHash,
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum SourceCodes {
+    Table,
+    Id,
+    ArchiveHash,
+}
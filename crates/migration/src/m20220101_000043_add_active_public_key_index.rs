@@ -0,0 +1,152 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Create a public keys table, optionally with a unique address constraint.
+///
+/// SQLite doesn't support dropping or adding constraints,
+/// thus the table is recreated with all of the existing columns instead.
+fn public_keys_table(table: PublicKeys, unique_address: bool) -> TableCreateStatement {
+    let mut address = ColumnDef::new(PublicKeys::Address);
+    address.binary().not_null();
+
+    if unique_address {
+        address.unique_key();
+    }
+
+    Table::create()
+        .table(table)
+        .col(
+            ColumnDef::new(PublicKeys::Id)
+                .big_integer()
+                .not_null()
+                .auto_increment()
+                .primary_key(),
+        )
+        .col(ColumnDef::new(PublicKeys::UserId).big_integer().not_null())
+        .col(&mut address)
+        .col(
+            ColumnDef::new(PublicKeys::CreatedAt)
+                .timestamp()
+                .not_null()
+                .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+        )
+        .col(ColumnDef::new(PublicKeys::Name).string_len(64).null())
+        .col(ColumnDef::new(PublicKeys::DeletedAt).timestamp().null())
+        .col(
+            ColumnDef::new(PublicKeys::UpdatedAt)
+                .timestamp()
+                .not_null()
+                .extra("DEFAULT CURRENT_TIMESTAMP".to_string()),
+        )
+        .foreign_key(
+            ForeignKey::create()
+                .from(table, PublicKeys::UserId)
+                .to(crate::Users::Table, crate::Users::Id)
+                .on_delete(ForeignKeyAction::Cascade)
+                .on_update(ForeignKeyAction::Cascade),
+        )
+        .to_owned()
+}
+
+/// Replace the public keys table with a new one created with the provided address constraint.
+async fn recreate_sqlite_table(
+    manager: &SchemaManager<'_>,
+    unique_address: bool,
+) -> Result<(), DbErr> {
+    manager
+        .create_table(public_keys_table(PublicKeys::NewTable, unique_address))
+        .await?;
+
+    manager
+        .get_connection()
+        .execute_unprepared(
+            "INSERT INTO public_keys_new SELECT id, user_id, address, created_at, name, deleted_at, updated_at FROM public_keys",
+        )
+        .await?;
+
+    manager
+        .drop_table(Table::drop().table(PublicKeys::Table).to_owned())
+        .await?;
+
+    manager
+        .rename_table(
+            Table::rename()
+                .table(PublicKeys::NewTable, PublicKeys::Table)
+                .to_owned(),
+        )
+        .await
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Soft-deleted public keys are kept as an audit trail,
+        // thus only addresses of active public keys must be unique.
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "ALTER TABLE public_keys DROP CONSTRAINT public_keys_address_key",
+                )
+                .await?;
+        } else {
+            recreate_sqlite_table(manager, false).await?;
+        }
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX idx_public_keys_active_address ON public_keys (address) WHERE deleted_at IS NULL",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Only a single row can be kept for each address.
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(PublicKeys::Table)
+                    .and_where(Expr::col(PublicKeys::DeletedAt).is_not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX idx_public_keys_active_address")
+            .await?;
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared(
+                    "ALTER TABLE public_keys ADD CONSTRAINT public_keys_address_key UNIQUE (address)",
+                )
+                .await?;
+        } else {
+            recreate_sqlite_table(manager, true).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden, Clone, Copy)]
+enum PublicKeys {
+    Table,
+    #[iden = "public_keys_new"]
+    NewTable,
+    Id,
+    UserId,
+    Address,
+    CreatedAt,
+    Name,
+    DeletedAt,
+    UpdatedAt,
+}
@@ -15,7 +15,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let config = Config::new(cli.config)?;
 
     info!("connecting to database");
-    let db = Database::connect(&config.database.url).await?;
+    let db = Database::connect(config.database.connect_options()).await?;
     info!("database connection established");
 
     run_migrate(migration::Migrator, &db, cli.command, false).await?;
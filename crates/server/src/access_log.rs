@@ -0,0 +1,177 @@
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, State},
+    http::{HeaderName, Request},
+    middleware::Next,
+    response::Response,
+};
+use common::logging::ACCESS_LOG_TARGET;
+use tracing::info;
+
+use crate::{auth::AuthenticatedUserId, client_ip::ClientIp};
+
+/// Access logging middleware.
+///
+/// Emits a single event per request after the response was produced.
+/// Neither request or response bodies, nor query strings are logged,
+/// and matched route templates are used instead of raw paths whenever possible
+/// to avoid exposing tokens that are passed as path segments.
+///
+/// This middleware has to be applied outside of a request span, but inside
+/// of a request identifier generation layer.
+pub(crate) async fn log<B>(
+    State(request_id_header): State<HeaderName>,
+    ClientIp(client_ip): ClientIp,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let method = request.method().clone();
+    let raw_path = request.uri().path().to_owned();
+    let request_id = request
+        .headers()
+        .get(&request_id_header)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let path = response
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or(&raw_path);
+    let user_id = response
+        .extensions()
+        .get::<AuthenticatedUserId>()
+        .map(AuthenticatedUserId::id);
+
+    info!(
+        target: ACCESS_LOG_TARGET,
+        method = method.as_str(),
+        path,
+        status = response.status().as_u16(),
+        latency_ms = start.elapsed().as_secs_f64() * 1000.,
+        user_id,
+        client_ip = client_ip.map(tracing::field::display),
+        request_id,
+        "request handled"
+    );
+
+    response
+}
+
+/// Route middleware that exposes the [`MatchedPath`] to the [`log`] middleware.
+pub(crate) async fn matched_path<B>(request: Request<B>, next: Next<B>) -> Response {
+    let route = request.extensions().get::<MatchedPath>().cloned();
+
+    let mut response = next.run(request).await;
+
+    if let Some(route) = route {
+        response.extensions_mut().insert(route);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::testing::create_database;
+
+    use axum::{body::Body, http::Request};
+    use common::{
+        config::{Config, LogFormat},
+        logging,
+    };
+    use db::{token, user, EntityTrait};
+    use serde_json::Value;
+    use tower::Service;
+    use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt, util::SubscriberInitExt};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn json_line() {
+        let db = create_database().await;
+
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(&db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert token");
+
+        let mut config = Config::for_tests();
+        config.logging.format = LogFormat::Json;
+        config.logging.access_log = true;
+
+        let buffer = Buffer::default();
+        let _guard = tracing_subscriber::registry()
+            .with(logging::layer(&config.logging, buffer.clone()))
+            .set_default();
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys?offset=0")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("x-request-id", "test-id")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json log line expected"))
+            .filter(|line: &Value| line["target"] == "access_log")
+            .collect();
+
+        assert_eq!(lines.len(), 1);
+
+        let line = &lines[0];
+        assert_eq!(line["method"], "GET");
+        assert_eq!(line["path"], "/keys");
+        assert_eq!(line["status"], 200);
+        assert_eq!(line["user_id"], user.id);
+        assert_eq!(line["request_id"], "test-id");
+        assert!(line["latency_ms"].is_number());
+        assert!(!output.contains(&token));
+    }
+}
@@ -1,20 +1,23 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use aide::OperationInput;
 use axum::{
-    extract::State,
+    async_trait,
+    extract::{FromRef, FromRequestParts, State},
     headers::{authorization::Bearer, Authorization},
-    http::{Request, StatusCode},
+    http::{request::Parts, Request, StatusCode},
     middleware::Next,
     response::Response,
     TypedHeader,
 };
-use axum_derive_error::ErrorResponse;
 use common::config::Config;
 use db::{
-    public_key, token, user, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
-    QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
+    login_challenge, public_key, token, user, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    PrimitiveDateTime, QueryFilter, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use tracing::error;
 
 /// User identifier typed wrapper.
 ///
@@ -35,17 +38,83 @@ impl AuthenticatedUserId {
     }
 }
 
+/// Administrator user identifier typed wrapper.
+///
+/// Can be extracted only from requests authenticated with the
+/// [`require_authentication`] middleware, and additionally requires
+/// the authenticated user to be marked as an administrator.
+///
+/// The same TOCTOU considerations as for [`AuthenticatedUserId`] apply.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct AuthenticatedAdminId(i64);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthenticatedAdminId
+where
+    Arc<DatabaseConnection>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthenticationError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user_id = parts
+            .extensions
+            .get::<AuthenticatedUserId>()
+            .ok_or(AuthenticationError::InvalidAuthenticationToken)?
+            .id();
+
+        let db = Arc::<DatabaseConnection>::from_ref(state);
+
+        let is_admin = user::Entity::find_by_id(user_id)
+            .select_only()
+            .filter(user::Column::IsAdmin.eq(true))
+            .exists(&*db)
+            .await?;
+
+        if !is_admin {
+            return Err(AuthenticationError::AdminRequired);
+        }
+
+        Ok(Self(user_id))
+    }
+}
+
+impl OperationInput for AuthenticatedAdminId {}
+
+/// Authentication token identifier typed wrapper.
+///
+/// Identifies the authentication token used to authenticate the current request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AuthenticatedTokenId(i64);
+
+impl AuthenticatedTokenId {
+    /// Get raw authentication token identifier value.
+    pub fn id(&self) -> i64 {
+        self.0
+    }
+}
+
 /// Errors that may occur during authentication process.
 #[derive(ErrorResponse, Display, From, Error)]
 pub(super) enum AuthenticationError {
     /// Database-related error.
     DatabaseError(DbErr),
 
+    /// User did not provide an authentication token.
+    #[status(StatusCode::UNAUTHORIZED)]
+    #[display(fmt = "authentication token is required to access")]
+    MissingAuthenticationToken,
+
     /// User provided incorrect authentication token.
-    #[status(StatusCode::FORBIDDEN)]
+    #[status(StatusCode::UNAUTHORIZED)]
     #[display(fmt = "invalid authentication token was provided")]
     InvalidAuthenticationToken,
 
+    /// User provided an expired authentication token.
+    #[status(StatusCode::UNAUTHORIZED)]
+    #[display(fmt = "authentication token is expired")]
+    ExpiredAuthenticationToken,
+
     /// User attempted to access a protected route without a verified key.
     #[status(StatusCode::FORBIDDEN)]
     #[display(fmt = "at least one verified key is required to access")]
@@ -55,6 +124,11 @@ pub(super) enum AuthenticationError {
     #[status(StatusCode::FORBIDDEN)]
     #[display(fmt = "paid membership is required to access")]
     PaymentRequired,
+
+    /// User without administrator privileges attempted to access an administration route.
+    #[status(StatusCode::FORBIDDEN)]
+    #[display(fmt = "administrator privileges are required to access")]
+    AdminRequired,
 }
 
 /// Authentication middleware for [`axum`].
@@ -74,28 +148,41 @@ pub(super) async fn require_authentication<
     B,
 >(
     State((db, config)): State<(Arc<DatabaseConnection>, Arc<Config>)>,
-    TypedHeader(authorization): TypedHeader<Authorization<Bearer>>,
+    authorization: Option<TypedHeader<Authorization<Bearer>>>,
     mut req: Request<B>,
     next: Next<B>,
 ) -> Result<Response, AuthenticationError> {
-    let user_id = db
+    let TypedHeader(authorization) =
+        authorization.ok_or(AuthenticationError::MissingAuthenticationToken)?;
+
+    let (token_id, user_id) = db
         .transaction::<_, _, AuthenticationError>(|txn| {
             Box::pin(async move {
-                let bearer = authorization.token();
+                let bearer = token::hash_token(authorization.token());
 
-                let user_id: i64 = token::Entity::find()
-                    .select_only()
-                    .column(token::Column::UserId)
-                    .filter(token::Column::Token.eq(bearer))
-                    .into_tuple()
-                    .one(txn)
-                    .await?
-                    .ok_or(AuthenticationError::InvalidAuthenticationToken)?;
+                let (token_id, user_id, expires_at): (i64, i64, PrimitiveDateTime) =
+                    token::Entity::find()
+                        .select_only()
+                        .columns([
+                            token::Column::Id,
+                            token::Column::UserId,
+                            token::Column::ExpiresAt,
+                        ])
+                        .filter(token::Column::Token.eq(bearer))
+                        .into_tuple()
+                        .one(txn)
+                        .await?
+                        .ok_or(AuthenticationError::InvalidAuthenticationToken)?;
+
+                if expires_at < db::now() {
+                    return Err(AuthenticationError::ExpiredAuthenticationToken);
+                }
 
                 if REQUIRE_VERIFIED_KEY {
                     let has_verified_keys = public_key::Entity::find()
                         .select_only()
                         .filter(public_key::Column::UserId.eq(user_id))
+                        .filter(public_key::Column::DeletedAt.is_null())
                         .exists(txn)
                         .await?;
 
@@ -116,13 +203,241 @@ pub(super) async fn require_authentication<
                     }
                 }
 
-                Ok(user_id)
+                Ok((token_id, user_id))
             })
         })
         .await
         .into_raw_result()?;
 
     req.extensions_mut().insert(AuthenticatedUserId(user_id));
+    req.extensions_mut().insert(AuthenticatedTokenId(token_id));
+
+    let mut response = next.run(req).await;
+
+    // Expose the authenticated user to the access log.
+    response
+        .extensions_mut()
+        .insert(AuthenticatedUserId(user_id));
+
+    Ok(response)
+}
+
+/// Get the lifespan of newly created authentication tokens.
+pub(crate) fn token_lifespan(config: &Config) -> db::Duration {
+    db::Duration::seconds(config.authentication.token_lifespan as i64)
+}
+
+/// Periodically delete expired authentication tokens and login challenges.
+pub(crate) async fn cleanup_expired_tokens(db: Arc<DatabaseConnection>, config: Arc<Config>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.authentication.token_cleanup_interval.get(),
+    ));
+
+    loop {
+        interval.tick().await;
 
-    Ok(next.run(req).await)
+        if let Err(err) = token::Entity::delete_many()
+            .filter(token::Column::ExpiresAt.lt(db::now()))
+            .exec(&*db)
+            .await
+        {
+            error!(%err, "unable to delete expired authentication tokens");
+        }
+
+        if let Err(err) = login_challenge::delete_expired(&*db).await {
+            error!(%err, "unable to delete expired login challenges");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use super::{require_authentication, AuthenticatedAdminId};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::from_fn_with_state,
+        routing::get,
+        Router,
+    };
+    use common::config::Config;
+    use db::{token, user, ActiveValue, DatabaseConnection, Duration, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection, lifespan: Duration) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, lifespan);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    async fn request_keys(db: DatabaseConnection, token: String) -> StatusCode {
+        crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn valid_token() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, token::TOKEN_LIFESPAN).await;
+
+        assert_eq!(request_keys(db, token).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn hashed_token() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, token::TOKEN_LIFESPAN).await;
+
+        let model = token::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .expect("token is missing");
+
+        assert_ne!(model.token, token);
+        assert_eq!(model.token, token::hash_token(&token));
+
+        assert_eq!(request_keys(db, token).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn expired_token() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, -Duration::minutes(1)).await;
+
+        assert_eq!(request_keys(db, token).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn unknown_token() {
+        let db = create_database().await;
+
+        assert_eq!(
+            request_keys(db, String::from("unknown")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    async fn create_admin_test_env(db: &DatabaseConnection, is_admin: bool) -> String {
+        let user = user::Entity::insert(user::ActiveModel {
+            is_admin: ActiveValue::Set(is_admin),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    async fn request_admin(
+        db: DatabaseConnection,
+        token: Option<String>,
+        authenticate: bool,
+    ) -> StatusCode {
+        let db = Arc::new(db);
+
+        let mut router = Router::new().route("/", get(|_: AuthenticatedAdminId| async {}));
+
+        if authenticate {
+            router = router.route_layer(from_fn_with_state(
+                (db.clone(), Arc::new(Config::for_tests())),
+                require_authentication::<false, false, _>,
+            ));
+        }
+
+        let mut request = Request::builder().method("GET").uri("/");
+
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+
+        router
+            .with_state(db)
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn admin() {
+        let db = create_database().await;
+
+        let token = create_admin_test_env(&db, true).await;
+
+        assert_eq!(request_admin(db, Some(token), true).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn non_admin() {
+        let db = create_database().await;
+
+        let token = create_admin_test_env(&db, false).await;
+
+        assert_eq!(
+            request_admin(db, Some(token), true).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_admin() {
+        let db = create_database().await;
+
+        create_admin_test_env(&db, true).await;
+
+        assert_eq!(
+            request_admin(db, Some(String::from("unknown")), true).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn admin_without_middleware() {
+        let db = create_database().await;
+
+        let token = create_admin_test_env(&db, true).await;
+
+        // Extractor must not trust requests that were not authenticated by the middleware.
+        assert_eq!(
+            request_admin(db, Some(token), false).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
 }
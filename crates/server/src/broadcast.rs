@@ -0,0 +1,221 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use db::{
+    event, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use futures_util::{stream, Stream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::error;
+
+/// Maximum count of events that can be buffered for slow subscribers of a single account.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Interval between database checks for newly discovered events.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Broadcast senders of contract accounts that have at least one subscriber.
+type Senders = Arc<Mutex<HashMap<Vec<u8>, broadcast::Sender<event::Model>>>>;
+
+/// Contract event broadcast channel.
+///
+/// Events are discovered by a separate event client process, thus the API server
+/// watches the database for newly inserted rows and publishes them to all subscribers.
+///
+/// Each contract account has a separate channel, so that events are sent only
+/// to subscribers of the related account.
+#[derive(Clone, Default)]
+pub(crate) struct EventBroadcast(Senders);
+
+/// Subscription to events of a single contract account.
+///
+/// Account channel is removed as soon as its last subscription is dropped.
+pub(crate) struct Subscription {
+    /// Account channel receiver, which is [`None`] only during the drop.
+    receiver: Option<broadcast::Receiver<event::Model>>,
+
+    /// Subscribed contract account.
+    account: Vec<u8>,
+
+    /// Senders of all subscribed accounts.
+    senders: Senders,
+}
+
+impl Subscription {
+    /// Receive the next event of the subscribed contract account.
+    pub(crate) async fn recv(&mut self) -> Result<event::Model, RecvError> {
+        self.receiver
+            .as_mut()
+            .expect("receiver is available until drop")
+            .recv()
+            .await
+    }
+
+    /// Convert this subscription into a [`Stream`] of contract events.
+    ///
+    /// Events missed by lagging subscribers are skipped.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = event::Model> {
+        stream::unfold(self, |mut subscription| async move {
+            loop {
+                match subscription.recv().await {
+                    Ok(event) => return Some((event, subscription)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut senders = self.senders.lock().unwrap();
+
+        // Drop the receiver while holding the lock, so that the receiver count
+        // can not be changed by other subscriptions.
+        drop(self.receiver.take());
+
+        if matches!(senders.get(&self.account), Some(sender) if sender.receiver_count() == 0) {
+            senders.remove(&self.account);
+        }
+    }
+}
+
+impl EventBroadcast {
+    /// Subscribe to newly discovered events of the provided contract account.
+    pub(crate) fn subscribe(&self, account: Vec<u8>) -> Subscription {
+        let receiver = self
+            .0
+            .lock()
+            .unwrap()
+            .entry(account.clone())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe();
+
+        Subscription {
+            receiver: Some(receiver),
+            account,
+            senders: self.0.clone(),
+        }
+    }
+
+    /// Get the count of contract accounts that have at least one subscriber.
+    #[cfg(test)]
+    pub(crate) fn subscribed_accounts(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Publish contract event to current subscribers of its contract account.
+    pub(crate) fn publish(&self, event: event::Model) {
+        if let Some(sender) = self.0.lock().unwrap().get(&event.account) {
+            // Subscribers may disconnect before the sender is removed.
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Watch the database for new contract events and publish them to subscribers.
+    pub(crate) async fn watch(self, db: Arc<DatabaseConnection>) -> Result<(), DbErr> {
+        let mut last_id = event::Entity::find()
+            .select_only()
+            .column(event::Column::Id)
+            .order_by_desc(event::Column::Id)
+            .into_tuple::<i64>()
+            .one(&*db)
+            .await?
+            .unwrap_or_default();
+
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let events = match event::Entity::find()
+                .filter(event::Column::Id.gt(last_id))
+                .order_by_asc(event::Column::Id)
+                .all(&*db)
+                .await
+            {
+                Ok(events) => events,
+                Err(err) => {
+                    error!(%err, "unable to fetch newly discovered events");
+                    continue;
+                }
+            };
+
+            for event in events {
+                last_id = event.id;
+                self.publish(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{event, OffsetDateTime, PrimitiveDateTime};
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    use super::EventBroadcast;
+
+    fn create_event(id: i64, account: [u8; 32]) -> event::Model {
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        event::Model {
+            id,
+            node_id: 1,
+            account: account.to_vec(),
+            event_type: event::EventType::Instantiation,
+            body: serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            raw_body: None,
+            block_timestamp: PrimitiveDateTime::new(datetime.date(), datetime.time()),
+            block_number: 42,
+            event_index: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn filtered() {
+        let broadcast = EventBroadcast::default();
+
+        let mut first = broadcast.subscribe(vec![1; 32]);
+        let mut second = broadcast.subscribe(vec![2; 32]);
+
+        broadcast.publish(create_event(1, [2; 32]));
+        broadcast.publish(create_event(2, [1; 32]));
+        broadcast.publish(create_event(3, [3; 32]));
+
+        assert_eq!(first.recv().await.unwrap().id, 2);
+        assert_eq!(second.recv().await.unwrap().id, 1);
+
+        assert_eq!(
+            first.receiver.as_mut().unwrap().try_recv(),
+            Err(TryRecvError::Empty)
+        );
+        assert_eq!(
+            second.receiver.as_mut().unwrap().try_recv(),
+            Err(TryRecvError::Empty)
+        );
+    }
+
+    #[test]
+    fn cleanup() {
+        let broadcast = EventBroadcast::default();
+
+        let first = broadcast.subscribe(vec![1; 32]);
+        let second = broadcast.subscribe(vec![1; 32]);
+
+        assert_eq!(broadcast.0.lock().unwrap().len(), 1);
+
+        drop(first);
+
+        assert_eq!(broadcast.0.lock().unwrap().len(), 1);
+
+        drop(second);
+
+        assert!(broadcast.0.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, HeaderMap},
+};
+use common::config::Config;
+
+/// Client IP address extractor.
+///
+/// Forwarded headers are taken into account only if the request was made by one
+/// of the configured trusted proxies. Requests received via a unix socket have no peer
+/// address, thus they are considered to be made by a trusted proxy.
+///
+/// [`None`] if the client IP address can not be determined.
+pub(crate) struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S: Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0.ip());
+
+        let trusted_proxies = parts
+            .extensions
+            .get::<Arc<Config>>()
+            .map(|config| &config.trusted_proxies[..])
+            .unwrap_or_default();
+
+        let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|net| net.contains(ip));
+
+        if let Some(peer) = peer.filter(|peer| !is_trusted(peer)) {
+            return Ok(Self(Some(peer)));
+        }
+
+        // Proxies append addresses to the end of the list, thus only the addresses
+        // following the last untrusted one can be spoofed by a client.
+        let mut client = peer;
+
+        for hop in forwarded_for(&parts.headers).into_iter().rev() {
+            let Some(hop) = hop else {
+                break;
+            };
+
+            client = Some(hop);
+
+            if !is_trusted(&hop) {
+                break;
+            }
+        }
+
+        Ok(Self(client))
+    }
+}
+
+/// Get forwarded client addresses from the `Forwarded` header,
+/// or from the `X-Forwarded-For` header if the former is not present.
+///
+/// Addresses are returned in the order they were appended by proxies,
+/// and unrecognized addresses, such as obfuscated identifiers, are returned as [`None`].
+fn forwarded_for(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    let values = |name| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+    };
+
+    if headers.contains_key(header::FORWARDED) {
+        values(header::FORWARDED)
+            .filter_map(|element| {
+                element.split(';').find_map(|pair| {
+                    let (key, value) = pair.trim().split_once('=')?;
+
+                    key.eq_ignore_ascii_case("for").then(|| parse_node(value))
+                })
+            })
+            .collect()
+    } else {
+        values(header::HeaderName::from_static("x-forwarded-for"))
+            .map(|value| value.parse().ok())
+            .collect()
+    }
+}
+
+/// Parse a node identifier of the `Forwarded` header, which may be quoted
+/// and may contain a port number, with IPv6 addresses enclosed in brackets.
+fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim_matches('"');
+
+    if let Some(value) = value.strip_prefix('[') {
+        return value.split_once(']')?.0.parse().ok();
+    }
+
+    value
+        .parse()
+        .ok()
+        .or_else(|| value.rsplit_once(':')?.0.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{IpAddr, SocketAddr},
+        sync::Arc,
+    };
+
+    use axum::{
+        extract::{ConnectInfo, FromRequestParts},
+        http::Request,
+    };
+    use common::config::Config;
+
+    use super::ClientIp;
+
+    async fn client_ip(peer: Option<&str>, headers: &[(&str, &str)]) -> Option<IpAddr> {
+        let mut config = Config::for_tests();
+        config.trusted_proxies = vec!["10.0.0.0/8".parse().unwrap()];
+
+        let mut request = Request::builder().extension(Arc::new(config));
+
+        if let Some(peer) = peer {
+            request = request.extension(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 1)));
+        }
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        let (mut parts, _) = request.body(()).unwrap().into_parts();
+
+        let ClientIp(ip) = ClientIp::from_request_parts(&mut parts, &()).await.unwrap();
+
+        ip
+    }
+
+    #[tokio::test]
+    async fn untrusted_peer() {
+        assert_eq!(
+            client_ip(Some("192.0.2.1"), &[("X-Forwarded-For", "198.51.100.1")]).await,
+            Some("192.0.2.1".parse().unwrap())
+        );
+
+        assert_eq!(
+            client_ip(Some("192.0.2.1"), &[("Forwarded", "for=198.51.100.1")]).await,
+            Some("192.0.2.1".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn trusted_peer() {
+        for (peer, headers, expected) in [
+            (
+                "10.0.0.1",
+                [("X-Forwarded-For", "198.51.100.1")],
+                "198.51.100.1",
+            ),
+            // Spoofed addresses preceding the real client address are ignored.
+            (
+                "10.0.0.1",
+                [("X-Forwarded-For", "203.0.113.1, 198.51.100.1, 10.0.0.2")],
+                "198.51.100.1",
+            ),
+            (
+                "10.0.0.1",
+                [(
+                    "Forwarded",
+                    r#"for="[2001:db8::1]:4711";proto=https, for=10.0.0.2"#,
+                )],
+                "2001:db8::1",
+            ),
+            (
+                "10.0.0.1",
+                [("Forwarded", "for=198.51.100.1:80")],
+                "198.51.100.1",
+            ),
+            // Obfuscated identifiers can not be used, thus the nearest hop is used instead.
+            (
+                "10.0.0.1",
+                [("Forwarded", "for=_hidden, for=10.0.0.2")],
+                "10.0.0.2",
+            ),
+            ("10.0.0.1", [("X-Request-Id", "test")], "10.0.0.1"),
+        ] {
+            assert_eq!(
+                client_ip(Some(peer), &headers).await,
+                Some(expected.parse().unwrap()),
+                "{headers:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn unix_socket() {
+        assert_eq!(
+            client_ip(None, &[("X-Forwarded-For", "198.51.100.1")]).await,
+            Some("198.51.100.1".parse().unwrap())
+        );
+
+        assert_eq!(client_ip(None, &[]).await, None);
+    }
+}
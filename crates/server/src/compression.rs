@@ -0,0 +1,91 @@
+use common::config::Compression;
+use tower_http::{
+    compression::{
+        predicate::{NotForContentType, SizeAbove},
+        CompressionLayer, Predicate,
+    },
+    CompressionLevel,
+};
+
+/// Server-sent event responses must be delivered without buffering.
+const EVENT_STREAM: NotForContentType = NotForContentType::const_new("text/event-stream");
+
+/// Create a [`CompressionLayer`] from the provided configuration.
+pub(crate) fn layer(config: &Compression) -> CompressionLayer<impl Predicate> {
+    let quality = config
+        .level
+        .map(CompressionLevel::Precise)
+        .unwrap_or_default();
+
+    CompressionLayer::new().quality(quality).compress_when(
+        SizeAbove::new(config.min_size)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(EVENT_STREAM),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{broadcast::EventBroadcast, testing::create_database};
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        Extension,
+    };
+    use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+    use tower::ServiceExt;
+
+    fn config(min_size: u16) -> Config {
+        let mut config = Config::for_tests();
+        config.compression.min_size = min_size;
+        config
+    }
+
+    async fn content_encoding(config: Config, uri: &str) -> Option<String> {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .layer(Extension(EventBroadcast::default()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(uri)
+                    .header(header::ACCEPT_ENCODING, "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .map(|value| value.to_str().unwrap().to_owned())
+    }
+
+    #[tokio::test]
+    async fn compressed() {
+        assert_eq!(
+            content_encoding(config(0), "/health").await.as_deref(),
+            Some("gzip")
+        );
+    }
+
+    #[tokio::test]
+    async fn small_response() {
+        assert_eq!(content_encoding(config(1024), "/health").await, None);
+    }
+
+    #[tokio::test]
+    async fn event_stream() {
+        let uri = format!("/contracts/events/{}/stream", AccountId32::new([1; 32]));
+
+        assert_eq!(content_encoding(config(0), &uri).await, None);
+    }
+}
@@ -0,0 +1,68 @@
+use std::convert::Infallible;
+
+use aide::{
+    gen::GenContext,
+    openapi::{
+        HeaderStyle, Operation, Parameter, ParameterData, ParameterSchemaOrContent, SchemaObject,
+    },
+    operation::add_parameters,
+    OperationInput,
+};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    headers::{self, ETag, Header, HeaderMapExt},
+    http::request::Parts,
+};
+
+/// Optional client-provided `If-None-Match` header value.
+///
+/// Malformed header values are ignored, as if no header was provided.
+pub(crate) struct IfNoneMatch(pub Option<headers::IfNoneMatch>);
+
+impl IfNoneMatch {
+    /// Check if the client already has a representation with the provided [`ETag`].
+    pub(crate) fn matches(&self, etag: &ETag) -> bool {
+        matches!(&self.0, Some(if_none_match) if !if_none_match.precondition_passes(etag))
+    }
+}
+
+impl OperationInput for IfNoneMatch {
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        let schema = ctx.schema.subschema_for::<String>();
+
+        add_parameters(
+            ctx,
+            operation,
+            [Parameter::Header {
+                parameter_data: ParameterData {
+                    name: headers::IfNoneMatch::name().to_string(),
+                    description: Some(String::from(
+                        "ETag value of a previous response, which is not returned again if unchanged.",
+                    )),
+                    required: false,
+                    format: ParameterSchemaOrContent::Schema(SchemaObject {
+                        json_schema: schema,
+                        example: None,
+                        external_docs: None,
+                    }),
+                    extensions: Default::default(),
+                    deprecated: None,
+                    example: None,
+                    examples: Default::default(),
+                    explode: None,
+                },
+                style: HeaderStyle::Simple,
+            }],
+        );
+    }
+}
+
+#[async_trait]
+impl<S: Sync> FromRequestParts<S> for IfNoneMatch {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(parts.headers.typed_get()))
+    }
+}
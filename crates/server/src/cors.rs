@@ -0,0 +1,184 @@
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use common::config::Cors;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer};
+use tracing::warn;
+
+/// Value that allows any origin, method or header.
+const WILDCARD: &str = "*";
+
+/// Create a [`CorsLayer`] from the provided configuration.
+///
+/// Values that can not be parsed are skipped with a warning.
+pub(crate) fn layer(config: &Cors) -> CorsLayer {
+    let allow_origin = if is_wildcard(&config.allowed_origins) {
+        AllowOrigin::from(Any)
+    } else {
+        AllowOrigin::list(parse(&config.allowed_origins, |origin| {
+            HeaderValue::from_str(origin).ok()
+        }))
+    };
+
+    let allow_methods = if is_wildcard(&config.allowed_methods) {
+        AllowMethods::from(Any)
+    } else {
+        AllowMethods::list(parse(&config.allowed_methods, |method| {
+            Method::from_bytes(method.as_bytes()).ok()
+        }))
+    };
+
+    let allow_headers = if is_wildcard(&config.allowed_headers) {
+        AllowHeaders::from(Any)
+    } else {
+        AllowHeaders::list(parse(&config.allowed_headers, |header| {
+            HeaderName::from_bytes(header.as_bytes()).ok()
+        }))
+    };
+
+    // CORS layer replaces the `Vary` header set by handlers,
+    // thus the `Accept` header used for response negotiation is listed here as well.
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(allow_methods)
+        .allow_headers(allow_headers)
+        .vary([
+            header::ORIGIN,
+            header::ACCESS_CONTROL_REQUEST_METHOD,
+            header::ACCESS_CONTROL_REQUEST_HEADERS,
+            header::ACCEPT,
+        ])
+}
+
+/// Check if the provided configuration values allow any value.
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == WILDCARD)
+}
+
+/// Parse configuration values, skipping invalid ones.
+fn parse<T, F: Fn(&str) -> Option<T>>(values: &[String], parser: F) -> Vec<T> {
+    values
+        .iter()
+        .filter_map(|value| {
+            let parsed = parser(value);
+
+            if parsed.is_none() {
+                warn!(%value, "skipping invalid CORS configuration value");
+            }
+
+            parsed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+    };
+    use common::config::Config;
+    use tower::Service;
+
+    const ORIGIN: &str = "https://example.com";
+
+    fn config(allowed_origins: &[&str]) -> Config {
+        let mut config = Config::for_tests();
+        config.cors.allowed_origins = allowed_origins.iter().map(|val| val.to_string()).collect();
+        config
+    }
+
+    #[tokio::test]
+    async fn allowed_origin() {
+        let db = create_database().await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(config(&[ORIGIN])));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("OPTIONS")
+                    .uri("/keys")
+                    .header(header::ORIGIN, ORIGIN)
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+                    .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            ORIGIN
+        );
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS],
+            "authorization,content-type,idempotency-key"
+        );
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header(header::ORIGIN, ORIGIN)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        // Rejected requests must be readable by a browser as well.
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            ORIGIN
+        );
+    }
+
+    #[tokio::test]
+    async fn disallowed_origin() {
+        for allowed_origins in [&[][..], &["https://other.com"][..]] {
+            let db = create_database().await;
+
+            let response = crate::app_router(Arc::new(db), Arc::new(config(allowed_origins)))
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri("/keys")
+                        .header(header::ORIGIN, ORIGIN)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert!(!response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+        }
+    }
+
+    #[tokio::test]
+    async fn wildcard() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config(&["*"])))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header(header::ORIGIN, ORIGIN)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+}
@@ -0,0 +1,103 @@
+use aide::{
+    gen::GenContext,
+    openapi::{MediaType, Operation, Response},
+    OperationOutput,
+};
+use axum::{
+    body::{Bytes, StreamBody},
+    http::header,
+    response::IntoResponse,
+    BoxError,
+};
+use futures_util::Stream;
+
+/// Streamed CSV file response.
+pub(crate) struct CsvStream<S> {
+    /// Stream of encoded CSV records.
+    stream: S,
+
+    /// File name suggested to clients.
+    filename: String,
+}
+
+impl<S, E> CsvStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    /// Create new [`CsvStream`] from the provided [`Stream`] of encoded records.
+    pub(crate) fn new(stream: S, filename: String) -> Self {
+        Self { stream, filename }
+    }
+}
+
+impl<S, E> IntoResponse for CsvStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    fn into_response(self) -> axum::response::Response {
+        (
+            [
+                (header::CONTENT_TYPE, String::from("text/csv")),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", self.filename),
+                ),
+            ],
+            StreamBody::new(self.stream),
+        )
+            .into_response()
+    }
+}
+
+impl<S> OperationOutput for CsvStream<S> {
+    type Inner = ();
+
+    fn operation_response(_ctx: &mut GenContext, _operation: &mut Operation) -> Option<Response> {
+        let mut response = Response {
+            description: "CSV file".into(),
+            ..Default::default()
+        };
+
+        response
+            .content
+            .insert("text/csv".into(), MediaType::default());
+
+        Some(response)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| Vec::from([(Some(200), response)]))
+            .unwrap_or_default()
+    }
+}
+
+/// Encode a single CSV record, terminated with a line break.
+///
+/// Fields containing separators, quotes or line breaks are quoted.
+pub(crate) fn record<'a, I: IntoIterator<Item = &'a str>>(fields: I) -> Bytes {
+    let mut buf = String::new();
+
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            buf.push(',');
+        }
+
+        if field.contains([',', '"', '\r', '\n']) {
+            buf.push('"');
+            buf.push_str(&field.replace('"', "\"\""));
+            buf.push('"');
+        } else {
+            buf.push_str(field);
+        }
+    }
+
+    buf.push_str("\r\n");
+
+    Bytes::from(buf)
+}
@@ -0,0 +1,517 @@
+use std::sync::Arc;
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Error, Json, Object, Result, Schema,
+    SimpleObject,
+};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    extract::{Path, State},
+    Extension,
+};
+use common::{
+    config::Config,
+    rpc::sp_core::crypto::{AccountId32, Ss58Codec},
+};
+use db::DatabaseConnection;
+use serde_json::Value;
+
+use crate::{
+    handlers::{
+        codes::{
+            contracts::find_contracts,
+            metadata::{metadata, CodeMetadataError},
+        },
+        contracts::{
+            details::{find_contract, ContractDetailsError},
+            events::{find_events, ContractEventsQuery, EventCursor},
+        },
+    },
+    hex_hash::HexHash,
+    pagination::OffsetPagination,
+};
+
+/// Maximum nesting depth of a single GraphQL query.
+const MAX_DEPTH: usize = 8;
+
+/// GraphQL schema served by the API server.
+pub(crate) type GraphQLSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Create a new [`GraphQLSchema`] backed by the provided database connection.
+pub(crate) fn schema(database: Arc<DatabaseConnection>, config: Arc<Config>) -> GraphQLSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(database)
+        .data(config)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// GraphQL request handler.
+pub(crate) async fn handler(
+    Extension(schema): Extension<GraphQLSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
+
+/// Parse an SS58-encoded account identifier.
+fn parse_account(account: &str) -> Result<AccountId32> {
+    AccountId32::from_ss58check(account).map_err(|_| Error::new("invalid account"))
+}
+
+/// Parse a hex-encoded code hash.
+fn parse_code_hash(hash: &str) -> Result<HexHash> {
+    serde_plain::from_str(hash).map_err(|_| Error::new("invalid code hash"))
+}
+
+/// Root query object.
+///
+/// Resolvers reuse the corresponding REST handlers, thus both APIs share
+/// the same database queries, limits and error messages.
+pub(crate) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Get details about the provided contract account.
+    async fn contract(&self, ctx: &Context<'_>, account: String) -> Result<Option<Contract>> {
+        let db = ctx.data::<Arc<DatabaseConnection>>()?.clone();
+        let config = ctx.data::<Arc<Config>>()?.clone();
+        let account = parse_account(&account)?;
+
+        match find_contract(&db, config, account.clone()).await {
+            Ok(contract) => Ok(Some(Contract {
+                account,
+                address: contract.address,
+                node_id: contract.node_id,
+                node: contract.node,
+                code_hash: hex::encode(contract.code_hash.0),
+                owner: contract.owner,
+                instantiation_block: contract.instantiation_block,
+                instantiated_at: contract.instantiated_at,
+                created_at: contract.created_at,
+                updated_at: contract.updated_at,
+            })),
+            Err(ContractDetailsError::ContractNotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Get events related to the contract account.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        account: String,
+        limit: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Events> {
+        contract_events(ctx, parse_account(&account)?, limit, before).await
+    }
+
+    /// Get information about the provided code hash.
+    async fn codes(&self, hash: String) -> Result<Code> {
+        Ok(Code {
+            hash: parse_code_hash(&hash)?,
+        })
+    }
+}
+
+/// Smart contract details.
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Contract {
+    /// Parsed contract account identifier.
+    #[graphql(skip)]
+    account: AccountId32,
+
+    /// Contract account address.
+    address: String,
+
+    /// Related node identifier.
+    node_id: i64,
+
+    /// Related node name.
+    node: String,
+
+    /// Related code hash.
+    code_hash: String,
+
+    /// Contract owner.
+    ///
+    /// This field is only available is the contract
+    /// was discovered after the initial activation of an event server.
+    owner: Option<String>,
+
+    /// Number of a block in which the contract was instantiated.
+    instantiation_block: Option<i64>,
+
+    /// Timestamp of a block in which the contract was instantiated.
+    instantiated_at: Option<i64>,
+
+    /// Contract discovery timestamp.
+    created_at: i64,
+
+    /// Last contract update timestamp.
+    updated_at: i64,
+}
+
+#[ComplexObject]
+impl Contract {
+    /// Get events related to the current contract.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Events> {
+        contract_events(ctx, self.account.clone(), limit, before).await
+    }
+}
+
+/// A single contract event.
+#[derive(SimpleObject)]
+pub(crate) struct Event {
+    /// Event identifier.
+    id: i64,
+
+    /// Type of a contract event.
+    event_type: String,
+
+    /// JSON body of a contract event.
+    body: Json<Value>,
+
+    /// Timestamp of a block in which the event was discovered.
+    timestamp: i64,
+
+    /// Number of a block in which the event was discovered.
+    block_number: i64,
+}
+
+/// Contract event list.
+#[derive(SimpleObject)]
+pub(crate) struct Events {
+    /// Contract events, ordered from the newest to the oldest one.
+    events: Vec<Event>,
+
+    /// Cursor that can be passed as the `before` argument to get the next page.
+    next_cursor: Option<String>,
+}
+
+/// Get events related to the provided contract account.
+async fn contract_events(
+    ctx: &Context<'_>,
+    account: AccountId32,
+    limit: Option<u64>,
+    before: Option<String>,
+) -> Result<Events> {
+    let db = ctx.data::<Arc<DatabaseConnection>>()?.clone();
+    let config = ctx.data::<Arc<Config>>()?.clone();
+
+    let before = before
+        .map(|cursor| cursor.parse::<EventCursor>())
+        .transpose()
+        .map_err(|_| Error::new("invalid event cursor"))?;
+
+    let query = ContractEventsQuery {
+        before,
+        limit,
+        topic: None,
+        q: None,
+        strict: false,
+        fields: None,
+        timestamp_format: Default::default(),
+        raw: false,
+    };
+
+    let response = find_events(&db, &config, &account, query).await?;
+
+    Ok(Events {
+        events: response
+            .events
+            .into_iter()
+            .map(|event| {
+                Ok(Event {
+                    id: event.id,
+                    event_type: serde_plain::to_string(&event.event_type)?,
+                    body: Json(event.body),
+                    timestamp: event.timestamp,
+                    block_number: event.block_number,
+                })
+            })
+            .collect::<Result<_>>()?,
+        next_cursor: response.next_cursor.map(|cursor| cursor.to_string()),
+    })
+}
+
+/// Uploaded code information.
+pub(crate) struct Code {
+    /// Code hash value.
+    hash: HexHash,
+}
+
+#[Object]
+impl Code {
+    /// Code hash value.
+    async fn hash(&self) -> String {
+        hex::encode(self.hash.0)
+    }
+
+    /// JSON metadata of the code.
+    ///
+    /// Metadata is available only for codes verified using build sessions.
+    async fn metadata(&self, ctx: &Context<'_>) -> Result<Option<Json<Value>>> {
+        let db = ctx.data::<Arc<DatabaseConnection>>()?.clone();
+
+        match metadata(Path(self.hash), State(db)).await {
+            Ok(metadata) => Ok(Some(Json(metadata.0))),
+            Err(CodeMetadataError::MetadataNotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Contract accounts deployed from the code.
+    async fn contracts(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<String>> {
+        let db = ctx.data::<Arc<DatabaseConnection>>()?.clone();
+        let config = ctx.data::<Arc<Config>>()?.clone();
+
+        let pagination = OffsetPagination {
+            limit,
+            offset: offset.unwrap_or_default(),
+        };
+
+        Ok(find_contracts(&db, &config, &self.hash, &pagination).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+    use db::{
+        code, contract, event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            metadata: ActiveValue::Set(Some(json!({ "version": "4" }).to_string())),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert code");
+
+        contract::Entity::insert(contract::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            code_hash: ActiveValue::Set(vec![0; 32]),
+            address: ActiveValue::Set(vec![1; 32]),
+            owner: ActiveValue::Set(Some(vec![2; 32])),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert contract");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Instantiation),
+            body: ActiveValue::Set(
+                serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            ),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert an event");
+    }
+
+    async fn query(db: DatabaseConnection, config: Config, query: String) -> (StatusCode, Value) {
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/graphql")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({ "query": query })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+
+        if status == StatusCode::OK {
+            (status, response.json().await)
+        } else {
+            (status, Value::Null)
+        }
+    }
+
+    #[tokio::test]
+    async fn contract_with_events() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let (status, body) = query(
+            db,
+            Config::for_tests(),
+            format!(
+                r#"{{
+                    contract(account: "{}") {{
+                        nodeId
+                        codeHash
+                        events(limit: 1) {{
+                            events {{ eventType body blockNumber }}
+                            nextCursor
+                        }}
+                    }}
+                    unknown: contract(account: "{}") {{ nodeId }}
+                }}"#,
+                AccountId32::new([1; 32]),
+                AccountId32::new([3; 32])
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_json!(body, {
+            "data": {
+                "contract": {
+                    "nodeId": 1,
+                    "codeHash": "0000000000000000000000000000000000000000000000000000000000000000",
+                    "events": {
+                        "events": [
+                            {
+                                "eventType": "instantiation",
+                                "body": "Instantiation",
+                                "blockNumber": 42
+                            }
+                        ],
+                        "nextCursor": "0_1"
+                    }
+                },
+                "unknown": validators::null()
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn codes() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let (status, body) = query(
+            db,
+            Config::for_tests(),
+            format!(
+                r#"{{ codes(hash: "0x{}") {{ metadata contracts }} }}"#,
+                "00".repeat(32)
+            ),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_json!(body, {
+            "data": {
+                "codes": {
+                    "metadata": {
+                        "version": "4"
+                    },
+                    "contracts": [AccountId32::new([1; 32]).to_string().as_str()]
+                }
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn ss58_prefix() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.ss58_prefix = 2;
+
+        let (status, body) = query(
+            db,
+            config,
+            format!(
+                r#"{{
+                    contract(account: "{}") {{ address owner }}
+                    codes(hash: "0x{}") {{ contracts }}
+                }}"#,
+                AccountId32::new([1; 32]),
+                "00".repeat(32)
+            ),
+        )
+        .await;
+
+        let format = Ss58AddressFormat::custom(2);
+        let address = AccountId32::new([1; 32]).to_ss58check_with_version(format);
+
+        assert_eq!(status, StatusCode::OK);
+        assert_json!(body, {
+            "data": {
+                "contract": {
+                    "address": address.as_str(),
+                    "owner": AccountId32::new([2; 32]).to_ss58check_with_version(format).as_str()
+                },
+                "codes": {
+                    "contracts": [address.as_str()]
+                }
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn disabled() {
+        let db = create_database().await;
+
+        let mut config = Config::for_tests();
+
+        if let Some(server) = config.server.as_mut() {
+            server.graphql = false;
+        }
+
+        let (status, _) = query(db, config, String::from("{ codes(hash: \"00\") { hash } }")).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
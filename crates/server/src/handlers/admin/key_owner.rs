@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{
+    public_key, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    auth::{AuthenticatedAdminId, AuthenticationError},
+    handlers::contracts::{AccountPath, WrappedAccountId32},
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the public key owner lookup.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum KeyOwnerError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided public key is not attached to any user.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "public key not found")]
+    PublicKeyNotFound,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct KeyOwnerResponse {
+    /// Identifier of a user, to which the public key is attached.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    user_id: i64,
+}
+
+/// Generate OAPI documentation for the [`key_owner`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get the user that owns the provided public key.")
+        .description("Only users with administrator privileges can access this route.")
+        .response::<200, Json<KeyOwnerResponse>>()
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("The current user is not an administrator.")
+                .example(example_error(AuthenticationError::AdminRequired))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided public key is not attached to any user.")
+                .example(example_error(KeyOwnerError::PublicKeyNotFound))
+        })
+}
+
+/// Public key owner lookup handler.
+pub(super) async fn key_owner(
+    _: AuthenticatedAdminId,
+    AccountPath(address): AccountPath<WrappedAccountId32>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<KeyOwnerResponse>, KeyOwnerError> {
+    let user_id = public_key::Entity::find()
+        .select_only()
+        .column(public_key::Column::UserId)
+        .filter(public_key::Column::Address.eq(address.0.as_slice()))
+        .filter(public_key::Column::DeletedAt.is_null())
+        .into_tuple::<i64>()
+        .one(&*db)
+        .await?
+        .ok_or(KeyOwnerError::PublicKeyNotFound)?;
+
+    Ok(Json(KeyOwnerResponse { user_id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        response::Response,
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection, is_admin: bool) -> String {
+        let user = user::Entity::insert(user::ActiveModel {
+            is_admin: ActiveValue::Set(is_admin),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        public_key::Entity::insert(public_key::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            address: ActiveValue::Set(vec![1; 32]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create public key");
+
+        token
+    }
+
+    async fn key_owner(db: DatabaseConnection, token: &str, account: [u8; 32]) -> Response {
+        crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/admin/keys/{}", AccountId32::new(account)))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, true).await;
+
+        let response = key_owner(db, &token, [1; 32]).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "user_id": 1
+        });
+    }
+
+    #[tokio::test]
+    async fn unattached() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, true).await;
+
+        let response = key_owner(db, &token, [2; 32]).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn forbidden() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, false).await;
+
+        let response = key_owner(db, &token, [1; 32]).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
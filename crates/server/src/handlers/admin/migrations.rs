@@ -0,0 +1,171 @@
+use std::{collections::HashSet, sync::Arc};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Json};
+use db::{DatabaseConnection, DbErr};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use migration::{Migrator, MigratorTrait};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    auth::{AuthenticatedAdminId, AuthenticationError},
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the migration list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum MigrationListError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// A single applied database migration.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct AppliedMigration {
+    /// Migration name.
+    #[schemars(example = "crate::schema::example_migration_name")]
+    name: String,
+
+    /// Timestamp at which the migration was applied.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    applied_at: i64,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct MigrationListResponse {
+    /// Applied migrations, ordered by their names.
+    applied: Vec<AppliedMigration>,
+
+    /// Names of migrations that are not yet applied.
+    pending: Vec<String>,
+}
+
+/// Generate OAPI documentation for the [`migrations`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List database migrations.")
+        .description("Only users with administrator privileges can access this route.")
+        .response::<200, Json<MigrationListResponse>>()
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("The current user is not an administrator.")
+                .example(example_error(AuthenticationError::AdminRequired))
+        })
+}
+
+/// Migration list request handler.
+pub(super) async fn migrations(
+    _: AuthenticatedAdminId,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<MigrationListResponse>, MigrationListError> {
+    let applied: Vec<_> = Migrator::get_migration_models(&*db)
+        .await?
+        .into_iter()
+        .map(|model| AppliedMigration {
+            name: model.version,
+            applied_at: model.applied_at,
+        })
+        .collect();
+
+    let pending = pending_migrations(applied.iter().map(|migration| &*migration.name).collect());
+
+    Ok(Json(MigrationListResponse { applied, pending }))
+}
+
+/// Get names of migrations known to the API server, which are not in the provided applied set.
+fn pending_migrations(applied: HashSet<&str>) -> Vec<String> {
+    Migrator::migrations()
+        .into_iter()
+        .map(|migration| migration.name().to_owned())
+        .filter(|name| !applied.contains(name.as_str()))
+        .collect()
+}
+
+/// Ensure that all database migrations are applied.
+///
+/// Pending migrations are applied if `run` is set, otherwise an error is returned.
+pub(crate) async fn ensure_applied(
+    db: &DatabaseConnection,
+    run: bool,
+) -> Result<(), anyhow::Error> {
+    let applied = Migrator::get_migration_models(db).await?;
+    let pending = pending_migrations(applied.iter().map(|model| &*model.version).collect());
+
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    if !run {
+        return Err(anyhow::Error::msg(format!(
+            "database has pending migrations ({}), apply them using the migration tool or enable the server.run_migrations option",
+            pending.join(", ")
+        )));
+    }
+
+    info!(migrations = %pending.join(", "), "applying pending database migrations");
+    Migrator::up(db, None).await?;
+    info!("database migrations applied");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{token, user, ActiveValue, EntityTrait};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let user = user::Entity::insert(user::ActiveModel {
+            is_admin: ActiveValue::Set(true),
+            ..Default::default()
+        })
+        .exec_with_returning(&db)
+        .await
+        .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert token");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/admin/migrations")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.json().await;
+
+        assert_eq!(
+            body["applied"][0]["name"],
+            "m20220101_000001_create_users_table"
+        );
+        assert!(body["applied"][0]["applied_at"].is_i64());
+        assert_eq!(body["pending"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,31 @@
+/// Public key owner lookup route.
+mod key_owner;
+
+/// Database migration list route.
+pub(crate) mod migrations;
+
+/// Event pruning route.
+mod prune;
+
+use aide::axum::{
+    routing::{get_with, post_with},
+    ApiRouter,
+};
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with administration routes
+/// available to authenticated users with administrator privileges.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route("/events/prune", post_with(prune::prune, prune::docs))
+        .api_route(
+            "/keys/:address",
+            get_with(key_owner::key_owner, key_owner::docs),
+        )
+        .api_route(
+            "/migrations",
+            get_with(migrations::migrations, migrations::docs),
+        )
+        .with_path_items(|op| op.tag("Administration"))
+}
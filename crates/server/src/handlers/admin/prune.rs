@@ -0,0 +1,211 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::config::Config;
+use db::{event, DatabaseConnection, DbErr, OffsetDateTime, PrimitiveDateTime};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{AuthenticatedAdminId, AuthenticationError},
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
+
+/// Errors that may occur during the event pruning process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum EventPruneError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided timestamp is out of the supported range.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "invalid timestamp")]
+    InvalidTimestamp,
+}
+
+/// JSON request body.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct EventPruneRequest {
+    /// Events that occured before this UNIX timestamp are deleted.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    before_timestamp: i64,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct EventPruneResponse {
+    /// Count of deleted events.
+    deleted: u64,
+}
+
+/// Generate OAPI documentation for the [`prune`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete events that occured before the provided timestamp.")
+        .description(
+            r#"Events are deleted in batches, size of which is configured
+by the event retention settings of the API server.
+
+Only users with administrator privileges can access this route."#,
+        )
+        .response::<200, Json<EventPruneResponse>>()
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
+            op.description("Invalid timestamp was provided.")
+                .example(example_error(EventPruneError::InvalidTimestamp))
+        })
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("The current user is not an administrator.")
+                .example(example_error(AuthenticationError::AdminRequired))
+        })
+}
+
+/// Event pruning handler.
+pub(super) async fn prune(
+    _: AuthenticatedAdminId,
+    State(db): State<Arc<DatabaseConnection>>,
+    Extension(config): Extension<Arc<Config>>,
+    JsonBody(request): JsonBody<EventPruneRequest>,
+) -> Result<Json<EventPruneResponse>, EventPruneError> {
+    let before = OffsetDateTime::from_unix_timestamp(request.before_timestamp)
+        .map_err(|_| EventPruneError::InvalidTimestamp)?;
+
+    let deleted = event::prune(
+        &*db,
+        PrimitiveDateTime::new(before.date(), before.time()),
+        config.event_retention.batch_size,
+    )
+    .await?;
+
+    Ok(Json(EventPruneResponse { deleted }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        event, node, token, user, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PaginatorTrait, PrimitiveDateTime,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_user_token(db: &DatabaseConnection, is_admin: bool) -> String {
+        let user = user::Entity::insert(user::ActiveModel {
+            is_admin: ActiveValue::Set(is_admin),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    fn prune_request(token: &str, before_timestamp: i64) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/admin/events/prune")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("Content-Type", "application/json")
+            .body(Body::from_json(json!({
+                "before_timestamp": before_timestamp,
+            })))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(&db)
+        .await
+        .expect("unable to insert node");
+
+        for (block_number, timestamp) in [(1, 100), (2, 200), (3, 300)] {
+            let datetime = OffsetDateTime::from_unix_timestamp(timestamp).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event::EventType::Instantiation),
+                body: ActiveValue::Set(String::from("{}")),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(block_number),
+                ..Default::default()
+            })
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert event");
+        }
+
+        let token = create_user_token(&db, true).await;
+        let db = Arc::new(db);
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(prune_request(&token, 250))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "deleted": 2,
+        });
+
+        assert_eq!(event::Entity::find().count(&*db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalid_token() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(prune_request("invalid", 250))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn not_admin() {
+        let db = create_database().await;
+
+        let token = create_user_token(&db, false).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(prune_request(&token, 250))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Json};
+use common::rpc::sp_core::sr25519::Public;
+use db::{login_challenge, DatabaseConnection, DbErr, EntityTrait};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{schema::ErrorBody, validation::JsonBody};
+
+/// Errors that may occur during the login challenge generation process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum LoginChallengeError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Login challenge request.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct LoginChallengeRequest {
+    /// Public key that will be used to sign the challenge.
+    #[schemars(example = "crate::schema::example_public_key", with = "String")]
+    account: Public,
+}
+
+/// Login challenge response.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct LoginChallengeResponse {
+    /// Nonce value that has to be signed to authenticate.
+    #[schemars(example = "crate::schema::example_nonce")]
+    nonce: String,
+}
+
+/// Generate OAPI documentation for the [`challenge`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Generate a new login challenge.")
+        .description(
+            r#"Generated nonce is bound to the provided public key, can be used only once
+and expires after 5 minutes since its creation.
+
+Sign the nonce wrapped in `<Bytes>` tags and pass both the nonce
+and the signature to the `/auth/login` route to authenticate."#,
+        )
+        .response::<200, Json<LoginChallengeResponse>>()
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
+}
+
+/// Generate a new login challenge for the provided public key.
+pub(super) async fn challenge(
+    State(db): State<Arc<DatabaseConnection>>,
+    JsonBody(request): JsonBody<LoginChallengeRequest>,
+) -> Result<Json<LoginChallengeResponse>, LoginChallengeError> {
+    let (model, nonce) = login_challenge::generate_challenge(request.account.0.to_vec());
+
+    login_challenge::Entity::insert(model)
+        .exec_without_returning(&*db)
+        .await?;
+
+    Ok(Json(LoginChallengeResponse { nonce }))
+}
@@ -2,17 +2,19 @@ use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{extract::State, http::StatusCode, Json};
-use axum_derive_error::ErrorResponse;
 use db::{
     cli_token, token, DatabaseConnection, DbErr, EntityTrait, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use validator::Validate;
 
-use crate::{schema::example_error, validation::ValidatedJson};
+use crate::{
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
 
 /// Errors related to the token exchange.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -53,10 +55,11 @@ to exchange a locally-generated token for an authentication one, which
 can be used to authenticate with any other route later."#,
         )
         .response::<200, Json<ExchangeTokenResponse>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("Invalid CLI token.")
                 .example(example_error(ExchangeTokenError::TokenNotFound))
         })
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
 }
 
 /// CLI token exchange handler.
@@ -82,11 +85,95 @@ pub(super) async fn exchange(
                 .exec(txn)
                 .await?;
 
-            Ok(Json(ExchangeTokenResponse {
-                token: token_model.token,
-            }))
+            // Only token hashes are stored, thus the token issued during the login
+            // is replaced with a new one, which is returned to the CLI.
+            let (model, token) = token::regenerate_token(token_model.id);
+
+            token::Entity::update(model).exec(txn).await?;
+
+            Ok(Json(ExchangeTokenResponse { token }))
         })
     })
     .await
     .into_raw_result()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{cli_token, token, user, ActiveValue, EntityTrait};
+    use serde_json::json;
+    use tower::Service;
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(&db)
+            .await
+            .expect("unable to create user");
+
+        let (model, _) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        let model = token::Entity::insert(model)
+            .exec_with_returning(&db)
+            .await
+            .expect("unable to insert token");
+
+        let cli_token = "a".repeat(db::cli_token::TOKEN_LENGTH);
+
+        cli_token::Entity::insert(cli_token::ActiveModel {
+            token: ActiveValue::Set(cli_token.clone()),
+            authentication_token_id: ActiveValue::Set(model.id),
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert cli token");
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/auth/exchange")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "cli_token": cli_token,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let token = response.json().await["token"]
+            .as_str()
+            .expect("token is missing")
+            .to_string();
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
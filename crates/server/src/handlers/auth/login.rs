@@ -4,24 +4,31 @@ use aide::{transform::TransformOperation, OperationIo};
 use axum::{
     extract::{Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
-use axum_derive_error::ErrorResponse;
-use common::rpc::sp_core::{
-    sr25519::{Pair, Public, Signature},
-    Pair as _,
+use common::{
+    config::Config,
+    rpc::sp_core::{
+        sr25519::{Pair, Public, Signature},
+        Pair as _,
+    },
 };
 use db::{
-    cli_token, public_key, sea_query::OnConflict, token, ActiveValue, ColumnTrait,
-    DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect, TransactionErrorExt,
-    TransactionTrait,
+    cli_token, login_challenge, public_key,
+    sea_query::{Expr, OnConflict},
+    token, user, ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr,
+    EntityTrait, QueryFilter, QuerySelect, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-use crate::schema::example_error;
+use crate::{
+    auth::token_lifespan,
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
 
 /// Errors that may occur during the authentication process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -35,6 +42,11 @@ pub(super) enum UserAuthenticationError {
     #[display(fmt = "invalid signature")]
     InvalidSignature,
 
+    /// User provided an unknown, already used or expired challenge nonce.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "invalid nonce")]
+    InvalidNonce,
+
     /// Provided key doesn't have any related account.
     // OK is used here to allow web app to interact more simply.
     #[status(StatusCode::OK)]
@@ -60,13 +72,22 @@ pub(super) struct UserAuthenticationRequest {
 
     /// Message signed with the provided public key for verification.
     ///
-    /// Verification message consists of
-    /// a string equal to the account address
-    /// used for verification purposes.
+    /// If a challenge nonce is provided, verification message
+    /// consists of the nonce value.
+    ///
+    /// Example: `<Bytes>x4VpkDX2rzYfMhdrI5HH8wg0knBZkHT1</Bytes>`
+    ///
+    /// Otherwise, verification message consists of
+    /// a string equal to the account address.
     ///
     /// Example: `<Bytes>5FeLhJAs4CUHqpWmPDBLeL7NLAoHsB2ZuFZ5Mk62EgYemtFj</Bytes>`
     #[schemars(example = "crate::schema::example_signature", with = "String")]
     signature: Signature,
+
+    /// Challenge nonce issued by the `/auth/challenge` route.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_nonce")]
+    nonce: Option<String>,
 }
 
 /// Conditional successful token exchange.
@@ -98,6 +119,11 @@ a new authentication token.
 Provided credentials are validated to ensure that the provided signature
 belongs to the provided public key.
 
+If a challenge nonce issued by the `/auth/challenge` route is provided,
+the signature must be made over that nonce instead of the account address.
+In that case, a new user is registered automatically if the provided
+public key is not attached to any user yet.
+
 This route returns different responses depending on the flow you want to use.
 Regular authentication flow returns an authentication token from this route
 as soon as the signature validation is successful. CLI authentication flow
@@ -106,10 +132,11 @@ To proceed with the CLI authentication flow, pass `cli_token` value as specified
 in the query string documentation."#,
         )
         .response::<200, Json<UserAuthenticationResponse>>()
-        .response_with::<422, Json<Value>, _>(|op| {
-            op.description("The provided signature is invalid.")
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("The provided signature or challenge nonce is invalid.")
                 .example(example_error(UserAuthenticationError::InvalidSignature))
         })
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
 }
 
 /// User authentication handler.
@@ -117,77 +144,181 @@ in the query string documentation."#,
 /// This handler will accept a verified key
 /// and return an authentication token for the relevant user.
 pub(super) async fn login(
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
     Query(query): Query<UserAuthenticationQuery>,
-    Json(request): Json<UserAuthenticationRequest>,
+    JsonBody(request): JsonBody<UserAuthenticationRequest>,
 ) -> Result<Json<UserAuthenticationResponse>, UserAuthenticationError> {
+    if let Some(nonce) = &request.nonce {
+        let challenge = login_challenge::Entity::find_by_id(nonce)
+            .filter(login_challenge::Column::Address.eq(&request.account.0[..]))
+            .one(&*db)
+            .await?
+            .ok_or(UserAuthenticationError::InvalidNonce)?;
+
+        // Consume the challenge before any further checks to prevent replay attempts.
+        let deleted = login_challenge::Entity::delete_by_id(&challenge.nonce)
+            .exec(&*db)
+            .await?;
+
+        if deleted.rows_affected == 0 || challenge.is_expired() {
+            return Err(UserAuthenticationError::InvalidNonce);
+        }
+
+        if !Pair::verify(
+            &request.signature,
+            format!("<Bytes>{nonce}</Bytes>"),
+            &request.account,
+        ) {
+            return Err(UserAuthenticationError::InvalidSignature);
+        }
+    }
+
     db.transaction(|txn| {
         Box::pin(async move {
-            let user_id: i64 = public_key::Entity::find()
-                .select_only()
-                .column(public_key::Column::UserId)
-                .filter(public_key::Column::Address.eq(&request.account.0[..]))
-                .into_tuple()
-                .one(txn)
-                .await?
-                .ok_or(UserAuthenticationError::NoRelatedAccounts)?;
-
-            if Pair::verify(
-                &request.signature,
-                format!("<Bytes>{}</Bytes>", &request.account),
-                &request.account,
-            ) {
-                let (active_model, token) = token::generate_token(user_id);
-
-                let model = token::Entity::insert(active_model)
-                    .exec_with_returning(txn)
-                    .await?;
-
-                let response = if let Some(token) = query.cli_token {
-                    cli_token::Entity::insert(cli_token::ActiveModel {
-                        token: ActiveValue::Set(token),
-                        authentication_token_id: ActiveValue::Set(model.id),
-                    })
-                    .on_conflict(
-                        OnConflict::column(cli_token::Column::Token)
-                            .do_nothing()
-                            .to_owned(),
-                    )
-                    .exec_without_returning(txn)
-                    .await?;
-
-                    UserAuthenticationResponse::Cli
-                } else {
-                    UserAuthenticationResponse::Web { token }
-                };
-
-                Ok(Json(response))
+            let user_id = if request.nonce.is_some() {
+                find_or_create_user(txn, &request.account).await?
             } else {
-                Err(UserAuthenticationError::InvalidSignature)
-            }
+                let user_id: i64 = public_key::Entity::find()
+                    .select_only()
+                    .column(public_key::Column::UserId)
+                    .filter(public_key::Column::Address.eq(&request.account.0[..]))
+                    .filter(public_key::Column::DeletedAt.is_null())
+                    .into_tuple()
+                    .one(txn)
+                    .await?
+                    .ok_or(UserAuthenticationError::NoRelatedAccounts)?;
+
+                if !Pair::verify(
+                    &request.signature,
+                    format!("<Bytes>{}</Bytes>", &request.account),
+                    &request.account,
+                ) {
+                    return Err(UserAuthenticationError::InvalidSignature);
+                }
+
+                user_id
+            };
+
+            let (active_model, token) = token::generate_token(user_id, token_lifespan(&config));
+
+            let model = token::Entity::insert(active_model)
+                .exec_with_returning(txn)
+                .await?;
+
+            let response = if let Some(token) = query.cli_token {
+                cli_token::Entity::insert(cli_token::ActiveModel {
+                    token: ActiveValue::Set(token),
+                    authentication_token_id: ActiveValue::Set(model.id),
+                })
+                .on_conflict(
+                    OnConflict::column(cli_token::Column::Token)
+                        .do_nothing()
+                        .to_owned(),
+                )
+                .exec_without_returning(txn)
+                .await?;
+
+                UserAuthenticationResponse::Cli
+            } else {
+                UserAuthenticationResponse::Web { token }
+            };
+
+            Ok(Json(response))
         })
     })
     .await
     .into_raw_result()
 }
 
+/// Find a user, to which the provided public key is attached.
+///
+/// If the public key is not attached to any user,
+/// a new user is created and the public key is attached to it.
+///
+/// Concurrent logins with the same public key may both attempt to create a user,
+/// in which case the unique active public key address index ensures that only one of them
+/// attaches the public key, while the other one discards its user and reuses the existing one.
+async fn find_or_create_user(txn: &DatabaseTransaction, account: &Public) -> Result<i64, DbErr> {
+    let user_id = public_key::Entity::find()
+        .select_only()
+        .column(public_key::Column::UserId)
+        .filter(public_key::Column::Address.eq(&account.0[..]))
+        .filter(public_key::Column::DeletedAt.is_null())
+        .into_tuple()
+        .one(txn)
+        .await?;
+
+    if let Some(user_id) = user_id {
+        return Ok(user_id);
+    }
+
+    let user = user::Entity::insert(user::ActiveModel::default())
+        .exec_with_returning(txn)
+        .await?;
+
+    // Soft-deleted public keys are kept, thus a new public key is attached instead.
+    let attached = public_key::Entity::insert(public_key::ActiveModel {
+        user_id: ActiveValue::Set(user.id),
+        address: ActiveValue::Set(account.0.to_vec()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::column(public_key::Column::Address)
+            .target_and_where(Expr::col(public_key::Column::DeletedAt).is_null())
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(txn)
+    .await?;
+
+    if attached == 0 {
+        // Public key was attached by a concurrent login in the meantime.
+        user::Entity::delete_by_id(user.id).exec(txn).await?;
+
+        return public_key::Entity::find()
+            .select_only()
+            .column(public_key::Column::UserId)
+            .filter(public_key::Column::Address.eq(&account.0[..]))
+            .filter(public_key::Column::DeletedAt.is_null())
+            .into_tuple()
+            .one(txn)
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(String::from("public key is missing")));
+    }
+
+    Ok(user.id)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
-    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+    use crate::testing::{
+        create_database, create_postgres_database, RequestBodyExt, ResponseBodyExt,
+    };
 
+    use aide::axum::ApiRouter;
     use assert_json::{assert_json, validators};
     use axum::{
         body::Body,
         http::{Request, StatusCode},
+        response::Response,
     };
     use common::config::Config;
-    use common::rpc::sp_core::crypto::{AccountId32, Ss58Codec};
+    use common::rpc::sp_core::{
+        crypto::{AccountId32, Ss58Codec},
+        sr25519::Pair,
+        Pair as _,
+    };
     use db::{
-        cli_token, public_key, token::TOKEN_LENGTH, user, ActiveValue, DatabaseConnection,
-        EntityTrait,
+        cli_token, public_key,
+        sea_orm::{DbBackend, Statement},
+        token::TOKEN_LENGTH,
+        user, ActiveValue, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+        PrimitiveDateTime, TransactionTrait,
     };
+    use futures_util::future::join_all;
     use rand::{
         distributions::{Alphanumeric, DistString},
         thread_rng,
@@ -432,4 +563,238 @@ mod tests {
 
         assert_eq!(login_response.status(), StatusCode::OK);
     }
+
+    async fn request_challenge(service: &mut ApiRouter, pair: &Pair) -> String {
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/auth/challenge")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": pair.public().to_ss58check(),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        response.json().await["nonce"]
+            .as_str()
+            .expect("nonce is missing")
+            .to_string()
+    }
+
+    async fn challenge_login(
+        service: &mut ApiRouter,
+        pair: &Pair,
+        nonce: &str,
+        message: &str,
+    ) -> Response {
+        let signature = pair.sign(format!("<Bytes>{message}</Bytes>").as_bytes());
+
+        service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/auth/login")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": pair.public().to_ss58check(),
+                        "signature": format!("0x{}", hex::encode(signature)),
+                        "nonce": nonce,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn challenge() {
+        let db = Arc::new(create_database().await);
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let pair = Pair::generate().0;
+
+        for _ in 0..2 {
+            let nonce = request_challenge(&mut service, &pair).await;
+
+            let response = challenge_login(&mut service, &pair, &nonce, &nonce).await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_json!(response.json().await, {
+                "token": validators::string(|val| {
+                    (val.len() == TOKEN_LENGTH)
+                        .then_some(())
+                        .ok_or(String::from("invalid length"))
+                })
+            });
+        }
+
+        // Subsequent logins must reuse the user created during the first one.
+        assert_eq!(user::Entity::find().count(&*db).await.unwrap(), 1);
+
+        let key = public_key::Entity::find()
+            .one(&*db)
+            .await
+            .unwrap()
+            .expect("public key is missing");
+
+        assert_eq!(key.address, pair.public().0.to_vec());
+    }
+
+    #[tokio::test]
+    async fn concurrent_challenges() {
+        let db = Arc::new(create_database().await);
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let pair = Pair::generate().0;
+
+        let mut nonces = Vec::new();
+
+        for _ in 0..5 {
+            nonces.push(request_challenge(&mut service, &pair).await);
+        }
+
+        let responses = join_all(nonces.iter().map(|nonce| {
+            let mut service = service.clone();
+            let pair = pair.clone();
+
+            async move { challenge_login(&mut service, &pair, nonce, nonce).await }
+        }))
+        .await;
+
+        for response in responses {
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        // Concurrent first logins must not create multiple users.
+        assert_eq!(user::Entity::find().count(&*db).await.unwrap(), 1);
+        assert_eq!(public_key::Entity::find().count(&*db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database provided with the TEST_DATABASE_URL variable"]
+    async fn conflicting_first_logins() {
+        let db = create_postgres_database().await;
+
+        let account = Pair::generate().0.public();
+
+        let first = db.begin().await.unwrap();
+        let second = db.begin().await.unwrap();
+
+        let user_id = super::find_or_create_user(&first, &account)
+            .await
+            .expect("unable to create user");
+
+        // Second login doesn't see the uncommitted public key,
+        // thus it attempts to attach the same address to a new user.
+        let second_login = tokio::spawn(async move {
+            let user_id = super::find_or_create_user(&second, &account).await?;
+            second.commit().await?;
+            Ok::<_, DbErr>(user_id)
+        });
+
+        // Wait for the public key insertion of the second login to block on the active address index.
+        let waiting_statement = Statement::from_string(
+            DbBackend::Postgres,
+            String::from("SELECT 1 FROM pg_locks WHERE NOT granted"),
+        );
+
+        while db
+            .query_one(waiting_statement.clone())
+            .await
+            .unwrap()
+            .is_none()
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        first.commit().await.unwrap();
+
+        assert_eq!(second_login.await.unwrap().unwrap(), user_id);
+
+        // User created by the second login is removed.
+        assert_eq!(user::Entity::find().count(&db).await.unwrap(), 1);
+        assert_eq!(public_key::Entity::find().count(&db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn soft_deleted_key() {
+        let db = Arc::new(create_database().await);
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let pair = Pair::generate().0;
+
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(&*db)
+            .await
+            .expect("unable to create user");
+
+        public_key::Entity::insert(public_key::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            address: ActiveValue::Set(pair.public().0.to_vec()),
+            deleted_at: ActiveValue::Set(Some(PrimitiveDateTime::MIN)),
+            ..Default::default()
+        })
+        .exec_without_returning(&*db)
+        .await
+        .expect("unable to create public key");
+
+        let nonce = request_challenge(&mut service, &pair).await;
+
+        let response = challenge_login(&mut service, &pair, &nonce, &nonce).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Deleted public key is kept, while a new user receives a new public key.
+        let keys = public_key::Entity::find().all(&*db).await.unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].user_id, user.id);
+        assert!(keys[0].deleted_at.is_some());
+        assert_ne!(keys[1].user_id, user.id);
+        assert!(keys[1].deleted_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn tampered_challenge() {
+        let db = Arc::new(create_database().await);
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let pair = Pair::generate().0;
+
+        let nonce = request_challenge(&mut service, &pair).await;
+
+        let response = challenge_login(&mut service, &pair, &nonce, "tampered").await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(user::Entity::find().count(&*db).await.unwrap(), 0);
+
+        // Challenge is consumed even if the signature is invalid.
+        let response = challenge_login(&mut service, &pair, &nonce, &nonce).await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn challenge_of_other_account() {
+        let db = create_database().await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let pair = Pair::generate().0;
+        let other = Pair::generate().0;
+
+        let nonce = request_challenge(&mut service, &other).await;
+
+        let response = challenge_login(&mut service, &pair, &nonce, &nonce).await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
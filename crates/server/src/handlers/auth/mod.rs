@@ -1,3 +1,6 @@
+/// Login challenge route.
+mod challenge;
+
 /// CLI token exchange route.
 mod exchange;
 
@@ -7,16 +10,59 @@ mod login;
 /// User registration route.
 mod register;
 
+/// Authentication token revocation route.
+mod revoke;
+
+/// Authentication session management routes.
+mod sessions;
+
 use std::sync::Arc;
 
-use aide::axum::{routing::post_with, ApiRouter};
+use aide::axum::{
+    routing::{delete_with, get_with, post_with},
+    ApiRouter,
+};
+use axum::middleware::from_fn_with_state;
+use common::config::Config;
 use db::DatabaseConnection;
 
+use crate::{
+    auth,
+    rate_limit::{self, RateLimiter},
+    state::AppState,
+};
+
 /// Create an [`ApiRouter`] that provides an API server with authentication routes.
-pub(crate) fn routes() -> ApiRouter<Arc<DatabaseConnection>> {
-    ApiRouter::new()
+pub(crate) fn routes(
+    database: Arc<DatabaseConnection>,
+    config: Arc<Config>,
+    rate_limiter: RateLimiter,
+) -> ApiRouter<AppState> {
+    let public_routes = ApiRouter::new()
+        .api_route(
+            "/challenge",
+            post_with(challenge::challenge, challenge::docs),
+        )
         .api_route("/login", post_with(login::login, login::docs))
         .api_route("/register", post_with(register::register, register::docs))
         .api_route("/exchange", post_with(exchange::exchange, exchange::docs))
+        .route_layer(from_fn_with_state(rate_limiter, rate_limit::limit));
+
+    let private_routes = ApiRouter::new()
+        .api_route("/token", delete_with(revoke::revoke, revoke::docs))
+        .api_route(
+            "/sessions",
+            get_with(sessions::list, sessions::list_docs)
+                .delete_with(sessions::revoke_other, sessions::revoke_other_docs),
+        )
+        .route_layer(from_fn_with_state(
+            (database, config),
+            auth::require_authentication::<false, false, _>,
+        ))
+        .with_path_items(|op| op.security_requirement("Authentication token"));
+
+    ApiRouter::new()
+        .merge(private_routes)
+        .merge(public_routes)
         .with_path_items(|op| op.tag("Authentication"))
 }
@@ -1,15 +1,18 @@
 use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
-use axum::{extract::State, Json};
-use axum_derive_error::ErrorResponse;
+use axum::{extract::State, Extension, Json};
+use common::config::Config;
 use db::{
     token, user, DatabaseConnection, DbErr, EntityTrait, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
 
+use crate::{auth::token_lifespan, schema::ErrorBody};
+
 /// Errors that may occur during the user registration process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
 #[aide(output)]
@@ -36,6 +39,7 @@ have any public keys attached to their account, meaning that you have to attach
 as soon as possible to ensure that a user account does not get lost."#,
         )
         .response::<200, Json<UserRegistrationResponse>>()
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
 }
 
 /// User registration handler.
@@ -43,6 +47,7 @@ as soon as possible to ensure that a user account does not get lost."#,
 /// This route will return an authentication token for a newly registered
 /// users to provide an ability to verify a public key for an account.
 pub(super) async fn register(
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
 ) -> Result<Json<UserRegistrationResponse>, UserRegistrationError> {
     db.transaction(|txn| {
@@ -52,7 +57,7 @@ pub(super) async fn register(
                     .exec_with_returning(txn)
                     .await?;
 
-            let (model, token) = token::generate_token(user.id);
+            let (model, token) = token::generate_token(user.id, token_lifespan(&config));
 
             token::Entity::insert(model)
                 .exec_without_returning(txn)
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension};
+use db::{token, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::auth::{AuthenticatedTokenId, AuthenticatedUserId};
+
+/// Errors that may occur during the authentication token revocation.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum TokenRevocationError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Generate OAPI documentation for the [`revoke`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke the current authentication token.")
+        .description(
+            r#"Authentication token used to access this route
+can not be used to authenticate any further requests."#,
+        )
+        .response::<200, ()>()
+}
+
+/// Authentication token revocation handler.
+pub(super) async fn revoke(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(current_token): Extension<AuthenticatedTokenId>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<(), TokenRevocationError> {
+    token::Entity::delete_many()
+        .filter(token::Column::Id.eq(current_token.id()))
+        .filter(token::Column::UserId.eq(current_user.id()))
+        .exec(&*db)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{token, user, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn revoke() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/auth/token")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use db::{
+    token, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PrimitiveDateTime, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::auth::{AuthenticatedTokenId, AuthenticatedUserId};
+
+/// Errors that may occur during the session management requests handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum SessionsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// A single authentication session.
+#[derive(Serialize, JsonSchema)]
+pub struct Session {
+    /// Authentication token identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+
+    /// Authentication token creation timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    created_at: i64,
+
+    /// Authentication token expiration timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    expires_at: i64,
+
+    /// Whether this session is used to authenticate the current request.
+    current: bool,
+}
+
+/// Generate OAPI documentation for the [`list`] handler.
+pub(super) fn list_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List authentication sessions of the current user.")
+        .response_with::<200, Json<Vec<Session>>, _>(|op| op.description("Session list."))
+}
+
+/// List authentication sessions of the current authenticated user.
+///
+/// Raw authentication token values are never returned.
+pub(super) async fn list(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(current_token): Extension<AuthenticatedTokenId>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<Vec<Session>>, SessionsError> {
+    token::Entity::find()
+        .select_only()
+        .columns([
+            token::Column::Id,
+            token::Column::CreatedAt,
+            token::Column::ExpiresAt,
+        ])
+        .filter(token::Column::UserId.eq(current_user.id()))
+        .order_by_asc(token::Column::Id)
+        .into_tuple::<(i64, PrimitiveDateTime, PrimitiveDateTime)>()
+        .stream(&*db)
+        .await?
+        .map_ok(|(id, created_at, expires_at)| Session {
+            id,
+            created_at: created_at.assume_utc().unix_timestamp(),
+            expires_at: expires_at.assume_utc().unix_timestamp(),
+            current: id == current_token.id(),
+        })
+        .err_into()
+        .try_collect()
+        .await
+        .map(Json)
+}
+
+/// Generate OAPI documentation for the [`revoke_other`] handler.
+pub(super) fn revoke_other_docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Revoke all authentication sessions of the current user except the current one.")
+        .response::<200, ()>()
+}
+
+/// Revoke all authentication sessions of the current authenticated user,
+/// except the one used to authenticate the current request.
+pub(super) async fn revoke_other(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(current_token): Extension<AuthenticatedTokenId>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<(), SessionsError> {
+    token::Entity::delete_many()
+        .filter(token::Column::UserId.eq(current_user.id()))
+        .filter(token::Column::Id.ne(current_token.id()))
+        .exec(&*db)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{token, user, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> (String, String) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (first_model, first) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+        let (second_model, second) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert_many([first_model, second_model])
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert tokens");
+
+        (first, second)
+    }
+
+    #[tokio::test]
+    async fn list_and_revoke() {
+        let db = create_database().await;
+
+        let (current, other) = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/auth/sessions")
+                    .header("Authorization", format!("Bearer {current}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "created_at": validators::i64(|_| Ok(())),
+                "expires_at": validators::i64(|_| Ok(())),
+                "current": true
+            },
+            {
+                "id": 2,
+                "created_at": validators::i64(|_| Ok(())),
+                "expires_at": validators::i64(|_| Ok(())),
+                "current": false
+            }
+        ]);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/auth/sessions")
+                    .header("Authorization", format!("Bearer {current}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for (token, status) in [(current, StatusCode::OK), (other, StatusCode::UNAUTHORIZED)] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri("/auth/sessions")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    Json,
+};
+use db::{build_session, code, DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use serde_json::Value;
+
+use crate::schema::{example_error, ErrorBody};
+
+/// Errors that may occur during the contract bundle request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildSessionArtifactError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Unable to serialize the contract bundle.
+    SerializationError(serde_json::Error),
+
+    /// Metadata stored inside of a database is not a JSON object.
+    #[display(fmt = "invalid metadata")]
+    InvalidMetadata,
+
+    /// The requested build session was not completed successfully,
+    /// or its artifacts are no longer available.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "artifact not found")]
+    ArtifactNotFound,
+}
+
+/// Generate OAPI documentation for the [`artifact`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Download contract bundle of a build session.")
+        .description(
+            r#"Contract bundle is a JSON metadata with an embedded WASM blob,
+which is available only for successfully completed build sessions."#,
+        )
+        .response_with::<200, Json<Value>, _>(|op| {
+            op.description("Contract bundle response.")
+                .example(Value::Object(Default::default()))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No artifacts are available for the provided build session identifier.")
+                .example(example_error(BuildSessionArtifactError::ArtifactNotFound))
+        })
+}
+
+/// Contract bundle request handler.
+pub(super) async fn artifact(
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<([(header::HeaderName, String); 2], Vec<u8>), BuildSessionArtifactError> {
+    let (status, code_hash, metadata) = build_session::Entity::find_by_id(id)
+        .select_only()
+        .columns([
+            build_session::Column::Status,
+            build_session::Column::CodeHash,
+            build_session::Column::Metadata,
+        ])
+        .into_tuple::<(build_session::Status, Option<Vec<u8>>, Option<Vec<u8>>)>()
+        .one(&*db)
+        .await?
+        .ok_or(BuildSessionArtifactError::ArtifactNotFound)?;
+
+    if status != build_session::Status::Completed {
+        return Err(BuildSessionArtifactError::ArtifactNotFound);
+    }
+
+    let (Some(code_hash), Some(metadata)) = (code_hash, metadata) else {
+        return Err(BuildSessionArtifactError::ArtifactNotFound);
+    };
+
+    let wasm = code::Entity::find_by_id(code_hash.clone())
+        .select_only()
+        .column(code::Column::Code)
+        .into_tuple::<Vec<u8>>()
+        .one(&*db)
+        .await?
+        .ok_or(BuildSessionArtifactError::ArtifactNotFound)?;
+
+    let mut bundle: Value = serde_json::from_slice(&metadata)
+        .map_err(|_| BuildSessionArtifactError::InvalidMetadata)?;
+
+    let source = bundle
+        .as_object_mut()
+        .ok_or(BuildSessionArtifactError::InvalidMetadata)?
+        .entry("source")
+        .or_insert_with(|| Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or(BuildSessionArtifactError::InvalidMetadata)?;
+
+    source.insert(
+        String::from("wasm"),
+        Value::String(format!("0x{}", hex::encode(wasm))),
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, String::from("application/json")),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}.contract\"",
+                    hex::encode(code_hash)
+                ),
+            ),
+        ],
+        serde_json::to_vec(&bundle)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        build_session, code, source_code, user, ActiveValue, DatabaseConnection, EntityTrait,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code")
+        .id;
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert code");
+
+        for status in [build_session::Status::Completed, build_session::Status::New] {
+            build_session::Entity::insert(build_session::ActiveModel {
+                user_id: ActiveValue::Set(Some(user.id)),
+                source_code_id: ActiveValue::Set(source_code_id),
+                status: ActiveValue::Set(status),
+                cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+                code_hash: ActiveValue::Set(Some(vec![0; 32])),
+                metadata: ActiveValue::Set(Some(
+                    serde_json::to_vec(&json!({
+                        "source": {
+                            "hash": format!("0x{}", hex::encode([0; 32])),
+                        },
+                        "spec": {}
+                    }))
+                    .unwrap(),
+                )),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert build session");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/buildSessions/artifact/1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()["content-disposition"],
+            format!("attachment; filename=\"{}.contract\"", hex::encode([0; 32]))
+        );
+
+        assert_json!(response.json().await, {
+            "source": {
+                "hash": format!("0x{}", hex::encode([0; 32])),
+                "wasm": "0x010203"
+            },
+            "spec": {}
+        });
+    }
+
+    #[tokio::test]
+    async fn unfinished() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for id in [2, 3] {
+            let response = service
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/buildSessions/artifact/{id}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
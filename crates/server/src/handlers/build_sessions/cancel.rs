@@ -0,0 +1,347 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use db::{
+    build_session, build_session_cancellation,
+    sea_query::{LockBehavior, LockType, OnConflict},
+    ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    QueryTrait, TransactionErrorExt, TransactionTrait,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the build session cancellation request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildSessionCancelError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested build session was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "build session not found")]
+    BuildSessionNotFound,
+
+    /// The requested build session was initiated by another user.
+    #[status(StatusCode::FORBIDDEN)]
+    #[display(fmt = "build session is not owned by the current user")]
+    NotBuildSessionOwner,
+
+    /// The requested build session has already finished.
+    #[status(StatusCode::CONFLICT)]
+    #[display(fmt = "build session has already finished")]
+    BuildSessionFinished,
+}
+
+/// Generate OAPI documentation for the [`cancel`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Cancel build session.")
+        .description(
+            r#"Pending build sessions are cancelled immediately.
+
+Build sessions that are already being processed are cancelled by a build worker
+as soon as the current build step is finished, in which case an `202 Accepted`
+response is returned instead."#,
+        )
+        .response_with::<204, (), _>(|op| op.description("Build session was cancelled."))
+        .response_with::<202, (), _>(|op| {
+            op.description("Build session will be cancelled by a build worker.")
+        })
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("Build session was initiated by another user.")
+                .example(example_error(BuildSessionCancelError::NotBuildSessionOwner))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No build sessions with the provided identifier were found.")
+                .example(example_error(BuildSessionCancelError::BuildSessionNotFound))
+        })
+        .response_with::<409, Json<ErrorBody>, _>(|op| {
+            op.description("Build session has already finished.")
+                .example(example_error(BuildSessionCancelError::BuildSessionFinished))
+        })
+}
+
+/// Build session cancellation request handler.
+pub(super) async fn cancel(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<StatusCode, BuildSessionCancelError> {
+    let (user_id, status) = build_session::Entity::find_by_id(id)
+        .select_only()
+        .columns([build_session::Column::UserId, build_session::Column::Status])
+        .into_tuple::<(Option<i64>, build_session::Status)>()
+        .one(&*db)
+        .await?
+        .ok_or(BuildSessionCancelError::BuildSessionNotFound)?;
+
+    if user_id != Some(current_user.id()) {
+        return Err(BuildSessionCancelError::NotBuildSessionOwner);
+    }
+
+    if status != build_session::Status::New {
+        return Err(BuildSessionCancelError::BuildSessionFinished);
+    }
+
+    let mut unlocked = build_session::Entity::find()
+        .select_only()
+        .column(build_session::Column::Id)
+        .filter(build_session::Column::Id.eq(id))
+        .filter(build_session::Column::Status.eq(build_session::Status::New));
+
+    // Build sessions that are being processed are locked by a build worker,
+    // and updating them directly would wait until the build is finished.
+    QuerySelect::query(&mut unlocked)
+        .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+    let cancelled = build_session::Entity::update_many()
+        .col_expr(
+            build_session::Column::Status,
+            build_session::Status::Cancelled.into(),
+        )
+        .filter(build_session::Column::Id.in_subquery(unlocked.into_query()))
+        .exec(&*db)
+        .await?
+        .rows_affected;
+
+    if cancelled > 0 {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    db.transaction(|txn| {
+        Box::pin(async move {
+            build_session_cancellation::Entity::insert(build_session_cancellation::ActiveModel {
+                build_session_id: ActiveValue::Set(id),
+                ..Default::default()
+            })
+            .on_conflict(
+                OnConflict::column(build_session_cancellation::Column::BuildSessionId)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(txn)
+            .await?;
+
+            // Build worker may have finished the build session after the previous status check,
+            // in which case the cancellation request is discarded.
+            let status = build_session::Entity::find_by_id(id)
+                .select_only()
+                .column(build_session::Column::Status)
+                .into_tuple::<build_session::Status>()
+                .one(txn)
+                .await?
+                .ok_or(BuildSessionCancelError::BuildSessionNotFound)?;
+
+            if status != build_session::Status::New {
+                return Err(BuildSessionCancelError::BuildSessionFinished);
+            }
+
+            Ok(StatusCode::ACCEPTED)
+        })
+    })
+    .await
+    .into_raw_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        build_session, build_session_cancellation, public_key, source_code, token, user,
+        ActiveValue, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(
+        db: &DatabaseConnection,
+        status: build_session::Status,
+    ) -> (i64, Vec<String>) {
+        let mut users = Vec::new();
+        let mut tokens = Vec::new();
+
+        for address in [[1; 32], [2; 32]] {
+            let user = user::Entity::insert(user::ActiveModel::default())
+                .exec_with_returning(db)
+                .await
+                .expect("unable to create user");
+
+            let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+            token::Entity::insert(model)
+                .exec_without_returning(db)
+                .await
+                .expect("unable to insert token");
+
+            // Build session routes require a verified public key.
+            public_key::Entity::insert(public_key::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                address: ActiveValue::Set(address.to_vec()),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to create public key");
+
+            users.push(user.id);
+            tokens.push(token);
+        }
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(users[0])),
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code")
+        .id;
+
+        let id = build_session::Entity::insert(build_session::ActiveModel {
+            user_id: ActiveValue::Set(Some(users[0])),
+            source_code_id: ActiveValue::Set(source_code_id),
+            status: ActiveValue::Set(status),
+            cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert build session")
+        .id;
+
+        (id, tokens)
+    }
+
+    fn cancel_request(id: i64, token: &str) -> Request<Body> {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/buildSessions/{id}"))
+            .header("Authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn pending() {
+        let db = Arc::new(create_database().await);
+
+        let (id, tokens) = create_test_env(&db, build_session::Status::New).await;
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(cancel_request(id, &tokens[0]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let build_session = build_session::Entity::find_by_id(id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .expect("build session is missing");
+
+        assert_eq!(build_session.status, build_session::Status::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn finished() {
+        let db = create_database().await;
+
+        let (id, tokens) = create_test_env(&db, build_session::Status::Completed).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(cancel_request(id, &tokens[0]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "build_session_finished",
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn not_owner() {
+        let db = create_database().await;
+
+        let (id, tokens) = create_test_env(&db, build_session::Status::New).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(cancel_request(id, &tokens[1]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "not_build_session_owner",
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn finished_concurrently() {
+        let db = Arc::new(create_database().await);
+
+        let (id, tokens) = create_test_env(&db, build_session::Status::New).await;
+
+        // Simulate a build worker that holds a lock on the build session
+        // and finishes it right after the cancellation request.
+        db.execute_unprepared(
+            "CREATE TRIGGER skip_cancellation BEFORE UPDATE ON build_sessions
+            WHEN NEW.status = 3 BEGIN SELECT RAISE(IGNORE); END",
+        )
+        .await
+        .unwrap();
+
+        db.execute_unprepared(
+            "CREATE TRIGGER finish_build_session AFTER INSERT ON build_session_cancellations
+            BEGIN UPDATE build_sessions SET status = 2 WHERE id = NEW.build_session_id; END",
+        )
+        .await
+        .unwrap();
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(cancel_request(id, &tokens[0]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "build_session_finished",
+            }
+        });
+
+        // Discarded cancellation request is rolled back.
+        let cancellations = build_session_cancellation::Entity::find()
+            .count(&*db)
+            .await
+            .unwrap();
+
+        assert_eq!(cancellations, 0);
+    }
+}
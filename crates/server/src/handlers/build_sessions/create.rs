@@ -2,19 +2,27 @@ use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{extract::State, http::StatusCode, Extension, Json};
-use axum_derive_error::ErrorResponse;
+use common::config::Config;
 use db::{
-    build_session, build_session_token, source_code, user, ActiveValue, DatabaseConnection, DbErr,
-    EntityTrait, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
+    build_session, build_session_token, code, idempotency_key, sea_query::OnConflict, source_code,
+    user, ActiveValue, ColumnTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
+    PrimitiveDateTime, QueryFilter, QueryOrder, QuerySelect, QueryTrait, SelectExt,
+    TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use validator::{Validate, ValidationError};
 
-use crate::{auth::AuthenticatedUserId, schema::example_error, validation::ValidatedJson};
+use crate::{
+    auth::AuthenticatedUserId,
+    hex_hash::HexHash,
+    idempotency::IdempotencyKey,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
 
 /// Errors that may occur during the build session creation process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -32,6 +40,20 @@ pub(super) enum BuildSessionCreateError {
     #[status(StatusCode::NOT_FOUND)]
     #[display(fmt = "source code not found")]
     SourceCodeNotFound,
+
+    /// Provided `cargo-contract` version is not supported by the current server.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "unsupported cargo-contract version")]
+    UnsupportedCargoContractVersion,
+
+    /// Provided code hash was not discovered on-chain.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "code not found")]
+    CodeNotFound,
+
+    /// API server configuration is missing.
+    #[display(fmt = "server configuration is missing")]
+    MissingServerConfig,
 }
 
 /// JSON request body.
@@ -42,13 +64,23 @@ pub(super) struct BuildSessionCreateRequest {
     source_code_id: i64,
 
     /// `cargo-contract` tooling version.
+    ///
+    /// The first supported version is used if none was provided.
     #[validate(length(max = 32), custom = "validate_cargo_contract_version")]
     #[schemars(example = "crate::schema::example_cargo_contract_version")]
-    cargo_contract_version: String,
+    cargo_contract_version: Option<String>,
+
+    /// Code hash of an on-chain WASM blob to verify the build result against.
+    ///
+    /// If provided, build session fails when the resulting code hash doesn't match.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: Option<HexHash>,
 }
 
 /// Validate the provided cargo-contract version to be a valid Semver string.
-fn validate_cargo_contract_version(cargo_contract_version: &str) -> Result<(), ValidationError> {
+pub(super) fn validate_cargo_contract_version(
+    cargo_contract_version: &str,
+) -> Result<(), ValidationError> {
     Version::parse(cargo_contract_version)
         .map(|_| ())
         .map_err(|_| ValidationError::new("invalid cargo-contract version"))
@@ -60,24 +92,86 @@ pub(super) struct BuildSessionCreateResponse {
     /// Build session identifier.
     #[schemars(example = "crate::schema::example_database_identifier")]
     id: i64,
+
+    /// Current build session status.
+    status: build_session::Status,
+}
+
+/// Find a build session created by a previous request with the same idempotency key,
+/// if the key was created after the provided time.
+async fn find_replayed(
+    txn: &DatabaseTransaction,
+    user_id: i64,
+    key: &str,
+    created_after: PrimitiveDateTime,
+) -> Result<Option<BuildSessionCreateResponse>, DbErr> {
+    let replayed = idempotency_key::Entity::find()
+        .select_only()
+        .column(idempotency_key::Column::BuildSessionId)
+        .column(build_session::Column::Status)
+        .inner_join(build_session::Entity)
+        .filter(idempotency_key::Column::UserId.eq(user_id))
+        .filter(idempotency_key::Column::Key.eq(key))
+        .filter(idempotency_key::Column::CreatedAt.gt(created_after))
+        .into_tuple::<(i64, build_session::Status)>()
+        .one(txn)
+        .await?;
+
+    Ok(replayed.map(|(id, status)| BuildSessionCreateResponse { id, status }))
 }
 
 /// Generate OAPI documentation for the [`create`] handler.
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Create new build session.")
+        .description(
+            r#"Requests can be safely retried by providing the same `Idempotency-Key` header,
+in which case the build session that was created by the first request is returned
+along with its current status.
+
+Idempotency keys are scoped to the current user and expire after a configured duration.
+
+If an identical source code archive was already built successfully with the same
+`cargo-contract` version (and the same code hash, if one is provided), the existing
+build session is returned instead of starting a new build.
+Such build session may be owned by another user."#,
+        )
         .response::<200, Json<BuildSessionCreateResponse>>()
-        .response_with::<404, Json<Value>, _>(|op| {
-            op.description("Provided source code identifier is incorrect.")
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("Provided source code identifier or code hash is incorrect.")
                 .example(example_error(BuildSessionCreateError::SourceCodeNotFound))
         })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("Provided cargo-contract version is invalid or unsupported.")
+                .example(example_error(
+                    BuildSessionCreateError::UnsupportedCargoContractVersion,
+                ))
+        })
 }
 
 /// Build session creation handler.
 pub(super) async fn create(
     Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
+    IdempotencyKey(idempotency_key): IdempotencyKey,
     ValidatedJson(request): ValidatedJson<BuildSessionCreateRequest>,
 ) -> Result<Json<BuildSessionCreateResponse>, BuildSessionCreateError> {
+    let server_config = config
+        .server
+        .as_ref()
+        .ok_or(BuildSessionCreateError::MissingServerConfig)?;
+
+    let idempotency_key_lifespan =
+        db::Duration::seconds(server_config.idempotency_key_lifespan as i64);
+
+    let supported_versions = &config.supported_cargo_contract_versions;
+
+    let cargo_contract_version = request
+        .cargo_contract_version
+        .or_else(|| supported_versions.first().cloned())
+        .filter(|version| supported_versions.contains(version))
+        .ok_or(BuildSessionCreateError::UnsupportedCargoContractVersion)?;
+
     db.transaction(|txn| {
         Box::pin(async move {
             let user_exists = user::Entity::find_by_id(current_user.id())
@@ -89,33 +183,135 @@ pub(super) async fn create(
                 return Err(BuildSessionCreateError::NonExistentUser);
             }
 
-            let source_code_exists = source_code::Entity::find_by_id(request.source_code_id)
+            let key_created_after = db::now() - idempotency_key_lifespan;
+
+            if let Some(key) = &idempotency_key {
+                let replayed =
+                    find_replayed(txn, current_user.id(), key, key_created_after).await?;
+
+                if let Some(response) = replayed {
+                    return Ok(Json(response));
+                }
+
+                // Expired keys can be reused for new build sessions.
+                idempotency_key::Entity::delete_many()
+                    .filter(idempotency_key::Column::UserId.eq(current_user.id()))
+                    .filter(idempotency_key::Column::Key.eq(key))
+                    .filter(idempotency_key::Column::CreatedAt.lte(key_created_after))
+                    .exec(txn)
+                    .await?;
+            }
+
+            let archive_hash = source_code::Entity::find_by_id(request.source_code_id)
                 .select_only()
-                .exists(txn)
+                .column(source_code::Column::ArchiveHash)
+                .into_tuple::<Vec<u8>>()
+                .one(txn)
                 .await?;
 
-            if source_code_exists {
-                let model = build_session::Entity::insert(build_session::ActiveModel {
-                    user_id: ActiveValue::Set(Some(current_user.id())),
-                    source_code_id: ActiveValue::Set(request.source_code_id),
-                    cargo_contract_version: ActiveValue::Set(request.cargo_contract_version),
-                    ..Default::default()
+            if let Some(code_hash) = &request.code_hash {
+                let code_exists = code::Entity::find_by_id(code_hash.0.to_vec())
+                    .select_only()
+                    .exists(txn)
+                    .await?;
+
+                if !code_exists {
+                    return Err(BuildSessionCreateError::CodeNotFound);
+                }
+            }
+
+            let archive_hash = archive_hash.ok_or(BuildSessionCreateError::SourceCodeNotFound)?;
+
+            // Dry-run build sessions never have their code hash set, thus they are not reused.
+            let existing = build_session::Entity::find()
+                .select_only()
+                .column(build_session::Column::Id)
+                .filter(build_session::Column::ArchiveHash.eq(&archive_hash[..]))
+                .filter(build_session::Column::CargoContractVersion.eq(&cargo_contract_version))
+                .filter(build_session::Column::Status.eq(build_session::Status::Completed))
+                .filter(build_session::Column::CodeHash.is_not_null())
+                .apply_if(request.code_hash.as_ref(), |query, code_hash| {
+                    query.filter(build_session::Column::CodeHash.eq(&code_hash.0[..]))
                 })
-                .exec_with_returning(txn)
+                .order_by_asc(build_session::Column::Id)
+                .into_tuple::<i64>()
+                .one(txn)
                 .await?;
 
-                build_session_token::Entity::insert(build_session_token::ActiveModel {
-                    token: ActiveValue::Set(build_session_token::generate_token()),
-                    source_code_id: ActiveValue::Set(request.source_code_id),
-                    build_session_id: ActiveValue::Set(model.id),
+            // Changes are made inside of a savepoint, which is rolled back
+            // if a concurrent request with the same idempotency key wins the race.
+            let savepoint = txn.begin().await?;
+
+            let response = match existing {
+                Some(id) => BuildSessionCreateResponse {
+                    id,
+                    status: build_session::Status::Completed,
+                },
+                None => {
+                    let model = build_session::Entity::insert(build_session::ActiveModel {
+                        user_id: ActiveValue::Set(Some(current_user.id())),
+                        source_code_id: ActiveValue::Set(request.source_code_id),
+                        archive_hash: ActiveValue::Set(Some(archive_hash)),
+                        cargo_contract_version: ActiveValue::Set(cargo_contract_version),
+                        expected_code_hash: ActiveValue::Set(
+                            request.code_hash.map(|code_hash| code_hash.0.to_vec()),
+                        ),
+                        ..Default::default()
+                    })
+                    .exec_with_returning(&savepoint)
+                    .await?;
+
+                    build_session_token::Entity::insert(build_session_token::ActiveModel {
+                        token: ActiveValue::Set(build_session_token::generate_token()),
+                        source_code_id: ActiveValue::Set(request.source_code_id),
+                        build_session_id: ActiveValue::Set(model.id),
+                    })
+                    .exec_without_returning(&savepoint)
+                    .await?;
+
+                    BuildSessionCreateResponse {
+                        id: model.id,
+                        status: model.status,
+                    }
+                }
+            };
+
+            if let Some(key) = idempotency_key {
+                let inserted = idempotency_key::Entity::insert(idempotency_key::ActiveModel {
+                    user_id: ActiveValue::Set(current_user.id()),
+                    key: ActiveValue::Set(key.clone()),
+                    build_session_id: ActiveValue::Set(response.id),
+                    ..Default::default()
                 })
-                .exec_without_returning(txn)
+                .on_conflict(
+                    OnConflict::columns([
+                        idempotency_key::Column::UserId,
+                        idempotency_key::Column::Key,
+                    ])
+                    .do_nothing()
+                    .to_owned(),
+                )
+                .exec_without_returning(&savepoint)
                 .await?;
 
-                Ok(Json(BuildSessionCreateResponse { id: model.id }))
-            } else {
-                Err(BuildSessionCreateError::SourceCodeNotFound)
+                if inserted == 0 {
+                    savepoint.rollback().await?;
+
+                    // Expired keys were deleted above, thus the conflicting key
+                    // was created by a concurrent request.
+                    let replayed = find_replayed(txn, current_user.id(), &key, key_created_after)
+                        .await?
+                        .ok_or_else(|| {
+                            DbErr::RecordNotFound(String::from("idempotency key is missing"))
+                        })?;
+
+                    return Ok(Json(replayed));
+                }
             }
+
+            savepoint.commit().await?;
+
+            Ok(Json(response))
         })
     })
     .await
@@ -126,7 +322,9 @@ pub(super) async fn create(
 mod tests {
     use std::sync::Arc;
 
-    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+    use crate::testing::{
+        create_database, create_postgres_database, RequestBodyExt, ResponseBodyExt,
+    };
 
     use assert_json::{assert_json, validators};
     use axum::{
@@ -134,7 +332,11 @@ mod tests {
         http::{Request, StatusCode},
     };
     use common::config::Config;
-    use db::{public_key, source_code, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use db::{
+        build_session, code, public_key, source_code, token, user, ActiveValue, ColumnTrait,
+        DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    };
+    use futures_util::future::join_all;
     use serde_json::json;
     use tower::ServiceExt;
 
@@ -144,7 +346,7 @@ mod tests {
             .await
             .expect("unable to create user");
 
-        let (model, token) = token::generate_token(user.id);
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
 
         token::Entity::insert(model)
             .exec_without_returning(db)
@@ -188,7 +390,7 @@ mod tests {
                     .header("Content-Type", "application/json")
                     .body(Body::from_json(json!({
                         "source_code_id": source_code_id,
-                        "cargo_contract_version": "3.0.0",
+                        "cargo_contract_version": "3.1.0",
                     })))
                     .unwrap(),
             )
@@ -196,7 +398,8 @@ mod tests {
             .unwrap();
 
         assert_json!(response.json().await, {
-            "id": validators::i64(|_| Ok(()))
+            "id": validators::i64(|_| Ok(())),
+            "status": "new",
         });
     }
 
@@ -240,7 +443,78 @@ mod tests {
                     .header("Content-Type", "application/json")
                     .body(Body::from_json(json!({
                         "source_code_id": 123,
-                        "cargo_contract_version": "3.0.0",
+                        "cargo_contract_version": "3.1.0",
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn expected_code_hash() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert code");
+
+        let db = Arc::new(db);
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                        "cargo_contract_version": "3.1.0",
+                        "code_hash": hex::encode([0; 32]),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let id = response.json().await["id"].as_i64().unwrap();
+
+        let build_session = build_session::Entity::find_by_id(id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(build_session.expected_code_hash, Some(vec![0; 32]));
+    }
+
+    #[tokio::test]
+    async fn unknown_code_hash() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                        "cargo_contract_version": "3.1.0",
+                        "code_hash": hex::encode([0; 32]),
                     })))
                     .unwrap(),
             )
@@ -249,4 +523,269 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn default_version() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let id = response.json().await["id"].as_i64().unwrap();
+
+        let build_session = build_session::Entity::find_by_id(id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            build_session.cargo_contract_version,
+            Config::for_tests().supported_cargo_contract_versions[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_version() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                        "cargo_contract_version": "3.0.0",
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+
+        let mut sessions = Vec::new();
+
+        for key in ["first", "first", "second"] {
+            let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/buildSessions")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .header("Content-Type", "application/json")
+                        .header("Idempotency-Key", key)
+                        .body(Body::from_json(json!({
+                            "source_code_id": source_code_id,
+                        })))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let json = response.json().await;
+            let id = json["id"].as_i64().unwrap();
+            sessions.push((id, json["status"].clone()));
+
+            // Replayed responses contain the current build session status.
+            build_session::Entity::update_many()
+                .filter(build_session::Column::Id.eq(id))
+                .col_expr(
+                    build_session::Column::Status,
+                    build_session::Status::Failed.into(),
+                )
+                .exec(&*db)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(sessions[0].0, sessions[1].0);
+        assert_ne!(sessions[0].0, sessions[2].0);
+
+        assert_eq!(sessions[0].1, "new");
+        assert_eq!(sessions[1].1, "failed");
+        assert_eq!(sessions[2].1, "new");
+
+        let count = build_session::Entity::find().count(&*db).await.unwrap();
+
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Postgres database provided with the TEST_DATABASE_URL variable"]
+    async fn concurrent_idempotency_key() {
+        let db = create_postgres_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+        let config = Arc::new(Config::for_tests());
+
+        let responses = join_all((0..8).map(|_| {
+            crate::app_router(db.clone(), config.clone()).oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .header("Idempotency-Key", "concurrent")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                    })))
+                    .unwrap(),
+            )
+        }))
+        .await;
+
+        let mut ids = Vec::new();
+
+        for response in responses {
+            let response = response.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            ids.push(response.json().await["id"].as_i64().unwrap());
+        }
+
+        ids.dedup();
+        assert_eq!(ids.len(), 1);
+
+        let count = build_session::Entity::find().count(&*db).await.unwrap();
+
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn invalid_idempotency_key() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .header("Idempotency-Key", "a".repeat(256))
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn identical_sources() {
+        let db = Arc::new(create_database().await);
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let create = |source_code_id| {
+            service.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                        "cargo_contract_version": "3.1.0",
+                    })))
+                    .unwrap(),
+            )
+        };
+
+        let first_id = create(source_code_id).await.unwrap().json().await["id"]
+            .as_i64()
+            .unwrap();
+
+        // Identical, but unfinished build sessions are not reused.
+        let second_id = create(source_code_id).await.unwrap().json().await["id"]
+            .as_i64()
+            .unwrap();
+
+        assert_ne!(first_id, second_id);
+
+        build_session::Entity::update(build_session::ActiveModel {
+            id: ActiveValue::Unchanged(first_id),
+            status: ActiveValue::Set(build_session::Status::Completed),
+            code_hash: ActiveValue::Set(Some(vec![0; 32])),
+            ..Default::default()
+        })
+        .exec(&*db)
+        .await
+        .expect("unable to update build session");
+
+        let reused_id = create(source_code_id).await.unwrap().json().await["id"]
+            .as_i64()
+            .unwrap();
+
+        assert_eq!(reused_id, first_id);
+        assert_eq!(build_session::Entity::find().count(&*db).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn missing_server_config() {
+        let db = create_database().await;
+
+        let (token, source_code_id) = create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.server = None;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": source_code_id,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
 }
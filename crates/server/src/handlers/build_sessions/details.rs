@@ -6,18 +6,20 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
     QuerySelect,
 };
 use db::{sea_orm, FromQueryResult};
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
-use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Build session tooling and source code details response.
 #[derive(Serialize, FromQueryResult, JsonSchema)]
@@ -48,7 +50,7 @@ pub(super) enum BuildSessionDetailsError {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get build session tooling and source code information.")
         .response::<200, Json<BuildSessionInfo>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No build sessions with the provided code hash were found.")
                 .example(example_error(
                     BuildSessionDetailsError::BuildSessionNotFound,
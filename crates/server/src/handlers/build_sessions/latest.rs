@@ -6,17 +6,19 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session, source_code, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
     QueryOrder, QuerySelect, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
-use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Code hash details.
 #[derive(Serialize, JsonSchema)]
@@ -46,7 +48,7 @@ pub(super) enum BuildSessionLatestError {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get the latest build session code hash.")
         .response::<200, Json<BuildSessionLatestData>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No related build sessions were found.")
                 .example(example_error(
                     BuildSessionLatestError::NoRelatedBuildSessions,
@@ -5,12 +5,13 @@ use axum::{
     extract::{Query, State},
     Extension, Json,
 };
-use axum_derive_error::ErrorResponse;
+use common::config::Config;
 use db::{
     build_session, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PrimitiveDateTime,
     QueryFilter, QueryOrder, QuerySelect,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use futures_util::TryStreamExt;
 use schemars::JsonSchema;
 use serde::Serialize;
@@ -63,6 +64,7 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 /// List build sessions related to the current authenticated user.
 pub(super) async fn list(
     Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<Vec<BuildSessionData>>, BuildSessionListError> {
@@ -76,8 +78,9 @@ pub(super) async fn list(
             build_session::Column::CreatedAt,
         ])
         .filter(build_session::Column::UserId.eq(current_user.id()))
-        .limit(pagination.limit())
-        .offset(pagination.offset())
+        .filter(build_session::Column::DryRun.eq(false))
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset(&config.pagination))
         .order_by_desc(build_session::Column::Id)
         .into_tuple::<(
             i64,
@@ -128,7 +131,7 @@ mod tests {
             .await
             .expect("unable to create user");
 
-        let (model, token) = token::generate_token(user.id);
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
 
         token::Entity::insert(model)
             .exec_without_returning(db)
@@ -179,6 +182,21 @@ mod tests {
         .expect("unable to insert build session")
         .created_at;
 
+        // Dry-run build sessions are only available via the verification routes.
+        build_session::Entity::insert(build_session::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            source_code_id: ActiveValue::Set(source_code_id),
+            status: ActiveValue::Set(build_session::Status::Completed),
+            cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+            expected_code_hash: ActiveValue::Set(Some(vec![0; 32])),
+            dry_run: ActiveValue::Set(true),
+            computed_code_hash: ActiveValue::Set(Some(vec![0; 32])),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert build session");
+
         (token, source_code_id, first_ts, second_ts)
     }
 
@@ -6,18 +6,20 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session, log, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
     QueryOrder, QuerySelect, QueryTrait, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use futures_util::TryStreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Errors that may occur during the log list request.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -55,11 +57,11 @@ pub(super) struct BuildSessionLogsQuery {
 pub(super) struct LogEntry {
     /// Log entry identifier.
     #[schemars(example = "crate::schema::example_database_identifier")]
-    id: i64,
+    pub(super) id: i64,
 
     /// Log entry text value.
     #[schemars(example = "crate::schema::example_log_entry")]
-    text: String,
+    pub(super) text: String,
 }
 
 /// Log entries response.
@@ -80,11 +82,11 @@ the exact build output by printing log entries without any additional newlines.
         "#,
         )
         .response::<200, Json<BuildSessionLogsResponse>>()
-        .response_with::<400, Json<Value>, _>(|op| {
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
             op.description("Incorrect identifier format was provided.")
                 .example(example_error(BuildSessionLogsError::UnknownIdFormat))
         })
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No build sessions with the provided identifier were found.")
                 .example(example_error(BuildSessionLogsError::BuildSessionNotFound))
         })
@@ -0,0 +1,228 @@
+use std::{sync::Arc, time::Duration};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::sse::Event,
+    BoxError, Json,
+};
+use db::{
+    build_session, log, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+
+use crate::{
+    schema::{example_error, ErrorBody},
+    sse::EventStream,
+};
+
+use super::logs::LogEntry;
+
+/// Interval between log entry lookups of an unfinished build session.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Errors that may occur during the log stream request.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildSessionLogStreamError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested build session was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "build session not found")]
+    BuildSessionNotFound,
+}
+
+/// Generate OAPI documentation for the [`logs_stream`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Stream build session logs.")
+        .description(
+            r#"Each server-sent event contains a JSON-serialized log entry,
+using the same format as the build session log list route.
+
+All previously stored log entries are sent first, after which
+new log entries are sent as soon as they are stored.
+The stream ends as soon as the build session is finished.
+
+Heartbeat comments are sent every 30 seconds to keep the connection alive."#,
+        )
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No build sessions with the provided identifier were found.")
+                .example(example_error(
+                    BuildSessionLogStreamError::BuildSessionNotFound,
+                ))
+        })
+}
+
+/// Build session log stream request handler.
+pub(super) async fn logs_stream(
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<EventStream<impl Stream<Item = Result<Event, BoxError>>>, BuildSessionLogStreamError> {
+    if build_session_status(&db, id).await?.is_none() {
+        return Err(BuildSessionLogStreamError::BuildSessionNotFound);
+    }
+
+    let logs = stream::try_unfold((db, 0, false), move |(db, position, finished)| async move {
+        let mut finished = finished;
+
+        loop {
+            let logs = log::Entity::find()
+                .select_only()
+                .columns([log::Column::Id, log::Column::Text])
+                .filter(log::Column::BuildSessionId.eq(id))
+                .filter(log::Column::Id.gt(position))
+                .order_by_asc(log::Column::Id)
+                .into_tuple::<(i64, String)>()
+                .all(&*db)
+                .await?;
+
+            if let Some((position, _)) = logs.last() {
+                let position = *position;
+                return Ok::<_, DbErr>(Some((logs, (db, position, finished))));
+            }
+
+            // Perform one more lookup after the build session is finished,
+            // since the last log entries may be stored after the status update.
+            if finished {
+                return Ok(None);
+            }
+
+            match build_session_status(&db, id).await? {
+                Some(build_session::Status::New) => tokio::time::sleep(POLL_INTERVAL).await,
+                _ => finished = true,
+            }
+        }
+    })
+    .map_ok(|logs| stream::iter(logs).map(Ok::<_, DbErr>))
+    .try_flatten()
+    .map(|entry| {
+        let (id, text) = entry?;
+        Ok(Event::default().json_data(LogEntry { id, text })?)
+    });
+
+    Ok(EventStream::new(logs))
+}
+
+/// Get the status of a build session with the provided identifier.
+async fn build_session_status(
+    db: &DatabaseConnection,
+    id: i64,
+) -> Result<Option<build_session::Status>, DbErr> {
+    build_session::Entity::find_by_id(id)
+        .select_only()
+        .column(build_session::Column::Status)
+        .into_tuple::<build_session::Status>()
+        .one(db)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{build_session, log, source_code, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> i64 {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code")
+        .id;
+
+        let build_session_id = build_session::Entity::insert(build_session::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            source_code_id: ActiveValue::Set(source_code_id),
+            status: ActiveValue::Set(build_session::Status::Completed),
+            cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert build session")
+        .id;
+
+        log::Entity::insert_many([
+            log::ActiveModel {
+                build_session_id: ActiveValue::Set(build_session_id),
+                text: ActiveValue::Set(String::from("First log\n")),
+                ..Default::default()
+            },
+            log::ActiveModel {
+                build_session_id: ActiveValue::Set(build_session_id),
+                text: ActiveValue::Set(String::from("Second log")),
+                ..Default::default()
+            },
+        ])
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert logs");
+
+        build_session_id
+    }
+
+    #[tokio::test]
+    async fn finished() {
+        let db = create_database().await;
+
+        let build_session_id = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/buildSessions/logs/{}/stream", build_session_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        assert_eq!(
+            response.text().await,
+            "data:{\"id\":1,\"text\":\"First log\\n\"}\n\ndata:{\"id\":2,\"text\":\"Second log\"}\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/buildSessions/logs/1/stream")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
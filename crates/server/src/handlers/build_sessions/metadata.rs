@@ -6,15 +6,18 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
     QuerySelect,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Errors that may occur during the contract metadata request.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -40,7 +43,7 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
             op.description("JSON metadata response.")
                 .example(Value::Object(Default::default()))
         })
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No build sessions with the provided code hash were found.")
                 .example(example_error(
                     BuildSessionMetadataError::BuildSessionNotFound,
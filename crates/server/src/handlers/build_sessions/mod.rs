@@ -1,3 +1,9 @@
+/// Contract bundle route.
+mod artifact;
+
+/// Build session cancellation route.
+mod cancel;
+
 /// Build session create route.
 mod create;
 
@@ -13,30 +19,50 @@ mod list;
 /// Build session logs route.
 mod logs;
 
+/// Build session log stream route.
+mod logs_stream;
+
 /// Contract JSON metadata route.
 mod metadata;
 
+/// Build queue depth route.
+mod queue;
+
 /// Build session status route.
 mod status;
 
+/// Dry-run build session creation route.
+mod verify;
+
+/// Dry-run build session result route.
+mod verify_result;
+
 /// WASM blob route.
 mod wasm;
 
 use std::sync::Arc;
 
-use aide::axum::{routing::get_with, ApiRouter};
-use axum::middleware::from_fn_with_state;
+use aide::axum::{
+    routing::{delete_with, get_with, post_with},
+    ApiRouter,
+};
+use axum::{middleware::from_fn_with_state, Extension};
 use common::config::Config;
 use db::DatabaseConnection;
 
-use crate::auth;
+use crate::{
+    auth,
+    rate_limit::{self, RateLimiter},
+    state::AppState,
+};
 
 /// Create a router that provides an API server with
 /// build session management routes.
 pub(crate) fn routes(
     database: Arc<DatabaseConnection>,
     config: Arc<Config>,
-) -> ApiRouter<Arc<DatabaseConnection>> {
+    rate_limiter: RateLimiter,
+) -> ApiRouter<AppState> {
     let public_routes = ApiRouter::new()
         .api_route(
             "/latest/:archiveHash",
@@ -51,14 +77,36 @@ pub(crate) fn routes(
             "/details/:codeHash",
             get_with(details::details, details::docs),
         )
+        .api_route(
+            "/queue",
+            get_with(queue::queue, queue::docs).layer(Extension(queue::BuildQueueCache::default())),
+        )
         .api_route("/status/:id", get_with(status::status, status::docs))
-        .api_route("/logs/:id", get_with(logs::logs, logs::docs));
+        .api_route(
+            "/artifact/:id",
+            get_with(artifact::artifact, artifact::docs),
+        )
+        .api_route("/logs/:id", get_with(logs::logs, logs::docs))
+        .api_route(
+            "/logs/:id/stream",
+            get_with(logs_stream::logs_stream, logs_stream::docs),
+        );
 
     let private_routes = ApiRouter::new()
         .api_route(
             "/",
             get_with(list::list, list::docs).post_with(create::create, create::docs),
         )
+        .api_route("/:id", delete_with(cancel::cancel, cancel::docs))
+        .api_route(
+            "/verify",
+            post_with(verify::verify, verify::docs)
+                .layer(from_fn_with_state(rate_limiter, rate_limit::limit)),
+        )
+        .api_route(
+            "/verify/:id",
+            get_with(verify_result::verify_result, verify_result::docs),
+        )
         .route_layer(from_fn_with_state(
             (database, config),
             auth::require_authentication::<true, true, _>,
@@ -0,0 +1,201 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use common::config::Config;
+use db::{
+    build_session, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Duration for which the build queue depth is cached.
+const CACHE_LIFESPAN: Duration = Duration::from_secs(2);
+
+/// Build queue depth cache, shared between build queue requests.
+#[derive(Clone, Default)]
+pub(super) struct BuildQueueCache(Arc<Mutex<Option<(Instant, BuildQueueResponse)>>>);
+
+impl BuildQueueCache {
+    /// Get a cached build queue depth, if it's not expired yet.
+    fn get(&self) -> Option<BuildQueueResponse> {
+        self.0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(cached_at, _)| cached_at.elapsed() < CACHE_LIFESPAN)
+            .map(|(_, response)| response.clone())
+    }
+
+    /// Store the provided build queue depth.
+    fn insert(&self, response: BuildQueueResponse) {
+        *self.0.lock().unwrap() = Some((Instant::now(), response));
+    }
+}
+
+/// Errors that may occur during the build queue request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildQueueError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// JSON response body.
+#[derive(Clone, Serialize, JsonSchema)]
+pub(super) struct BuildQueueResponse {
+    /// Count of build sessions waiting for an available worker.
+    #[schemars(example = "crate::schema::example_build_count")]
+    pending: u64,
+
+    /// Count of build sessions that are being processed by workers.
+    #[schemars(example = "crate::schema::example_build_count")]
+    running: u64,
+
+    /// Total count of configured build workers.
+    ///
+    /// [`None`] if the builder configuration is not available to the API server.
+    #[schemars(example = "crate::schema::example_worker_count")]
+    workers: Option<u64>,
+
+    /// Count of build workers that are not processing any build sessions.
+    ///
+    /// [`None`] if the builder configuration is not available to the API server.
+    #[schemars(example = "crate::schema::example_worker_count")]
+    available_workers: Option<u64>,
+}
+
+/// Generate OAPI documentation for the [`queue`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get build queue depth.")
+        .description(
+            r#"Build queue depth is cached for 2 seconds.
+
+Build sessions are considered running as soon as they are claimed by a build worker."#,
+        )
+        .response::<200, Json<BuildQueueResponse>>()
+}
+
+/// Build queue depth request handler.
+pub(super) async fn queue(
+    Extension(config): Extension<Arc<Config>>,
+    Extension(cache): Extension<BuildQueueCache>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<BuildQueueResponse>, BuildQueueError> {
+    if let Some(response) = cache.get() {
+        return Ok(Json(response));
+    }
+
+    let pending = build_session::Entity::find()
+        .filter(build_session::Column::Status.eq(build_session::Status::New))
+        .filter(build_session::Column::StartedAt.is_null())
+        .count(&*db)
+        .await?;
+
+    // Build workers persist the start time of build sessions they claim.
+    let running = build_session::Entity::find()
+        .filter(build_session::Column::Status.eq(build_session::Status::New))
+        .filter(build_session::Column::StartedAt.is_not_null())
+        .count(&*db)
+        .await?;
+
+    let workers = config
+        .builder
+        .as_ref()
+        .map(|builder| builder.worker_count as u64);
+
+    let response = BuildQueueResponse {
+        pending,
+        running,
+        workers,
+        available_workers: workers.map(|workers| workers.saturating_sub(running)),
+    };
+
+    cache.insert(response.clone());
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{build_session, source_code, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code")
+        .id;
+
+        build_session::Entity::insert_many(
+            [
+                (build_session::Status::New, None),
+                (build_session::Status::New, Some(db::now())),
+                (build_session::Status::Completed, Some(db::now())),
+            ]
+            .map(|(status, started_at)| build_session::ActiveModel {
+                user_id: ActiveValue::Set(Some(user.id)),
+                source_code_id: ActiveValue::Set(source_code_id),
+                status: ActiveValue::Set(status),
+                cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+                started_at: ActiveValue::Set(started_at),
+                ..Default::default()
+            }),
+        )
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert build sessions");
+    }
+
+    #[tokio::test]
+    async fn depth() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/buildSessions/queue")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert_json!(response.json().await, {
+            "pending": 1,
+            "running": 1,
+            "workers": validators::null(),
+            "available_workers": validators::null(),
+        });
+    }
+}
@@ -6,14 +6,16 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{build_session, DatabaseConnection, DbErr, EntityTrait, QuerySelect};
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
-use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Errors that may occur during the build session status request handling.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -38,6 +40,10 @@ pub(super) struct BuildSessionStatusResponse {
     #[schemars(example = "crate::schema::example_build_session_status")]
     status: build_session::Status,
 
+    /// Version of `cargo-contract` used to build the contract.
+    #[schemars(example = "crate::schema::example_cargo_contract_version")]
+    cargo_contract_version: String,
+
     /// Code hash, if the build session was completed successfully.
     #[schemars(example = "crate::schema::example_hex_hash")]
     code_hash: Option<HexHash>,
@@ -47,7 +53,7 @@ pub(super) struct BuildSessionStatusResponse {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get build session status.")
         .response::<200, Json<BuildSessionStatusResponse>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No build sessions with the provided identifier were found.")
                 .example(example_error(BuildSessionStatusError::BuildSessionNotFound))
         })
@@ -61,19 +67,21 @@ pub(super) async fn status(
     Path(id): Path<i64>,
     State(db): State<Arc<DatabaseConnection>>,
 ) -> Result<Json<BuildSessionStatusResponse>, BuildSessionStatusError> {
-    let (status, code_hash) = build_session::Entity::find_by_id(id)
+    let (status, cargo_contract_version, code_hash) = build_session::Entity::find_by_id(id)
         .select_only()
         .columns([
             build_session::Column::Status,
+            build_session::Column::CargoContractVersion,
             build_session::Column::CodeHash,
         ])
-        .into_tuple::<(build_session::Status, Option<Vec<u8>>)>()
+        .into_tuple::<(build_session::Status, String, Option<Vec<u8>>)>()
         .one(&*db)
         .await?
         .ok_or(BuildSessionStatusError::BuildSessionNotFound)?;
 
     Ok(Json(BuildSessionStatusResponse {
         status,
+        cargo_contract_version,
         code_hash: code_hash.as_deref().map(HexHash::try_from).transpose()?,
     }))
 }
@@ -142,6 +150,7 @@ mod tests {
 
         assert_json!(response.json().await, {
             "status": "completed",
+            "cargo_contract_version": "3.0.0",
             "code_hash": hex::encode([0; 32])
         });
     }
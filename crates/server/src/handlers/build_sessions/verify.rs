@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::config::Config;
+use db::{
+    build_session, build_session_token, source_code, ActiveValue, DatabaseConnection, DbErr,
+    EntityTrait, QuerySelect, TransactionErrorExt, TransactionTrait,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
+
+use super::create::validate_cargo_contract_version;
+
+/// Errors that may occur during the dry-run build session creation process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildSessionVerifyError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Provided source code identifier does not exist.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "source code not found")]
+    SourceCodeNotFound,
+
+    /// Provided `cargo-contract` version is not supported by the current server.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "unsupported cargo-contract version")]
+    UnsupportedCargoContractVersion,
+}
+
+/// JSON request body.
+#[derive(Deserialize, Validate, JsonSchema)]
+pub(super) struct BuildSessionVerifyRequest {
+    /// Source code identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    source_code_id: i64,
+
+    /// `cargo-contract` tooling version.
+    ///
+    /// The first supported version is used if none was provided.
+    #[validate(length(max = 32), custom = "validate_cargo_contract_version")]
+    #[schemars(example = "crate::schema::example_cargo_contract_version")]
+    cargo_contract_version: Option<String>,
+
+    /// Code hash of an on-chain WASM blob to compare the build result with.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: HexHash,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct BuildSessionVerifyResponse {
+    /// Dry-run build session identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+}
+
+/// Generate OAPI documentation for the [`verify`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Create new dry-run build session.")
+        .description(
+            r#"Dry-run build sessions are processed the same way as regular ones,
+but their results are not persisted as an official verification of the provided code hash.
+
+Use the `/buildSessions/verify/{id}` route to get the computed code hash
+and check whether it matches the provided one.
+
+This route is rate limited, since builds are expensive."#,
+        )
+        .response::<200, Json<BuildSessionVerifyResponse>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("Provided source code identifier is incorrect.")
+                .example(example_error(BuildSessionVerifyError::SourceCodeNotFound))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("Provided cargo-contract version is invalid or unsupported.")
+                .example(example_error(
+                    BuildSessionVerifyError::UnsupportedCargoContractVersion,
+                ))
+        })
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
+}
+
+/// Dry-run build session creation handler.
+pub(super) async fn verify(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    ValidatedJson(request): ValidatedJson<BuildSessionVerifyRequest>,
+) -> Result<Json<BuildSessionVerifyResponse>, BuildSessionVerifyError> {
+    let supported_versions = &config.supported_cargo_contract_versions;
+
+    let cargo_contract_version = request
+        .cargo_contract_version
+        .or_else(|| supported_versions.first().cloned())
+        .filter(|version| supported_versions.contains(version))
+        .ok_or(BuildSessionVerifyError::UnsupportedCargoContractVersion)?;
+
+    db.transaction(|txn| {
+        Box::pin(async move {
+            let archive_hash = source_code::Entity::find_by_id(request.source_code_id)
+                .select_only()
+                .column(source_code::Column::ArchiveHash)
+                .into_tuple::<Vec<u8>>()
+                .one(txn)
+                .await?
+                .ok_or(BuildSessionVerifyError::SourceCodeNotFound)?;
+
+            let model = build_session::Entity::insert(build_session::ActiveModel {
+                user_id: ActiveValue::Set(Some(current_user.id())),
+                source_code_id: ActiveValue::Set(request.source_code_id),
+                archive_hash: ActiveValue::Set(Some(archive_hash)),
+                cargo_contract_version: ActiveValue::Set(cargo_contract_version),
+                expected_code_hash: ActiveValue::Set(Some(request.code_hash.0.to_vec())),
+                dry_run: ActiveValue::Set(true),
+                ..Default::default()
+            })
+            .exec_with_returning(txn)
+            .await?;
+
+            build_session_token::Entity::insert(build_session_token::ActiveModel {
+                token: ActiveValue::Set(build_session_token::generate_token()),
+                source_code_id: ActiveValue::Set(request.source_code_id),
+                build_session_id: ActiveValue::Set(model.id),
+            })
+            .exec_without_returning(txn)
+            .await?;
+
+            Ok(Json(BuildSessionVerifyResponse { id: model.id }))
+        })
+    })
+    .await
+    .into_raw_result()
+}
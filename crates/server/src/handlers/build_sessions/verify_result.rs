@@ -0,0 +1,301 @@
+use std::{array::TryFromSliceError, sync::Arc};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use db::{
+    build_session, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the dry-run build session result request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BuildSessionVerifyResultError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Incorrect hash size stored inside of a database.
+    IncorrectCodeHash(TryFromSliceError),
+
+    /// The requested dry-run build session was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "build session not found")]
+    BuildSessionNotFound,
+
+    /// The requested dry-run build session was initiated by another user.
+    #[status(StatusCode::FORBIDDEN)]
+    #[display(fmt = "build session was initiated by another user")]
+    NotBuildSessionOwner,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct BuildSessionVerifyResultResponse {
+    /// Build session status.
+    #[schemars(example = "crate::schema::example_build_session_status")]
+    status: build_session::Status,
+
+    /// Code hash that the build result is compared with.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    expected_code_hash: HexHash,
+
+    /// Code hash computed by the build, if it was completed successfully.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: Option<HexHash>,
+
+    /// Whether the computed code hash matches the expected one.
+    ///
+    /// [`None`] if the build was not completed successfully.
+    matches: Option<bool>,
+}
+
+/// Generate OAPI documentation for the [`verify_result`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get dry-run build session result.")
+        .response::<200, Json<BuildSessionVerifyResultResponse>>()
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("Dry-run build session was initiated by another user.")
+                .example(example_error(
+                    BuildSessionVerifyResultError::NotBuildSessionOwner,
+                ))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No dry-run build sessions with the provided identifier were found.")
+                .example(example_error(
+                    BuildSessionVerifyResultError::BuildSessionNotFound,
+                ))
+        })
+}
+
+/// Dry-run build session result request handler.
+pub(super) async fn verify_result(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<BuildSessionVerifyResultResponse>, BuildSessionVerifyResultError> {
+    let (user_id, status, expected_code_hash, code_hash) = build_session::Entity::find_by_id(id)
+        .select_only()
+        .columns([
+            build_session::Column::UserId,
+            build_session::Column::Status,
+            build_session::Column::ExpectedCodeHash,
+            build_session::Column::ComputedCodeHash,
+        ])
+        .filter(build_session::Column::DryRun.eq(true))
+        .into_tuple::<(
+            Option<i64>,
+            build_session::Status,
+            Option<Vec<u8>>,
+            Option<Vec<u8>>,
+        )>()
+        .one(&*db)
+        .await?
+        .ok_or(BuildSessionVerifyResultError::BuildSessionNotFound)?;
+
+    if user_id != Some(current_user.id()) {
+        return Err(BuildSessionVerifyResultError::NotBuildSessionOwner);
+    }
+
+    // Dry-run build sessions are always created with an expected code hash.
+    let expected_code_hash =
+        expected_code_hash.ok_or(BuildSessionVerifyResultError::BuildSessionNotFound)?;
+
+    Ok(Json(BuildSessionVerifyResultResponse {
+        status,
+        matches: code_hash
+            .as_ref()
+            .map(|code_hash| *code_hash == expected_code_hash),
+        expected_code_hash: HexHash::try_from(&expected_code_hash[..])?,
+        code_hash: code_hash.as_deref().map(HexHash::try_from).transpose()?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        build_session, public_key, source_code, token, user, ActiveValue, DatabaseConnection,
+        EntityTrait,
+    };
+    use serde_json::json;
+    use tower::{Service, ServiceExt};
+
+    async fn create_test_env(db: &DatabaseConnection, seed: u8) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        // Build session routes require a verified public key.
+        public_key::Entity::insert(public_key::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            address: ActiveValue::Set(vec![seed; 32]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create public key");
+
+        let source_code_id = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            archive_hash: ActiveValue::Set(vec![seed; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code")
+        .id;
+
+        for computed_code_hash in [[1; 32], [2; 32]] {
+            build_session::Entity::insert(build_session::ActiveModel {
+                user_id: ActiveValue::Set(Some(user.id)),
+                source_code_id: ActiveValue::Set(source_code_id),
+                status: ActiveValue::Set(build_session::Status::Completed),
+                cargo_contract_version: ActiveValue::Set(String::from("3.0.0")),
+                expected_code_hash: ActiveValue::Set(Some(vec![1; 32])),
+                dry_run: ActiveValue::Set(true),
+                computed_code_hash: ActiveValue::Set(Some(computed_code_hash.to_vec())),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert build session");
+        }
+
+        token
+    }
+
+    #[tokio::test]
+    async fn matching_and_mismatching() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, 0).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (id, code_hash, matches) in [(1, [1; 32], true), (2, [2; 32], false)] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/buildSessions/verify/{id}"))
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_json!(response.json().await, {
+                "status": "completed",
+                "expected_code_hash": hex::encode([1; 32]),
+                "code_hash": hex::encode(code_hash),
+                "matches": matches,
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn not_persisted() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db, 0).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/buildSessions/verify")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "source_code_id": 1,
+                        "code_hash": hex::encode([1; 32]),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert_json!(response.json().await, { "id": 3 });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/buildSessions/verify/3")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "status": "new",
+            "code_hash": validators::null(),
+            "matches": validators::null(),
+        });
+    }
+
+    #[tokio::test]
+    async fn another_user() {
+        let db = create_database().await;
+
+        create_test_env(&db, 0).await;
+        let token = create_test_env(&db, 1).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/buildSessions/verify/1")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "not_build_session_owner",
+            }
+        });
+    }
+}
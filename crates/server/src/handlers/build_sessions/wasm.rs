@@ -6,12 +6,14 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{code, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect};
 use derive_more::{Display, Error, From};
-use serde_json::Value;
+use error_response::ErrorResponse;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
 
 /// Errors that may occur during the WASM blob request handling.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -30,7 +32,7 @@ pub(super) enum BuildSessionWasmError {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get WASM blob of the latest build session.")
         .response::<200, Vec<u8>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("No build sessions with the provided code hash were found.")
                 .example(example_error(BuildSessionWasmError::BuildSessionNotFound))
         })
@@ -71,6 +73,7 @@ mod tests {
         code::Entity::insert(code::ActiveModel {
             hash: ActiveValue::Set(vec![0; 32]),
             code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
         })
         .exec_without_returning(db)
         .await
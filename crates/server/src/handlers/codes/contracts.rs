@@ -0,0 +1,223 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Path, Query, State},
+    Extension, Json,
+};
+use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+use db::{
+    contract, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+
+use crate::{
+    hex_hash::HexHash,
+    pagination::{OffsetPagination, Paginated},
+    ss58,
+};
+
+/// Errors that may occur during the code contract list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(crate) enum CodeContractsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Contract address stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract address size stored in db")]
+    InvalidContractAddressSize,
+}
+
+/// Generate OAPI documentation for the [`contracts`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List contract accounts deployed from the provided code hash.")
+        .description(
+            r#"Contracts are ordered by their discovery time in an ascending order.
+
+Unknown code hashes are not considered an error,
+instead an empty list will be returned."#,
+        )
+        .response_with::<200, Paginated<Json<Vec<String>>>, _>(|op| {
+            op.description("Contract account list.")
+        })
+}
+
+/// Code contract list request handler.
+pub(super) async fn contracts(
+    Path(code_hash): Path<HexHash>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<String>>>, CodeContractsError> {
+    let total = contract::Entity::find()
+        .filter(contract::Column::CodeHash.eq(&code_hash.0[..]))
+        .count(&*db)
+        .await?;
+
+    let contracts = find_contracts(&db, &config, &code_hash, &pagination).await?;
+
+    Ok(Paginated::offset(
+        Json(contracts),
+        &uri,
+        &pagination,
+        &config.pagination,
+        total,
+    ))
+}
+
+/// Find contract accounts deployed from the provided code hash.
+pub(crate) async fn find_contracts(
+    db: &DatabaseConnection,
+    config: &Config,
+    code_hash: &HexHash,
+    pagination: &OffsetPagination,
+) -> Result<Vec<String>, CodeContractsError> {
+    contract::Entity::find()
+        .select_only()
+        .column(contract::Column::Address)
+        .filter(contract::Column::CodeHash.eq(&code_hash.0[..]))
+        .order_by_asc(contract::Column::Id)
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .into_tuple::<Vec<u8>>()
+        .stream(db)
+        .await?
+        .err_into()
+        .and_then(|address| async move {
+            let address = AccountId32::new(
+                address
+                    .try_into()
+                    .map_err(|_| CodeContractsError::InvalidContractAddressSize)?,
+            );
+
+            Ok(ss58::encode(&address, config))
+        })
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{code, contract, node, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        for hash in [[0; 32], [1; 32]] {
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(hash.to_vec()),
+                code: ActiveValue::Set(vec![1, 2, 3]),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert code");
+        }
+
+        for (code_hash, address) in [([0; 32], [1; 32]), ([0; 32], [2; 32]), ([1; 32], [3; 32])] {
+            contract::Entity::insert(contract::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                code_hash: ActiveValue::Set(code_hash.to_vec()),
+                address: ActiveValue::Set(address.to_vec()),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert contract");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/contracts", hex::encode([0; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(
+            response.json().await,
+            [
+                AccountId32::new([1; 32]).to_string(),
+                AccountId32::new([2; 32]).to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn paginated() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/codes/{}/contracts?limit=1&offset=1",
+                        hex::encode([0; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(
+            response.json().await,
+            [AccountId32::new([2; 32]).to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/contracts", hex::encode([2; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, []);
+    }
+}
@@ -0,0 +1,327 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+use db::{
+    contract, event, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    handlers::contracts::events::{ContractEvent, EventCursor},
+    hex_hash::HexHash,
+    ss58,
+};
+
+/// Maximum count of contracts deployed from a single code hash, events of which are returned.
+const MAX_CONTRACTS: u64 = 1000;
+
+/// Errors that may occur during the code event list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum CodeEventsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Contract address stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract address size stored in db")]
+    InvalidContractAddressSize,
+}
+
+/// Query string used to paginate code events.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct CodeEventsQuery {
+    /// Cursor value returned from the previous request.
+    ///
+    /// If provided, only events older than the one
+    /// this cursor points to will be returned.
+    #[serde(default)]
+    #[schemars(
+        example = "crate::schema::example_event_cursor",
+        with = "Option<String>"
+    )]
+    before: Option<EventCursor>,
+
+    /// Count of events to return.
+    ///
+    /// Defaults to 25, values larger than 100 are capped,
+    /// unless configured otherwise by the server.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_limit")]
+    limit: Option<u64>,
+}
+
+/// A single event emitted by a contract deployed from the requested code hash.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct CodeEvent {
+    /// Account of a contract that emitted the event.
+    #[schemars(example = "crate::schema::example_account", with = "String")]
+    account: String,
+
+    /// Contract event.
+    #[serde(flatten)]
+    event: ContractEvent,
+}
+
+/// Code event list response.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct CodeEventsResponse {
+    /// Contract events, ordered from the newest to the oldest one.
+    events: Vec<CodeEvent>,
+
+    /// Cursor that can be passed as the `before` query string parameter to get the next page.
+    ///
+    /// [`None`] if there are no more events available.
+    #[schemars(
+        example = "crate::schema::example_event_cursor",
+        with = "Option<String>"
+    )]
+    next_cursor: Option<EventCursor>,
+}
+
+/// Generate OAPI documentation for the [`events`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get events of all contracts deployed from the provided code hash.")
+        .description(
+            r#"Events are returned from the newest to the oldest one. To get older events,
+pass the `next_cursor` value from the previous response as the `before` query string parameter.
+
+Only events of the first 1000 discovered contracts are returned.
+
+Unknown code hashes are not considered an error,
+instead an empty list will be returned."#,
+        )
+        .response_with::<200, Json<CodeEventsResponse>, _>(|op| {
+            op.description("Event list response.")
+        })
+}
+
+/// Code event list request handler.
+pub(super) async fn events(
+    Path(code_hash): Path<HexHash>,
+    Query(query): Query<CodeEventsQuery>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<CodeEventsResponse>, CodeEventsError> {
+    let limit = config.pagination.limit(query.limit).max(1);
+
+    let events = event::Entity::find()
+        .filter(
+            event::Column::Account.in_subquery(
+                contract::Entity::find()
+                    .select_only()
+                    .column(contract::Column::Address)
+                    .filter(contract::Column::CodeHash.eq(&code_hash.0[..]))
+                    .order_by_asc(contract::Column::Id)
+                    .limit(MAX_CONTRACTS)
+                    .into_query(),
+            ),
+        )
+        .apply_if(query.before, |query, cursor| {
+            query.filter(cursor.older_than())
+        })
+        .order_by_desc(event::Column::BlockTimestamp)
+        .order_by_desc(event::Column::Id)
+        .limit(limit)
+        .all(&*db)
+        .await?;
+
+    let next_cursor = if events.len() as u64 == limit {
+        events.last().map(EventCursor::from)
+    } else {
+        None
+    };
+
+    let events = events
+        .into_iter()
+        .map(|event| {
+            let account = AccountId32::new(
+                event
+                    .account
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| CodeEventsError::InvalidContractAddressSize)?,
+            );
+
+            Ok(CodeEvent {
+                account: ss58::encode(&account, &config),
+                event: ContractEvent::from(event),
+            })
+        })
+        .collect::<Result<_, CodeEventsError>>()?;
+
+    Ok(Json(CodeEventsResponse {
+        events,
+        next_cursor,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{
+        code, contract, event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        for hash in [[0; 32], [1; 32]] {
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(hash.to_vec()),
+                code: ActiveValue::Set(vec![1, 2, 3]),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert code");
+        }
+
+        for (code_hash, address) in [([0; 32], [1; 32]), ([0; 32], [2; 32]), ([1; 32], [3; 32])] {
+            contract::Entity::insert(contract::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                code_hash: ActiveValue::Set(code_hash.to_vec()),
+                address: ActiveValue::Set(address.to_vec()),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert contract");
+        }
+
+        for (account, timestamp) in [([1; 32], 10), ([3; 32], 20), ([2; 32], 30), ([1; 32], 40)] {
+            let datetime = OffsetDateTime::from_unix_timestamp(timestamp).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(account.to_vec()),
+                event_type: ActiveValue::Set(event::EventType::Termination),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::Termination).unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(timestamp),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert an event");
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_code_hash() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/events?limit=2", hex::encode([0; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "account": AccountId32::new([1; 32]).to_string(),
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 40,
+                    "block_number": 40
+                },
+                {
+                    "account": AccountId32::new([2; 32]).to_string(),
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 30,
+                    "block_number": 30
+                }
+            ],
+            "next_cursor": "30_3"
+        });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/codes/{}/events?limit=2&before=30_3",
+                        hex::encode([0; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "account": AccountId32::new([1; 32]).to_string(),
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 10,
+                    "block_number": 10
+                }
+            ],
+            "next_cursor": validators::null()
+        });
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/events", hex::encode([0; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [],
+            "next_cursor": validators::null()
+        });
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use db::{code, DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use serde_json::Value;
+
+use crate::{
+    hex_hash::HexHash,
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the code metadata request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(crate) enum CodeMetadataError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Unable to parse the metadata stored inside of a database as a JSON value.
+    #[display(fmt = "invalid metadata")]
+    InvalidMetadata,
+
+    /// Unable to find the requested code or its metadata.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "metadata not found")]
+    MetadataNotFound,
+}
+
+/// Generate OAPI documentation for the [`metadata`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get JSON metadata of the provided code hash.")
+        .description("Metadata is available only for codes verified using build sessions.")
+        .response_with::<200, Json<Value>, _>(|op| {
+            op.description("JSON metadata response.")
+                .example(Value::Object(Default::default()))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No metadata is available for the provided code hash.")
+                .example(example_error(CodeMetadataError::MetadataNotFound))
+        })
+}
+
+/// Code metadata request handler.
+pub(crate) async fn metadata(
+    Path(code_hash): Path<HexHash>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<Value>, CodeMetadataError> {
+    let metadata = code::Entity::find_by_id(code_hash.0.to_vec())
+        .select_only()
+        .column(code::Column::Metadata)
+        .into_tuple::<Option<String>>()
+        .one(&*db)
+        .await?
+        .flatten()
+        .ok_or(CodeMetadataError::MetadataNotFound)?;
+
+    let json = serde_json::from_str(&metadata).map_err(|_| CodeMetadataError::InvalidMetadata)?;
+
+    Ok(Json(json))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{code, ActiveValue, DatabaseConnection, EntityTrait};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        for (hash, metadata) in [
+            (
+                [0; 32],
+                Some(json!({ "source": { "language": "ink! 4.2.1" } }).to_string()),
+            ),
+            ([1; 32], None),
+        ] {
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(hash.to_vec()),
+                code: ActiveValue::Set(vec![1, 2, 3]),
+                metadata: ActiveValue::Set(metadata),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert code");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/metadata", hex::encode([0; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "source": {
+                "language": "ink! 4.2.1"
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn missing() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for hash in [[1; 32], [2; 32]] {
+            let response = service
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/codes/{}/metadata", hex::encode(hash)))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+/// Contracts deployed from a code hash list route.
+pub(crate) mod contracts;
+
+/// Events of contracts deployed from a code hash list route.
+mod events;
+
+/// Code metadata route.
+pub(crate) mod metadata;
+
+/// Verified code list route.
+mod verified;
+
+use aide::axum::{routing::get_with, ApiRouter};
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with uploaded code information routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route("/verified", get_with(verified::verified, verified::docs))
+        .api_route(
+            "/:code_hash/contracts",
+            get_with(contracts::contracts, contracts::docs),
+        )
+        .api_route("/:code_hash/events", get_with(events::events, events::docs))
+        .api_route(
+            "/:code_hash/metadata",
+            get_with(metadata::metadata, metadata::docs),
+        )
+        .with_path_items(|op| op.tag("Contract management"))
+}
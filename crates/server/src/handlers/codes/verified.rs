@@ -0,0 +1,275 @@
+use std::{array::TryFromSliceError, collections::HashMap, sync::Arc};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Query, State},
+    Extension, Json,
+};
+use common::config::Config;
+use db::{
+    build_session, code, sea_orm::Select, sea_query::Expr, ColumnTrait, DatabaseConnection, DbErr,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hex_hash::HexHash,
+    pagination::{OffsetPagination, Paginated},
+};
+
+/// Query string filters of the verified code list request.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct VerifiedCodesQuery {
+    /// Return only codes verified using the provided `cargo-contract` version.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_cargo_contract_version_filter")]
+    cargo_contract_version: Option<String>,
+}
+
+/// A single verified code data.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct VerifiedCodeData {
+    /// Verified code hash.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: HexHash,
+
+    /// `cargo-contract` tooling versions of successful build sessions
+    /// that produced this code, in an ascending order.
+    #[schemars(example = "crate::schema::example_cargo_contract_versions")]
+    cargo_contract_versions: Vec<String>,
+}
+
+/// Errors that may occur during the verified code list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum VerifiedCodesError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Incorrect hash size stored inside of a database.
+    IncorrectCodeHash(TryFromSliceError),
+}
+
+/// Generate OAPI documentation for the [`verified`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List code hashes verified using a build session.")
+        .description(
+            r#"Code hashes are ordered by the time of their first successful build session
+in an ascending order."#,
+        )
+        .response_with::<200, Paginated<Json<Vec<VerifiedCodeData>>>, _>(|op| {
+            op.description("Verified code list.")
+        })
+}
+
+/// Verified code list request handler.
+pub(super) async fn verified(
+    Query(query): Query<VerifiedCodesQuery>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<VerifiedCodeData>>>, VerifiedCodesError> {
+    let code_hashes = completed_build_sessions(&query)
+        .select_only()
+        .column(build_session::Column::CodeHash)
+        .group_by(build_session::Column::CodeHash);
+
+    let total = code_hashes.clone().count(&*db).await?;
+
+    let code_hashes = code_hashes
+        .order_by_asc(Expr::col(build_session::Column::Id).min())
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .into_tuple::<Vec<u8>>()
+        .all(&*db)
+        .await?;
+
+    let mut versions = completed_build_sessions(&query)
+        .select_only()
+        .columns([
+            build_session::Column::CodeHash,
+            build_session::Column::CargoContractVersion,
+        ])
+        .distinct()
+        .filter(build_session::Column::CodeHash.is_in(code_hashes.iter().map(Vec::as_slice)))
+        .order_by_asc(build_session::Column::CargoContractVersion)
+        .into_tuple::<(Vec<u8>, String)>()
+        .stream(&*db)
+        .await?
+        .try_fold(
+            HashMap::<_, Vec<_>>::new(),
+            |mut versions, (code_hash, version)| async move {
+                versions.entry(code_hash).or_default().push(version);
+                Ok(versions)
+            },
+        )
+        .await?;
+
+    let codes = code_hashes
+        .into_iter()
+        .map(|code_hash| {
+            Ok(VerifiedCodeData {
+                code_hash: HexHash::try_from(&code_hash[..])?,
+                cargo_contract_versions: versions.remove(&code_hash).unwrap_or_default(),
+            })
+        })
+        .collect::<Result<_, VerifiedCodesError>>()?;
+
+    Ok(Paginated::offset(
+        Json(codes),
+        &uri,
+        &pagination,
+        &config.pagination,
+        total,
+    ))
+}
+
+/// Find successfully completed build sessions with their related codes
+/// that satisfy the provided query filters.
+fn completed_build_sessions(query: &VerifiedCodesQuery) -> Select<build_session::Entity> {
+    build_session::Entity::find()
+        .inner_join(code::Entity)
+        .filter(build_session::Column::Status.eq(build_session::Status::Completed))
+        .apply_if(
+            query.cargo_contract_version.as_deref(),
+            |select, version| {
+                select.filter(build_session::Column::CargoContractVersion.eq(version))
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use db::{
+        build_session, code, source_code, user, ActiveValue, DatabaseConnection, EntityTrait,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let source_code = source_code::Entity::insert(source_code::ActiveModel {
+            user_id: ActiveValue::Set(Some(user.id)),
+            archive_hash: ActiveValue::Set(vec![0; 32]),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create source code");
+
+        for hash in [[1; 32], [2; 32]] {
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(hash.to_vec()),
+                code: ActiveValue::Set(vec![1, 2, 3]),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert code");
+        }
+
+        for (status, version, code_hash) in [
+            (build_session::Status::Completed, "3.0.0", Some([1; 32])),
+            (build_session::Status::Completed, "3.2.0", Some([1; 32])),
+            (build_session::Status::Failed, "3.2.0", Some([2; 32])),
+            (build_session::Status::New, "3.2.0", None),
+        ] {
+            build_session::Entity::insert(build_session::ActiveModel {
+                user_id: ActiveValue::Set(Some(user.id)),
+                source_code_id: ActiveValue::Set(source_code.id),
+                status: ActiveValue::Set(status),
+                cargo_contract_version: ActiveValue::Set(String::from(version)),
+                code_hash: ActiveValue::Set(code_hash.map(|hash| hash.to_vec())),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert build session");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/codes/verified")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["X-Total-Count"], "1");
+
+        assert_json!(
+            response.json().await,
+            [{
+                "code_hash": hex::encode([1; 32]),
+                "cargo_contract_versions": ["3.0.0", "3.2.0"],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn version_filter() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/codes/verified?cargo_contract_version=3.2.0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(
+            response.json().await,
+            [{
+                "code_hash": hex::encode([1; 32]),
+                "cargo_contract_versions": ["3.2.0"],
+            }]
+        );
+
+        let response = service
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/codes/verified?cargo_contract_version=4.0.0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, []);
+    }
+}
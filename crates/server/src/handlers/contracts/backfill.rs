@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{backfill_job, node, ActiveValue, DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Maximum count of blocks that can be rescanned by a single backfill job.
+const MAX_BLOCK_RANGE: u32 = 100_000;
+
+/// Errors that may occur during the backfill job creation.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BackfillError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided node was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "node not found")]
+    NodeNotFound,
+
+    /// The provided block range is empty or too large.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "invalid block range")]
+    InvalidRange,
+
+    /// The provided block range includes blocks that were not yet discovered.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "block range is ahead of the confirmed block")]
+    BlockNotConfirmed,
+}
+
+/// JSON request body.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct BackfillRequest {
+    /// Identifier of a node that has to be rescanned.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    node_id: i64,
+
+    /// First block of the rescanned range, inclusive.
+    #[schemars(example = "crate::schema::example_block_number")]
+    from_block: u32,
+
+    /// Last block of the rescanned range, inclusive.
+    #[schemars(example = "crate::schema::example_block_number")]
+    to_block: u32,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct BackfillResponse {
+    /// Backfill job identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+}
+
+/// Generate OAPI documentation for the [`backfill`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Rescan a block range for contract events.")
+        .description(
+            r#"Enqueues a backfill job, which is processed by an event client
+to discover events related to the provided contract account.
+
+At most 100000 blocks can be rescanned by a single job, and the block range
+must not be ahead of the node's confirmed block. Events that were
+already discovered are not duplicated.
+
+Job status can be polled with the `/contracts/{account}/backfill/{id}` route."#,
+        )
+        .response::<200, Json<BackfillResponse>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided node was not found.")
+                .example(example_error(BackfillError::NodeNotFound))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("Invalid block range was provided.")
+                .example(example_error(BackfillError::InvalidRange))
+        })
+}
+
+/// Backfill job creation handler.
+pub(super) async fn backfill(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    State(db): State<Arc<DatabaseConnection>>,
+    JsonBody(request): JsonBody<BackfillRequest>,
+) -> Result<Json<BackfillResponse>, BackfillError> {
+    if request.from_block > request.to_block
+        || request.to_block - request.from_block >= MAX_BLOCK_RANGE
+    {
+        return Err(BackfillError::InvalidRange);
+    }
+
+    let confirmed_block = node::Entity::find_by_id(request.node_id)
+        .select_only()
+        .column(node::Column::ConfirmedBlock)
+        .into_tuple::<i64>()
+        .one(&*db)
+        .await?
+        .ok_or(BackfillError::NodeNotFound)?;
+
+    if i64::from(request.to_block) > confirmed_block {
+        return Err(BackfillError::BlockNotConfirmed);
+    }
+
+    let model = backfill_job::Entity::insert(backfill_job::ActiveModel {
+        node_id: ActiveValue::Set(request.node_id),
+        account: ActiveValue::Set(account.0.to_raw_vec()),
+        from_block: ActiveValue::Set(request.from_block.into()),
+        to_block: ActiveValue::Set(request.to_block.into()),
+        status: ActiveValue::Set(backfill_job::Status::New),
+        ..Default::default()
+    })
+    .exec_with_returning(&*db)
+    .await?;
+
+    Ok(Json(BackfillResponse { id: model.id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use aide::axum::ApiRouter;
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{node, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use serde_json::{json, Value};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(1000),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        token
+    }
+
+    async fn backfill(service: &mut ApiRouter, token: &str, body: Value) -> (StatusCode, Value) {
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/contracts/{}/backfill", AccountId32::new([1; 32])))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        (response.status(), response.json().await)
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let (status, body) = backfill(
+            &mut service,
+            &token,
+            json!({
+                "node_id": 1,
+                "from_block": 10,
+                "to_block": 20,
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_json!(body, { "id": 1 });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/{}/backfill/1",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "id": 1,
+            "node_id": 1,
+            "from_block": 10,
+            "to_block": 20,
+            "status": "new"
+        });
+    }
+
+    #[tokio::test]
+    async fn invalid_range() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (from_block, to_block) in [(20, 10), (0, 100_000), (10, 1001)] {
+            let (status, _) = backfill(
+                &mut service,
+                &token,
+                json!({
+                    "node_id": 1,
+                    "from_block": from_block,
+                    "to_block": to_block,
+                }),
+            )
+            .await;
+
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_node() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let (status, _) = backfill(
+            &mut service,
+            &token,
+            json!({
+                "node_id": 2,
+                "from_block": 10,
+                "to_block": 20,
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
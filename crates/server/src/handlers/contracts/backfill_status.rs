@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{
+    backfill_job, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::schema::{example_error, ErrorBody};
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Errors that may occur during the backfill job status request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum BackfillStatusError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested backfill job was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "backfill job not found")]
+    JobNotFound,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct BackfillStatusResponse {
+    /// Backfill job identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+
+    /// Identifier of a node that is rescanned.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    node_id: i64,
+
+    /// First block of the rescanned range, inclusive.
+    #[schemars(example = "crate::schema::example_block_number")]
+    from_block: i64,
+
+    /// Last block of the rescanned range, inclusive.
+    #[schemars(example = "crate::schema::example_block_number")]
+    to_block: i64,
+
+    /// Last block of the rescanned range that was already processed.
+    ///
+    /// [`None`] if no blocks were processed yet.
+    #[schemars(example = "crate::schema::example_block_number")]
+    last_processed_block: Option<i64>,
+
+    /// Current backfill job status.
+    #[schemars(example = "crate::schema::example_backfill_job_status")]
+    status: backfill_job::Status,
+}
+
+/// Generate OAPI documentation for the [`backfill_status`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get contract event backfill job status.")
+        .response::<200, Json<BackfillStatusResponse>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No backfill jobs with the provided identifier were found.")
+                .example(example_error(BackfillStatusError::JobNotFound))
+        })
+}
+
+/// Backfill job status request handler.
+pub(super) async fn backfill_status(
+    AccountPath((account, id)): AccountPath<(WrappedAccountId32, i64)>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<BackfillStatusResponse>, BackfillStatusError> {
+    let (node_id, from_block, to_block, last_processed_block, status) =
+        backfill_job::Entity::find_by_id(id)
+            .select_only()
+            .columns([
+                backfill_job::Column::NodeId,
+                backfill_job::Column::FromBlock,
+                backfill_job::Column::ToBlock,
+                backfill_job::Column::LastProcessedBlock,
+                backfill_job::Column::Status,
+            ])
+            .filter(backfill_job::Column::Account.eq(account.0.as_slice()))
+            .into_tuple::<(i64, i64, i64, Option<i64>, backfill_job::Status)>()
+            .one(&*db)
+            .await?
+            .ok_or(BackfillStatusError::JobNotFound)?;
+
+    Ok(Json(BackfillStatusResponse {
+        id,
+        node_id,
+        from_block,
+        to_block,
+        last_processed_block,
+        status,
+    }))
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::{config::Config, rpc::sp_core::ByteArray};
+use db::{
+    event,
+    sea_query::{Alias, Expr, Order, Query},
+    ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::{
+    schema::{example_error, ErrorBody},
+    ss58,
+    state::ReadReplica,
+    validation::JsonBody,
+};
+
+use super::{events::ContractEvent, WrappedAccountId32};
+
+/// Maximum count of accounts that can be requested at once.
+const MAX_ACCOUNTS: usize = 50;
+
+/// Maximum count of events returned for each of the requested accounts.
+const MAX_EVENTS_PER_ACCOUNT: u64 = 10;
+
+/// Errors that may occur during the batch contract event request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractEventsBatchError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Too many accounts were provided.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "too many accounts provided")]
+    TooManyAccounts,
+}
+
+/// Generate OAPI documentation for the [`batch`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get latest events related to multiple contract accounts.")
+        .description(
+            r#"At most 50 accounts can be requested at once.
+
+Response contains a map from an account to its latest events,
+ordered from the newest to the oldest one. At most 10 events
+are returned for each of the requested accounts."#,
+        )
+        .response::<200, Json<BTreeMap<String, Vec<ContractEvent>>>>()
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
+            op.description("Too many accounts were provided.")
+                .example(example_error(ContractEventsBatchError::TooManyAccounts))
+        })
+}
+
+/// Batch contract event list request handler.
+pub(super) async fn batch(
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+    JsonBody(accounts): JsonBody<Vec<WrappedAccountId32>>,
+) -> Result<Json<BTreeMap<String, Vec<ContractEvent>>>, ContractEventsBatchError> {
+    if accounts.len() > MAX_ACCOUNTS {
+        return Err(ContractEventsBatchError::TooManyAccounts);
+    }
+
+    let mut events: HashMap<_, _> = accounts
+        .iter()
+        .map(|account| (account.0.to_raw_vec(), Vec::new()))
+        .collect();
+
+    // Latest events of the account the outer query row belongs to,
+    // which allows the database to apply the limit to each account separately.
+    let latest = Alias::new("latest");
+    let latest_events = Query::select()
+        .column((latest.clone(), event::Column::Id))
+        .from_as(event::Entity, latest.clone())
+        .and_where(
+            Expr::col((latest.clone(), event::Column::Account))
+                .equals((event::Entity, event::Column::Account)),
+        )
+        .order_by((latest.clone(), event::Column::BlockTimestamp), Order::Desc)
+        .order_by((latest, event::Column::Id), Order::Desc)
+        .limit(MAX_EVENTS_PER_ACCOUNT)
+        .to_owned();
+
+    let latest_events = event::Entity::find()
+        .filter(event::Column::Account.is_in(events.keys().map(Vec::as_slice)))
+        .filter(event::Column::Id.in_subquery(latest_events))
+        .order_by_desc(event::Column::BlockTimestamp)
+        .order_by_desc(event::Column::Id)
+        .all(&*db)
+        .await?;
+
+    for event in latest_events {
+        if let Some(account_events) = events.get_mut(&event.account) {
+            account_events.push(ContractEvent::from(event));
+        }
+    }
+
+    Ok(Json(
+        accounts
+            .into_iter()
+            .filter_map(|account| {
+                events
+                    .remove(account.0.as_slice())
+                    .map(|account_events| (ss58::encode(&account.0, &config), account_events))
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+    use db::{
+        event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        for (account, count) in [([1; 32], 12), ([2; 32], 1)] {
+            for block_number in 0..count {
+                event::Entity::insert(event::ActiveModel {
+                    node_id: ActiveValue::Set(node.id),
+                    account: ActiveValue::Set(account.to_vec()),
+                    event_type: ActiveValue::Set(event::EventType::Instantiation),
+                    body: ActiveValue::Set(
+                        serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+                    ),
+                    block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                        datetime.date(),
+                        datetime.time(),
+                    )),
+                    block_number: ActiveValue::Set(block_number),
+                    ..Default::default()
+                })
+                .exec_without_returning(db)
+                .await
+                .expect("unable to insert an event");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let accounts = [[1; 32], [2; 32], [3; 32]].map(|val| AccountId32::new(val).to_ss58check());
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/contracts/events/batch")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!(accounts)))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let json = response.json().await;
+
+        assert_eq!(json[&accounts[0]].as_array().unwrap().len(), 10);
+        assert_eq!(json[&accounts[0]][0]["block_number"], 11);
+
+        assert_json!(json[&accounts[1]].clone(), [{
+            "event_type": "instantiation",
+            "body": "Instantiation",
+            "timestamp": 0,
+            "block_number": 0
+        }]);
+
+        assert_json!(json[&accounts[2]].clone(), []);
+    }
+
+    #[tokio::test]
+    async fn ss58_prefix() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.ss58_prefix = 2;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/contracts/events/batch")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!([
+                        AccountId32::new([2; 32]).to_string()
+                    ])))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let address =
+            AccountId32::new([2; 32]).to_ss58check_with_version(Ss58AddressFormat::custom(2));
+
+        assert_json!(response.json().await[&address].clone(), [{
+            "event_type": "instantiation",
+            "block_number": 0
+        }]);
+    }
+
+    #[tokio::test]
+    async fn too_many_accounts() {
+        let db = create_database().await;
+
+        let accounts = vec![AccountId32::new([1; 32]).to_ss58check(); 51];
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/contracts/events/batch")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!(accounts)))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,175 @@
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{event, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::state::ReadReplica;
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Errors that may occur during the contract event count request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractEventCountError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Contract event count response.
+#[derive(Serialize, JsonSchema)]
+pub struct ContractEventCount {
+    /// Total count of events related to the contract account.
+    #[schemars(example = "crate::schema::example_event_count")]
+    count: u64,
+}
+
+/// Generate OAPI documentation for the [`count`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get the total count of events related to the contract account.")
+        .description(
+            r#"Unknown contract accounts are not considered an error,
+instead the returned event count will be equal to zero."#,
+        )
+        .response::<200, Json<ContractEventCount>>()
+}
+
+/// Contract event count request handler.
+pub(super) async fn count(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<Json<ContractEventCount>, ContractEventCountError> {
+    let count = event::Entity::find()
+        .filter(event::Column::Account.eq(account.0.as_slice()))
+        .count(&*db)
+        .await?;
+
+    Ok(Json(ContractEventCount { count }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{
+        code, contract, event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert code");
+
+        contract::Entity::insert(contract::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            code_hash: ActiveValue::Set(vec![0; 32]),
+            address: ActiveValue::Set(vec![1; 32]),
+            owner: ActiveValue::Set(Some(vec![2; 32])),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert contract");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        for (event_type, body) in [
+            (
+                event::EventType::Instantiation,
+                event::EventBody::Instantiation,
+            ),
+            (event::EventType::Termination, event::EventBody::Termination),
+        ] {
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event_type),
+                body: ActiveValue::Set(serde_json::to_string(&body).unwrap()),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert an event");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/count",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "count": 2
+        })
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/count",
+                        AccountId32::new([2; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "count": 0
+        })
+    }
+}
@@ -1,33 +1,34 @@
 use std::{array::TryFromSliceError, sync::Arc};
 
 use aide::{transform::TransformOperation, OperationIo};
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    Json,
-};
-use axum_derive_error::ErrorResponse;
-use common::rpc::sp_core::{
-    crypto::{AccountId32, Ss58Codec},
-    ByteArray,
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
 };
 use db::{
     contract, node, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
     TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
-use serde_json::Value;
 
-use crate::{hex_hash::HexHash, schema::example_error};
+use crate::{
+    hex_hash::HexHash,
+    negotiation::{Negotiated, ResponseFormat},
+    schema::{example_error, ErrorBody},
+    ss58,
+    state::ReadReplica,
+};
 
-use super::WrappedAccountId32;
+use super::{AccountPath, WrappedAccountId32};
 
 /// Errors that may occur during the contract details request handling.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
 #[aide(output)]
-pub(super) enum ContractDetailsError {
+pub(crate) enum ContractDetailsError {
     /// Database-related error.
     DatabaseError(DbErr),
 
@@ -51,6 +52,14 @@ pub(super) enum ContractDetailsError {
 /// Contract details response.
 #[derive(Serialize, JsonSchema)]
 pub struct ContractData {
+    /// Contract account address.
+    #[schemars(example = "crate::schema::example_account")]
+    pub address: String,
+
+    /// Related node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    pub node_id: i64,
+
     /// Related node name.
     #[schemars(example = "crate::schema::example_node")]
     pub node: String,
@@ -65,13 +74,39 @@ pub struct ContractData {
     /// was discovered after the initial activation of an event server.
     #[schemars(example = "crate::schema::example_account")]
     pub owner: Option<String>,
+
+    /// Number of a block in which the contract was instantiated.
+    ///
+    /// This field is only available if the contract instantiation
+    /// was discovered by an event client.
+    #[schemars(example = "crate::schema::example_block_number")]
+    pub instantiation_block: Option<i64>,
+
+    /// Timestamp of a block in which the contract was instantiated.
+    ///
+    /// This field is only available if the contract instantiation
+    /// was discovered by an event client.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub instantiated_at: Option<i64>,
+
+    /// Contract discovery timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub created_at: i64,
+
+    /// Last contract update timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub updated_at: i64,
 }
 
 /// Generate OAPI documentation for the [`details`] handler.
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get details about the provided contract account.")
+        .description(
+            r#"Response is encoded using MessagePack if requested
+with the `Accept: application/msgpack` header."#,
+        )
         .response::<200, Json<ContractData>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("Provided contract account was not found.")
                 .example(example_error(ContractDetailsError::ContractNotFound))
         })
@@ -79,25 +114,31 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 
 /// Contract details request handler.
 pub(super) async fn details(
-    Path(account): Path<WrappedAccountId32>,
-    State(db): State<Arc<DatabaseConnection>>,
-) -> Result<Json<ContractData>, ContractDetailsError> {
+    format: ResponseFormat,
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<Negotiated<ContractData>, ContractDetailsError> {
+    find_contract(&db, config, account.0)
+        .await
+        .map(|contract| Negotiated(format, contract))
+}
+
+/// Find details about the provided contract account.
+pub(crate) async fn find_contract(
+    db: &DatabaseConnection,
+    config: Arc<Config>,
+    account: AccountId32,
+) -> Result<ContractData, ContractDetailsError> {
     db.transaction(|txn| {
         Box::pin(async move {
-            let (node_id, code_hash, owner) = contract::Entity::find()
-                .select_only()
-                .columns([
-                    contract::Column::NodeId,
-                    contract::Column::CodeHash,
-                    contract::Column::Owner,
-                ])
-                .filter(contract::Column::Address.eq(account.0.as_slice()))
-                .into_tuple::<(i64, Vec<u8>, Option<Vec<u8>>)>()
+            let contract = contract::Entity::find()
+                .filter(contract::Column::Address.eq(account.as_slice()))
                 .one(txn)
                 .await?
                 .ok_or(ContractDetailsError::ContractNotFound)?;
 
-            let node = node::Entity::find_by_id(node_id)
+            let node = node::Entity::find_by_id(contract.node_id)
                 .select_only()
                 .column(node::Column::Name)
                 .into_tuple::<String>()
@@ -105,24 +146,33 @@ pub(super) async fn details(
                 .await?
                 .ok_or(ContractDetailsError::ContractWithoutRelatedNode)?;
 
-            let owner = owner
+            let owner = contract
+                .owner
                 .map(|address| {
-                    Result::<_, ContractDetailsError>::Ok(
-                        AccountId32::new(
+                    Result::<_, ContractDetailsError>::Ok(ss58::encode(
+                        &AccountId32::new(
                             address
                                 .try_into()
                                 .map_err(|_| ContractDetailsError::IncorrectAddressSizeOfOwner)?,
-                        )
-                        .to_ss58check(),
-                    )
+                        ),
+                        &config,
+                    ))
                 })
                 .transpose()?;
 
-            Ok(Json(ContractData {
+            Ok(ContractData {
+                address: ss58::encode(&account, &config),
+                node_id: contract.node_id,
                 node,
-                code_hash: code_hash.as_slice().try_into()?,
+                code_hash: contract.code_hash.as_slice().try_into()?,
                 owner,
-            }))
+                instantiation_block: contract.instantiation_block,
+                instantiated_at: contract
+                    .instantiated_at
+                    .map(|timestamp| timestamp.assume_utc().unix_timestamp()),
+                created_at: contract.created_at.assume_utc().unix_timestamp(),
+                updated_at: contract.updated_at.assume_utc().unix_timestamp(),
+            })
         })
     })
     .await
@@ -135,14 +185,18 @@ mod tests {
 
     use crate::testing::{create_database, ResponseBodyExt};
 
-    use assert_json::assert_json;
+    use assert_json::{assert_json, validators};
     use axum::{
         body::Body,
-        http::{Request, StatusCode},
+        http::{header, Request, StatusCode},
     };
     use common::config::Config;
-    use common::rpc::sp_core::crypto::AccountId32;
-    use db::{code, contract, node, ActiveValue, DatabaseConnection, EntityTrait};
+    use common::rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+    use db::{
+        code, contract, event, node, ActiveValue, Database, DatabaseConnection, EntityTrait,
+        OffsetDateTime, PrimitiveDateTime, QuerySelect,
+    };
+    use migration::MigratorTrait;
     use tower::ServiceExt;
 
     async fn create_test_env(db: &DatabaseConnection) {
@@ -159,6 +213,7 @@ mod tests {
         code::Entity::insert(code::ActiveModel {
             hash: ActiveValue::Set(vec![0; 32]),
             code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
         })
         .exec_without_returning(db)
         .await
@@ -194,12 +249,232 @@ mod tests {
             .unwrap();
 
         assert_json!(response.json().await, {
+            "address": AccountId32::from([1; 32]).to_string(),
+            "node_id": 1,
             "node": "test",
             "code_hash": hex::encode([0; 32]),
             "owner": AccountId32::from([2; 32]).to_string(),
         })
     }
 
+    #[tokio::test]
+    async fn msgpack() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}", AccountId32::new([1; 32])))
+                    .header(header::ACCEPT, "application/msgpack")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/msgpack"
+        );
+
+        let body: serde_json::Value =
+            rmp_serde::from_slice(&response.bytes().await).expect("valid msgpack body");
+
+        assert_json!(body, {
+            "address": AccountId32::from([1; 32]).to_string(),
+            "node": "test",
+            "code_hash": hex::encode([0; 32]),
+        })
+    }
+
+    #[tokio::test]
+    async fn instantiation() {
+        let db = Database::connect("sqlite::memory:")
+            .await
+            .expect("unable to create test database");
+
+        // Contracts discovered before instantiation details were tracked
+        // receive them from their events during the migration.
+        let applied = migration::Migrator::migrations()
+            .iter()
+            .position(|migration| migration.name() == "m20220101_000035_add_contract_instantiation")
+            .expect("migration is missing");
+
+        migration::Migrator::up(&db, Some(applied as u32))
+            .await
+            .expect("unable to run migrations");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert node");
+
+        contract::Entity::insert(contract::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            code_hash: ActiveValue::Set(vec![0; 32]),
+            address: ActiveValue::Set(vec![1; 32]),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert contract");
+
+        let events = [
+            (10, event::EventType::CodeHashUpdate),
+            (42, event::EventType::Instantiation),
+            (50, event::EventType::Instantiation),
+        ];
+
+        for (block_number, event_type) in events {
+            let datetime =
+                OffsetDateTime::from_unix_timestamp(block_number + 58).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(1),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event_type),
+                body: ActiveValue::Set(String::new()),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(block_number),
+                ..Default::default()
+            })
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert an event");
+        }
+
+        migration::Migrator::up(&db, Some(1))
+            .await
+            .expect("unable to run migrations");
+
+        // Later migrations can not add columns to non-empty SQLite tables,
+        // thus the migrated values are checked directly.
+        let instantiation = contract::Entity::find()
+            .select_only()
+            .columns([
+                contract::Column::InstantiationBlock,
+                contract::Column::InstantiatedAt,
+            ])
+            .into_tuple::<(Option<i64>, Option<PrimitiveDateTime>)>()
+            .one(&db)
+            .await
+            .expect("unable to find contract")
+            .expect("contract is missing");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(100).expect("invalid date");
+
+        assert_eq!(
+            instantiation,
+            (
+                Some(42),
+                Some(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn timestamps() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let recent = move |timestamp: &i64| {
+            if (now - 60..=now + 60).contains(timestamp) {
+                Ok(())
+            } else {
+                Err(format!("timestamp {timestamp} is not recent"))
+            }
+        };
+
+        assert_json!(response.json().await, {
+            "created_at": validators::i64(recent),
+            "updated_at": validators::i64(recent),
+        })
+    }
+
+    #[tokio::test]
+    async fn hex_account() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/0x{}", hex::encode([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "address": AccountId32::from([1; 32]).to_string(),
+            "node_id": 1,
+            "node": "test",
+            "code_hash": hex::encode([0; 32]),
+            "owner": AccountId32::from([2; 32]).to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn ss58_prefix() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.ss58_prefix = 2;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let format = Ss58AddressFormat::custom(2);
+        let address = AccountId32::from([1; 32]).to_ss58check_with_version(format);
+
+        assert_ne!(address, AccountId32::from([1; 32]).to_string());
+        assert_json!(response.json().await, {
+            "address": address,
+            "node_id": 1,
+            "node": "test",
+            "code_hash": hex::encode([0; 32]),
+            "owner": AccountId32::from([2; 32]).to_ss58check_with_version(format),
+        })
+    }
+
     #[tokio::test]
     async fn unknown() {
         let db = create_database().await;
@@ -216,5 +491,11 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "contract_not_found",
+                "message": "contract not found",
+            }
+        });
     }
 }
@@ -1,41 +1,293 @@
-use std::sync::Arc;
+use std::{fmt, str::FromStr, sync::Arc};
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{
-    extract::{Path, State},
-    Json,
+    extract::{OriginalUri, Query, State},
+    headers::ETag,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json, TypedHeader,
 };
-use axum_derive_error::ErrorResponse;
+use common::config::Config;
+use common::rpc::sp_core::crypto::AccountId32;
 use common::rpc::sp_core::ByteArray;
 use db::{
-    event, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PrimitiveDateTime, QueryFilter,
-    QueryOrder, QuerySelect,
+    contract, event, event_topic,
+    sea_orm::{DbBackend, Select},
+    sea_query::{Expr, Func, LikeExpr, SimpleExpr},
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    OffsetDateTime, PaginatorTrait, PrimitiveDateTime, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, SelectExt,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use futures_util::TryStreamExt;
 use schemars::JsonSchema;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use time::format_description::well_known::Rfc3339;
 
-use super::WrappedAccountId32;
+use crate::{
+    conditional::IfNoneMatch,
+    hex_hash::HexHash,
+    negotiation::{Negotiated, ResponseFormat},
+    pagination::Paginated,
+    schema::{example_error, ErrorBody},
+    state::ReadReplica,
+};
+
+use super::{AccountPath, WrappedAccountId32};
 
 /// Errors that may occur during the contract event list request handling.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
 #[aide(output)]
-pub(super) enum ContractEventsError {
+pub(crate) enum ContractEventsError {
     /// Database-related error.
     DatabaseError(DbErr),
+
+    /// Strict mode was requested, but the provided contract account was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "contract not found")]
+    ContractNotFound,
+
+    /// Unknown event field name was requested.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "unknown event field requested")]
+    UnknownField,
+}
+
+/// Names of [`ContractEvent`] fields that can be requested via the `fields` query string parameter.
+const EVENT_FIELDS: [&str; 6] = [
+    "id",
+    "event_type",
+    "body",
+    "raw_body",
+    "timestamp",
+    "block_number",
+];
+
+/// Opaque cursor pointing to a single contract event.
+///
+/// Cursors are represented as `<timestamp>_<id>` strings, where timestamp
+/// is a block timestamp of an event and id is its database identifier.
+/// Clients should not rely on this format and should pass cursor values as-is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EventCursor {
+    /// Timestamp of a block in which the event was discovered.
+    timestamp: PrimitiveDateTime,
+
+    /// Event identifier.
+    id: i64,
+}
+
+impl EventCursor {
+    /// Create a [`Condition`] that matches events older than the one this cursor points to.
+    pub(crate) fn older_than(&self) -> Condition {
+        Condition::any()
+            .add(event::Column::BlockTimestamp.lt(self.timestamp))
+            .add(
+                Condition::all()
+                    .add(event::Column::BlockTimestamp.eq(self.timestamp))
+                    .add(event::Column::Id.lt(self.id)),
+            )
+    }
+}
+
+impl From<&event::Model> for EventCursor {
+    fn from(event: &event::Model) -> Self {
+        Self {
+            timestamp: event.block_timestamp,
+            id: event.id,
+        }
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}_{}",
+            self.timestamp.assume_utc().unix_timestamp(),
+            self.id
+        )
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (timestamp, id) = s.split_once('_').ok_or(())?;
+
+        let timestamp = OffsetDateTime::from_unix_timestamp(timestamp.parse().map_err(|_| ())?)
+            .map_err(|_| ())?;
+
+        Ok(Self {
+            timestamp: PrimitiveDateTime::new(timestamp.date(), timestamp.time()),
+            id: id.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+serde_plain::derive_serialize_from_display!(EventCursor);
+serde_plain::derive_deserialize_from_fromstr!(EventCursor, "valid event cursor");
+
+/// Query string used to paginate contract events.
+#[derive(Deserialize, JsonSchema)]
+pub(crate) struct ContractEventsQuery {
+    /// Cursor value returned from the previous request.
+    ///
+    /// If provided, only events older than the one
+    /// this cursor points to will be returned.
+    #[serde(default)]
+    #[schemars(
+        example = "crate::schema::example_event_cursor",
+        with = "Option<String>"
+    )]
+    pub(crate) before: Option<EventCursor>,
+
+    /// Count of events to return.
+    ///
+    /// Defaults to 25, values larger than 100 are capped,
+    /// unless configured otherwise by the server.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_limit")]
+    pub(crate) limit: Option<u64>,
+
+    /// Return only events with the provided indexed topic.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    pub(crate) topic: Option<HexHash>,
+
+    /// Return only events with a body containing the provided search term.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_search_term")]
+    pub(crate) q: Option<String>,
+
+    /// Return an error if the provided contract account is not known,
+    /// instead of an empty event list.
+    #[serde(default)]
+    pub(crate) strict: bool,
+
+    /// Comma-separated list of event fields to return.
+    ///
+    /// All fields are returned by default.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_event_fields")]
+    pub(crate) fields: Option<String>,
+
+    /// Format of event timestamps.
+    #[serde(default)]
+    pub(crate) timestamp_format: TimestampFormat,
+
+    /// Return hex-encoded SCALE event fields along with decoded event bodies.
+    #[serde(default)]
+    pub(crate) raw: bool,
+}
+
+/// Format of event timestamps.
+#[derive(Deserialize, JsonSchema, Default, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TimestampFormat {
+    /// Unix timestamp integer, in seconds.
+    #[default]
+    Unix,
+
+    /// RFC 3339 string in the UTC timezone.
+    Rfc3339,
 }
 
 /// A single contract event.
 #[derive(Serialize, JsonSchema)]
 pub struct ContractEvent {
-    /// Serialized JSON body of a contract event.
+    /// Event identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    pub id: i64,
+
+    /// Type of a contract event.
+    #[schemars(example = "crate::schema::example_event_type")]
+    pub event_type: event::EventType,
+
+    /// JSON body of a contract event.
+    ///
+    /// Malformed event bodies are returned as raw strings.
     #[schemars(example = "crate::schema::example_event_body")]
-    body: String,
+    pub body: Value,
+
+    /// Hex-encoded SCALE fields of a chain event from which the contract event was derived.
+    ///
+    /// Returned only if requested and available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(example = "crate::schema::example_raw_event_body")]
+    pub raw_body: Option<String>,
 
     /// Timestamp of a block in which the event was discovered.
+    ///
+    /// Returned as an RFC 3339 string instead if requested.
     #[schemars(example = "crate::schema::example_timestamp")]
-    timestamp: i64,
+    pub timestamp: i64,
+
+    /// Number of a block in which the event was discovered.
+    #[schemars(example = "crate::schema::example_block_number")]
+    pub block_number: i64,
+}
+
+/// Create an expression that matches events with a body containing the provided search term.
+///
+/// Postgres databases use full-text search, while other backends
+/// fall back to a case-insensitive substring match.
+fn body_contains(backend: DbBackend, term: &str) -> SimpleExpr {
+    match backend {
+        DbBackend::Postgres => Expr::cust_with_values(
+            r#"to_tsvector('simple', "events"."body") @@ plainto_tsquery('simple', $1)"#,
+            [term],
+        ),
+        _ => {
+            let escaped = term
+                .to_lowercase()
+                .replace('\\', r"\\")
+                .replace('%', r"\%")
+                .replace('_', r"\_");
+
+            Expr::expr(Func::lower(Expr::col(event::Column::Body)))
+                .like(LikeExpr::new(format!("%{escaped}%")).escape('\\'))
+        }
+    }
+}
+
+/// Parse a raw event body stored in the database.
+fn parse_body(body: String) -> Value {
+    serde_json::from_str(&body).unwrap_or(Value::String(body))
+}
+
+impl From<event::Model> for ContractEvent {
+    fn from(event: event::Model) -> Self {
+        Self {
+            id: event.id,
+            event_type: event.event_type,
+            body: parse_body(event.body),
+            raw_body: None,
+            timestamp: event.block_timestamp.assume_utc().unix_timestamp(),
+            block_number: event.block_number,
+        }
+    }
+}
+
+/// Contract event list response.
+#[derive(Serialize, JsonSchema)]
+pub struct ContractEventsResponse {
+    /// Contract events, ordered from the newest to the oldest one.
+    pub events: Vec<ContractEvent>,
+
+    /// Cursor that can be passed as the `before` query string parameter to get the next page.
+    ///
+    /// [`None`] if there are no more events available.
+    #[schemars(
+        example = "crate::schema::example_event_cursor",
+        with = "Option<String>"
+    )]
+    pub next_cursor: Option<EventCursor>,
 }
 
 /// Generate OAPI documentation for the [`events`] handler.
@@ -43,35 +295,312 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Get events related to the contract account.")
         .description(
             r#"Smart contract events are discovered
-only after the initial activation of an event client."#,
+only after the initial activation of an event client.
+
+Events are returned from the newest to the oldest one. To get older events,
+pass the `next_cursor` value from the previous response as the `before` query string parameter.
+
+Pass the `topic` query string parameter to get only events with a matching indexed topic.
+
+Pass the `q` query string parameter to search for events containing the provided term.
+Matching is done against the raw serialized event body, which means that JSON keys
+and hex-encoded values are matched as well. Postgres deployments use full-text search
+that matches whole words, while other databases use a case-insensitive substring match.
+
+Responses include an `ETag` header, which changes whenever events of the contract account
+are discovered or pruned, and differs between query string parameters and response formats.
+Pass it as the `If-None-Match` header to get an empty `304 Not Modified` response
+if nothing has changed since the previous request.
+
+The `X-Total-Count` header contains the count of events matching the provided filters,
+and the `Link` header contains a link to the next page, if there is one.
+
+Unknown contract accounts have no events, pass the `strict=true` query string parameter
+to get a `404 Not Found` response instead, which helps to detect mistyped addresses.
+
+Pass a comma-separated list of field names as the `fields` query string parameter
+to get only the requested fields of each event, for example `fields=timestamp`.
+
+Pass the `timestamp_format=rfc3339` query string parameter to get event timestamps
+as RFC 3339 strings in the UTC timezone, instead of unix timestamp integers.
+
+Pass the `raw=true` query string parameter to get hex-encoded SCALE fields of chain events
+as the `raw_body` field, which allows to decode events using your own metadata.
+Raw fields are not available for events discovered before they were recorded.
+
+Responses are encoded using MessagePack if requested
+with the `Accept: application/msgpack` header."#,
         )
-        .response_with::<200, Json<Vec<ContractEvent>>, _>(|op| {
+        .response_with::<200, Paginated<Json<ContractEventsResponse>>, _>(|op| {
             op.description("Event list response.")
         })
+        .response_with::<304, (), _>(|op| {
+            op.description("Events have not changed since the request with the provided ETag.")
+        })
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
+            op.description("Unknown event field name was requested.")
+                .example(example_error(ContractEventsError::UnknownField))
+        })
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("Provided contract account was not found in strict mode.")
+                .example(example_error(ContractEventsError::ContractNotFound))
+        })
 }
 
 /// Contract event list request handler.
 pub(super) async fn events(
-    Path(account): Path<WrappedAccountId32>,
-    State(db): State<Arc<DatabaseConnection>>,
-) -> Result<Json<Vec<ContractEvent>>, ContractEventsError> {
-    let model = event::Entity::find()
+    format: ResponseFormat,
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Query(query): Query<ContractEventsQuery>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+    OriginalUri(uri): OriginalUri,
+    if_none_match: IfNoneMatch,
+) -> Result<Response, ContractEventsError> {
+    let fields = query.fields.as_deref().map(parse_fields).transpose()?;
+
+    if query.strict {
+        let exists = contract::Entity::find()
+            .select_only()
+            .filter(contract::Column::Address.eq(account.0.as_slice()))
+            .exists(&*db)
+            .await?;
+
+        if !exists {
+            return Err(ContractEventsError::ContractNotFound);
+        }
+    }
+
+    let etag = events_etag(&db, &account.0, format, uri.query().unwrap_or_default()).await?;
+
+    if if_none_match.matches(&etag) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::VARY, HeaderValue::from_static("accept"))],
+            TypedHeader(etag),
+        )
+            .into_response());
+    }
+
+    let total = filtered_events(&db, &account.0, &query).count(&*db).await?;
+
+    let timestamp_format = query.timestamp_format;
+
+    let response = find_events(&db, &config, &account.0, query).await?;
+    let next_cursor = response.next_cursor;
+
+    let body = if fields.is_none() && timestamp_format == TimestampFormat::Unix {
+        Negotiated(format, response).into_response()
+    } else {
+        Negotiated(
+            format,
+            format_events(response, fields.as_deref(), timestamp_format),
+        )
+        .into_response()
+    };
+
+    Ok((
+        TypedHeader(etag),
+        Paginated::cursor(body, &uri, "before", next_cursor, total),
+    )
+        .into_response())
+}
+
+/// Format a unix timestamp as an RFC 3339 string in the UTC timezone.
+///
+/// [`None`] if the timestamp can not be represented in RFC 3339 format.
+fn format_rfc3339(timestamp: i64) -> Option<String> {
+    OffsetDateTime::from_unix_timestamp(timestamp)
+        .ok()?
+        .format(&Rfc3339)
+        .ok()
+}
+
+/// Parse a comma-separated list of requested event field names.
+fn parse_fields(fields: &str) -> Result<Vec<&'static str>, ContractEventsError> {
+    fields
+        .split(',')
+        .map(|field| {
+            EVENT_FIELDS
+                .into_iter()
+                .find(|known| *known == field.trim())
+                .ok_or(ContractEventsError::UnknownField)
+        })
+        .collect()
+}
+
+/// Build an event list response body that contains only the requested event fields,
+/// with timestamps formatted as requested.
+fn format_events(
+    response: ContractEventsResponse,
+    fields: Option<&[&str]>,
+    timestamp_format: TimestampFormat,
+) -> Value {
+    let events = response
+        .events
+        .into_iter()
+        .map(|event| {
+            let timestamp = event.timestamp;
+            let mut event = serde_json::to_value(event).expect("serializable event");
+
+            if let Some(event) = event.as_object_mut() {
+                if let Some(fields) = fields {
+                    event.retain(|key, _| fields.contains(&key.as_str()));
+                }
+
+                if timestamp_format == TimestampFormat::Rfc3339 {
+                    if let (Some(value), Some(formatted)) =
+                        (event.get_mut("timestamp"), format_rfc3339(timestamp))
+                    {
+                        *value = Value::String(formatted);
+                    }
+                }
+            }
+
+            event
+        })
+        .collect();
+
+    json!({
+        "events": Value::Array(events),
+        "next_cursor": response.next_cursor,
+    })
+}
+
+/// Compute an [`ETag`] of the event list of the provided contract account.
+///
+/// Events discovered by backfill may be older than already known ones, thus the
+/// largest event identifier is used instead of the newest event, and is combined
+/// with the event count, which changes when events are pruned.
+///
+/// Response format and query string affect the response body as well,
+/// thus their hash is included to distinguish different representations.
+async fn events_etag(
+    db: &DatabaseConnection,
+    account: &AccountId32,
+    format: ResponseFormat,
+    query: &str,
+) -> Result<ETag, DbErr> {
+    let (max_id, count): (Option<i64>, i64) = event::Entity::find()
+        .select_only()
+        .column_as(Expr::col(event::Column::Id).max(), "max_id")
+        .column_as(Expr::col(event::Column::Id).count(), "count")
+        .filter(event::Column::Account.eq(account.as_slice()))
+        .into_tuple()
+        .one(db)
+        .await?
+        .unwrap_or_default();
+
+    let format = match format {
+        ResponseFormat::Json => "json",
+        ResponseFormat::MessagePack => "msgpack",
+    };
+
+    let representation = Sha256::new()
+        .chain_update(format)
+        .chain_update([0])
+        .chain_update(query)
+        .finalize();
+
+    Ok(format!(
+        "\"{}-{count}-{}\"",
+        max_id.unwrap_or_default(),
+        hex::encode(&representation[..8])
+    )
+    .parse()
+    .expect("valid etag value"))
+}
+
+/// Create a query that selects events related to the provided contract account,
+/// which match the provided topic and search term filters.
+fn filtered_events(
+    db: &DatabaseConnection,
+    account: &AccountId32,
+    query: &ContractEventsQuery,
+) -> Select<event::Entity> {
+    event::Entity::find()
+        .filter(event::Column::Account.eq(account.as_slice()))
+        .apply_if(query.topic.as_ref(), |query, topic| {
+            query.filter(
+                event::Column::Id.in_subquery(
+                    event_topic::Entity::find()
+                        .select_only()
+                        .column(event_topic::Column::EventId)
+                        .filter(event_topic::Column::Topic.eq(&topic.0[..]))
+                        .into_query(),
+                ),
+            )
+        })
+        .apply_if(query.q.as_deref(), |query, term| {
+            query.filter(body_contains(db.get_database_backend(), term))
+        })
+}
+
+/// Find events related to the provided contract account.
+pub(crate) async fn find_events(
+    db: &DatabaseConnection,
+    config: &Config,
+    account: &AccountId32,
+    query: ContractEventsQuery,
+) -> Result<ContractEventsResponse, DbErr> {
+    let limit = config.pagination.limit(query.limit).max(1);
+    let raw = query.raw;
+
+    let mut events: Vec<_> = filtered_events(db, account, &query)
         .select_only()
-        .columns([event::Column::Body, event::Column::BlockTimestamp])
-        .filter(event::Column::Account.eq(account.0.as_slice()))
+        .columns([
+            event::Column::Id,
+            event::Column::EventType,
+            event::Column::Body,
+            event::Column::RawBody,
+            event::Column::BlockTimestamp,
+            event::Column::BlockNumber,
+        ])
+        .apply_if(query.before, |query, cursor| {
+            query.filter(cursor.older_than())
+        })
         .order_by_desc(event::Column::BlockTimestamp)
-        .limit(25)
-        .into_tuple::<(String, PrimitiveDateTime)>()
-        .stream(&*db)
+        .order_by_desc(event::Column::Id)
+        .limit(limit)
+        .into_tuple::<(
+            i64,
+            event::EventType,
+            String,
+            Option<Vec<u8>>,
+            PrimitiveDateTime,
+            i64,
+        )>()
+        .stream(db)
         .await?
-        .map_ok(|(body, date)| ContractEvent {
-            body,
-            timestamp: date.assume_utc().unix_timestamp(),
+        .map_ok(|(id, event_type, body, raw_body, date, block_number)| {
+            (
+                EventCursor {
+                    timestamp: date,
+                    id,
+                },
+                ContractEvent {
+                    id,
+                    event_type,
+                    body: parse_body(body),
+                    raw_body: raw_body.filter(|_| raw).map(hex::encode),
+                    timestamp: date.assume_utc().unix_timestamp(),
+                    block_number,
+                },
+            )
         })
         .try_collect()
         .await?;
 
-    Ok(Json(model))
+    let next_cursor = if events.len() as u64 == limit {
+        events.last().map(|(cursor, _)| *cursor)
+    } else {
+        None
+    };
+
+    Ok(ContractEventsResponse {
+        events: events.drain(..).map(|(_, event)| event).collect(),
+        next_cursor,
+    })
 }
 
 #[cfg(test)]
@@ -80,15 +609,18 @@ mod tests {
 
     use crate::testing::{create_database, ResponseBodyExt};
 
-    use assert_json::assert_json;
-    use axum::{body::Body, http::Request};
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+    };
     use common::config::Config;
     use common::rpc::sp_core::crypto::AccountId32;
     use db::{
-        code, contract, event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
-        PrimitiveDateTime,
+        code, contract, event, event_topic, node, ActiveValue, DatabaseConnection, EntityTrait,
+        OffsetDateTime, PrimitiveDateTime,
     };
-    use tower::ServiceExt;
+    use tower::{Service, ServiceExt};
 
     async fn create_test_env(db: &DatabaseConnection) {
         let node = node::Entity::insert(node::ActiveModel {
@@ -104,6 +636,7 @@ mod tests {
         code::Entity::insert(code::ActiveModel {
             hash: ActiveValue::Set(vec![0; 32]),
             code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
         })
         .exec_without_returning(db)
         .await
@@ -133,6 +666,7 @@ mod tests {
                 datetime.date(),
                 datetime.time(),
             )),
+            block_number: ActiveValue::Set(42),
             ..Default::default()
         })
         .exec_without_returning(db)
@@ -146,6 +680,119 @@ mod tests {
 
         create_test_env(&db).await;
 
+        let datetime = OffsetDateTime::from_unix_timestamp(10).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::CodeHashUpdate),
+            body: ActiveValue::Set(
+                serde_json::to_string(&event::EventBody::CodeHashUpdate {
+                    new_code_hash: hex::encode([3; 32]),
+                })
+                .unwrap(),
+            ),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(43),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert an event");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/events/{}", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "id": 2,
+                    "event_type": "code_hash_update",
+                    "body": {
+                        "CodeHashUpdate": {
+                            "new_code_hash": "0303030303030303030303030303030303030303030303030303030303030303"
+                        }
+                    },
+                    "timestamp": 10,
+                    "block_number": 43
+                },
+                {
+                    "id": 1,
+                    "event_type": "instantiation",
+                    "body": "Instantiation",
+                    "timestamp": 0,
+                    "block_number": 42
+                }
+            ],
+            "next_cursor": validators::null()
+        })
+    }
+
+    #[tokio::test]
+    async fn malformed_body() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let datetime = OffsetDateTime::from_unix_timestamp(10).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Termination),
+            body: ActiveValue::Set(String::from("{malformed")),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(43),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert an event");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?limit=1",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "termination",
+                    "body": "{malformed",
+                    "timestamp": 10,
+                    "block_number": 43
+                }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
         let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
             .oneshot(
                 Request::builder()
@@ -157,22 +804,754 @@ mod tests {
             .await
             .unwrap();
 
-        assert_json!(response.json().await, [
-            {
-                "body": r#""Instantiation""#,
-                "timestamp": 0
+        assert_json!(response.json().await, {
+            "events": [],
+            "next_cursor": validators::null()
+        })
+    }
+
+    #[tokio::test]
+    async fn strict() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?strict=true",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.json().await["events"]
+            .as_array()
+            .expect("array expected")
+            .is_empty());
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?strict=true",
+                        AccountId32::new([2; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "contract_not_found",
+                "message": "contract not found",
             }
-        ])
+        });
     }
 
     #[tokio::test]
-    async fn unknown() {
+    async fn sparse_fields() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?fields=timestamp",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.json().await,
+            serde_json::json!({
+                "events": [{ "timestamp": 0 }],
+                "next_cursor": null
+            })
+        );
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?fields=timestamp,blocknumber",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "unknown_field",
+                "message": "unknown event field requested",
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn rfc3339_timestamps() {
         let db = create_database().await;
 
+        create_test_env(&db).await;
+
         let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
             .oneshot(
                 Request::builder()
                     .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?timestamp_format=rfc3339",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "instantiation",
+                    "body": "Instantiation",
+                    "timestamp": "1970-01-01T00:00:00Z",
+                    "block_number": 42
+                }
+            ],
+            "next_cursor": validators::null()
+        })
+    }
+
+    #[tokio::test]
+    async fn raw_bodies() {
+        let db = Arc::new(create_database().await);
+
+        create_test_env(&db).await;
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Termination),
+            body: ActiveValue::Set(serde_json::to_string(&event::EventBody::Termination).unwrap()),
+            raw_body: ActiveValue::Set(Some(vec![1, 2, 3])),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                OffsetDateTime::UNIX_EPOCH.date(),
+                OffsetDateTime::UNIX_EPOCH.time(),
+            )),
+            block_number: ActiveValue::Set(43),
+            ..Default::default()
+        })
+        .exec_without_returning(&*db)
+        .await
+        .expect("unable to insert an event");
+
+        let mut service = crate::app_router(db, Arc::new(Config::for_tests()));
+
+        let mut events = |query: &'static str| {
+            service.call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}{query}",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let body = events("?raw=true").await.unwrap().json().await;
+
+        assert_eq!(body["events"][0]["raw_body"], "010203");
+        assert!(body["events"][1].get("raw_body").is_none());
+
+        let body = events("").await.unwrap().json().await;
+
+        assert!(body["events"][0].get("raw_body").is_none());
+    }
+
+    #[tokio::test]
+    async fn msgpack() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/events/{}", AccountId32::new([1; 32])))
+                    .header(header::ACCEPT, "application/msgpack")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/msgpack"
+        );
+
+        let body: serde_json::Value =
+            rmp_serde::from_slice(&response.bytes().await).expect("valid msgpack body");
+
+        assert_json!(body, {
+            "events": [
+                {
+                    "event_type": "instantiation",
+                    "body": "Instantiation",
+                    "timestamp": 0,
+                    "block_number": 42
+                }
+            ],
+            "next_cursor": validators::null()
+        })
+    }
+
+    #[tokio::test]
+    async fn pagination() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        for timestamp in [10, 20] {
+            let datetime = OffsetDateTime::from_unix_timestamp(timestamp).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(1),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event::EventType::Termination),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::Termination).unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(timestamp),
+                ..Default::default()
+            })
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert an event");
+        }
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?limit=2",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["X-Total-Count"], "3");
+        assert_eq!(
+            response.headers()[header::LINK],
+            format!(
+                "</contracts/events/{}?limit=2&before=10_2>; rel=\"next\"",
+                AccountId32::new([1; 32])
+            )
+        );
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 20,
+                    "block_number": 20
+                },
+                {
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 10,
+                    "block_number": 10
+                }
+            ],
+            "next_cursor": "10_2"
+        });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?limit=2&before=10_2",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["X-Total-Count"], "3");
+        assert!(!response.headers().contains_key(header::LINK));
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "instantiation",
+                    "body": "Instantiation",
+                    "timestamp": 0,
+                    "block_number": 42
+                }
+            ],
+            "next_cursor": validators::null()
+        });
+    }
+
+    #[tokio::test]
+    async fn invalid_cursor() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?before=abc",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn etag() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let db = Arc::new(db);
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+        let uri = format!("/contracts/events/{}", AccountId32::new([1; 32]));
+
+        let response = service
+            .call(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].clone();
+
+        let response = service
+            .call(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag);
+        assert!(response
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .any(|value| value == "accept"));
+
+        let datetime = OffsetDateTime::from_unix_timestamp(10).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Termination),
+            body: ActiveValue::Set(serde_json::to_string(&event::EventBody::Termination).unwrap()),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(43),
+            ..Default::default()
+        })
+        .exec_without_returning(&*db)
+        .await
+        .expect("unable to insert an event");
+
+        let response = service
+            .call(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag);
+    }
+
+    #[tokio::test]
+    async fn etag_representation() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+        let uri = format!("/contracts/events/{}", AccountId32::new([1; 32]));
+
+        let response = service
+            .call(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].clone();
+
+        let response = service
+            .call(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::ACCEPT, "application/msgpack")
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/msgpack"
+        );
+        assert_ne!(response.headers()[header::ETAG], etag);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .uri(format!("{uri}?fields=timestamp"))
+                    .header(header::IF_NONE_MATCH, &etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag);
+    }
+
+    #[tokio::test]
+    async fn invalid_account() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/contracts/events/garbage")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "invalid_account",
+                "message": validators::string(|message| {
+                    if message.contains("SS58") {
+                        Ok(())
+                    } else {
+                        Err(String::from("message does not describe the expected format"))
+                    }
+                }),
+            }
+        });
+    }
+
+    #[tokio::test]
+    async fn topic_filter() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let datetime = OffsetDateTime::from_unix_timestamp(10).expect("invalid date");
+
+        let emission = event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::ContractEmission),
+            body: ActiveValue::Set(
+                serde_json::to_string(&event::EventBody::ContractEmission {
+                    data: String::from("deadbeef"),
+                })
+                .unwrap(),
+            ),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(43),
+            ..Default::default()
+        })
+        .exec_with_returning(&db)
+        .await
+        .expect("unable to insert an event");
+
+        event_topic::Entity::insert(event_topic::ActiveModel {
+            event_id: ActiveValue::Set(emission.id),
+            topic: ActiveValue::Set(vec![5; 32]),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert an event topic");
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?topic=0x{}",
+                        AccountId32::new([1; 32]),
+                        "05".repeat(32)
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "contract_emission",
+                    "body": {
+                        "ContractEmission": {
+                            "data": "deadbeef"
+                        }
+                    },
+                    "timestamp": 10,
+                    "block_number": 43
+                }
+            ],
+            "next_cursor": validators::null()
+        });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?topic={}",
+                        AccountId32::new([1; 32]),
+                        "06".repeat(32)
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [],
+            "next_cursor": validators::null()
+        });
+    }
+
+    #[tokio::test]
+    async fn duplicate_events() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let datetime = OffsetDateTime::from_unix_timestamp(10).expect("invalid date");
+
+        for _ in 0..2 {
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(1),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event::EventType::Termination),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::Termination).unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(43),
+                event_index: ActiveValue::Set(Some(1)),
+                ..Default::default()
+            })
+            .on_conflict(event::skip_duplicates())
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert an event");
+        }
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/events/{}", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "termination",
+                    "body": "Termination",
+                    "timestamp": 10,
+                    "block_number": 43
+                },
+                {
+                    "event_type": "instantiation",
+                    "body": "Instantiation",
+                    "timestamp": 0,
+                    "block_number": 42
+                }
+            ],
+            "next_cursor": validators::null()
+        });
+    }
+
+    #[tokio::test]
+    async fn search() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        for (block_number, data) in [(43, "deadbeef"), (44, "cafebabe")] {
+            let datetime = OffsetDateTime::from_unix_timestamp(block_number).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(1),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event::EventType::ContractEmission),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::ContractEmission {
+                        data: String::from(data),
+                    })
+                    .unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(block_number),
+                ..Default::default()
+            })
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert an event");
+        }
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?q=DEADBEEF",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [
+                {
+                    "event_type": "contract_emission",
+                    "body": {
+                        "ContractEmission": {
+                            "data": "deadbeef"
+                        }
+                    },
+                    "timestamp": 43,
+                    "block_number": 43
+                }
+            ],
+            "next_cursor": validators::null()
+        });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}?q=%25",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "events": [],
+            "next_cursor": validators::null()
+        });
+    }
+
+    #[tokio::test]
+    async fn method_not_allowed() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
                     .uri(format!("/contracts/events/{}", AccountId32::new([1; 32])))
                     .body(Body::empty())
                     .unwrap(),
@@ -180,6 +1559,7 @@ mod tests {
             .await
             .unwrap();
 
-        assert_json!(response.json().await, [])
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(response.headers()[header::ALLOW], "GET,HEAD");
     }
 }
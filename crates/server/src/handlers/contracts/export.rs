@@ -0,0 +1,246 @@
+use std::{future::ready, sync::Arc};
+
+use aide::transform::TransformOperation;
+use axum::{body::Bytes, extract::State, Extension};
+use common::{config::Config, rpc::sp_core::ByteArray};
+use db::{
+    event, ColumnTrait, DbErr, EntityTrait, PrimitiveDateTime, QueryFilter, QueryOrder, QuerySelect,
+};
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use tokio::sync::mpsc;
+
+use crate::csv::{self, CsvStream};
+
+use crate::{ss58, state::ReadReplica};
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Count of encoded CSV records buffered before waiting for a client to receive them.
+const BUFFER_SIZE: usize = 64;
+
+/// Generate OAPI documentation for the [`export`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Export all events related to the contract account as a CSV file.")
+        .description(
+            r#"Events are ordered from the oldest to the newest one and are not paginated.
+
+The file contains `timestamp`, `event_type` and `body` columns,
+where body is a raw JSON-serialized event body."#,
+        )
+}
+
+/// Contract event export request handler.
+pub(super) async fn export(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> CsvStream<impl Stream<Item = Result<Bytes, DbErr>>> {
+    let filename = format!("{}-events.csv", ss58::encode(&account.0, &config));
+    let (sender, receiver) = mpsc::channel(BUFFER_SIZE);
+
+    // Database stream borrows the connection, thus it's driven by a separate task.
+    tokio::spawn(async move {
+        let result = async {
+            let mut events = event::Entity::find()
+                .select_only()
+                .columns([
+                    event::Column::EventType,
+                    event::Column::Body,
+                    event::Column::BlockTimestamp,
+                ])
+                .filter(event::Column::Account.eq(account.0.as_slice()))
+                .order_by_asc(event::Column::BlockTimestamp)
+                .order_by_asc(event::Column::Id)
+                .into_tuple::<(event::EventType, String, PrimitiveDateTime)>()
+                .stream(&*db)
+                .await?;
+
+            while let Some((event_type, body, date)) = events.try_next().await? {
+                let record = csv::record([
+                    date.assume_utc().unix_timestamp().to_string().as_str(),
+                    serde_plain::to_string(&event_type)
+                        .unwrap_or_default()
+                        .as_str(),
+                    body.as_str(),
+                ]);
+
+                // Client has disconnected.
+                if sender.send(Ok(record)).await.is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            let _ = sender.send(Err(err)).await;
+        }
+    });
+
+    let records = stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|record| (record, receiver))
+    });
+
+    CsvStream::new(
+        stream::once(ready(Ok(csv::record(["timestamp", "event_type", "body"])))).chain(records),
+        filename,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
+    use db::{
+        event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        let events = [
+            (
+                20,
+                event::EventType::Termination,
+                event::EventBody::Termination,
+            ),
+            (
+                10,
+                event::EventType::CodeHashUpdate,
+                event::EventBody::CodeHashUpdate {
+                    new_code_hash: String::from("00"),
+                },
+            ),
+        ];
+
+        for (timestamp, event_type, body) in events {
+            let datetime = OffsetDateTime::from_unix_timestamp(timestamp).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event_type),
+                body: ActiveValue::Set(serde_json::to_string(&body).unwrap()),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(timestamp),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert an event");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/export.csv",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            format!(
+                "attachment; filename=\"{}-events.csv\"",
+                AccountId32::new([1; 32])
+            )
+        );
+        assert_eq!(
+            response.text().await,
+            "timestamp,event_type,body\r\n\
+            10,code_hash_update,\"{\"\"CodeHashUpdate\"\":{\"\"new_code_hash\"\":\"\"00\"\"}}\"\r\n\
+            20,termination,\"\"\"Termination\"\"\"\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn ss58_prefix() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.ss58_prefix = 2;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/export.csv",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            format!(
+                "attachment; filename=\"{}-events.csv\"",
+                AccountId32::new([1; 32]).to_ss58check_with_version(Ss58AddressFormat::custom(2))
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/export.csv",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.text().await, "timestamp,event_type,body\r\n");
+    }
+}
@@ -0,0 +1,367 @@
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{
+    contract, event, public_key, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, SelectExt,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::{stream, Stream};
+use tokio::sync::mpsc;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    json_stream::JsonArrayStream,
+    schema::{example_error, ErrorBody},
+    state::ReadReplica,
+};
+
+use super::{
+    events::{ContractEvent, EventCursor},
+    AccountPath, WrappedAccountId32,
+};
+
+/// Count of serialized events buffered before waiting for a client to receive them.
+const BUFFER_SIZE: usize = 64;
+
+/// Count of events read from the database by a single query.
+const BATCH_SIZE: u64 = 500;
+
+/// Errors that may occur during the full contract event history request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractHistoryError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Contract is not owned by any of the current user's public keys.
+    #[status(StatusCode::FORBIDDEN)]
+    #[display(fmt = "contract is not owned by the current user")]
+    NotContractOwner,
+}
+
+/// Generate OAPI documentation for the [`history`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get all events related to the owned contract account.")
+        .description(
+            r#"Events are returned from the newest to the oldest one and are not paginated,
+the response body is streamed as a JSON array instead.
+
+Only the contract owner, which is one of the current user's verified public keys,
+is allowed to request the full event history."#,
+        )
+        .response_with::<200, JsonArrayStream<(), ContractEvent>, _>(|op| {
+            op.description("Full event list.")
+        })
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
+            op.description("Contract is not owned by the current user.")
+                .example(example_error(ContractHistoryError::NotContractOwner))
+        })
+}
+
+/// Full contract event history request handler.
+pub(super) async fn history(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<
+    JsonArrayStream<impl Stream<Item = Result<ContractEvent, DbErr>>, ContractEvent>,
+    ContractHistoryError,
+> {
+    let owned = contract::Entity::find()
+        .select_only()
+        .filter(contract::Column::Address.eq(account.0.as_slice()))
+        .filter(
+            contract::Column::Owner.in_subquery(
+                public_key::Entity::find()
+                    .select_only()
+                    .column(public_key::Column::Address)
+                    .filter(public_key::Column::UserId.eq(current_user.id()))
+                    .filter(public_key::Column::DeletedAt.is_null())
+                    .into_query(),
+            ),
+        )
+        .exists(&*db)
+        .await?;
+
+    if !owned {
+        return Err(ContractHistoryError::NotContractOwner);
+    }
+
+    let (sender, receiver) = mpsc::channel(BUFFER_SIZE);
+
+    // Events are sent to the client by a separate task, which reads them in batches,
+    // thus the connection is released while the client receives already read events.
+    tokio::spawn(async move {
+        let result = async {
+            let mut cursor = None;
+
+            loop {
+                let events = event::Entity::find()
+                    .filter(event::Column::Account.eq(account.0.as_slice()))
+                    .apply_if(cursor, |query, cursor: EventCursor| {
+                        query.filter(cursor.older_than())
+                    })
+                    .order_by_desc(event::Column::BlockTimestamp)
+                    .order_by_desc(event::Column::Id)
+                    .limit(BATCH_SIZE)
+                    .all(&*db)
+                    .await?;
+
+                let is_last_batch = (events.len() as u64) < BATCH_SIZE;
+                cursor = events.last().map(EventCursor::from);
+
+                for event in events {
+                    // Client has disconnected.
+                    if sender.send(Ok(ContractEvent::from(event))).await.is_err() {
+                        return Ok(());
+                    }
+                }
+
+                if is_last_batch {
+                    return Ok(());
+                }
+            }
+        }
+        .await;
+
+        if let Err(err) = result {
+            let _ = sender.send(Err(err)).await;
+        }
+    });
+
+    Ok(JsonArrayStream::new(stream::unfold(
+        receiver,
+        |mut receiver| async move { receiver.recv().await.map(|event| (event, receiver)) },
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{
+        code, contract, event, node, public_key, token, user, ActiveValue, DatabaseConnection,
+        EntityTrait, OffsetDateTime, PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> (String, String) {
+        let mut tokens = Vec::new();
+
+        for owner in [[1; 32], [2; 32]] {
+            let user = user::Entity::insert(user::ActiveModel::default())
+                .exec_with_returning(db)
+                .await
+                .expect("unable to create user");
+
+            let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+            token::Entity::insert(model)
+                .exec_without_returning(db)
+                .await
+                .expect("unable to insert token");
+
+            public_key::Entity::insert(public_key::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                address: ActiveValue::Set(owner.to_vec()),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert public key");
+
+            tokens.push(token);
+        }
+
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert code");
+
+        contract::Entity::insert(contract::ActiveModel {
+            code_hash: ActiveValue::Set(vec![0; 32]),
+            node_id: ActiveValue::Set(node.id),
+            address: ActiveValue::Set(vec![3; 32]),
+            owner: ActiveValue::Set(Some(vec![1; 32])),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert contract");
+
+        event::Entity::insert_many((1..=30).map(|timestamp| {
+            let datetime = OffsetDateTime::from_unix_timestamp(timestamp).expect("invalid date");
+
+            event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(vec![3; 32]),
+                event_type: ActiveValue::Set(event::EventType::Termination),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::Termination).unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(timestamp),
+                ..Default::default()
+            }
+        }))
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert events");
+
+        let owner = tokens.remove(0);
+
+        (owner, tokens.remove(0))
+    }
+
+    #[tokio::test]
+    async fn owner() {
+        let db = create_database().await;
+
+        let (token, _) = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/{}/events/all",
+                        AccountId32::new([3; 32])
+                    ))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.json().await;
+        let block_numbers: Vec<_> = body
+            .as_array()
+            .expect("array expected")
+            .iter()
+            .map(|event| {
+                event["block_number"]
+                    .as_i64()
+                    .expect("block number expected")
+            })
+            .collect();
+
+        assert_eq!(block_numbers, (1..=30).rev().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn batches() {
+        let db = create_database().await;
+
+        let (token, _) = create_test_env(&db).await;
+
+        let count = super::BATCH_SIZE as i64 * 2;
+        let datetime = OffsetDateTime::from_unix_timestamp(100).expect("invalid date");
+
+        // Events of the same block are ordered by their identifiers across batches.
+        event::Entity::insert_many((31..=count).map(|block_number| event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![3; 32]),
+            event_type: ActiveValue::Set(event::EventType::Termination),
+            body: ActiveValue::Set(serde_json::to_string(&event::EventBody::Termination).unwrap()),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(block_number),
+            ..Default::default()
+        }))
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert events");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/{}/events/all",
+                        AccountId32::new([3; 32])
+                    ))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let body = response.json().await;
+        let block_numbers: Vec<_> = body
+            .as_array()
+            .expect("array expected")
+            .iter()
+            .map(|event| {
+                event["block_number"]
+                    .as_i64()
+                    .expect("block number expected")
+            })
+            .collect();
+
+        assert_eq!(block_numbers, (1..=count).rev().collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn not_owner() {
+        let db = create_database().await;
+
+        let (_, token) = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/{}/events/all",
+                        AccountId32::new([3; 32])
+                    ))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "not_contract_owner",
+            }
+        });
+    }
+}
@@ -1,28 +1,247 @@
+/// Historical event backfill route.
+mod backfill;
+
+/// Historical event backfill status route.
+mod backfill_status;
+
+/// Batch smart contract events list route.
+mod batch;
+
+/// Smart contract event count route.
+mod count;
+
 /// Smart contract details route.
-mod details;
+pub(crate) mod details;
 
 /// Smart contract events list route.
-mod events;
+pub(crate) mod events;
+
+/// Smart contract events CSV export route.
+mod export;
+
+/// Full smart contract event history route.
+mod history;
+
+/// Owned smart contracts list route.
+mod owned;
+
+/// Smart contract ownership history route.
+mod owners;
+
+/// Smart contract search route.
+mod search;
+
+/// Smart contract event statistics route.
+mod stats;
+
+/// Smart contract live event stream route.
+mod stream;
 
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
-use aide::axum::{routing::get_with, ApiRouter};
-use common::rpc::sp_core::crypto::AccountId32;
+use aide::{
+    axum::{
+        routing::{get_with, post_with},
+        ApiRouter,
+    },
+    OperationInput,
+};
+use axum::{
+    async_trait,
+    extract::{path::ErrorKind, rejection::PathRejection, FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+    middleware::from_fn_with_state,
+};
+use common::{
+    config::Config,
+    rpc::sp_core::crypto::{AccountId32, PublicError, Ss58Codec},
+};
 use db::DatabaseConnection;
+use derive_more::{Display, Error};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{de::Error as _, Deserialize, Deserializer};
+
+use crate::{auth, state::AppState};
 
 /// [`AccountId32`] wrapper for OAPI documentation purposes.
-#[derive(Deserialize, JsonSchema)]
+///
+/// Deserialized from SS58-encoded account addresses or `0x`-prefixed hex values.
+#[derive(JsonSchema)]
 #[serde(transparent)]
-struct WrappedAccountId32(
+pub(crate) struct WrappedAccountId32(
     #[schemars(example = "crate::schema::example_account", with = "String")] pub AccountId32,
 );
 
+/// Errors that may occur during account parsing.
+#[derive(Debug, Display)]
+pub(crate) enum AccountParseError {
+    /// Provided hex value is not a valid 32-byte array.
+    #[display(fmt = "invalid hex account, 32 bytes expected")]
+    InvalidHex,
+
+    /// Provided value is not a valid SS58 address.
+    #[display(fmt = "invalid SS58 address: {_0:?}")]
+    InvalidSs58(PublicError),
+}
+
+impl FromStr for WrappedAccountId32 {
+    type Err = AccountParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex) = s.strip_prefix("0x") {
+            let mut buf = [0; 32];
+
+            hex::decode_to_slice(hex, &mut buf).map_err(|_| AccountParseError::InvalidHex)?;
+
+            Ok(Self(AccountId32::new(buf)))
+        } else {
+            AccountId32::from_ss58check(s)
+                .map(Self)
+                .map_err(AccountParseError::InvalidSs58)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WrappedAccountId32 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(|err| {
+            D::Error::custom(format!(
+                "expected an SS58 address or a 0x-prefixed 32-byte hex value, got {value:?} ({err})"
+            ))
+        })
+    }
+}
+
+/// Errors related to contract route path parameter extraction.
+#[derive(ErrorResponse, Display, Error)]
+pub(crate) enum AccountPathRejection {
+    /// Provided account is not a valid SS58-encoded address.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "invalid account: {_0}")]
+    InvalidAccount(#[error(not(source))] String),
+
+    /// Other path parameters are invalid.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "invalid path parameters: {_0}")]
+    InvalidPath(PathRejection),
+}
+
+/// [`Path`] extractor wrapper, which rejects malformed accounts with a descriptive error.
+pub(crate) struct AccountPath<T>(pub T);
+
+impl<T> OperationInput for AccountPath<T>
+where
+    Path<T>: OperationInput,
+{
+    fn operation_input(ctx: &mut aide::gen::GenContext, operation: &mut aide::openapi::Operation) {
+        <Path<T> as OperationInput>::operation_input(ctx, operation)
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for AccountPath<T>
+where
+    T: Send,
+    S: Send + Sync,
+    Path<T>: FromRequestParts<S, Rejection = PathRejection>,
+{
+    type Rejection = AccountPathRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(Self(value)),
+            // Other path parameters are integers, which produce parsing errors instead.
+            Err(PathRejection::FailedToDeserializePathParams(err))
+                if matches!(err.kind(), ErrorKind::Message(_)) =>
+            {
+                Err(AccountPathRejection::InvalidAccount(err.kind().to_string()))
+            }
+            Err(err) => Err(AccountPathRejection::InvalidPath(err)),
+        }
+    }
+}
+
 /// Create an [`ApiRouter`] that provides an API server with contract information routes.
-pub(crate) fn routes() -> ApiRouter<Arc<DatabaseConnection>> {
-    ApiRouter::new()
+pub(crate) fn routes(
+    database: Arc<DatabaseConnection>,
+    config: Arc<Config>,
+) -> ApiRouter<AppState> {
+    let private_routes = ApiRouter::new()
+        .api_route(
+            "/:account/backfill",
+            post_with(backfill::backfill, backfill::docs),
+        )
+        .api_route(
+            "/:account/events/all",
+            get_with(history::history, history::docs),
+        )
+        .route_layer(from_fn_with_state(
+            (database, config),
+            auth::require_authentication::<false, false, _>,
+        ))
+        .with_path_items(|op| op.security_requirement("Authentication token"));
+
+    let public_routes = ApiRouter::new()
+        .api_route("/", get_with(search::search, search::docs))
+        .api_route("/events/batch", post_with(batch::batch, batch::docs))
         .api_route("/events/:account", get_with(events::events, events::docs))
+        .api_route(
+            "/events/:account/count",
+            get_with(count::count, count::docs),
+        )
+        .api_route(
+            "/events/:account/export.csv",
+            get_with(export::export, export::docs),
+        )
+        .api_route(
+            "/events/:account/stream",
+            get_with(stream::stream, stream::docs),
+        )
+        .api_route("/owned/:owner", get_with(owned::owned, owned::docs))
         .api_route("/:account", get_with(details::details, details::docs))
+        .api_route("/:account/owners", get_with(owners::owners, owners::docs))
+        .api_route("/:account/stats", get_with(stats::stats, stats::docs))
+        .api_route(
+            "/:account/backfill/:id",
+            get_with(backfill_status::backfill_status, backfill_status::docs),
+        );
+
+    ApiRouter::new()
+        .merge(private_routes)
+        .merge(public_routes)
         .with_path_items(|op| op.tag("Contract management"))
 }
+
+#[cfg(test)]
+mod tests {
+    use common::rpc::sp_core::crypto::AccountId32;
+
+    use super::WrappedAccountId32;
+
+    #[test]
+    fn account_encodings() {
+        let account = AccountId32::new([1; 32]);
+
+        let ss58: WrappedAccountId32 = account.to_string().parse().unwrap();
+        let hex: WrappedAccountId32 = format!("0x{}", hex::encode([1; 32])).parse().unwrap();
+
+        assert_eq!(ss58.0, account);
+        assert_eq!(hex.0, account);
+    }
+
+    #[test]
+    fn invalid_accounts() {
+        for value in [
+            "garbage",
+            "0x",
+            "0xzz",
+            &format!("0x{}", hex::encode([1; 31])),
+            &hex::encode([1; 32]),
+        ] {
+            assert!(value.parse::<WrappedAccountId32>().is_err());
+        }
+    }
+}
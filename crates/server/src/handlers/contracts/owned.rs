@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Query, State},
+    Extension, Json,
+};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
+};
+use db::{
+    contract, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    hex_hash::HexHash,
+    pagination::{OffsetPagination, Paginated},
+    ss58,
+    state::ReadReplica,
+};
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Errors that may occur during the owned contract list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum OwnedContractsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Contract address stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract address size stored in db")]
+    InvalidContractAddressSize,
+
+    /// Code hash stored inside of a database has an invalid size.
+    #[display(fmt = "invalid code hash size stored in db")]
+    InvalidCodeHashSize,
+}
+
+/// A single owned contract data.
+#[derive(Serialize, JsonSchema)]
+pub struct OwnedContract {
+    /// Contract account address.
+    #[schemars(example = "crate::schema::example_account")]
+    pub address: String,
+
+    /// Related node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    pub node_id: i64,
+
+    /// Related code hash.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    pub code_hash: HexHash,
+}
+
+/// Generate OAPI documentation for the [`owned`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List contracts owned by the provided account.")
+        .description(
+            r#"Contracts are ordered by their discovery time in an ascending order.
+
+Only contracts discovered after the initial activation of an event server
+have their owners known, thus other contracts are never returned."#,
+        )
+        .response_with::<200, Paginated<Json<Vec<OwnedContract>>>, _>(|op| {
+            op.description("Owned contract list.")
+        })
+}
+
+/// Owned contract list request handler.
+pub(super) async fn owned(
+    AccountPath(owner): AccountPath<WrappedAccountId32>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<OwnedContract>>>, OwnedContractsError> {
+    let config = &config;
+
+    let total = contract::Entity::find()
+        .filter(contract::Column::Owner.eq(owner.0.as_slice()))
+        .count(&*db)
+        .await?;
+
+    contract::Entity::find()
+        .select_only()
+        .columns([
+            contract::Column::Address,
+            contract::Column::NodeId,
+            contract::Column::CodeHash,
+        ])
+        .filter(contract::Column::Owner.eq(owner.0.as_slice()))
+        .order_by_asc(contract::Column::Id)
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .into_tuple::<(Vec<u8>, i64, Vec<u8>)>()
+        .stream(&*db)
+        .await?
+        .err_into()
+        .and_then(|(address, node_id, code_hash)| async move {
+            let address = AccountId32::new(
+                address
+                    .try_into()
+                    .map_err(|_| OwnedContractsError::InvalidContractAddressSize)?,
+            );
+
+            Ok(OwnedContract {
+                address: ss58::encode(&address, config),
+                node_id,
+                code_hash: code_hash
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| OwnedContractsError::InvalidCodeHashSize)?,
+            })
+        })
+        .try_collect()
+        .await
+        .map(|items| Paginated::offset(Json(items), &uri, &pagination, &config.pagination, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{code, contract, node, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert code");
+
+        for (address, owner) in [
+            ([1; 32], Some([5; 32])),
+            ([2; 32], Some([6; 32])),
+            ([3; 32], None),
+        ] {
+            contract::Entity::insert(contract::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                code_hash: ActiveValue::Set(vec![0; 32]),
+                address: ActiveValue::Set(address.to_vec()),
+                owner: ActiveValue::Set(owner.map(|owner| owner.to_vec())),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert contract");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/owned/{}", AccountId32::new([5; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "address": AccountId32::new([1; 32]).to_string(),
+                "node_id": 1,
+                "code_hash": hex::encode([0; 32])
+            }
+        ]);
+    }
+}
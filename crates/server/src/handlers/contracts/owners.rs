@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
+};
+use db::{
+    event, ColumnTrait, DbErr, EntityTrait, PrimitiveDateTime, QueryFilter, QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{ss58, state::ReadReplica};
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Errors that may occur during the contract owner history request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractOwnersError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Ownership change event body stored inside of a database is invalid.
+    #[display(fmt = "invalid ownership change event body stored in db")]
+    InvalidEventBody,
+}
+
+/// A single contract owner.
+#[derive(Serialize, JsonSchema)]
+pub struct ContractOwner {
+    /// Owner account address.
+    #[schemars(example = "crate::schema::example_account")]
+    pub owner: String,
+
+    /// Number of a block in which the ownership took effect.
+    #[schemars(example = "crate::schema::example_block_number")]
+    pub block_number: i64,
+
+    /// Timestamp of a block in which the ownership took effect.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub timestamp: i64,
+}
+
+/// Generate OAPI documentation for the [`owners`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get ownership history of the provided contract account.")
+        .description(
+            r#"Owners are ordered by the time they took effect in an ascending order,
+thus the last entry represents the current contract owner.
+
+Only ownership changes discovered by an event server are returned,
+historical ones can be discovered with a contract event backfill."#,
+        )
+        .response_with::<200, Json<Vec<ContractOwner>>, _>(|op| {
+            op.description("Contract ownership history.")
+        })
+}
+
+/// Contract owner history request handler.
+pub(super) async fn owners(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<Json<Vec<ContractOwner>>, ContractOwnersError> {
+    let config = &config;
+
+    event::Entity::find()
+        .select_only()
+        .columns([
+            event::Column::Body,
+            event::Column::BlockNumber,
+            event::Column::BlockTimestamp,
+        ])
+        .filter(event::Column::Account.eq(account.0.as_slice()))
+        .filter(event::Column::EventType.eq(event::EventType::OwnershipChange))
+        .order_by_asc(event::Column::BlockNumber)
+        .order_by_asc(event::Column::EventIndex)
+        .into_tuple::<(String, i64, PrimitiveDateTime)>()
+        .stream(&*db)
+        .await?
+        .err_into()
+        .and_then(|(body, block_number, timestamp)| async move {
+            let event::EventBody::OwnershipChange { new_owner } =
+                serde_json::from_str(&body).map_err(|_| ContractOwnersError::InvalidEventBody)?
+            else {
+                return Err(ContractOwnersError::InvalidEventBody);
+            };
+
+            let owner = hex::decode(new_owner)
+                .ok()
+                .and_then(|owner| AccountId32::from_slice(&owner).ok())
+                .ok_or(ContractOwnersError::InvalidEventBody)?;
+
+            Ok(ContractOwner {
+                owner: ss58::encode(&owner, config),
+                block_number,
+                timestamp: timestamp.assume_utc().unix_timestamp(),
+            })
+        })
+        .try_collect()
+        .await
+        .map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+    use db::{
+        event, node, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        for (block_number, account, owner) in [
+            (20, [1; 32], [3; 32]),
+            (10, [1; 32], [2; 32]),
+            (15, [4; 32], [5; 32]),
+        ] {
+            let datetime = OffsetDateTime::from_unix_timestamp(block_number).expect("invalid date");
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(account.to_vec()),
+                event_type: ActiveValue::Set(event::EventType::OwnershipChange),
+                body: ActiveValue::Set(
+                    serde_json::to_string(&event::EventBody::OwnershipChange {
+                        new_owner: hex::encode(owner),
+                    })
+                    .unwrap(),
+                ),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                block_number: ActiveValue::Set(block_number),
+                event_index: ActiveValue::Set(Some(0)),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert an event");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}/owners", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "owner": AccountId32::new([2; 32]).to_string(),
+                "block_number": 10,
+                "timestamp": 10,
+            },
+            {
+                "owner": AccountId32::new([3; 32]).to_string(),
+                "block_number": 20,
+                "timestamp": 20,
+            }
+        ]);
+    }
+}
@@ -0,0 +1,331 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
+};
+use db::{
+    contract, sea_orm::Select, ColumnTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hex_hash::HexHash,
+    pagination::{OffsetPagination, Paginated},
+    schema::{example_error, ErrorBody},
+    ss58,
+    state::ReadReplica,
+};
+
+use super::WrappedAccountId32;
+
+/// Errors that may occur during the contract search request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractSearchError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// No search filters were provided.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "at least one filter has to be provided")]
+    MissingFilters,
+
+    /// Contract address stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract address size stored in db")]
+    InvalidContractAddressSize,
+
+    /// Contract owner stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract owner size stored in db")]
+    InvalidOwnerSize,
+
+    /// Code hash stored inside of a database has an invalid size.
+    #[display(fmt = "invalid code hash size stored in db")]
+    InvalidCodeHashSize,
+}
+
+/// Query string used to filter contracts.
+///
+/// All provided filters have to match.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct ContractSearchQuery {
+    /// Return only contracts deployed from the provided code hash.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: Option<HexHash>,
+
+    /// Return only contracts owned by the provided account.
+    #[serde(default)]
+    owner: Option<WrappedAccountId32>,
+
+    /// Return only contracts discovered on the provided node.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    node_id: Option<i64>,
+}
+
+impl ContractSearchQuery {
+    /// Check if at least one filter was provided.
+    fn has_filters(&self) -> bool {
+        self.code_hash.is_some() || self.owner.is_some() || self.node_id.is_some()
+    }
+
+    /// Create a query that selects contracts matching all provided filters.
+    fn select(&self) -> Select<contract::Entity> {
+        contract::Entity::find()
+            .apply_if(self.code_hash, |query, code_hash| {
+                query.filter(contract::Column::CodeHash.eq(&code_hash.0[..]))
+            })
+            .apply_if(self.owner.as_ref(), |query, owner| {
+                query.filter(contract::Column::Owner.eq(owner.0.as_slice()))
+            })
+            .apply_if(self.node_id, |query, node_id| {
+                query.filter(contract::Column::NodeId.eq(node_id))
+            })
+    }
+}
+
+/// A single contract search result.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct ContractSearchResult {
+    /// Contract account address.
+    #[schemars(example = "crate::schema::example_account")]
+    address: String,
+
+    /// Related node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    node_id: i64,
+
+    /// Related code hash.
+    #[schemars(example = "crate::schema::example_hex_hash")]
+    code_hash: HexHash,
+
+    /// Contract owner account address, if known.
+    #[schemars(example = "crate::schema::example_account")]
+    owner: Option<String>,
+}
+
+/// Generate OAPI documentation for the [`search`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Search contracts using the provided filters.")
+        .description(
+            r#"At least one filter has to be provided. If multiple filters are provided,
+only contracts matching all of them are returned.
+
+Contracts are ordered by their discovery time in an ascending order."#,
+        )
+        .response_with::<200, Paginated<Json<Vec<ContractSearchResult>>>, _>(|op| {
+            op.description("Contract search results.")
+        })
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
+            op.description("No filters were provided.")
+                .example(example_error(ContractSearchError::MissingFilters))
+        })
+}
+
+/// Contract search request handler.
+pub(super) async fn search(
+    Query(query): Query<ContractSearchQuery>,
+    Query(pagination): Query<OffsetPagination>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<ContractSearchResult>>>, ContractSearchError> {
+    let config = &config;
+
+    if !query.has_filters() {
+        return Err(ContractSearchError::MissingFilters);
+    }
+
+    let total = query.select().count(&*db).await?;
+
+    query
+        .select()
+        .select_only()
+        .columns([
+            contract::Column::Address,
+            contract::Column::NodeId,
+            contract::Column::CodeHash,
+            contract::Column::Owner,
+        ])
+        .order_by_asc(contract::Column::Id)
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .into_tuple::<(Vec<u8>, i64, Vec<u8>, Option<Vec<u8>>)>()
+        .stream(&*db)
+        .await?
+        .err_into()
+        .and_then(|(address, node_id, code_hash, owner)| async move {
+            let address = AccountId32::new(
+                address
+                    .try_into()
+                    .map_err(|_| ContractSearchError::InvalidContractAddressSize)?,
+            );
+
+            let owner = owner
+                .map(|owner| {
+                    AccountId32::from_slice(&owner)
+                        .map_err(|_| ContractSearchError::InvalidOwnerSize)
+                })
+                .transpose()?;
+
+            Ok(ContractSearchResult {
+                address: ss58::encode(&address, config),
+                node_id,
+                code_hash: code_hash
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| ContractSearchError::InvalidCodeHashSize)?,
+                owner: owner.map(|owner| ss58::encode(&owner, config)),
+            })
+        })
+        .try_collect()
+        .await
+        .map(|items| Paginated::offset(Json(items), &uri, &pagination, &config.pagination, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{code, contract, node, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        for (name, port) in [("first", 9944), ("second", 9945)] {
+            node::Entity::insert(node::ActiveModel {
+                name: ActiveValue::Set(String::from(name)),
+                url: ActiveValue::Set(format!("ws://localhost:{port}")),
+                confirmed_block: ActiveValue::Set(0),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert node");
+        }
+
+        for hash in [[0; 32], [1; 32]] {
+            code::Entity::insert(code::ActiveModel {
+                hash: ActiveValue::Set(hash.to_vec()),
+                code: ActiveValue::Set(vec![1, 2, 3]),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert code");
+        }
+
+        for (address, node_id, code_hash, owner) in [
+            ([1; 32], 1, [0; 32], Some([10; 32])),
+            ([2; 32], 1, [1; 32], Some([10; 32])),
+            ([3; 32], 2, [0; 32], Some([11; 32])),
+            ([4; 32], 2, [0; 32], None),
+        ] {
+            contract::Entity::insert(contract::ActiveModel {
+                node_id: ActiveValue::Set(node_id),
+                code_hash: ActiveValue::Set(code_hash.to_vec()),
+                address: ActiveValue::Set(address.to_vec()),
+                owner: ActiveValue::Set(owner.map(|owner| owner.to_vec())),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert contract");
+        }
+    }
+
+    #[tokio::test]
+    async fn filters() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (query, expected) in [
+            (format!("code_hash={}", hex::encode([0; 32])), vec![1, 3, 4]),
+            (format!("owner={}", AccountId32::new([10; 32])), vec![1, 2]),
+            (String::from("node_id=2"), vec![3, 4]),
+            (
+                format!("code_hash={}&node_id=1", hex::encode([0; 32])),
+                vec![1],
+            ),
+            (
+                format!(
+                    "code_hash={}&owner={}&node_id=2",
+                    hex::encode([0; 32]),
+                    AccountId32::new([11; 32])
+                ),
+                vec![3],
+            ),
+            (
+                format!("owner={}&node_id=2", AccountId32::new([10; 32])),
+                vec![],
+            ),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(format!("/contracts?{query}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            let addresses: Vec<_> = response
+                .json()
+                .await
+                .as_array()
+                .expect("array expected")
+                .iter()
+                .map(|contract| contract["address"].as_str().unwrap().to_string())
+                .collect();
+
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|byte| AccountId32::new([byte; 32]).to_string())
+                .collect();
+
+            assert_eq!(addresses, expected, "{query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_filters() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/contracts")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
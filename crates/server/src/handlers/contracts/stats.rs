@@ -0,0 +1,238 @@
+use std::collections::BTreeMap;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Json};
+use common::rpc::sp_core::ByteArray;
+use db::{
+    event, sea_orm::Iterable, sea_query::Expr, ColumnTrait, DbErr, Duration, EntityTrait,
+    OffsetDateTime, PaginatorTrait, PrimitiveDateTime, QueryFilter, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::state::ReadReplica;
+
+use super::{AccountPath, WrappedAccountId32};
+
+/// Errors that may occur during the contract event statistics request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ContractEventStatsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Unable to serialize an event type.
+    SerializationError(serde_plain::Error),
+}
+
+/// Contract event statistics response.
+#[derive(Serialize, JsonSchema)]
+pub struct ContractEventStats {
+    /// Total count of events related to the contract account.
+    #[schemars(example = "crate::schema::example_event_count")]
+    total: u64,
+
+    /// Count of events that occured during the last 24 hours.
+    #[schemars(example = "crate::schema::example_event_count")]
+    last_day: u64,
+
+    /// Count of events that occured during the last 7 days.
+    #[schemars(example = "crate::schema::example_event_count")]
+    last_week: u64,
+
+    /// Count of events of each event type.
+    ///
+    /// Event types without any related events are included with a zero count.
+    by_type: BTreeMap<String, u64>,
+}
+
+/// Generate OAPI documentation for the [`stats`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get event emission statistics of the contract account.")
+        .description(
+            r#"Time windows are calculated using block timestamps of events.
+
+Unknown contract accounts are not considered an error,
+instead all of the returned event counts will be equal to zero."#,
+        )
+        .response::<200, Json<ContractEventStats>>()
+}
+
+/// Contract event statistics request handler.
+pub(super) async fn stats(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<Json<ContractEventStats>, ContractEventStatsError> {
+    let events = || event::Entity::find().filter(event::Column::Account.eq(account.0.as_slice()));
+
+    let type_counts = events()
+        .select_only()
+        .column(event::Column::EventType)
+        .column_as(Expr::col(event::Column::Id).count(), "count")
+        .group_by(event::Column::EventType)
+        .into_tuple::<(event::EventType, i64)>()
+        .all(&*db)
+        .await?;
+
+    let mut by_type = event::EventType::iter()
+        .map(|event_type| Ok((serde_plain::to_string(&event_type)?, 0)))
+        .collect::<Result<BTreeMap<_, _>, ContractEventStatsError>>()?;
+
+    let mut total = 0;
+
+    for (event_type, count) in type_counts {
+        total += count as u64;
+        by_type.insert(serde_plain::to_string(&event_type)?, count as u64);
+    }
+
+    let now = OffsetDateTime::now_utc();
+    let now = PrimitiveDateTime::new(now.date(), now.time());
+
+    let last_day = events()
+        .filter(event::Column::BlockTimestamp.gte(now - Duration::DAY))
+        .count(&*db)
+        .await?;
+
+    let last_week = events()
+        .filter(event::Column::BlockTimestamp.gte(now - Duration::WEEK))
+        .count(&*db)
+        .await?;
+
+    Ok(Json(ContractEventStats {
+        total,
+        last_day,
+        last_week,
+        by_type,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{
+        event, node, ActiveValue, DatabaseConnection, Duration, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        let now = OffsetDateTime::now_utc();
+
+        for (event_type, body, age) in [
+            (
+                event::EventType::Instantiation,
+                event::EventBody::Instantiation,
+                Duration::days(30),
+            ),
+            (
+                event::EventType::CodeHashUpdate,
+                event::EventBody::CodeHashUpdate {
+                    new_code_hash: hex::encode([0; 32]),
+                },
+                Duration::days(3),
+            ),
+            (
+                event::EventType::CodeHashUpdate,
+                event::EventBody::CodeHashUpdate {
+                    new_code_hash: hex::encode([1; 32]),
+                },
+                Duration::hours(1),
+            ),
+        ] {
+            let datetime = now - age;
+
+            event::Entity::insert(event::ActiveModel {
+                node_id: ActiveValue::Set(node.id),
+                account: ActiveValue::Set(vec![1; 32]),
+                event_type: ActiveValue::Set(event_type),
+                body: ActiveValue::Set(serde_json::to_string(&body).unwrap()),
+                block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                    datetime.date(),
+                    datetime.time(),
+                )),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert an event");
+        }
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}/stats", AccountId32::new([1; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.json().await,
+            json!({
+                "total": 3,
+                "last_day": 1,
+                "last_week": 2,
+                "by_type": {
+                    "instantiation": 1,
+                    "code_hash_update": 2,
+                    "termination": 0,
+                    "contract_emission": 0,
+                    "ownership_change": 0,
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/contracts/{}/stats", AccountId32::new([2; 32])))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "total": 0,
+            "last_day": 0,
+            "last_week": 0,
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use aide::transform::TransformOperation;
+use axum::{response::sse::Event, Extension};
+use common::rpc::sp_core::ByteArray;
+use futures_util::{Stream, StreamExt};
+
+use crate::{broadcast::EventBroadcast, sse::EventStream};
+
+use super::{events::ContractEvent, AccountPath, WrappedAccountId32};
+
+/// Generate OAPI documentation for the [`stream`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Stream newly discovered events related to the contract account.")
+        .description(
+            r#"Each server-sent event contains a JSON-serialized contract event,
+using the same format as the contract event list route.
+
+Heartbeat comments are sent every 30 seconds to keep the connection alive."#,
+        )
+}
+
+/// Contract event stream request handler.
+pub(super) async fn stream(
+    AccountPath(account): AccountPath<WrappedAccountId32>,
+    Extension(broadcast): Extension<EventBroadcast>,
+) -> EventStream<impl Stream<Item = Result<Event, serde_json::Error>>> {
+    let events = broadcast
+        .subscribe(account.0.to_raw_vec())
+        .into_stream()
+        .map(|event| Event::default().json_data(ContractEvent::from(event)));
+
+    EventStream::new(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{broadcast::EventBroadcast, testing::create_database};
+
+    use axum::{body::Body, http::Request, Extension};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{event, OffsetDateTime, PrimitiveDateTime};
+    use hyper::body::HttpBody;
+    use tower::ServiceExt;
+
+    fn create_event(id: i64, account: [u8; 32]) -> event::Model {
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        event::Model {
+            id,
+            node_id: 1,
+            account: account.to_vec(),
+            event_type: event::EventType::Instantiation,
+            body: serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            raw_body: None,
+            block_timestamp: PrimitiveDateTime::new(datetime.date(), datetime.time()),
+            block_number: 42,
+            event_index: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn filtered() {
+        let db = create_database().await;
+
+        let broadcast = EventBroadcast::default();
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .layer(Extension(broadcast.clone()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!(
+                        "/contracts/events/{}/stream",
+                        AccountId32::new([1; 32])
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        broadcast.publish(create_event(1, [2; 32]));
+        broadcast.publish(create_event(2, [1; 32]));
+
+        let chunk = response
+            .into_body()
+            .data()
+            .await
+            .expect("stream ended")
+            .expect("unable to read stream");
+
+        assert_eq!(
+            chunk,
+            "data:{\"id\":2,\"event_type\":\"instantiation\",\"body\":\"Instantiation\",\"timestamp\":0,\"block_number\":42}\n\n"
+        );
+    }
+}
@@ -6,14 +6,119 @@ use aide::{
     redoc::Redoc,
 };
 use axum::{Extension, Json};
-use db::DatabaseConnection;
+
+use crate::state::AppState;
+
+/// Path of a generated OpenAPI document.
+const SPEC_PATH: &str = "/openapi.json";
 
 /// Create an [`ApiRouter`] that provides an API server with documentation routes.
-pub(crate) fn routes() -> ApiRouter<Arc<DatabaseConnection>> {
+///
+/// Redoc page is bundled into the API server, thus no external resources are loaded.
+pub(crate) fn routes() -> ApiRouter<AppState> {
     ApiRouter::new()
-        .route("/", Redoc::new("/docs/api.json").axum_route())
-        .route(
-            "/api.json",
-            get(|Extension(oapi): Extension<Arc<OpenApi>>| async move { Json(oapi) }),
-        )
+        .route("/docs", Redoc::new(SPEC_PATH).axum_route())
+        .route(SPEC_PATH, get(spec))
+        // Previously used OpenAPI document path.
+        .route("/docs/api.json", get(spec))
+}
+
+/// Serve the generated OpenAPI document.
+async fn spec(Extension(oapi): Extension<Arc<OpenApi>>) -> Json<Arc<OpenApi>> {
+    Json(oapi)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn security_scheme() {
+        let db = create_database().await;
+
+        let mut config = Config::for_tests();
+        config.server.as_mut().unwrap().openapi_servers =
+            vec![String::from("https://api.example.com")];
+
+        let response = crate::documented_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/openapi.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let spec = response.json().await;
+
+        assert_json!(spec["servers"].clone(), [{ "url": "https://api.example.com" }]);
+        assert_json!(spec["components"]["securitySchemes"]["Authentication token"].clone(), {
+            "type": "http",
+            "scheme": "bearer",
+            "description": "Authentication token issued by the authentication routes.",
+        });
+        assert_json!(spec["paths"]["/keys/nonce"]["post"]["security"].clone(), [
+            { "Authentication token": [] }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn page() {
+        let db = create_database().await;
+
+        let response = crate::documented_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/docs")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/html; charset=utf-8"
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled() {
+        let db = Arc::new(create_database().await);
+
+        let mut config = Config::for_tests();
+        config.server.as_mut().unwrap().docs = false;
+        let config = Arc::new(config);
+
+        for uri in ["/docs", "/openapi.json"] {
+            let response = crate::documented_router(db.clone(), config.clone())
+                .oneshot(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
 }
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+use db::{event, DbErr, EntityTrait};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    handlers::contracts::events::ContractEvent,
+    schema::{example_error, ErrorBody},
+    ss58,
+    state::ReadReplica,
+};
+
+/// Errors that may occur during the event details request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum EventDetailsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested event was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "event not found")]
+    EventNotFound,
+
+    /// Contract address stored inside of a database has an invalid size.
+    #[display(fmt = "invalid contract address size stored in db")]
+    InvalidContractAddressSize,
+}
+
+/// A single contract event, along with its contract account.
+#[derive(Serialize, JsonSchema)]
+pub struct EventDetails {
+    /// Account of a contract that emitted the event.
+    #[schemars(example = "crate::schema::example_account")]
+    pub account: String,
+
+    /// Contract event data.
+    #[serde(flatten)]
+    pub event: ContractEvent,
+}
+
+/// Generate OAPI documentation for the [`details`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get a single contract event.")
+        .description(
+            r#"Events are public, as is the account of a contract that emitted an event,
+which can be used to get other events of the same contract."#,
+        )
+        .response::<200, Json<EventDetails>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No events with the provided identifier were found.")
+                .example(example_error(EventDetailsError::EventNotFound))
+        })
+}
+
+/// Event details request handler.
+pub(super) async fn details(
+    Path(id): Path<i64>,
+    Extension(config): Extension<Arc<Config>>,
+    State(ReadReplica(db)): State<ReadReplica>,
+) -> Result<Json<EventDetails>, EventDetailsError> {
+    let event = event::Entity::find_by_id(id)
+        .one(&*db)
+        .await?
+        .ok_or(EventDetailsError::EventNotFound)?;
+
+    let account = AccountId32::new(
+        event
+            .account
+            .as_slice()
+            .try_into()
+            .map_err(|_| EventDetailsError::InvalidContractAddressSize)?,
+    );
+
+    Ok(Json(EventDetails {
+        account: ss58::encode(&account, &config),
+        event: ContractEvent::from(event),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::{Config, RouteGroup};
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{
+        event, node, token, user, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::{Service, ServiceExt};
+
+    async fn create_test_env(db: &DatabaseConnection) -> i64 {
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(1672531200).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Termination),
+            body: ActiveValue::Set(serde_json::to_string(&event::EventBody::Termination).unwrap()),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(123),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert event")
+        .id
+    }
+
+    #[tokio::test]
+    async fn found() {
+        let db = create_database().await;
+
+        let id = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/events/{id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let account = AccountId32::new([1; 32]).to_string();
+
+        assert_json!(response.json().await, {
+            "id": id,
+            "account": account.as_str(),
+            "event_type": "termination",
+            "body": "Termination",
+            "timestamp": 1672531200,
+            "block_number": 123,
+        });
+    }
+
+    #[tokio::test]
+    async fn not_found() {
+        let db = create_database().await;
+
+        let id = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for uri in [format!("/events/{}", id + 1), String::from("/events/-1")] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+            assert_json!(response.json().await, {
+                "error": {
+                    "code": "event_not_found",
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn protected() {
+        let db = create_database().await;
+
+        let id = create_test_env(&db).await;
+
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(&db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(&db)
+            .await
+            .expect("unable to insert token");
+
+        let mut config = Config::for_tests();
+        config.authentication.protected_routes = vec![RouteGroup::Events];
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(config));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/events/{id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/events/{id}"))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
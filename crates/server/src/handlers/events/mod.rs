@@ -0,0 +1,13 @@
+/// Single event details route.
+mod details;
+
+use aide::axum::{routing::get_with, ApiRouter};
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with event information routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route("/:id", get_with(details::details, details::docs))
+        .with_path_items(|op| op.tag("Contract management"))
+}
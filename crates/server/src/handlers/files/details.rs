@@ -6,14 +6,13 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{file, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect};
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-use crate::schema::example_error;
+use crate::schema::{example_error, ErrorBody};
 
 /// Max count of files that can be fetched from the database.
 const MAX_FILES: u64 = 1000;
@@ -68,7 +67,7 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 or a list of files contained within a provided source code archive."#,
         )
         .response::<200, Json<DetailsResponse>>()
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("File not found.")
                 .example(example_error(DetailsError::FileNotFound))
         })
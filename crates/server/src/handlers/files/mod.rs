@@ -7,16 +7,15 @@ mod seal;
 /// File upload route
 mod upload;
 
-use std::sync::Arc;
-
 use aide::axum::{
     routing::{get_with, post_with},
     ApiRouter,
 };
-use db::DatabaseConnection;
+
+use crate::state::AppState;
 
 /// Create an [`ApiRouter`] that provides an API server with source code file handling routes.
-pub(crate) fn routes() -> ApiRouter<Arc<DatabaseConnection>> {
+pub(crate) fn routes() -> ApiRouter<AppState> {
     ApiRouter::new()
         .api_route("/seal/:token", post_with(seal::seal, seal::docs))
         .api_route("/upload/:token", post_with(upload::upload, upload::docs))
@@ -2,12 +2,12 @@ use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::extract::{Path, State};
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session_token, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
     TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 
 /// Errors that may occur during the file upload sealing process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
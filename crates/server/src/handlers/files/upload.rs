@@ -6,15 +6,14 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use db::{
     build_session_token, file, sea_query::OnConflict, ActiveValue, ColumnTrait, DatabaseConnection,
     DbErr, EntityTrait, QueryFilter, QuerySelect, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
-use serde_json::Value;
+use error_response::ErrorResponse;
 
-use crate::schema::example_error;
+use crate::schema::{example_error, ErrorBody};
 
 /// Errors that may occur during the file upload process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -42,14 +41,14 @@ pub(super) enum UploadFileError {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Upload new file with the provided build session token.")
         .response::<200, ()>()
-        .response_with::<400, Json<Value>, _>(|op| {
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
             op.description("Incorrect multipart/form-data request.")
         })
-        .response_with::<403, Json<Value>, _>(|op| {
+        .response_with::<403, Json<ErrorBody>, _>(|op| {
             op.description("Invalid build session token was provided.")
                 .example(example_error(UploadFileError::InvalidToken))
         })
-        .response_with::<422, Json<Value>, _>(|op| {
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
             op.description("No file upload was found in the request.")
                 .example(example_error(UploadFileError::NoFileUpload))
         })
@@ -0,0 +1,43 @@
+use aide::transform::TransformOperation;
+
+/// Generate OAPI documentation for the [`live`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Check if the API server is running.")
+        .description("This route does not check availability of any dependencies.")
+        .response::<200, ()>()
+}
+
+/// Liveness probe handler.
+pub(super) async fn live() {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/health")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,23 @@
+/// Liveness probe route.
+mod live;
+
+/// Readiness probe route.
+mod ready;
+
+/// Indexing status overview route.
+mod status;
+
+use aide::axum::{routing::get_with, ApiRouter};
+use axum::Extension;
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with health check routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route("/health", get_with(live::live, live::docs))
+        .api_route("/ready", get_with(ready::ready, ready::docs))
+        .api_route("/status", get_with(status::status, status::docs))
+        .layer(Extension(status::ServerStatus::new()))
+        .with_path_items(|op| op.tag("Health checks"))
+}
@@ -0,0 +1,186 @@
+use std::{sync::Arc, time::Duration};
+
+use aide::transform::TransformOperation;
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::config::Config;
+use db::{node, sea_orm::Statement, ConnectionTrait, DatabaseConnection, EntityTrait, QuerySelect};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::handlers::nodes::status::best_block;
+
+/// Maximum duration of a database availability check.
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct ReadyResponse {
+    /// Whether the database responded to a query in time.
+    database: bool,
+
+    /// Whether at least one registered RPC node is reachable.
+    ///
+    /// [`None`] if RPC nodes are not checked, or the database is unavailable.
+    nodes: Option<bool>,
+}
+
+/// Generate OAPI documentation for the [`ready`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Check if the API server is ready to handle requests.")
+        .description(
+            r#"Database availability is always checked, while RPC node availability
+is checked only if enabled in the server configuration.
+
+The response body describes which dependency is unavailable."#,
+        )
+        .response::<200, Json<ReadyResponse>>()
+        .response_with::<503, Json<ReadyResponse>, _>(|op| {
+            op.description("At least one of dependencies is unavailable.")
+        })
+}
+
+/// Readiness probe handler.
+pub(super) async fn ready(
+    State(db): State<Arc<DatabaseConnection>>,
+    Extension(config): Extension<Arc<Config>>,
+) -> (StatusCode, Json<ReadyResponse>) {
+    let database = database_available(&db).await;
+
+    let check_nodes = matches!(config.server.as_ref(), Some(server) if server.ready_check_nodes);
+
+    let nodes = if database && check_nodes {
+        Some(node_reachable(&db).await)
+    } else {
+        None
+    };
+
+    let status = if database && nodes.unwrap_or(true) {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(ReadyResponse { database, nodes }))
+}
+
+/// Check if the database responds to a query in time.
+async fn database_available(db: &DatabaseConnection) -> bool {
+    let query = db.execute(Statement::from_string(
+        db.get_database_backend(),
+        String::from("SELECT 1"),
+    ));
+
+    match tokio::time::timeout(DATABASE_TIMEOUT, query).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(err)) => {
+            warn!(%err, "database availability check failed");
+            false
+        }
+        Err(_) => {
+            warn!("database availability check timed out");
+            false
+        }
+    }
+}
+
+/// Check if at least one registered RPC node is reachable.
+async fn node_reachable(db: &DatabaseConnection) -> bool {
+    let urls = match node::Entity::find()
+        .select_only()
+        .column(node::Column::Url)
+        .into_tuple::<String>()
+        .all(db)
+        .await
+    {
+        Ok(urls) => urls,
+        Err(err) => {
+            warn!(%err, "unable to fetch registered nodes");
+            return false;
+        }
+    };
+
+    let mut lookups: FuturesUnordered<_> = urls.into_iter().map(best_block).collect();
+
+    while let Some(best_block) = lookups.next().await {
+        if best_block.is_some() {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{node, ActiveValue, EntityTrait};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "database": true,
+            "nodes": null,
+        });
+    }
+
+    #[tokio::test]
+    async fn unreachable_nodes() {
+        let db = create_database().await;
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://127.0.0.1:1")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert node");
+
+        let mut config = Config::for_tests();
+        config.server.as_mut().unwrap().ready_check_nodes = true;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/ready")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_json!(response.json().await, {
+            "database": true,
+            "nodes": false,
+        });
+    }
+}
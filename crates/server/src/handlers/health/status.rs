@@ -0,0 +1,257 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use db::{
+    contract, event, node, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryOrder,
+    QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Duration for which event and contract counts are cached.
+const CACHE_LIFESPAN: Duration = Duration::from_secs(5);
+
+/// Cached event and contract counts.
+#[derive(Copy, Clone)]
+struct Counts {
+    /// Total count of indexed events.
+    events: u64,
+
+    /// Total count of discovered contracts.
+    contracts: u64,
+}
+
+/// Server start time and a count cache, shared between status requests.
+#[derive(Clone)]
+pub(super) struct ServerStatus {
+    /// Time at which the API server was started.
+    started_at: Instant,
+
+    /// Last computed counts, along with the time they were computed at.
+    counts: Arc<Mutex<Option<(Instant, Counts)>>>,
+}
+
+impl ServerStatus {
+    /// Create a new [`ServerStatus`] with the current time used as the server start time.
+    pub(super) fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            counts: Default::default(),
+        }
+    }
+
+    /// Get cached counts, or compute them if the cached ones are outdated.
+    async fn counts(&self, db: &DatabaseConnection) -> Result<Counts, DbErr> {
+        let cached = *self.counts.lock().unwrap();
+
+        if let Some((cached_at, counts)) = cached {
+            if cached_at.elapsed() < CACHE_LIFESPAN {
+                return Ok(counts);
+            }
+        }
+
+        let counts = Counts {
+            events: event::Entity::find().count(db).await?,
+            contracts: contract::Entity::find().count(db).await?,
+        };
+
+        *self.counts.lock().unwrap() = Some((Instant::now(), counts));
+
+        Ok(counts)
+    }
+}
+
+/// Errors that may occur during the server status request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum ServerStatusError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Indexing status of a single node.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct NodeSummary {
+    /// Node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+
+    /// Node name.
+    #[schemars(example = "crate::schema::example_node")]
+    name: String,
+
+    /// Last confirmed block that was discovered by an event client.
+    #[schemars(example = "crate::schema::example_block_number")]
+    confirmed_block: i64,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct ServerStatusResponse {
+    /// Count of registered nodes.
+    node_count: usize,
+
+    /// Registered nodes, ordered by their identifiers.
+    nodes: Vec<NodeSummary>,
+
+    /// Total count of indexed events.
+    #[schemars(example = "crate::schema::example_event_count")]
+    event_count: u64,
+
+    /// Total count of discovered contracts.
+    contract_count: u64,
+
+    /// API server uptime, in seconds.
+    #[schemars(example = "crate::schema::example_uptime")]
+    uptime: u64,
+}
+
+/// Generate OAPI documentation for the [`status`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get an overview of the indexing status.")
+        .description(
+            r#"Event and contract counts are cached for 5 seconds,
+while node information is always up to date."#,
+        )
+        .response::<200, Json<ServerStatusResponse>>()
+}
+
+/// Server status request handler.
+pub(super) async fn status(
+    State(db): State<Arc<DatabaseConnection>>,
+    Extension(status): Extension<ServerStatus>,
+) -> Result<Json<ServerStatusResponse>, ServerStatusError> {
+    let nodes: Vec<_> = node::Entity::find()
+        .select_only()
+        .columns([
+            node::Column::Id,
+            node::Column::Name,
+            node::Column::ConfirmedBlock,
+        ])
+        .order_by_asc(node::Column::Id)
+        .into_tuple::<(i64, String, i64)>()
+        .stream(&*db)
+        .await?
+        .map_ok(|(id, name, confirmed_block)| NodeSummary {
+            id,
+            name,
+            confirmed_block,
+        })
+        .try_collect()
+        .await?;
+
+    let counts = status.counts(&db).await?;
+
+    Ok(Json(ServerStatusResponse {
+        node_count: nodes.len(),
+        nodes,
+        event_count: counts.events,
+        contract_count: counts.contracts,
+        uptime: status.started_at.elapsed().as_secs(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        code, contract, event, node, ActiveValue, EntityTrait, OffsetDateTime, PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_with_returning(&db)
+        .await
+        .expect("unable to insert node");
+
+        code::Entity::insert(code::ActiveModel {
+            hash: ActiveValue::Set(vec![0; 32]),
+            code: ActiveValue::Set(vec![1, 2, 3]),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert code");
+
+        contract::Entity::insert(contract::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            code_hash: ActiveValue::Set(vec![0; 32]),
+            address: ActiveValue::Set(vec![1; 32]),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert contract");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(100).expect("invalid date");
+
+        event::Entity::insert_many((0..3).map(|block_number| event::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Instantiation),
+            body: ActiveValue::Set(
+                serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            ),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(block_number),
+            ..Default::default()
+        }))
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert events");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "node_count": 1,
+            "nodes": [
+                {
+                    "id": node.id,
+                    "name": "test",
+                    "confirmed_block": 42,
+                }
+            ],
+            "event_count": 3,
+            "contract_count": 1,
+            "uptime": validators::u64(|_| Ok(())),
+        });
+    }
+}
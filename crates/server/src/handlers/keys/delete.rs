@@ -1,18 +1,30 @@
 use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
-use axum::{extract::State, Extension, Json};
-use axum_derive_error::ErrorResponse;
-use common::rpc::sp_core::sr25519::Public;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use common::{config::Config, rpc::sp_core::sr25519::Public};
 use db::{
-    public_key, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
-    TransactionErrorExt, TransactionTrait,
+    public_key, sea_query::Expr, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection,
+    DbErr, EntityTrait, QueryFilter,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
 
-use crate::auth::AuthenticatedUserId;
+/// Maximum count of accounts that can be deleted at once.
+const MAX_ACCOUNTS: usize = 50;
 
 /// Errors that may occur during the public key deletion request handling.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -20,45 +32,145 @@ use crate::auth::AuthenticatedUserId;
 pub(super) enum PublicKeyDeletionError {
     /// Database-related error.
     DatabaseError(DbErr),
+
+    /// Neither a single account, nor a list of accounts were provided.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "no accounts provided")]
+    NoAccountsProvided,
+
+    /// Too many accounts were provided.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "too many accounts provided")]
+    TooManyAccounts,
 }
 
 /// JSON request body.
 #[derive(Deserialize, JsonSchema)]
 pub(super) struct PublicKeyDeletionRequest {
     /// Public key that has to be deleted.
-    #[schemars(example = "crate::schema::example_public_key", with = "String")]
-    account: Public,
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_public_key", with = "Option<String>")]
+    account: Option<Public>,
+
+    /// Public keys that have to be deleted in addition to the `account` value.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_public_keys", with = "Vec<String>")]
+    accounts: Vec<Public>,
+}
+
+/// Query string used to configure the public key deletion response.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct PublicKeyDeletionQuery {
+    /// Return the count of deleted public keys.
+    #[serde(default)]
+    report: bool,
+}
+
+/// Public key deletion report.
+#[derive(Serialize, JsonSchema)]
+pub struct PublicKeyDeletionReport {
+    /// Count of deleted public keys.
+    #[schemars(example = "crate::schema::example_deleted_count")]
+    pub deleted: u64,
 }
 
 /// Generate OAPI documentation for the [`delete`] handler.
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
-    op.summary("Delete public key attached to the current user.")
+    op.summary("Delete public keys attached to the current user.")
         .description(
-            r#"This route does not return information
-on whether the provided public key was attached to the current user or not."#,
+            r#"Either a single public key can be provided using the `account` field,
+or up to 50 public keys at once using the `accounts` field.
+
+By default, this route does not return information
+on whether the provided public keys were attached to the current user or not.
+
+If the `report` query string parameter is set to `true`,
+the count of deleted public keys is returned instead."#,
         )
-        .response::<200, ()>()
+        .response_with::<200, Json<PublicKeyDeletionReport>, _>(|op| {
+            op.description(
+                "Empty response, or a deletion report if the `report` parameter was provided.",
+            )
+        })
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
+            op.description("No accounts or too many accounts were provided.")
+                .example(example_error(PublicKeyDeletionError::TooManyAccounts))
+        })
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
+}
+
+/// Delete public keys that match the provided condition.
+///
+/// If soft deletion is enabled, public keys are marked as deleted instead.
+///
+/// Returns the count of deleted public keys.
+pub(super) async fn delete_public_keys<C: ConnectionTrait>(
+    db: &C,
+    config: &Config,
+    condition: Condition,
+) -> Result<u64, DbErr> {
+    let condition = condition.add(public_key::Column::DeletedAt.is_null());
+
+    let deleted = if config.authentication.soft_delete_public_keys {
+        public_key::Entity::update_many()
+            .col_expr(public_key::Column::DeletedAt, Expr::value(db::now()))
+            .col_expr(public_key::Column::UpdatedAt, Expr::value(db::now()))
+            .col_expr(
+                public_key::Column::Version,
+                Expr::col(public_key::Column::Version).add(1),
+            )
+            .filter(condition)
+            .exec(db)
+            .await?
+            .rows_affected
+    } else {
+        public_key::Entity::delete_many()
+            .filter(condition)
+            .exec(db)
+            .await?
+            .rows_affected
+    };
+
+    Ok(deleted)
 }
 
-/// Delete public key attached to the current authenticated user's account.
+/// Delete public keys attached to the current authenticated user's account.
 pub(super) async fn delete(
     Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
-    Json(request): Json<PublicKeyDeletionRequest>,
-) -> Result<(), PublicKeyDeletionError> {
-    db.transaction(|txn| {
-        Box::pin(async move {
-            public_key::Entity::delete_many()
-                .filter(public_key::Column::UserId.eq(current_user.id()))
-                .filter(public_key::Column::Address.eq(&request.account.0[..]))
-                .exec(txn)
-                .await?;
-
-            Ok(())
-        })
-    })
-    .await
-    .into_raw_result()
+    Query(query): Query<PublicKeyDeletionQuery>,
+    JsonBody(request): JsonBody<PublicKeyDeletionRequest>,
+) -> Result<Response, PublicKeyDeletionError> {
+    let accounts: Vec<_> = request
+        .account
+        .into_iter()
+        .chain(request.accounts)
+        .collect();
+
+    if accounts.is_empty() {
+        return Err(PublicKeyDeletionError::NoAccountsProvided);
+    }
+
+    if accounts.len() > MAX_ACCOUNTS {
+        return Err(PublicKeyDeletionError::TooManyAccounts);
+    }
+
+    // A single statement is used, thus either all of the provided keys are deleted, or none.
+    let deleted = delete_public_keys(
+        &*db,
+        &config,
+        Condition::all()
+            .add(public_key::Column::UserId.eq(current_user.id()))
+            .add(public_key::Column::Address.is_in(accounts.iter().map(|account| &account.0[..]))),
+    )
+    .await?;
+
+    if query.report {
+        Ok(Json(PublicKeyDeletionReport { deleted }).into_response())
+    } else {
+        Ok(().into_response())
+    }
 }
 
 #[cfg(test)]
@@ -74,7 +186,9 @@ mod tests {
     };
     use common::config::Config;
     use common::rpc::sp_core::crypto::{AccountId32, Ss58Codec};
-    use db::{public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use db::{
+        public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait, PaginatorTrait,
+    };
     use serde_json::json;
     use tower::Service;
 
@@ -86,7 +200,7 @@ mod tests {
             .await
             .expect("unable to create user");
 
-        let (model, token) = token::generate_token(user.id);
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
 
         token::Entity::insert(model)
             .exec_without_returning(db)
@@ -108,13 +222,8 @@ mod tests {
         token
     }
 
-    #[tokio::test]
-    async fn list_and_delete() {
-        let db = create_database().await;
-
-        let token = create_test_env(&db).await;
-
-        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+    async fn delete_and_list(db: Arc<DatabaseConnection>, config: Config, token: &str) {
+        let mut service = crate::app_router(db, Arc::new(config));
 
         let response = service
             .call(
@@ -166,4 +275,224 @@ mod tests {
 
         assert_json!(response.json().await, []);
     }
+
+    #[tokio::test]
+    async fn list_and_delete() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+
+        delete_and_list(db.clone(), Config::for_tests(), &token).await;
+
+        assert_eq!(public_key::Entity::find().count(&*db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn soft_delete() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+
+        let mut config = Config::for_tests();
+        config.authentication.soft_delete_public_keys = true;
+
+        delete_and_list(db.clone(), config, &token).await;
+
+        let model = public_key::Entity::find()
+            .one(&*db)
+            .await
+            .unwrap()
+            .expect("soft-deleted public key is missing");
+
+        assert!(model.deleted_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn report() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/keys?report=true")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": ACCOUNT_ID,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, { "deleted": 1 });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/keys?report=true")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": ACCOUNT_ID,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, { "deleted": 0 });
+    }
+
+    #[tokio::test]
+    async fn multiple_accounts() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let user_id = public_key::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .expect("public key is missing")
+            .user_id;
+
+        public_key::Entity::insert_many((1..=3).map(|i| public_key::ActiveModel {
+            user_id: ActiveValue::Set(user_id),
+            address: ActiveValue::Set(vec![i; 32]),
+            ..Default::default()
+        }))
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to create public keys");
+
+        let db = Arc::new(db);
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/keys?report=true")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "accounts": [
+                            AccountId32::new([1; 32]).to_string(),
+                            AccountId32::new([2; 32]).to_string(),
+                            AccountId32::new([3; 32]).to_string(),
+                        ],
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, { "deleted": 3 });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "address": ACCOUNT_ID
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn invalid_account_count() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (body, code) in [
+            (json!({}), "no_accounts_provided"),
+            (
+                json!({ "accounts": vec![ACCOUNT_ID; 51] }),
+                "too_many_accounts",
+            ),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("DELETE")
+                        .uri("/keys")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .header("Content-Type", "application/json")
+                        .body(Body::from_json(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            assert_json!(response.json().await, {
+                "error": {
+                    "code": code,
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_body() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (body, field) in [
+            (json!({ "account": "invalid" }), "account"),
+            (json!({ "accounts": [ACCOUNT_ID, 1] }), "accounts[1]"),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("DELETE")
+                        .uri("/keys")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .header("Content-Type", "application/json")
+                        .body(Body::from_json(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+            let body = response.json().await;
+            assert_json!(body.clone(), {
+                "error": {
+                    "code": "invalid_request_body",
+                }
+            });
+            assert_eq!(body["errors"][0]["field"], field);
+            assert!(body["errors"][0]["message"].is_string());
+        }
+    }
 }
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use common::config::Config;
+use db::{
+    public_key, ColumnTrait, Condition, DatabaseConnection, DbErr, RetryTransactionExt,
+    TransactionErrorExt, TRANSACTION_RETRIES,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::auth::AuthenticatedUserId;
+
+use super::delete::{delete_public_keys, PublicKeyDeletionReport};
+
+/// Errors that may occur during the deletion of all public keys.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum PublicKeyDeleteAllError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Generate OAPI documentation for the [`delete_all`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete all public keys attached to the current user.")
+        .response_with::<200, Json<PublicKeyDeletionReport>, _>(|op| {
+            op.description("Count of deleted public keys.")
+        })
+}
+
+/// Delete all public keys attached to the current authenticated user's account.
+pub(super) async fn delete_all(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<PublicKeyDeletionReport>, PublicKeyDeleteAllError> {
+    db.retry_transaction(TRANSACTION_RETRIES, |txn| {
+        let config = config.clone();
+
+        Box::pin(async move {
+            let deleted = delete_public_keys(
+                txn,
+                &config,
+                Condition::all().add(public_key::Column::UserId.eq(current_user.id())),
+            )
+            .await?;
+
+            Ok(Json(PublicKeyDeletionReport { deleted }))
+        })
+    })
+    .await
+    .into_raw_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use db::{
+        public_key, token, user, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait,
+        PaginatorTrait, QueryFilter,
+    };
+    use tower::ServiceExt;
+
+    async fn create_user(db: &DatabaseConnection, keys: &[[u8; 32]]) -> (i64, String) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        for key in keys {
+            public_key::Entity::insert(public_key::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                address: ActiveValue::Set(key.to_vec()),
+                ..Default::default()
+            })
+            .exec_without_returning(db)
+            .await
+            .expect("unable to create public key");
+        }
+
+        (user.id, token)
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let (user_id, token) = create_user(&db, &[[1; 32], [2; 32]]).await;
+        let (other_user_id, _) = create_user(&db, &[[3; 32]]).await;
+
+        let db = Arc::new(db);
+
+        let response = crate::app_router(db.clone(), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/keys/all")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, { "deleted": 2 });
+
+        let count_keys = |user_id| {
+            public_key::Entity::find()
+                .filter(public_key::Column::UserId.eq(user_id))
+                .count(&*db)
+        };
+
+        assert_eq!(count_keys(user_id).await.unwrap(), 0);
+        assert_eq!(count_keys(other_user_id).await.unwrap(), 1);
+    }
+}
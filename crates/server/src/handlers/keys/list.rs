@@ -2,20 +2,25 @@ use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{
-    extract::{Query, State},
+    extract::{OriginalUri, Query, State},
     Extension, Json,
 };
-use axum_derive_error::ErrorResponse;
-use common::rpc::sp_core::crypto::AccountId32;
+use common::{config::Config, rpc::sp_core::crypto::AccountId32};
 use db::{
-    public_key, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    public_key, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+    PrimitiveDateTime, QueryFilter, QueryOrder, QuerySelect,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use futures_util::TryStreamExt;
 use schemars::JsonSchema;
 use serde::Serialize;
 
-use crate::{auth::AuthenticatedUserId, pagination::Pagination};
+use crate::{
+    auth::AuthenticatedUserId,
+    pagination::{OffsetPagination, Paginated},
+    ss58,
+};
 
 /// A single public key data.
 #[derive(Serialize, JsonSchema)]
@@ -25,8 +30,24 @@ pub struct PublicKeyData {
     pub id: i64,
 
     /// Account address.
-    #[schemars(example = "crate::schema::example_account", with = "String")]
-    pub address: AccountId32,
+    #[schemars(example = "crate::schema::example_account")]
+    pub address: String,
+
+    /// User-provided public key name.
+    #[schemars(example = "crate::schema::example_public_key_name")]
+    pub name: Option<String>,
+
+    /// Public key verification timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub created_at: i64,
+
+    /// Last public key update timestamp.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    pub updated_at: i64,
+
+    /// Public key version, which changes on each update.
+    #[schemars(example = "crate::schema::example_public_key_version")]
+    pub version: i64,
 }
 
 /// Errors that may occur during the public key list request handling.
@@ -44,36 +65,265 @@ pub(super) enum PublicKeyListError {
 /// Generate OAPI documentation for the [`list`] handler.
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("List public keys attached to the current user.")
-        .response_with::<200, Json<Vec<PublicKeyData>>, _>(|op| op.description("Public key list."))
+        .description("Public keys are ordered by their identifiers in an ascending order.")
+        .response_with::<200, Paginated<Json<Vec<PublicKeyData>>>, _>(|op| {
+            op.description("Public key list.")
+        })
 }
 
 /// List public keys attached to the current authenticated user's account.
+///
+/// Public key versions are used for optimistic locking of subsequent updates,
+/// thus public keys are read from the primary database to avoid replication delays.
 pub(super) async fn list(
     Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
-    Query(pagination): Query<Pagination>,
-) -> Result<Json<Vec<PublicKeyData>>, PublicKeyListError> {
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<PublicKeyData>>>, PublicKeyListError> {
+    let config = &config;
+
+    let total = public_key::Entity::find()
+        .filter(public_key::Column::UserId.eq(current_user.id()))
+        .filter(public_key::Column::DeletedAt.is_null())
+        .count(&*db)
+        .await?;
+
     public_key::Entity::find()
         .select_only()
-        .columns([public_key::Column::Id, public_key::Column::Address])
+        .columns([
+            public_key::Column::Id,
+            public_key::Column::Address,
+            public_key::Column::Name,
+            public_key::Column::CreatedAt,
+            public_key::Column::UpdatedAt,
+            public_key::Column::Version,
+        ])
         .filter(public_key::Column::UserId.eq(current_user.id()))
-        .limit(pagination.limit())
+        .filter(public_key::Column::DeletedAt.is_null())
+        .order_by_asc(public_key::Column::Id)
+        .limit(pagination.limit(&config.pagination))
         .offset(pagination.offset())
-        .into_tuple::<(i64, Vec<u8>)>()
+        .into_tuple::<(
+            i64,
+            Vec<u8>,
+            Option<String>,
+            PrimitiveDateTime,
+            PrimitiveDateTime,
+            i64,
+        )>()
         .stream(&*db)
         .await?
         .err_into()
-        .and_then(|(id, address)| async move {
-            Ok(PublicKeyData {
-                id,
-                address: AccountId32::new(
+        .and_then(
+            |(id, address, name, created_at, updated_at, version)| async move {
+                let address = AccountId32::new(
                     address
                         .try_into()
                         .map_err(|_| PublicKeyListError::InvalidPublicKeySize)?,
-                ),
-            })
-        })
+                );
+
+                Ok(PublicKeyData {
+                    id,
+                    address: ss58::encode(&address, config),
+                    name,
+                    created_at: created_at.assume_utc().unix_timestamp(),
+                    updated_at: updated_at.assume_utc().unix_timestamp(),
+                    version,
+                })
+            },
+        )
         .try_collect()
         .await
-        .map(Json)
+        .map(|items| Paginated::offset(Json(items), &uri, &pagination, &config.pagination, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        state::AppState,
+        testing::{create_database, ResponseBodyExt},
+    };
+
+    use axum::{
+        body::Body,
+        http::{header, Request},
+    };
+    use common::config::Config;
+    use db::{public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        public_key::Entity::insert_many((0..30).map(|i| public_key::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            address: ActiveValue::Set(vec![i; 32]),
+            ..Default::default()
+        }))
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create public keys");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn pagination() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (uri, expected_ids) in [
+            ("/keys", (1..=25).collect::<Vec<_>>()),
+            ("/keys?offset=25", (26..=30).collect()),
+            ("/keys?limit=10&offset=5", (6..=15).collect()),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            let ids: Vec<_> = response
+                .json()
+                .await
+                .as_array()
+                .expect("array expected")
+                .iter()
+                .map(|key| key["id"].as_i64().expect("id expected"))
+                .collect();
+
+            assert_eq!(ids, expected_ids);
+        }
+    }
+
+    #[tokio::test]
+    async fn pagination_headers() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (uri, expected_link) in [
+            ("/keys", Some(r#"</keys?offset=25>; rel="next""#)),
+            (
+                "/keys?limit=10&offset=5",
+                Some(
+                    r#"</keys?limit=10&offset=15>; rel="next", </keys?limit=10&offset=0>; rel="prev""#,
+                ),
+            ),
+            ("/keys?offset=25", Some(r#"</keys?offset=0>; rel="prev""#)),
+            ("/keys?limit=30", None),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.headers()["X-Total-Count"], "30");
+            assert_eq!(
+                response
+                    .headers()
+                    .get(header::LINK)
+                    .map(|value| value.to_str().unwrap()),
+                expected_link
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn clamped_limit() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.pagination.default_page_size = 5.try_into().unwrap();
+        config.pagination.max_page_size = 10.try_into().unwrap();
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(config));
+
+        for (uri, expected_count) in [("/keys", 5), ("/keys?limit=1000", 10)] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("GET")
+                        .uri(uri)
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                response
+                    .json()
+                    .await
+                    .as_array()
+                    .expect("array expected")
+                    .len(),
+                expected_count
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn primary_database() {
+        let db = Arc::new(create_database().await);
+
+        let token = create_test_env(&db).await;
+
+        // Read replica that didn't receive any writes yet.
+        let read_replica = Arc::new(create_database().await);
+
+        let mut service = crate::app_router(
+            AppState::new(db, Some(read_replica)),
+            Arc::new(Config::for_tests()),
+        );
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["X-Total-Count"], "30");
+    }
 }
@@ -1,25 +1,54 @@
 /// Public key deletion route.
 mod delete;
 
+/// Route for deletion of all public keys.
+mod delete_all;
+
 /// Public key list route.
 mod list;
 
+/// Public key rename route.
+mod rename;
+
+/// Public key verification nonce route.
+mod nonce;
+
+/// Public key transfer route.
+mod transfer;
+
 /// Public key verification route.
 mod verify;
 
-use std::sync::Arc;
+use aide::axum::{
+    routing::{delete_with, post_with},
+    ApiRouter,
+};
+use axum::middleware::from_fn_with_state;
+
+use crate::rate_limit::{self, RateLimiter};
 
-use aide::axum::{routing::get_with, ApiRouter};
-use db::DatabaseConnection;
+use crate::state::AppState;
 
 /// Create an [`ApiRouter`] that provides an API server with public key management routes.
-pub(crate) fn routes() -> ApiRouter<Arc<DatabaseConnection>> {
+pub(crate) fn routes(rate_limiter: RateLimiter) -> ApiRouter<AppState> {
     ApiRouter::new()
         .api_route(
             "/",
-            get_with(list::list, list::docs)
+            delete_with(delete::delete, delete::docs)
+                .layer(from_fn_with_state(rate_limiter.clone(), rate_limit::limit))
+                .get_with(list::list, list::docs)
                 .post_with(verify::verify, verify::docs)
-                .delete_with(delete::delete, delete::docs),
+                .patch_with(rename::rename, rename::docs),
+        )
+        .api_route("/nonce", post_with(nonce::nonce, nonce::docs))
+        .api_route(
+            "/transfer",
+            post_with(transfer::transfer, transfer::docs)
+                .layer(from_fn_with_state(rate_limiter, rate_limit::limit)),
+        )
+        .api_route(
+            "/all",
+            delete_with(delete_all::delete_all, delete_all::docs),
         )
         .with_path_items(|op| op.tag("Public key verification"))
 }
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, Extension, Json};
+use db::{public_key_nonce, DatabaseConnection, DbErr, EntityTrait};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::auth::AuthenticatedUserId;
+
+/// Errors that may occur during the nonce generation process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum PublicKeyNonceError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Nonce generation response.
+#[derive(Serialize, JsonSchema)]
+pub struct PublicKeyNonceResponse {
+    /// Nonce value that has to be signed to verify a public key.
+    #[schemars(example = "crate::schema::example_nonce")]
+    nonce: String,
+}
+
+/// Generate OAPI documentation for the [`nonce`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Generate a new nonce for public key verification.")
+        .description(
+            r#"Generated nonce can be used only once
+and expires after 10 minutes since its creation."#,
+        )
+        .response::<200, Json<PublicKeyNonceResponse>>()
+}
+
+/// Generate a new public key verification nonce for the current authenticated user.
+pub(super) async fn nonce(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<PublicKeyNonceResponse>, PublicKeyNonceError> {
+    let (model, nonce) = public_key_nonce::generate_nonce(current_user.id());
+
+    public_key_nonce::Entity::insert(model)
+        .exec_without_returning(&*db)
+        .await?;
+
+    Ok(Json(PublicKeyNonceResponse { nonce }))
+}
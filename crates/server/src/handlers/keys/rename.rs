@@ -0,0 +1,392 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::rpc::sp_core::sr25519::Public;
+use db::{
+    public_key, sea_query::Expr, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QuerySelect, SelectExt,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
+
+/// Errors that may occur during the public key rename request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum PublicKeyRenameError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided public key is not attached to the current user.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "public key not found")]
+    PublicKeyNotFound,
+
+    /// The provided public key was updated after the last-known version.
+    #[status(StatusCode::CONFLICT)]
+    #[display(fmt = "public key was modified concurrently")]
+    PublicKeyModified,
+}
+
+/// JSON request body.
+#[derive(Deserialize, Validate, JsonSchema)]
+pub(super) struct PublicKeyRenameRequest {
+    /// Public key that has to be renamed.
+    #[schemars(example = "crate::schema::example_public_key", with = "String")]
+    account: Public,
+
+    /// New public key name.
+    ///
+    /// Passing `null` removes the current name.
+    #[validate(length(max = "db::public_key::NAME_MAX_LENGTH"))]
+    #[schemars(example = "crate::schema::example_public_key_name")]
+    name: Option<String>,
+
+    /// Last-known public key version, as returned by the public key list route.
+    ///
+    /// If provided, public key is renamed only if it was not updated since then.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_public_key_version")]
+    version: Option<i64>,
+}
+
+/// Generate OAPI documentation for the [`rename`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Rename public key attached to the current user.")
+        .description(
+            r#"Pass the `version` value of a public key to prevent overwriting
+concurrent changes, in which case a `409 Conflict` response is returned
+if the public key was updated since then."#,
+        )
+        .response::<200, ()>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided public key is not attached to the current user.")
+                .example(example_error(PublicKeyRenameError::PublicKeyNotFound))
+        })
+        .response_with::<409, Json<ErrorBody>, _>(|op| {
+            op.description("The provided public key was updated concurrently.")
+                .example(example_error(PublicKeyRenameError::PublicKeyModified))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("Public key name is too long.")
+        })
+}
+
+/// Rename public key attached to the current authenticated user's account.
+pub(super) async fn rename(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    State(db): State<Arc<DatabaseConnection>>,
+    ValidatedJson(request): ValidatedJson<PublicKeyRenameRequest>,
+) -> Result<(), PublicKeyRenameError> {
+    let mut update = public_key::Entity::update_many()
+        .col_expr(public_key::Column::Name, Expr::value(request.name))
+        .col_expr(public_key::Column::UpdatedAt, Expr::value(db::now()))
+        .col_expr(
+            public_key::Column::Version,
+            Expr::col(public_key::Column::Version).add(1),
+        )
+        .filter(public_key::Column::UserId.eq(current_user.id()))
+        .filter(public_key::Column::Address.eq(&request.account.0[..]))
+        .filter(public_key::Column::DeletedAt.is_null());
+
+    if let Some(version) = request.version {
+        update = update.filter(public_key::Column::Version.eq(version));
+    }
+
+    let result = update.exec(&*db).await?;
+
+    if result.rows_affected > 0 {
+        return Ok(());
+    }
+
+    let exists = public_key::Entity::find()
+        .select_only()
+        .filter(public_key::Column::UserId.eq(current_user.id()))
+        .filter(public_key::Column::Address.eq(&request.account.0[..]))
+        .filter(public_key::Column::DeletedAt.is_null())
+        .exists(&*db)
+        .await?;
+
+    if exists && request.version.is_some() {
+        Err(PublicKeyRenameError::PublicKeyModified)
+    } else {
+        Err(PublicKeyRenameError::PublicKeyNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::{AccountId32, Ss58Codec};
+    use db::{
+        public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use serde_json::json;
+    use tower::Service;
+
+    const ACCOUNT_ID: &str = "5FeLhJAs4CUHqpWmPDBLeL7NLAoHsB2ZuFZ5Mk62EgYemtFj";
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        let account = AccountId32::from_ss58check(ACCOUNT_ID).unwrap();
+        let account_buf: &[u8] = account.as_ref();
+        let epoch = PrimitiveDateTime::new(
+            OffsetDateTime::UNIX_EPOCH.date(),
+            OffsetDateTime::UNIX_EPOCH.time(),
+        );
+
+        public_key::Entity::insert(public_key::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            address: ActiveValue::Set(account_buf.to_vec()),
+            created_at: ActiveValue::Set(epoch),
+            updated_at: ActiveValue::Set(epoch),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create public key");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": ACCOUNT_ID,
+                        "name": "Hardware wallet",
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "address": ACCOUNT_ID,
+                "name": "Hardware wallet",
+                "created_at": 0,
+                "updated_at": validators::i64(|&timestamp| {
+                    if timestamp > 0 {
+                        Ok(())
+                    } else {
+                        Err(String::from("update timestamp was not changed"))
+                    }
+                }),
+                "version": 1,
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn stale_update() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for (name, expected_status) in [
+            ("Hardware wallet", StatusCode::OK),
+            ("Browser extension", StatusCode::CONFLICT),
+        ] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("PATCH")
+                        .uri("/keys")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .header("Content-Type", "application/json")
+                        .body(Body::from_json(json!({
+                            "account": ACCOUNT_ID,
+                            "name": name,
+                            "version": 0,
+                        })))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), expected_status);
+        }
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "name": "Hardware wallet",
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn same_second_updates() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let mut rename = |name: &str, version: Option<i64>| {
+            service.call(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": ACCOUNT_ID,
+                        "name": name,
+                        "version": version,
+                    })))
+                    .unwrap(),
+            )
+        };
+
+        // Both updates happen within the same second, thus only versions can tell them apart.
+        let response = rename("Hardware wallet", None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = rename("Browser extension", Some(1)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = rename("Mobile wallet", Some(1)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "name": "Browser extension",
+                "version": 2,
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn too_long() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .call(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": ACCOUNT_ID,
+                        "name": "a".repeat(65),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .call(
+                Request::builder()
+                    .method("PATCH")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": AccountId32::new([1; 32]).to_ss58check(),
+                        "name": "Hardware wallet",
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
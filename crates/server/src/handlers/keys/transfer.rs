@@ -0,0 +1,344 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::rpc::sp_core::{
+    sr25519::{Pair, Public, Signature},
+    Pair as _,
+};
+use db::{
+    public_key, public_key_nonce, sea_query::Expr, ActiveValue, ColumnTrait, DatabaseConnection,
+    DbErr, EntityTrait, QueryFilter, QuerySelect, RetryTransactionExt, TransactionErrorExt,
+    TRANSACTION_RETRIES,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
+
+/// Errors that may occur during the public key transfer process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum PublicKeyTransferError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided public key is not attached to any user.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "public key not found")]
+    PublicKeyNotFound,
+
+    /// The provided public key is already attached to the current user.
+    #[status(StatusCode::CONFLICT)]
+    #[display(fmt = "public key is already attached to the current user")]
+    AlreadyAttached,
+
+    /// User provided an unknown, already used or expired nonce.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "invalid nonce")]
+    InvalidNonce,
+
+    /// User provided an invalid signature.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "invalid signature")]
+    InvalidSignature,
+}
+
+/// JSON request body.
+#[derive(Deserialize, JsonSchema)]
+pub(super) struct PublicKeyTransferRequest {
+    /// Public key text value.
+    #[schemars(example = "crate::schema::example_public_key", with = "String")]
+    account: Public,
+
+    /// Signed verification message.
+    ///
+    /// Verification message consists of
+    /// a nonce value issued by an API server.
+    ///
+    /// Example: `<Bytes>x4VpkDX2rzYfMhdrI5HH8wg0knBZkHT1</Bytes>`
+    #[schemars(example = "crate::schema::example_signature", with = "String")]
+    signature: Signature,
+
+    /// Nonce value issued by an API server.
+    #[schemars(example = "crate::schema::example_nonce")]
+    token: String,
+}
+
+/// Generate OAPI documentation for the [`transfer`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Transfer a public key attached to another user to the current user.")
+        .description(
+            r#"Ownership of the public key has to be verified again
+with a nonce issued by the `/keys/nonce` route, the same way
+as during the regular public key verification.
+
+Be aware, that the previous user may be left without any public keys."#,
+        )
+        .response::<200, ()>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided public key is not attached to any user.")
+                .example(example_error(PublicKeyTransferError::PublicKeyNotFound))
+        })
+        .response_with::<409, Json<ErrorBody>, _>(|op| {
+            op.description("The provided public key is already attached to the current user.")
+                .example(example_error(PublicKeyTransferError::AlreadyAttached))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("An invalid nonce or signature was provided.")
+                .example(example_error(PublicKeyTransferError::InvalidSignature))
+        })
+        .response_with::<429, Json<ErrorBody>, _>(|op| op.description("Too many requests."))
+}
+
+/// Transfer a public key attached to another user to the current authenticated user.
+///
+/// For more information on the format used for verification
+/// signature see [`PublicKeyTransferRequest`].
+pub(super) async fn transfer(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    State(db): State<Arc<DatabaseConnection>>,
+    JsonBody(request): JsonBody<PublicKeyTransferRequest>,
+) -> Result<(), PublicKeyTransferError> {
+    let nonce = public_key_nonce::Entity::find_by_id(&request.token)
+        .filter(public_key_nonce::Column::UserId.eq(current_user.id()))
+        .one(&*db)
+        .await?
+        .ok_or(PublicKeyTransferError::InvalidNonce)?;
+
+    // Consume the nonce before any further checks to prevent replay attempts.
+    let deleted = public_key_nonce::Entity::delete_by_id(&nonce.nonce)
+        .exec(&*db)
+        .await?;
+
+    if deleted.rows_affected == 0 || nonce.is_expired() {
+        return Err(PublicKeyTransferError::InvalidNonce);
+    }
+
+    if !Pair::verify(
+        &request.signature,
+        format!("<Bytes>{}</Bytes>", &nonce.nonce),
+        &request.account,
+    ) {
+        return Err(PublicKeyTransferError::InvalidSignature);
+    }
+
+    db.retry_transaction(TRANSACTION_RETRIES, |txn| {
+        Box::pin(async move {
+            let (id, user_id): (i64, i64) = public_key::Entity::find()
+                .select_only()
+                .columns([public_key::Column::Id, public_key::Column::UserId])
+                .filter(public_key::Column::Address.eq(&request.account.0[..]))
+                .filter(public_key::Column::DeletedAt.is_null())
+                .into_tuple()
+                .one(txn)
+                .await?
+                .ok_or(PublicKeyTransferError::PublicKeyNotFound)?;
+
+            if user_id == current_user.id() {
+                return Err(PublicKeyTransferError::AlreadyAttached);
+            }
+
+            // Previous owner is matched as well to detect concurrent transfers.
+            let result = public_key::Entity::update_many()
+                .set(public_key::ActiveModel {
+                    user_id: ActiveValue::Set(current_user.id()),
+                    updated_at: ActiveValue::Set(db::now()),
+                    ..Default::default()
+                })
+                .col_expr(
+                    public_key::Column::Version,
+                    Expr::col(public_key::Column::Version).add(1),
+                )
+                .filter(public_key::Column::Id.eq(id))
+                .filter(public_key::Column::UserId.eq(user_id))
+                .filter(public_key::Column::DeletedAt.is_null())
+                .exec(txn)
+                .await?;
+
+            if result.rows_affected == 0 {
+                return Err(PublicKeyTransferError::PublicKeyNotFound);
+            }
+
+            Ok(())
+        })
+    })
+    .await
+    .into_raw_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use aide::axum::ApiRouter;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use common::rpc::sp_core::{crypto::Ss58Codec, sr25519::Pair, Pair as _};
+    use db::{public_key, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use serde_json::json;
+    use tower::Service;
+
+    async fn create_user(db: &DatabaseConnection) -> (i64, String) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        (user.id, token)
+    }
+
+    async fn request_nonce(service: &mut ApiRouter, token: &str) -> String {
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/keys/nonce")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        response.json().await["nonce"]
+            .as_str()
+            .expect("nonce is missing")
+            .to_string()
+    }
+
+    async fn transfer(
+        service: &mut ApiRouter,
+        token: &str,
+        pair: &Pair,
+        message: &str,
+        nonce: &str,
+    ) -> StatusCode {
+        let signature = pair.sign(format!("<Bytes>{message}</Bytes>").as_bytes());
+
+        service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/keys/transfer")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": pair.public().to_ss58check(),
+                        "signature": format!("0x{}", hex::encode(signature)),
+                        "token": nonce,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    async fn create_test_env(db: &DatabaseConnection, pair: &Pair) -> (i64, String) {
+        let (old_user, _) = create_user(db).await;
+
+        public_key::Entity::insert(public_key::ActiveModel {
+            user_id: ActiveValue::Set(old_user),
+            address: ActiveValue::Set(pair.public().0.to_vec()),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create public key");
+
+        create_user(db).await
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = Arc::new(create_database().await);
+
+        let pair = Pair::from_seed(&[1; 32]);
+        let (new_user, token) = create_test_env(&db, &pair).await;
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            transfer(&mut service, &token, &pair, &nonce, &nonce).await,
+            StatusCode::OK
+        );
+
+        let key = public_key::Entity::find()
+            .one(&*db)
+            .await
+            .unwrap()
+            .expect("public key is missing");
+
+        assert_eq!(key.user_id, new_user);
+
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            transfer(&mut service, &token, &pair, &nonce, &nonce).await,
+            StatusCode::CONFLICT
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_signature() {
+        let db = Arc::new(create_database().await);
+
+        let pair = Pair::from_seed(&[1; 32]);
+        let (_, token) = create_test_env(&db, &pair).await;
+
+        let mut service = crate::app_router(db.clone(), Arc::new(Config::for_tests()));
+
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            transfer(&mut service, &token, &pair, "tampered", &nonce).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let key = public_key::Entity::find()
+            .one(&*db)
+            .await
+            .unwrap()
+            .expect("public key is missing");
+
+        assert_eq!(key.user_id, 1);
+    }
+
+    #[tokio::test]
+    async fn unattached() {
+        let db = create_database().await;
+
+        let (_, token) = create_user(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let pair = Pair::from_seed(&[1; 32]);
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            transfer(&mut service, &token, &pair, &nonce, &nonce).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -2,21 +2,26 @@ use std::sync::Arc;
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{extract::State, http::StatusCode, Extension, Json};
-use axum_derive_error::ErrorResponse;
 use common::rpc::sp_core::{
     sr25519::{Pair, Public, Signature},
     Pair as _,
 };
 use db::{
-    public_key, user, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
-    QueryFilter, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
+    public_key, public_key_nonce, user, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, QuerySelect, RetryTransactionExt, SelectExt, TransactionErrorExt,
+    TRANSACTION_RETRIES,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use serde_json::Value;
+use validator::Validate;
 
-use crate::{auth::AuthenticatedUserId, schema::example_error};
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
 
 /// Errors that may occur during the public key verification process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -26,10 +31,15 @@ pub(super) enum PublicKeyVerificationError {
     DatabaseError(DbErr),
 
     /// The provided public key is already in use by this or another account.
-    #[status(StatusCode::FORBIDDEN)]
+    #[status(StatusCode::CONFLICT)]
     #[display(fmt = "account already exists")]
     AccountExists,
 
+    /// User provided an unknown, already used or expired nonce.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "invalid nonce")]
+    InvalidNonce,
+
     /// User provided an invalid signature.
     #[status(StatusCode::UNPROCESSABLE_ENTITY)]
     #[display(fmt = "invalid signature")]
@@ -37,7 +47,7 @@ pub(super) enum PublicKeyVerificationError {
 }
 
 /// JSON request body.
-#[derive(Deserialize, JsonSchema)]
+#[derive(Deserialize, Validate, JsonSchema)]
 pub(super) struct PublicKeyVerificationRequest {
     /// Public key text value.
     #[schemars(example = "crate::schema::example_public_key", with = "String")]
@@ -46,24 +56,33 @@ pub(super) struct PublicKeyVerificationRequest {
     /// Signed verification message.
     ///
     /// Verification message consists of
-    /// a string equal to the account address
-    /// used for verification purposes.
+    /// a nonce value issued by an API server.
     ///
-    /// Example: `<Bytes>5FeLhJAs4CUHqpWmPDBLeL7NLAoHsB2ZuFZ5Mk62EgYemtFj</Bytes>`
+    /// Example: `<Bytes>x4VpkDX2rzYfMhdrI5HH8wg0knBZkHT1</Bytes>`
     #[schemars(example = "crate::schema::example_signature", with = "String")]
     signature: Signature,
+
+    /// Nonce value issued by an API server.
+    #[schemars(example = "crate::schema::example_nonce")]
+    token: String,
+
+    /// Optional public key name, used to distinguish between multiple public keys.
+    #[serde(default)]
+    #[validate(length(max = "db::public_key::NAME_MAX_LENGTH"))]
+    #[schemars(example = "crate::schema::example_public_key_name")]
+    name: Option<String>,
 }
 
 /// Generate OAPI documentation for the [`docs`] handler.
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Verify a new public key.")
         .response::<200, ()>()
-        .response_with::<403, Json<Value>, _>(|op| {
+        .response_with::<409, Json<ErrorBody>, _>(|op| {
             op.description("The provided public key is already attached.")
                 .example(example_error(PublicKeyVerificationError::AccountExists))
         })
-        .response_with::<422, Json<Value>, _>(|op| {
-            op.description("An invalid signature was provided.")
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("An invalid nonce, signature or public key name was provided.")
                 .example(example_error(PublicKeyVerificationError::InvalidSignature))
         })
 }
@@ -75,46 +94,66 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 pub(super) async fn verify(
     Extension(current_user): Extension<AuthenticatedUserId>,
     State(db): State<Arc<DatabaseConnection>>,
-    Json(request): Json<PublicKeyVerificationRequest>,
+    ValidatedJson(request): ValidatedJson<PublicKeyVerificationRequest>,
 ) -> Result<(), PublicKeyVerificationError> {
-    if Pair::verify(
+    let nonce = public_key_nonce::Entity::find_by_id(&request.token)
+        .filter(public_key_nonce::Column::UserId.eq(current_user.id()))
+        .one(&*db)
+        .await?
+        .ok_or(PublicKeyVerificationError::InvalidNonce)?;
+
+    // Consume the nonce before any further checks to prevent replay attempts.
+    let deleted = public_key_nonce::Entity::delete_by_id(&nonce.nonce)
+        .exec(&*db)
+        .await?;
+
+    if deleted.rows_affected == 0 || nonce.is_expired() {
+        return Err(PublicKeyVerificationError::InvalidNonce);
+    }
+
+    if !Pair::verify(
         &request.signature,
-        format!("<Bytes>{}</Bytes>", &request.account),
+        format!("<Bytes>{}</Bytes>", &nonce.nonce),
         &request.account,
     ) {
-        db.transaction(|txn| {
-            Box::pin(async move {
-                let user_exists = user::Entity::find_by_id(current_user.id())
-                    .select_only()
-                    .exists(txn)
-                    .await?;
-
-                let key_exists = public_key::Entity::find()
-                    .select_only()
-                    .filter(public_key::Column::Address.eq(&request.account.0[..]))
-                    .exists(txn)
-                    .await?;
-
-                if user_exists && !key_exists {
-                    public_key::Entity::insert(public_key::ActiveModel {
-                        user_id: ActiveValue::Set(current_user.id()),
-                        address: ActiveValue::Set(request.account.0.to_vec()),
-                        ..Default::default()
-                    })
-                    .exec_without_returning(txn)
-                    .await?;
-
-                    Ok(())
-                } else {
-                    Err(PublicKeyVerificationError::AccountExists)
-                }
+        return Err(PublicKeyVerificationError::InvalidSignature);
+    }
+
+    db.retry_transaction(TRANSACTION_RETRIES, |txn| {
+        let name = request.name.clone();
+
+        Box::pin(async move {
+            let user_exists = user::Entity::find_by_id(current_user.id())
+                .select_only()
+                .exists(txn)
+                .await?;
+
+            // Soft-deleted public keys are kept, thus their addresses can be verified again.
+            let key_exists = public_key::Entity::find()
+                .select_only()
+                .filter(public_key::Column::Address.eq(&request.account.0[..]))
+                .filter(public_key::Column::DeletedAt.is_null())
+                .exists(txn)
+                .await?;
+
+            if !user_exists || key_exists {
+                return Err(PublicKeyVerificationError::AccountExists);
+            }
+
+            public_key::Entity::insert(public_key::ActiveModel {
+                user_id: ActiveValue::Set(current_user.id()),
+                address: ActiveValue::Set(request.account.0.to_vec()),
+                name: ActiveValue::Set(name),
+                ..Default::default()
             })
+            .exec_without_returning(txn)
+            .await?;
+
+            Ok(())
         })
-        .await
-        .into_raw_result()
-    } else {
-        Err(PublicKeyVerificationError::InvalidSignature)
-    }
+    })
+    .await
+    .into_raw_result()
 }
 
 #[cfg(test)]
@@ -123,25 +162,28 @@ mod tests {
 
     use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
 
+    use aide::axum::ApiRouter;
     use assert_json::assert_json;
     use axum::{
         body::Body,
         http::{Request, StatusCode},
     };
     use common::config::Config;
-    use db::{token, user, DatabaseConnection, EntityTrait};
+    use common::rpc::sp_core::{crypto::Ss58Codec, sr25519::Pair, Pair as _};
+    use db::{
+        public_key, public_key_nonce, token, user, ActiveValue, DatabaseConnection, EntityTrait,
+        PrimitiveDateTime,
+    };
     use serde_json::json;
     use tower::Service;
 
-    const ACCOUNT_ID: &str = "5FeLhJAs4CUHqpWmPDBLeL7NLAoHsB2ZuFZ5Mk62EgYemtFj";
-
     async fn create_test_env(db: &DatabaseConnection) -> String {
         let user = user::Entity::insert(user::ActiveModel::default())
             .exec_with_returning(db)
             .await
             .expect("unable to create user");
 
-        let (model, token) = token::generate_token(user.id);
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
 
         token::Entity::insert(model)
             .exec_without_returning(db)
@@ -151,6 +193,54 @@ mod tests {
         token
     }
 
+    async fn request_nonce(service: &mut ApiRouter, token: &str) -> String {
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/keys/nonce")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        response.json().await["nonce"]
+            .as_str()
+            .expect("nonce is missing")
+            .to_string()
+    }
+
+    async fn verify(
+        service: &mut ApiRouter,
+        token: &str,
+        pair: &Pair,
+        nonce: &str,
+        name: Option<&str>,
+    ) -> StatusCode {
+        let signature = pair.sign(format!("<Bytes>{nonce}</Bytes>").as_bytes());
+
+        service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/keys")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": pair.public().to_ss58check(),
+                        "signature": format!("0x{}", hex::encode(signature)),
+                        "token": nonce,
+                        "name": name,
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
     #[tokio::test]
     async fn list_and_verify() {
         let db = create_database().await;
@@ -173,41 +263,199 @@ mod tests {
 
         assert_json!(response.json().await, []);
 
+        let pair = Pair::from_seed(&[1; 32]);
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            verify(&mut service, &token, &pair, &nonce, Some("Hardware wallet")).await,
+            StatusCode::OK
+        );
+
         let response = service
             .call(
                 Request::builder()
-                    .method("POST")
+                    .method("GET")
                     .uri("/keys")
                     .header("Authorization", format!("Bearer {token}"))
-                    .header("Content-Type", "application/json")
-                    .body(Body::from_json(json!({
-                        "account": ACCOUNT_ID,
-                        "signature": "0x6aa1134d5082aae91dc710cf70d79d2abf6c261cc58eeb13d25ef4dfc8eeed54de76e49f186cde3efd41f6008598ab8d895c78b4354f26e868ead1d8e6410d8a"
-                    })))
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "address": pair.public().to_ss58check(),
+                "name": "Hardware wallet"
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn soft_deleted_key() {
+        let db = Arc::new(create_database().await);
+
+        let token = create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.authentication.soft_delete_public_keys = true;
+
+        let mut service = crate::app_router(db.clone(), Arc::new(config));
+
+        let pair = Pair::from_seed(&[1; 32]);
+
+        for _ in 0..2 {
+            let nonce = request_nonce(&mut service, &token).await;
+
+            assert_eq!(
+                verify(&mut service, &token, &pair, &nonce, None).await,
+                StatusCode::OK
+            );
+
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("DELETE")
+                        .uri("/keys?report=true")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .header("Content-Type", "application/json")
+                        .body(Body::from_json(json!({
+                            "account": pair.public().to_ss58check(),
+                        })))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_json!(response.json().await, { "deleted": 1 });
+        }
+
+        // Deleted public keys are kept as separate rows.
+        let keys = public_key::Entity::find().all(&*db).await.unwrap();
+
+        assert_eq!(keys.len(), 2);
+        assert!(keys.iter().all(|key| key.deleted_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn replayed_nonce() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let nonce = request_nonce(&mut service, &token).await;
+
+        assert_eq!(
+            verify(
+                &mut service,
+                &token,
+                &Pair::from_seed(&[1; 32]),
+                &nonce,
+                None
+            )
+            .await,
+            StatusCode::OK
+        );
+
+        assert_eq!(
+            verify(
+                &mut service,
+                &token,
+                &Pair::from_seed(&[2; 32]),
+                &nonce,
+                None
+            )
+            .await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_nonce() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        public_key_nonce::Entity::insert(public_key_nonce::ActiveModel {
+            nonce: ActiveValue::Set(String::from("expired")),
+            user_id: ActiveValue::Set(1),
+            created_at: ActiveValue::Set(PrimitiveDateTime::MIN),
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert nonce");
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        assert_eq!(
+            verify(
+                &mut service,
+                &token,
+                &Pair::from_seed(&[1; 32]),
+                "expired",
+                None
+            )
+            .await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn mismatched_account() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let nonce = request_nonce(&mut service, &token).await;
+
+        let signature =
+            Pair::from_seed(&[2; 32]).sign(format!("<Bytes>{nonce}</Bytes>").as_bytes());
 
         let response = service
             .call(
                 Request::builder()
-                    .method("GET")
+                    .method("POST")
                     .uri("/keys")
                     .header("Authorization", format!("Bearer {token}"))
-                    .body(Body::empty())
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "account": Pair::from_seed(&[1; 32]).public().to_ss58check(),
+                        "signature": format!("0x{}", hex::encode(signature)),
+                        "token": nonce,
+                    })))
                     .unwrap(),
             )
             .await
             .unwrap();
 
-        assert_json!(response.json().await, [
-            {
-                "id": 1,
-                "address": ACCOUNT_ID
-            }
-        ]);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn already_attached() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let pair = Pair::from_seed(&[1; 32]);
+
+        let nonce = request_nonce(&mut service, &token).await;
+        assert_eq!(
+            verify(&mut service, &token, &pair, &nonce, None).await,
+            StatusCode::OK
+        );
+
+        let nonce = request_nonce(&mut service, &token).await;
+        assert_eq!(
+            verify(&mut service, &token, &pair, &nonce, None).await,
+            StatusCode::CONFLICT
+        );
     }
 }
@@ -1,23 +1,44 @@
+/// Administration routes.
+pub(crate) mod admin;
+
 /// Authentication-related routes.
 pub(crate) mod auth;
 
 /// Build session management routes.
 pub(crate) mod build_sessions;
 
+/// Uploaded code information routes.
+pub(crate) mod codes;
+
 /// Smart contract management routes.
 pub(crate) mod contracts;
 
 /// OAPI documentation.
 pub(crate) mod docs;
 
+/// Single contract event routes.
+pub(crate) mod events;
+
 /// Source code file browsing and uploading routes.
 pub(crate) mod files;
 
+/// Liveness and readiness probe routes.
+pub(crate) mod health;
+
 /// Authentication key management routes.
 pub(crate) mod keys;
 
+/// Node management routes.
+pub(crate) mod nodes;
+
 /// Payment-related routes.
 pub(crate) mod payment;
 
 /// Source code routes.
 pub(crate) mod source_code;
+
+/// Contract event subscription routes.
+pub(crate) mod subscriptions;
+
+/// Contract event webhook management routes.
+pub(crate) mod webhooks;
@@ -0,0 +1,314 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Json};
+use db::{
+    node, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    QuerySelect, SelectExt,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
+
+use super::status::best_block;
+
+/// Errors that may occur during the node registration process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum NodeCreateError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Node with the same URL is already registered.
+    #[status(StatusCode::CONFLICT)]
+    #[display(fmt = "node url already exists")]
+    UrlExists,
+
+    /// Node with the same name is already registered.
+    #[status(StatusCode::CONFLICT)]
+    #[display(fmt = "node name already exists")]
+    NameExists,
+
+    /// Node is unreachable, thus the start block cannot be validated.
+    #[status(StatusCode::BAD_GATEWAY)]
+    #[display(fmt = "node is unreachable")]
+    NodeUnreachable,
+
+    /// The provided start block is ahead of the chain head.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "start block is ahead of the chain head")]
+    StartBlockAhead,
+}
+
+/// JSON request body.
+#[derive(Deserialize, Validate, JsonSchema)]
+pub(super) struct NodeCreateRequest {
+    /// Node name.
+    #[validate(length(min = 1, max = 255))]
+    #[schemars(example = "crate::schema::example_node")]
+    name: String,
+
+    /// RPC node WebSocket URL.
+    #[validate(url, length(max = 255), custom = "validate_node_url")]
+    #[schemars(example = "crate::schema::example_node_url")]
+    url: String,
+
+    /// Block to start discovering events from.
+    ///
+    /// Events that occured before the start block are not archived.
+    #[schemars(example = "crate::schema::example_block_number")]
+    start_block: Option<u32>,
+}
+
+/// Validate the provided node URL to use either `ws` or `wss` scheme.
+fn validate_node_url(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid node url scheme"))
+    }
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct NodeCreateResponse {
+    /// Node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+}
+
+/// Generate OAPI documentation for the [`create`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Register a new node.")
+        .description(
+            r#"Node URL must use either `ws` or `wss` scheme.
+
+If `start_block` is provided, an event client will discover events starting from that block,
+meaning that events which occured before it will not be archived.
+Start block must not be ahead of the current chain head."#,
+        )
+        .response::<200, Json<NodeCreateResponse>>()
+        .response_with::<409, Json<ErrorBody>, _>(|op| {
+            op.description("Node with the same URL or name is already registered.")
+                .example(example_error(NodeCreateError::UrlExists))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description("Invalid node name or URL, or start block is ahead of the chain head.")
+                .example(example_error(NodeCreateError::StartBlockAhead))
+        })
+        .response_with::<502, Json<ErrorBody>, _>(|op| {
+            op.description("Node is unreachable, thus the start block cannot be validated.")
+                .example(example_error(NodeCreateError::NodeUnreachable))
+        })
+}
+
+/// Node registration handler.
+pub(super) async fn create(
+    State(db): State<Arc<DatabaseConnection>>,
+    ValidatedJson(request): ValidatedJson<NodeCreateRequest>,
+) -> Result<Json<NodeCreateResponse>, NodeCreateError> {
+    let url_exists = node::Entity::find()
+        .select_only()
+        .filter(node::Column::Url.eq(&request.url))
+        .exists(&*db)
+        .await?;
+
+    if url_exists {
+        return Err(NodeCreateError::UrlExists);
+    }
+
+    let name_exists = node::Entity::find()
+        .select_only()
+        .filter(node::Column::Name.eq(&request.name))
+        .exists(&*db)
+        .await?;
+
+    if name_exists {
+        return Err(NodeCreateError::NameExists);
+    }
+
+    let confirmed_block = match request.start_block {
+        Some(start_block) => {
+            let best_block = best_block(request.url.clone())
+                .await
+                .ok_or(NodeCreateError::NodeUnreachable)?;
+
+            if u64::from(start_block) > best_block {
+                return Err(NodeCreateError::StartBlockAhead);
+            }
+
+            // Event client discovers events starting from the block following the confirmed one.
+            i64::from(start_block.saturating_sub(1))
+        }
+        None => 0,
+    };
+
+    let model = node::Entity::insert(node::ActiveModel {
+        name: ActiveValue::Set(request.name),
+        url: ActiveValue::Set(request.url),
+        confirmed_block: ActiveValue::Set(confirmed_block),
+        ..Default::default()
+    })
+    .exec_with_returning(&*db)
+    .await?;
+
+    Ok(Json(NodeCreateResponse { id: model.id }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use aide::axum::ApiRouter;
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{token, user, DatabaseConnection, EntityTrait};
+    use serde_json::{json, Value};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    async fn create(service: &mut ApiRouter, token: &str, body: Value) -> (StatusCode, Value) {
+        let response = service
+            .call(
+                Request::builder()
+                    .method("POST")
+                    .uri("/nodes")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        (response.status(), response.json().await)
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let (status, body) = create(
+            &mut service,
+            &token,
+            json!({
+                "name": "alephzero",
+                "url": "wss://ws.test.azero.dev",
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_json!(body, { "id": 1 });
+    }
+
+    #[tokio::test]
+    async fn invalid_url() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for url in ["https://ws.test.azero.dev", "wss://", "not a url"] {
+            let (status, _) = create(
+                &mut service,
+                &token,
+                json!({
+                    "name": "alephzero",
+                    "url": url,
+                }),
+            )
+            .await;
+
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicate_url() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let (status, _) = create(
+            &mut service,
+            &token,
+            json!({
+                "name": "alephzero",
+                "url": "wss://ws.test.azero.dev",
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = create(
+            &mut service,
+            &token,
+            json!({
+                "name": "alephzero-testnet",
+                "url": "wss://ws.test.azero.dev",
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn start_block_unreachable() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let (status, _) = create(
+            &mut service,
+            &token,
+            json!({
+                "name": "local",
+                "url": "ws://127.0.0.1:1",
+                "start_block": 100,
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+    }
+}
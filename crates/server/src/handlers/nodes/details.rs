@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use db::{node, DatabaseConnection, DbErr, EntityTrait};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::schema::{example_error, ErrorBody};
+
+use super::list::NodeData;
+
+/// Errors that may occur during the node details request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum NodeDetailsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested node was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "node not found")]
+    NodeNotFound,
+}
+
+/// Generate OAPI documentation for the [`details`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get node details.")
+        .description(
+            r#"Runtime specification name and version are updated by an event client
+whenever a runtime upgrade is detected, and can be used to pick the correct
+metadata for decoding node events."#,
+        )
+        .response::<200, Json<NodeData>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No nodes with the provided identifier were found.")
+                .example(example_error(NodeDetailsError::NodeNotFound))
+        })
+}
+
+/// Node details request handler.
+pub(super) async fn details(
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<NodeData>, NodeDetailsError> {
+    node::Entity::find_by_id(id)
+        .one(&*db)
+        .await?
+        .map(|node| Json(node.into()))
+        .ok_or(NodeDetailsError::NodeNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{node, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(42),
+            spec_name: ActiveValue::Set(Some(String::from("aleph-node"))),
+            spec_version: ActiveValue::Set(Some(67)),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes/1")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, {
+            "id": 1,
+            "name": "test",
+            "confirmed_block": 42,
+            "spec_name": "aleph-node",
+            "spec_version": 67,
+        });
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes/2")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use db::{
+    event, node, now, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PrimitiveDateTime,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::schema::{example_error, ErrorBody};
+
+/// Errors that may occur during the node indexing status request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum NodeIndexingError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested node was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "node not found")]
+    NodeNotFound,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct NodeIndexingResponse {
+    /// Last confirmed block that was discovered by an event client.
+    #[schemars(example = "crate::schema::example_block_number")]
+    confirmed_block: i64,
+
+    /// Block timestamp of the most recently indexed event.
+    ///
+    /// [`None`] if no events were indexed yet.
+    #[schemars(example = "crate::schema::example_timestamp")]
+    last_event_timestamp: Option<i64>,
+
+    /// Count of seconds elapsed since the most recently indexed event.
+    ///
+    /// [`None`] if no events were indexed yet.
+    lag: Option<i64>,
+}
+
+/// Generate OAPI documentation for the [`indexing`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get node indexing freshness.")
+        .description(
+            r#"Unlike the status route, this route does not connect to the RPC node,
+and can be used to detect a stalled event client."#,
+        )
+        .response::<200, Json<NodeIndexingResponse>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No nodes with the provided identifier were found.")
+                .example(example_error(NodeIndexingError::NodeNotFound))
+        })
+}
+
+/// Node indexing status request handler.
+pub(super) async fn indexing(
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<Json<NodeIndexingResponse>, NodeIndexingError> {
+    let confirmed_block = node::Entity::find_by_id(id)
+        .select_only()
+        .column(node::Column::ConfirmedBlock)
+        .into_tuple::<i64>()
+        .one(&*db)
+        .await?
+        .ok_or(NodeIndexingError::NodeNotFound)?;
+
+    let last_event = event::Entity::find()
+        .select_only()
+        .column(event::Column::BlockTimestamp)
+        .filter(event::Column::NodeId.eq(id))
+        .order_by_desc(event::Column::BlockTimestamp)
+        .limit(1)
+        .into_tuple::<PrimitiveDateTime>()
+        .one(&*db)
+        .await?;
+
+    Ok(Json(NodeIndexingResponse {
+        confirmed_block,
+        last_event_timestamp: last_event.map(|date| date.assume_utc().unix_timestamp()),
+        lag: last_event.map(|date| (now() - date).whole_seconds()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{
+        event, node, token, user, ActiveValue, DatabaseConnection, EntityTrait, OffsetDateTime,
+        PrimitiveDateTime,
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://127.0.0.1:1")),
+            confirmed_block: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        token
+    }
+
+    async fn indexing(db: DatabaseConnection, token: &str) -> serde_json::Value {
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes/1/indexing")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        response.json().await
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let datetime = OffsetDateTime::from_unix_timestamp(100).expect("invalid date");
+
+        event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(1),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Instantiation),
+            body: ActiveValue::Set(String::from("{}")),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_without_returning(&db)
+        .await
+        .expect("unable to insert event");
+
+        assert_json!(indexing(db, &token).await, {
+            "confirmed_block": 42,
+            "last_event_timestamp": 100,
+            "lag": validators::i64(|lag| {
+                if *lag >= OffsetDateTime::now_utc().unix_timestamp() - 100 - 60 {
+                    Ok(())
+                } else {
+                    Err(String::from("lag is too small"))
+                }
+            }),
+        });
+    }
+
+    #[tokio::test]
+    async fn no_events() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        assert_json!(indexing(db, &token).await, {
+            "confirmed_block": 42,
+            "last_event_timestamp": null,
+            "lag": null,
+        });
+    }
+}
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Query, State},
+    Extension, Json,
+};
+use common::config::Config;
+use db::{node, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryOrder, QuerySelect};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::pagination::{OffsetPagination, Paginated};
+
+/// A single node data.
+#[derive(Serialize, JsonSchema)]
+pub struct NodeData {
+    /// Node identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    pub id: i64,
+
+    /// Node name.
+    #[schemars(example = "crate::schema::example_node")]
+    pub name: String,
+
+    /// RPC node WebSocket URL.
+    #[schemars(example = "crate::schema::example_node_url")]
+    pub url: String,
+
+    /// Last confirmed block that was discovered by an event client.
+    #[schemars(example = "crate::schema::example_block_number")]
+    pub confirmed_block: i64,
+
+    /// Runtime specification name of the node chain.
+    ///
+    /// [`None`] if the node was not yet watched by an event client.
+    #[schemars(example = "crate::schema::example_spec_name")]
+    pub spec_name: Option<String>,
+
+    /// Runtime specification version of the node chain,
+    /// which changes with each runtime upgrade.
+    ///
+    /// [`None`] if the node was not yet watched by an event client.
+    #[schemars(example = "crate::schema::example_spec_version")]
+    pub spec_version: Option<i64>,
+}
+
+impl From<node::Model> for NodeData {
+    fn from(node: node::Model) -> Self {
+        Self {
+            id: node.id,
+            name: node.name,
+            url: node.url,
+            confirmed_block: node.confirmed_block,
+            spec_name: node.spec_name,
+            spec_version: node.spec_version,
+        }
+    }
+}
+
+/// Errors that may occur during the node list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum NodeListError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Generate OAPI documentation for the [`list`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List registered nodes.")
+        .description("Nodes are ordered by their identifiers in an ascending order.")
+        .response_with::<200, Paginated<Json<Vec<NodeData>>>, _>(|op| op.description("Node list."))
+}
+
+/// List registered nodes.
+pub(super) async fn list(
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<NodeData>>>, NodeListError> {
+    let total = node::Entity::find().count(&*db).await?;
+
+    node::Entity::find()
+        .order_by_asc(node::Column::Id)
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .stream(&*db)
+        .await?
+        .map_ok(NodeData::from)
+        .err_into()
+        .try_collect()
+        .await
+        .map(|items| Paginated::offset(Json(items), &uri, &pagination, &config.pagination, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use db::{node, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "name": "test",
+                "url": "ws://localhost:9944",
+                "confirmed_block": 42,
+                "spec_name": null,
+                "spec_version": null,
+            }
+        ]);
+    }
+}
@@ -0,0 +1,36 @@
+/// Node registration route.
+mod create;
+
+/// Node details route.
+mod details;
+
+/// Node indexing freshness route.
+mod indexing;
+
+/// Node list route.
+mod list;
+
+/// Node status route.
+pub(crate) mod status;
+
+use aide::axum::{routing::get_with, ApiRouter};
+use axum::Extension;
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with node management routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route(
+            "/",
+            get_with(list::list, list::docs).post_with(create::create, create::docs),
+        )
+        .api_route("/:id", get_with(details::details, details::docs))
+        .api_route(
+            "/:id/indexing",
+            get_with(indexing::indexing, indexing::docs),
+        )
+        .api_route("/:id/status", get_with(status::status, status::docs))
+        .layer(Extension(status::NodeStatusCache::default()))
+        .with_path_items(|op| op.tag("Node management"))
+}
@@ -0,0 +1,267 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use common::rpc::{
+    self,
+    substrate_api_client::{self, rpc::JsonrpseeClient, Api},
+};
+use db::{node, DatabaseConnection, DbErr, EntityTrait, QuerySelect};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Serialize;
+use tokio::runtime::Handle;
+use tracing::warn;
+
+use crate::schema::{example_error, ErrorBody};
+
+/// Duration for which the best block number of a node is cached.
+const CACHE_LIFESPAN: Duration = Duration::from_secs(5);
+
+/// Maximum duration of a best block number lookup.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Cached best block lookup results, keyed by node identifiers.
+type CacheEntries = HashMap<i64, (Instant, Option<u64>)>;
+
+/// Best block number cache, shared between node status requests.
+///
+/// Failed lookups are cached as well, to avoid reconnecting
+/// to unreachable nodes on each request.
+#[derive(Clone, Default)]
+pub(super) struct NodeStatusCache(Arc<Mutex<CacheEntries>>);
+
+impl NodeStatusCache {
+    /// Get a cached best block number lookup result for the provided node identifier.
+    fn get(&self, node_id: i64) -> Option<Option<u64>> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(&node_id)
+            .filter(|(cached_at, _)| cached_at.elapsed() < CACHE_LIFESPAN)
+            .map(|(_, best_block)| *best_block)
+    }
+
+    /// Store the best block number lookup result for the provided node identifier.
+    fn insert(&self, node_id: i64, best_block: Option<u64>) {
+        let mut cache = self.0.lock().unwrap();
+
+        cache.retain(|_, (cached_at, _)| cached_at.elapsed() < CACHE_LIFESPAN);
+        cache.insert(node_id, (Instant::now(), best_block));
+    }
+}
+
+/// Errors that may occur during the node status request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum NodeStatusError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The requested node was not found.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "node not found")]
+    NodeNotFound,
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct NodeStatusResponse {
+    /// Whether the RPC node responded to the best block request.
+    reachable: bool,
+
+    /// Last confirmed block that was discovered by an event client.
+    #[schemars(example = "crate::schema::example_block_number")]
+    confirmed_block: i64,
+
+    /// Current best block of the RPC node.
+    ///
+    /// [`None`] if the RPC node is unreachable.
+    #[schemars(example = "crate::schema::example_block_number")]
+    best_block: Option<u64>,
+
+    /// Count of blocks that were not yet discovered by an event client.
+    ///
+    /// [`None`] if the RPC node is unreachable.
+    #[schemars(example = "crate::schema::example_block_number")]
+    lag: Option<u64>,
+}
+
+/// Generate OAPI documentation for the [`status`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Get node indexing status.")
+        .description(
+            r#"Best block number of a node is cached for 5 seconds.
+
+Unreachable nodes are not considered an error,
+instead the `reachable` field will be set to `false`."#,
+        )
+        .response::<200, Json<NodeStatusResponse>>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("No nodes with the provided identifier were found.")
+                .example(example_error(NodeStatusError::NodeNotFound))
+        })
+}
+
+/// Node status request handler.
+pub(super) async fn status(
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+    Extension(cache): Extension<NodeStatusCache>,
+) -> Result<Json<NodeStatusResponse>, NodeStatusError> {
+    let (url, confirmed_block) = node::Entity::find_by_id(id)
+        .select_only()
+        .columns([node::Column::Url, node::Column::ConfirmedBlock])
+        .into_tuple::<(String, i64)>()
+        .one(&*db)
+        .await?
+        .ok_or(NodeStatusError::NodeNotFound)?;
+
+    let best_block = match cache.get(id) {
+        Some(best_block) => best_block,
+        None => {
+            let best_block = best_block(url).await;
+            cache.insert(id, best_block);
+            best_block
+        }
+    };
+
+    Ok(Json(NodeStatusResponse {
+        reachable: best_block.is_some(),
+        confirmed_block,
+        best_block,
+        lag: best_block.map(|best_block| best_block.saturating_sub(confirmed_block as u64)),
+    }))
+}
+
+/// Fetch the best block number of an RPC node with the provided URL.
+///
+/// Returns [`None`] if the RPC node is unreachable.
+pub(crate) async fn best_block(url: String) -> Option<u64> {
+    let lookup = tokio::task::spawn_blocking(|| {
+        Handle::current().block_on(async move {
+            let client =
+                JsonrpseeClient::new(&url).map_err(substrate_api_client::Error::RpcClient)?;
+            let api = Api::new(client).await?;
+
+            rpc::block(&api, None)
+                .await
+                .map(|block| block.map(|block| u64::from(block.header.number)))
+        })
+    });
+
+    match tokio::time::timeout(LOOKUP_TIMEOUT, lookup).await {
+        Ok(Ok(Ok(best_block))) => best_block,
+        Ok(Ok(Err(err))) => {
+            warn!(?err, "unable to fetch node best block");
+            None
+        }
+        Ok(Err(err)) => {
+            warn!(%err, "node best block lookup task failed");
+            None
+        }
+        Err(_) => {
+            warn!("node best block lookup timed out");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{node, token, user, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://127.0.0.1:1")),
+            confirmed_block: ActiveValue::Set(42),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        token
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unreachable() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes/1/status")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "reachable": false,
+            "confirmed_block": 42,
+            "best_block": null,
+            "lag": null
+        });
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/nodes/2/status")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
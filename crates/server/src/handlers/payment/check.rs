@@ -2,7 +2,6 @@ use std::{array::TryFromSliceError, sync::Arc};
 
 use aide::{transform::TransformOperation, OperationIo};
 use axum::{extract::State, http::StatusCode, Extension, Json};
-use axum_derive_error::ErrorResponse;
 use common::hash::blake2;
 use common::rpc::parity_scale_codec::Decode;
 use common::rpc::sp_core::crypto::AccountId32;
@@ -10,18 +9,22 @@ use common::rpc::substrate_api_client::rpc::JsonrpseeClient;
 use common::rpc::substrate_api_client::Api;
 use common::rpc::{self, parity_scale_codec, substrate_api_client};
 use db::{
-    node, public_key, user, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
-    QueryFilter, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
+    node, public_key, user, ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use ink_metadata::LangError;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use serde_json::Value;
 use tokio::runtime::Handle;
 use tokio::task::JoinError;
 
-use crate::{auth::AuthenticatedUserId, schema::example_error};
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::JsonBody,
+};
 
 /// JSON request body.
 #[derive(Deserialize, JsonSchema)]
@@ -95,11 +98,11 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Check membership payment with the provided node.")
         .description("See self-hosted documentation for more information about the contract ABI.")
         .response::<200, ()>()
-        .response_with::<400, Json<Value>, _>(|op| {
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
             op.description("Invalid account identifier was provided.")
                 .example(example_error(PaymentCheckError::InvalidKey))
         })
-        .response_with::<404, Json<Value>, _>(|op| {
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
             op.description("The provided node identifier is invalid.")
                 .example(example_error(PaymentCheckError::InvalidNodeId))
         })
@@ -111,7 +114,7 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 pub(super) async fn check(
     Extension(current_user): Extension<AuthenticatedUserId>,
     State(db): State<Arc<DatabaseConnection>>,
-    Json(request): Json<PaymentCheckRequest>,
+    JsonBody(request): JsonBody<PaymentCheckRequest>,
 ) -> Result<(), PaymentCheckError> {
     db.transaction(|txn| {
         Box::pin(async move {
@@ -129,6 +132,7 @@ pub(super) async fn check(
                 .select_only()
                 .filter(public_key::Column::UserId.eq(current_user.id()))
                 .filter(public_key::Column::Address.eq(AsRef::<[u8]>::as_ref(&request.account)))
+                .filter(public_key::Column::DeletedAt.is_null())
                 .exists(txn)
                 .await?;
 
@@ -180,7 +184,7 @@ pub(super) async fn check(
 
             let mut active_model: user::ActiveModel = user.into();
             active_model.paid = ActiveValue::Set(true);
-            user::Entity::update(active_model).exec(txn).await?;
+            active_model.update(txn).await?;
 
             Ok(())
         })
@@ -5,11 +5,12 @@ use axum::{
     extract::{Query, State},
     Extension, Json,
 };
-use axum_derive_error::ErrorResponse;
+use common::config::Config;
 use db::{
     source_code, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use futures_util::TryStreamExt;
 use schemars::JsonSchema;
 use serde::Serialize;
@@ -50,6 +51,7 @@ pub(super) fn docs(op: TransformOperation) -> TransformOperation {
 /// List source code archives related to the current authenticated user.
 pub(super) async fn list(
     Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
     State(db): State<Arc<DatabaseConnection>>,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<Vec<SourceCodeData>>, SourceCodeListError> {
@@ -57,8 +59,8 @@ pub(super) async fn list(
         .select_only()
         .columns([source_code::Column::Id, source_code::Column::ArchiveHash])
         .filter(source_code::Column::UserId.eq(current_user.id()))
-        .limit(pagination.limit())
-        .offset(pagination.offset())
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset(&config.pagination))
         .into_tuple::<(i64, Vec<u8>)>()
         .stream(&*db)
         .await?
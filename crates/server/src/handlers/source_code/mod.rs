@@ -11,13 +11,13 @@ use axum::middleware::from_fn_with_state;
 use common::config::Config;
 use db::DatabaseConnection;
 
-use crate::auth;
+use crate::{auth, state::AppState};
 
 /// Create a router that provides an API server with source code management routes.
 pub(crate) fn routes(
     database: Arc<DatabaseConnection>,
     config: Arc<Config>,
-) -> ApiRouter<Arc<DatabaseConnection>> {
+) -> ApiRouter<AppState> {
     ApiRouter::new()
         .api_route(
             "/",
@@ -6,18 +6,20 @@ use axum::{
     http::StatusCode,
     Extension, Json,
 };
-use axum_derive_error::ErrorResponse;
 use common::{config::Config, hash, s3};
 use db::{
     sea_query::OnConflict, source_code, user, ActiveValue, ColumnTrait, DatabaseConnection, DbErr,
     EntityTrait, QueryFilter, QuerySelect, SelectExt, TransactionErrorExt, TransactionTrait,
 };
 use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
 use schemars::JsonSchema;
 use serde::Serialize;
-use serde_json::Value;
 
-use crate::{auth::AuthenticatedUserId, schema::example_error};
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+};
 
 /// Errors that may occur during the source code upload process.
 #[derive(ErrorResponse, Display, From, Error, OperationIo)]
@@ -61,10 +63,10 @@ pub(super) struct SourceCodeUploadResponse {
 pub(super) fn docs(op: TransformOperation) -> TransformOperation {
     op.summary("Upload a new source code archive.")
         .response::<200, Json<SourceCodeUploadResponse>>()
-        .response_with::<400, Json<Value>, _>(|op| {
+        .response_with::<400, Json<ErrorBody>, _>(|op| {
             op.description("Incorrect multipart/form-data request.")
         })
-        .response_with::<422, Json<Value>, _>(|op| {
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
             op.description("Incorrect file upload.")
                 .example(example_error(SourceCodeUploadError::NoFileUpload))
         })
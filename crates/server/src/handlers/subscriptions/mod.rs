@@ -0,0 +1,13 @@
+/// WebSocket event subscription route.
+mod ws;
+
+use aide::axum::{routing::get_with, ApiRouter};
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with event subscription routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route("/ws", get_with(ws::ws, ws::docs))
+        .with_path_items(|op| op.tag("Event subscriptions"))
+}
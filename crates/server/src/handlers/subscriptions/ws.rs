@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+
+use aide::transform::TransformOperation;
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    response::Response,
+    Extension,
+};
+use common::rpc::sp_core::ByteArray;
+use db::event;
+use derive_more::Display;
+use futures_util::{
+    stream::{abortable, AbortHandle, Abortable, BoxStream, SelectAll},
+    StreamExt,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    broadcast::EventBroadcast,
+    handlers::contracts::{events::ContractEvent, AccountParseError, WrappedAccountId32},
+};
+
+/// Maximum count of active subscriptions of a single WebSocket connection.
+const MAX_SUBSCRIPTIONS: usize = 32;
+
+/// Topics that can be subscribed to.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Topic {
+    /// Newly discovered events of a contract account.
+    Events,
+}
+
+/// Message sent by a WebSocket client.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ClientMessage {
+    /// Subscribe to the provided topic of a contract account.
+    Subscribe {
+        /// Subscription topic.
+        subscribe: Topic,
+
+        /// SS58-encoded or hex-encoded contract account.
+        account: String,
+    },
+
+    /// Cancel a subscription.
+    Unsubscribe {
+        /// Subscription identifier.
+        unsubscribe: u64,
+    },
+}
+
+/// Message sent to a WebSocket client.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ServerMessage {
+    /// Subscription was created.
+    Subscribed {
+        /// Created subscription identifier.
+        subscribed: u64,
+    },
+
+    /// Subscription was cancelled.
+    Unsubscribed {
+        /// Cancelled subscription identifier.
+        unsubscribed: u64,
+    },
+
+    /// Newly discovered event related to a subscribed contract account.
+    Event {
+        /// Identifier of a subscription that matched the event.
+        subscription: u64,
+
+        /// Contract event.
+        event: ContractEvent,
+    },
+
+    /// Client message could not be handled.
+    Error {
+        /// Error message.
+        error: String,
+    },
+}
+
+/// Errors that may occur during the client message handling.
+#[derive(Display)]
+enum SubscriptionError {
+    /// Client message is not a valid subscription request.
+    #[display(fmt = "invalid message: {_0}")]
+    InvalidMessage(serde_json::Error),
+
+    /// Provided account is not a valid SS58 address or hex value.
+    #[display(fmt = "invalid account: {_0}")]
+    InvalidAccount(AccountParseError),
+
+    /// Provided subscription identifier is not active.
+    #[display(fmt = "unknown subscription")]
+    UnknownSubscription,
+
+    /// Connection already has [`MAX_SUBSCRIPTIONS`] active subscriptions.
+    #[display(fmt = "too many subscriptions")]
+    TooManySubscriptions,
+}
+
+/// Active subscriptions of a single WebSocket connection.
+///
+/// Dropping this struct cancels all subscriptions.
+#[derive(Default)]
+struct Subscriptions {
+    /// Identifier of the most recently created subscription.
+    last_id: u64,
+
+    /// Handles used to cancel subscriptions by their identifiers.
+    handles: HashMap<u64, AbortHandle>,
+
+    /// Merged event streams of all subscriptions.
+    events: SelectAll<Abortable<BoxStream<'static, (u64, event::Model)>>>,
+}
+
+impl Subscriptions {
+    /// Handle a text message sent by a WebSocket client.
+    fn handle(
+        &mut self,
+        broadcast: &EventBroadcast,
+        message: &str,
+    ) -> Result<ServerMessage, SubscriptionError> {
+        match serde_json::from_str(message).map_err(SubscriptionError::InvalidMessage)? {
+            ClientMessage::Subscribe {
+                subscribe: Topic::Events,
+                account,
+            } => {
+                let account: WrappedAccountId32 =
+                    account.parse().map_err(SubscriptionError::InvalidAccount)?;
+
+                if self.handles.len() >= MAX_SUBSCRIPTIONS {
+                    return Err(SubscriptionError::TooManySubscriptions);
+                }
+
+                self.last_id += 1;
+                let id = self.last_id;
+
+                let (events, handle) = abortable(
+                    broadcast
+                        .subscribe(account.0.to_raw_vec())
+                        .into_stream()
+                        .map(move |event| (id, event))
+                        .boxed(),
+                );
+
+                self.events.push(events);
+                self.handles.insert(id, handle);
+
+                Ok(ServerMessage::Subscribed { subscribed: id })
+            }
+            ClientMessage::Unsubscribe { unsubscribe } => {
+                // Aborted streams are removed from the merged stream on the next poll.
+                self.handles
+                    .remove(&unsubscribe)
+                    .ok_or(SubscriptionError::UnknownSubscription)?
+                    .abort();
+
+                Ok(ServerMessage::Unsubscribed {
+                    unsubscribed: unsubscribe,
+                })
+            }
+        }
+    }
+}
+
+/// Generate OAPI documentation for the [`ws`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Subscribe to newly discovered contract events using a WebSocket connection.")
+        .description(
+            r#"After the connection is established, send
+`{"subscribe": "events", "account": "<account>"}` text messages to subscribe
+to events of contract accounts. Each subscription is confirmed with
+a `{"subscribed": <id>}` message containing a subscription identifier.
+
+Events are sent as `{"subscription": <id>, "event": <event>}` messages,
+where the event uses the same format as the contract event list route.
+
+Send `{"unsubscribe": <id>}` to cancel a subscription, which is confirmed
+with an `{"unsubscribed": <id>}` message. All subscriptions are cancelled
+once the connection is closed.
+
+Invalid messages are answered with `{"error": "<message>"}`.
+A single connection can have up to 32 active subscriptions."#,
+        )
+        .response_with::<101, (), _>(|op| op.description("Switching to the WebSocket protocol."))
+}
+
+/// WebSocket event subscription request handler.
+pub(super) async fn ws(
+    upgrade: WebSocketUpgrade,
+    Extension(broadcast): Extension<EventBroadcast>,
+) -> Response {
+    upgrade.on_upgrade(|socket| handle_socket(socket, broadcast))
+}
+
+/// Serve subscription requests of a single WebSocket connection until it is closed.
+async fn handle_socket(mut socket: WebSocket, broadcast: EventBroadcast) {
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        let message = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(message))) => subscriptions
+                    .handle(&broadcast, &message)
+                    .unwrap_or_else(|err| ServerMessage::Error {
+                        error: err.to_string(),
+                    }),
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                // Ping frames are answered automatically.
+                Some(Ok(_)) => continue,
+            },
+            Some((subscription, event)) = subscriptions.events.next() => ServerMessage::Event {
+                subscription,
+                event: ContractEvent::from(event),
+            },
+        };
+
+        let message = serde_json::to_string(&message).expect("server messages are serializable");
+
+        if socket.send(Message::Text(message)).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{SocketAddr, TcpListener},
+        sync::Arc,
+        time::Duration,
+    };
+
+    use crate::{broadcast::EventBroadcast, testing::create_database};
+
+    use assert_json::assert_json;
+    use axum::{Extension, Router, Server};
+    use common::config::Config;
+    use common::rpc::sp_core::crypto::AccountId32;
+    use db::{event, OffsetDateTime, PrimitiveDateTime};
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{json, Value};
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    fn create_event(id: i64, account: [u8; 32]) -> event::Model {
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        event::Model {
+            id,
+            node_id: 1,
+            account: account.to_vec(),
+            event_type: event::EventType::Instantiation,
+            body: serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            raw_body: None,
+            block_timestamp: PrimitiveDateTime::new(datetime.date(), datetime.time()),
+            block_number: id,
+            event_index: None,
+        }
+    }
+
+    async fn connect(broadcast: EventBroadcast) -> Client {
+        let db = create_database().await;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("unable to bind listener");
+        let address = listener.local_addr().unwrap();
+
+        let router: Router = crate::app_router(Arc::new(db), Arc::new(Config::for_tests())).into();
+        let server = Server::from_tcp(listener).unwrap().serve(
+            router
+                .layer(Extension(broadcast))
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        );
+
+        tokio::spawn(server);
+
+        tokio_tungstenite::connect_async(format!("ws://{address}/ws"))
+            .await
+            .expect("unable to connect")
+            .0
+    }
+
+    async fn send(client: &mut Client, message: Value) {
+        client
+            .send(Message::Text(message.to_string()))
+            .await
+            .expect("unable to send message");
+    }
+
+    async fn recv(client: &mut Client) -> Value {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("no message received")
+            .expect("connection closed")
+            .expect("unable to receive message");
+
+        serde_json::from_str(message.to_text().unwrap()).expect("invalid message")
+    }
+
+    #[tokio::test]
+    async fn subscriptions() {
+        let broadcast = EventBroadcast::default();
+
+        let mut client = connect(broadcast.clone()).await;
+
+        for (account, id) in [([1; 32], 1), ([2; 32], 2)] {
+            send(
+                &mut client,
+                json!({
+                    "subscribe": "events",
+                    "account": AccountId32::new(account).to_string(),
+                }),
+            )
+            .await;
+
+            assert_json!(recv(&mut client).await, { "subscribed": id });
+        }
+
+        broadcast.publish(create_event(1, [2; 32]));
+
+        assert_json!(recv(&mut client).await, {
+            "subscription": 2,
+            "event": {
+                "event_type": "instantiation",
+                "body": "Instantiation",
+                "timestamp": 0,
+                "block_number": 1
+            }
+        });
+
+        send(&mut client, json!({ "unsubscribe": 2 })).await;
+        assert_json!(recv(&mut client).await, { "unsubscribed": 2 });
+
+        broadcast.publish(create_event(2, [2; 32]));
+        broadcast.publish(create_event(3, [1; 32]));
+
+        assert_json!(recv(&mut client).await, {
+            "subscription": 1,
+            "event": {
+                "event_type": "instantiation",
+                "body": "Instantiation",
+                "timestamp": 0,
+                "block_number": 3
+            }
+        });
+
+        send(&mut client, json!({ "unsubscribe": 2 })).await;
+        assert_json!(recv(&mut client).await, { "error": "unknown subscription" });
+
+        client
+            .close(None)
+            .await
+            .expect("unable to close connection");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while broadcast.subscribed_accounts() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("subscriptions were not cleaned up");
+    }
+
+    #[tokio::test]
+    async fn invalid_messages() {
+        let mut client = connect(EventBroadcast::default()).await;
+
+        send(&mut client, json!({ "subscribe": "unknown" })).await;
+        assert!(recv(&mut client).await["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid message"));
+
+        send(
+            &mut client,
+            json!({
+                "subscribe": "events",
+                "account": "abc",
+            }),
+        )
+        .await;
+        assert!(recv(&mut client).await["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid account"));
+    }
+}
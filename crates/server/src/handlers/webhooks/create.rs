@@ -0,0 +1,262 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
+};
+use db::{webhook, ActiveValue, DatabaseConnection, DbErr, EntityTrait};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
+
+/// Errors that may occur during the webhook registration process.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum WebhookCreateError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Webhook URL host can not be resolved or resolves to a non-public address.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "webhook url must resolve to a public address")]
+    NonPublicUrl,
+}
+
+/// JSON request body.
+#[derive(Deserialize, Validate, JsonSchema)]
+pub(super) struct WebhookCreateRequest {
+    /// Target URL, to which event notifications are sent.
+    #[validate(
+        url,
+        length(max = "db::webhook::URL_MAX_LENGTH"),
+        custom = "validate_webhook_url"
+    )]
+    #[schemars(example = "crate::schema::example_webhook_url")]
+    url: String,
+
+    /// Smart contract account, events of which are sent.
+    #[schemars(example = "crate::schema::example_account", with = "String")]
+    account: AccountId32,
+}
+
+/// Validate the provided webhook URL to use either `http` or `https` scheme.
+pub(super) fn validate_webhook_url(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid webhook url scheme"))
+    }
+}
+
+/// Check that the provided webhook URL resolves to public addresses only,
+/// unless private addresses are allowed by the server configuration.
+pub(super) async fn is_allowed_url(config: &Config, url: &str) -> bool {
+    let private_addresses = config
+        .server
+        .as_ref()
+        .is_some_and(|server| server.webhook_private_addresses);
+
+    private_addresses || crate::webhook::check_url(url).await.is_ok()
+}
+
+/// JSON response body.
+#[derive(Serialize, JsonSchema)]
+pub(super) struct WebhookCreateResponse {
+    /// Webhook identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    id: i64,
+
+    /// Secret used to sign event notifications.
+    #[schemars(example = "crate::schema::example_webhook_secret")]
+    secret: String,
+}
+
+/// Generate OAPI documentation for the [`create`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Register a new webhook.")
+        .description(
+            r#"Newly discovered events of the provided contract account are sent
+to the webhook URL as `POST` requests with a JSON contract event body.
+
+Each request body is signed using HMAC-SHA256 with the returned secret,
+and the hex-encoded signature is passed in the `X-Signature` header.
+The secret is not returned by any other route.
+
+Webhook URL host must resolve to public addresses only,
+and redirects returned by webhooks are not followed.
+
+Failed deliveries are retried with an exponential backoff. Deliveries that
+failed all attempts are counted in the webhook's `dead_letter_count` value."#,
+        )
+        .response::<200, Json<WebhookCreateResponse>>()
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description(
+                "Invalid webhook URL or account was provided, \
+                or webhook URL does not resolve to a public address.",
+            )
+            .example(example_error(WebhookCreateError::NonPublicUrl))
+        })
+}
+
+/// Webhook registration handler.
+pub(super) async fn create(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    ValidatedJson(request): ValidatedJson<WebhookCreateRequest>,
+) -> Result<Json<WebhookCreateResponse>, WebhookCreateError> {
+    if !is_allowed_url(&config, &request.url).await {
+        return Err(WebhookCreateError::NonPublicUrl);
+    }
+
+    let model = webhook::Entity::insert(webhook::ActiveModel {
+        user_id: ActiveValue::Set(current_user.id()),
+        url: ActiveValue::Set(request.url),
+        account: ActiveValue::Set(request.account.to_raw_vec()),
+        secret: ActiveValue::Set(webhook::generate_secret()),
+        ..Default::default()
+    })
+    .exec_with_returning(&*db)
+    .await?;
+
+    Ok(Json(WebhookCreateResponse {
+        id: model.id,
+        secret: model.secret,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt, ResponseBodyExt};
+
+    use assert_json::{assert_json, validators};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+    use db::{token, user, webhook, DatabaseConnection, EntityTrait};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/webhooks")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "url": "https://example.com/webhook",
+                        "account": AccountId32::new([1; 32]).to_string(),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_json!(response.json().await, {
+            "id": 1,
+            "secret": validators::string(|secret| {
+                if secret.len() == webhook::SECRET_LENGTH {
+                    Ok(())
+                } else {
+                    Err(String::from("invalid secret length"))
+                }
+            }),
+        });
+    }
+
+    #[tokio::test]
+    async fn invalid_url() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/webhooks")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "url": "ftp://example.com/webhook",
+                        "account": AccountId32::new([1; 32]).to_string(),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn private_url() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.server.as_mut().unwrap().webhook_private_addresses = false;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/webhooks")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(json!({
+                        "url": "http://169.254.169.254/latest/meta-data",
+                        "account": AccountId32::new([1; 32]).to_string(),
+                    })))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_json!(response.json().await, {
+            "error": {
+                "code": "non_public_url",
+            }
+        });
+    }
+}
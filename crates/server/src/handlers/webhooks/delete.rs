@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use db::{webhook, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+};
+
+/// Errors that may occur during the webhook deletion request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum WebhookDeletionError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided webhook is not registered by the current user.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "webhook not found")]
+    WebhookNotFound,
+}
+
+/// Generate OAPI documentation for the [`delete`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Delete webhook registered by the current user.")
+        .description("Pending event notifications of the deleted webhook are discarded.")
+        .response::<200, ()>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided webhook is not registered by the current user.")
+                .example(example_error(WebhookDeletionError::WebhookNotFound))
+        })
+}
+
+/// Delete webhook registered by the current authenticated user.
+pub(super) async fn delete(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Path(id): Path<i64>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<(), WebhookDeletionError> {
+    let result = webhook::Entity::delete_many()
+        .filter(webhook::Column::Id.eq(id))
+        .filter(webhook::Column::UserId.eq(current_user.id()))
+        .exec(&*db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(WebhookDeletionError::WebhookNotFound);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::config::Config;
+    use db::{token, user, webhook, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::Service;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        webhook::Entity::insert(webhook::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            url: ActiveValue::Set(String::from("https://example.com/webhook")),
+            account: ActiveValue::Set(vec![1; 32]),
+            secret: ActiveValue::Set(webhook::generate_secret()),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create webhook");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()));
+
+        for expected_status in [StatusCode::OK, StatusCode::NOT_FOUND] {
+            let response = service
+                .call(
+                    Request::builder()
+                        .method("DELETE")
+                        .uri("/webhooks/1")
+                        .header("Authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), expected_status);
+        }
+    }
+}
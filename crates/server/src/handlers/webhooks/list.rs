@@ -0,0 +1,220 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{OriginalUri, Query, State},
+    Extension, Json,
+};
+use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+use db::{
+    webhook, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use futures_util::TryStreamExt;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    pagination::{OffsetPagination, Paginated},
+    ss58,
+};
+
+/// A single webhook data.
+#[derive(Serialize, JsonSchema)]
+pub struct WebhookData {
+    /// Webhook identifier.
+    #[schemars(example = "crate::schema::example_database_identifier")]
+    pub id: i64,
+
+    /// Target URL, to which event notifications are sent.
+    #[schemars(example = "crate::schema::example_webhook_url")]
+    pub url: String,
+
+    /// Smart contract account, events of which are sent.
+    #[schemars(example = "crate::schema::example_account")]
+    pub account: String,
+
+    /// Count of event notifications that were not delivered after all attempts.
+    #[schemars(example = "crate::schema::example_dead_letter_count")]
+    pub dead_letter_count: i64,
+}
+
+/// Errors that may occur during the webhook list request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum WebhookListError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// Account stored inside of a database has an invalid size.
+    #[display(fmt = "invalid account size stored in db")]
+    InvalidAccountSize,
+}
+
+/// Generate OAPI documentation for the [`list`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("List webhooks registered by the current user.")
+        .description("Webhooks are ordered by their identifiers in an ascending order.")
+        .response_with::<200, Paginated<Json<Vec<WebhookData>>>, _>(|op| {
+            op.description("Webhook list.")
+        })
+}
+
+/// List webhooks registered by the current authenticated user.
+pub(super) async fn list(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    Query(pagination): Query<OffsetPagination>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<Paginated<Json<Vec<WebhookData>>>, WebhookListError> {
+    let config = &config;
+
+    let total = webhook::Entity::find()
+        .filter(webhook::Column::UserId.eq(current_user.id()))
+        .count(&*db)
+        .await?;
+
+    webhook::Entity::find()
+        .select_only()
+        .columns([
+            webhook::Column::Id,
+            webhook::Column::Url,
+            webhook::Column::Account,
+            webhook::Column::DeadLetterCount,
+        ])
+        .filter(webhook::Column::UserId.eq(current_user.id()))
+        .order_by_asc(webhook::Column::Id)
+        .limit(pagination.limit(&config.pagination))
+        .offset(pagination.offset())
+        .into_tuple::<(i64, String, Vec<u8>, i64)>()
+        .stream(&*db)
+        .await?
+        .err_into()
+        .and_then(|(id, url, account, dead_letter_count)| async move {
+            let account = AccountId32::new(
+                account
+                    .try_into()
+                    .map_err(|_| WebhookListError::InvalidAccountSize)?,
+            );
+
+            Ok(WebhookData {
+                id,
+                url,
+                account: ss58::encode(&account, config),
+                dead_letter_count,
+            })
+        })
+        .try_collect()
+        .await
+        .map(|items| Paginated::offset(Json(items), &uri, &pagination, &config.pagination, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use assert_json::assert_json;
+    use axum::{body::Body, http::Request};
+    use common::{
+        config::Config,
+        rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec},
+    };
+    use db::{token, user, webhook, ActiveValue, DatabaseConnection, EntityTrait};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let users = [
+            user::Entity::insert(user::ActiveModel::default())
+                .exec_with_returning(db)
+                .await
+                .expect("unable to create user"),
+            user::Entity::insert(user::ActiveModel::default())
+                .exec_with_returning(db)
+                .await
+                .expect("unable to create user"),
+        ];
+
+        let (model, token) = token::generate_token(users[0].id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        webhook::Entity::insert_many(users.iter().map(|user| webhook::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            url: ActiveValue::Set(format!("https://example.com/{}", user.id)),
+            account: ActiveValue::Set(vec![1; 32]),
+            secret: ActiveValue::Set(webhook::generate_secret()),
+            ..Default::default()
+        }))
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create webhooks");
+
+        token
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/webhooks")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "id": 1,
+                "url": "https://example.com/1",
+                "account": AccountId32::new([1; 32]).to_string(),
+                "dead_letter_count": 0,
+            }
+        ]);
+    }
+
+    #[tokio::test]
+    async fn ss58_prefix() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let mut config = Config::for_tests();
+        config.ss58_prefix = 2;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .oneshot(
+                Request::builder()
+                    .method("GET")
+                    .uri("/webhooks")
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_json!(response.json().await, [
+            {
+                "account": AccountId32::new([1; 32])
+                    .to_ss58check_with_version(Ss58AddressFormat::custom(2)),
+            }
+        ]);
+    }
+}
@@ -0,0 +1,32 @@
+/// Webhook registration route.
+mod create;
+
+/// Webhook deletion route.
+mod delete;
+
+/// Webhook list route.
+mod list;
+
+/// Webhook update route.
+mod update;
+
+use aide::axum::{
+    routing::{delete_with, get_with},
+    ApiRouter,
+};
+
+use crate::state::AppState;
+
+/// Create an [`ApiRouter`] that provides an API server with webhook management routes.
+pub(crate) fn routes() -> ApiRouter<AppState> {
+    ApiRouter::new()
+        .api_route(
+            "/",
+            get_with(list::list, list::docs).post_with(create::create, create::docs),
+        )
+        .api_route(
+            "/:id",
+            delete_with(delete::delete, delete::docs).patch_with(update::update, update::docs),
+        )
+        .with_path_items(|op| op.tag("Webhook management"))
+}
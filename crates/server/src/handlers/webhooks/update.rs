@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use aide::{transform::TransformOperation, OperationIo};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use common::{
+    config::Config,
+    rpc::sp_core::{crypto::AccountId32, ByteArray},
+};
+use db::{webhook, ActiveValue, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    auth::AuthenticatedUserId,
+    schema::{example_error, ErrorBody},
+    validation::ValidatedJson,
+};
+
+use super::create::{is_allowed_url, validate_webhook_url};
+
+/// Errors that may occur during the webhook update request handling.
+#[derive(ErrorResponse, Display, From, Error, OperationIo)]
+#[aide(output)]
+pub(super) enum WebhookUpdateError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+
+    /// The provided webhook is not registered by the current user.
+    #[status(StatusCode::NOT_FOUND)]
+    #[display(fmt = "webhook not found")]
+    WebhookNotFound,
+
+    /// Webhook URL host can not be resolved or resolves to a non-public address.
+    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
+    #[display(fmt = "webhook url must resolve to a public address")]
+    NonPublicUrl,
+}
+
+/// JSON request body.
+#[derive(Deserialize, Validate, JsonSchema)]
+#[validate(schema(function = "validate_changes"))]
+pub(super) struct WebhookUpdateRequest {
+    /// New target URL, to which event notifications are sent.
+    #[validate(
+        url,
+        length(max = "db::webhook::URL_MAX_LENGTH"),
+        custom = "validate_webhook_url"
+    )]
+    #[schemars(example = "crate::schema::example_webhook_url")]
+    url: Option<String>,
+
+    /// New smart contract account, events of which are sent.
+    #[schemars(example = "crate::schema::example_account", with = "Option<String>")]
+    account: Option<AccountId32>,
+}
+
+/// Validate that at least one webhook field has to be changed.
+fn validate_changes(request: &WebhookUpdateRequest) -> Result<(), ValidationError> {
+    if request.url.is_none() && request.account.is_none() {
+        Err(ValidationError::new("no webhook changes provided"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Generate OAPI documentation for the [`update`] handler.
+pub(super) fn docs(op: TransformOperation) -> TransformOperation {
+    op.summary("Update webhook registered by the current user.")
+        .description("Fields that are not provided are left unchanged.")
+        .response::<200, ()>()
+        .response_with::<404, Json<ErrorBody>, _>(|op| {
+            op.description("The provided webhook is not registered by the current user.")
+                .example(example_error(WebhookUpdateError::WebhookNotFound))
+        })
+        .response_with::<422, Json<ErrorBody>, _>(|op| {
+            op.description(
+                "Invalid webhook URL or account was provided, \
+                or webhook URL does not resolve to a public address.",
+            )
+            .example(example_error(WebhookUpdateError::NonPublicUrl))
+        })
+}
+
+/// Update webhook registered by the current authenticated user.
+pub(super) async fn update(
+    Extension(current_user): Extension<AuthenticatedUserId>,
+    Path(id): Path<i64>,
+    Extension(config): Extension<Arc<Config>>,
+    State(db): State<Arc<DatabaseConnection>>,
+    ValidatedJson(request): ValidatedJson<WebhookUpdateRequest>,
+) -> Result<(), WebhookUpdateError> {
+    let mut model = webhook::ActiveModel::default();
+
+    if let Some(url) = request.url {
+        if !is_allowed_url(&config, &url).await {
+            return Err(WebhookUpdateError::NonPublicUrl);
+        }
+
+        model.url = ActiveValue::Set(url);
+    }
+
+    if let Some(account) = request.account {
+        model.account = ActiveValue::Set(account.to_raw_vec());
+    }
+
+    let result = webhook::Entity::update_many()
+        .set(model)
+        .filter(webhook::Column::Id.eq(id))
+        .filter(webhook::Column::UserId.eq(current_user.id()))
+        .exec(&*db)
+        .await?;
+
+    if result.rows_affected == 0 {
+        return Err(WebhookUpdateError::WebhookNotFound);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use common::{config::Config, rpc::sp_core::crypto::AccountId32};
+    use db::{token, user, webhook, ActiveValue, DatabaseConnection, EntityTrait};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn create_test_env(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        webhook::Entity::insert(webhook::ActiveModel {
+            user_id: ActiveValue::Set(user.id),
+            url: ActiveValue::Set(String::from("https://example.com/webhook")),
+            account: ActiveValue::Set(vec![1; 32]),
+            secret: ActiveValue::Set(webhook::generate_secret()),
+            ..Default::default()
+        })
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create webhook");
+
+        token
+    }
+
+    async fn update(db: Arc<DatabaseConnection>, token: &str, id: i64, body: Value) -> StatusCode {
+        crate::app_router(db, Arc::new(Config::for_tests()))
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri(format!("/webhooks/{id}"))
+                    .header("Authorization", format!("Bearer {token}"))
+                    .header("Content-Type", "application/json")
+                    .body(Body::from_json(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let db = Arc::new(db);
+
+        let status = update(
+            db.clone(),
+            &token,
+            1,
+            json!({
+                "account": AccountId32::new([2; 32]).to_string(),
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+
+        let model = webhook::Entity::find_by_id(1)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(model.url, "https://example.com/webhook");
+        assert_eq!(model.account, vec![2; 32]);
+    }
+
+    #[tokio::test]
+    async fn empty() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let status = update(Arc::new(db), &token, 1, json!({})).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn unknown() {
+        let db = create_database().await;
+
+        let token = create_test_env(&db).await;
+
+        let status = update(
+            Arc::new(db),
+            &token,
+            2,
+            json!({
+                "url": "https://example.com/other",
+            }),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,16 +1,32 @@
 use std::array::TryFromSliceError;
 
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
 /// Hexidecimal representation of a 32-byte array.
+///
+/// Deserialized values may be optionally prefixed with `0x`.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct HexHash(
-    #[serde(with = "hex")]
+    #[serde(
+        serialize_with = "hex::serialize",
+        deserialize_with = "deserialize_hex"
+    )]
     #[schemars(with = "String")]
     pub [u8; 32],
 );
 
+/// Deserialize a hex-encoded 32-byte array with an optional `0x` prefix.
+fn deserialize_hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let value = String::deserialize(deserializer)?;
+    let mut buf = [0; 32];
+
+    hex::decode_to_slice(value.strip_prefix("0x").unwrap_or(&value), &mut buf)
+        .map_err(D::Error::custom)?;
+
+    Ok(buf)
+}
+
 impl TryFrom<&[u8]> for HexHash {
     type Error = TryFromSliceError;
 
@@ -0,0 +1,82 @@
+use aide::{
+    gen::GenContext,
+    openapi::{
+        HeaderStyle, Operation, Parameter, ParameterData, ParameterSchemaOrContent, SchemaObject,
+    },
+    operation::add_parameters,
+    OperationInput,
+};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderName, StatusCode},
+};
+use db::idempotency_key::KEY_MAX_LENGTH;
+use derive_more::{Display, Error};
+use error_response::ErrorResponse;
+
+/// Name of a header that identifies retried requests.
+static IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Errors related to idempotency key extraction.
+#[derive(ErrorResponse, Display, Error)]
+pub enum IdempotencyKeyRejection {
+    /// Provided header value is empty, too long or contains non-visible characters.
+    #[status(StatusCode::BAD_REQUEST)]
+    #[display(fmt = "invalid idempotency key")]
+    InvalidIdempotencyKey,
+}
+
+/// Optional client-provided `Idempotency-Key` header value.
+///
+/// Values must be non-empty and have their length limited to [`KEY_MAX_LENGTH`].
+pub(crate) struct IdempotencyKey(pub Option<String>);
+
+impl OperationInput for IdempotencyKey {
+    fn operation_input(ctx: &mut GenContext, operation: &mut Operation) {
+        let schema = ctx.schema.subschema_for::<String>();
+
+        add_parameters(
+            ctx,
+            operation,
+            [Parameter::Header {
+                parameter_data: ParameterData {
+                    name: IDEMPOTENCY_KEY.to_string(),
+                    description: Some(String::from(
+                        "Client-generated key, which allows to safely retry the request.",
+                    )),
+                    required: false,
+                    format: ParameterSchemaOrContent::Schema(SchemaObject {
+                        json_schema: schema,
+                        example: None,
+                        external_docs: None,
+                    }),
+                    extensions: Default::default(),
+                    deprecated: None,
+                    example: None,
+                    examples: Default::default(),
+                    explode: None,
+                },
+                style: HeaderStyle::Simple,
+            }],
+        );
+    }
+}
+
+#[async_trait]
+impl<S: Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = IdempotencyKeyRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(&IDEMPOTENCY_KEY) else {
+            return Ok(Self(None));
+        };
+
+        value
+            .to_str()
+            .ok()
+            .filter(|value| !value.is_empty() && value.len() <= KEY_MAX_LENGTH)
+            .map(|value| Self(Some(value.to_owned())))
+            .ok_or(IdempotencyKeyRejection::InvalidIdempotencyKey)
+    }
+}
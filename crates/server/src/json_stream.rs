@@ -0,0 +1,86 @@
+use std::{future::ready, marker::PhantomData};
+
+use aide::{
+    gen::GenContext,
+    openapi::{Operation, Response},
+    OperationOutput,
+};
+use axum::{
+    body::{Bytes, StreamBody},
+    http::header,
+    response::IntoResponse,
+    BoxError, Json,
+};
+use futures_util::{stream, Stream, StreamExt};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Streamed JSON array response.
+///
+/// Array items are serialized one by one as they arrive from the underlying stream.
+pub(crate) struct JsonArrayStream<S, T> {
+    /// Stream of array items.
+    stream: S,
+
+    /// Array item type marker.
+    item: PhantomData<fn() -> T>,
+}
+
+impl<S, T, E> JsonArrayStream<S, T>
+where
+    S: Stream<Item = Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    /// Create new [`JsonArrayStream`] from the provided [`Stream`] of array items.
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            item: PhantomData,
+        }
+    }
+}
+
+impl<S, T, E> IntoResponse for JsonArrayStream<S, T>
+where
+    S: Stream<Item = Result<T, E>> + Send + 'static,
+    T: Serialize,
+    E: Into<BoxError>,
+{
+    fn into_response(self) -> axum::response::Response {
+        let items = self
+            .stream
+            .enumerate()
+            .map(|(index, item)| -> Result<Bytes, BoxError> {
+                let mut buf = if index > 0 { vec![b','] } else { Vec::new() };
+                serde_json::to_writer(&mut buf, &item.map_err(Into::into)?)?;
+
+                Ok(Bytes::from(buf))
+            });
+
+        let body = stream::once(ready(Ok(Bytes::from_static(b"["))))
+            .chain(items)
+            .chain(stream::once(ready(Ok(Bytes::from_static(b"]")))));
+
+        (
+            [(header::CONTENT_TYPE, "application/json")],
+            StreamBody::new(body),
+        )
+            .into_response()
+    }
+}
+
+impl<S, T: JsonSchema> OperationOutput for JsonArrayStream<S, T> {
+    type Inner = Vec<T>;
+
+    fn operation_response(ctx: &mut GenContext, operation: &mut Operation) -> Option<Response> {
+        Json::<Vec<T>>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Json::<Vec<T>>::inferred_responses(ctx, operation)
+    }
+}
@@ -11,38 +11,117 @@
 #![deny(missing_docs)]
 #![deny(clippy::missing_docs_in_private_items)]
 
+/// Structured request access logging.
+mod access_log;
+
 /// API authentication middleware and helpers.
 mod auth;
 
+/// Contract event broadcasting.
+mod broadcast;
+
+/// Client IP address extraction.
+mod client_ip;
+
+/// Cross-origin resource sharing middleware.
+mod cors;
+
+/// HTTP response compression middleware.
+mod compression;
+
+/// Conditional request extractors.
+mod conditional;
+
+/// Streamed CSV responses.
+mod csv;
+
+/// GraphQL API mirroring the public contract information routes.
+mod graphql;
+
 /// Route handlers.
 mod handlers;
 
 /// Hex-encoded array wrapper.
 mod hex_hash;
 
+/// Idempotency key extractor.
+mod idempotency;
+
+/// Streamed JSON array responses.
+mod json_stream;
+
+/// Prometheus metrics.
+mod metrics;
+
+/// Response content negotiation.
+mod negotiation;
+
 /// Resource pagination structs.
 mod pagination;
 
+/// Per-client request rate limiting.
+mod rate_limit;
+
+/// Request identifier propagation and tracing.
+mod request_id;
+
+/// Event retention policy.
+mod retention;
+
+/// Unix socket listener.
+mod unix_socket;
+
 /// Validated JSON bodies.
 mod validation;
 
 /// [`schemars`] crate helper functions.
 mod schema;
 
+/// Server-sent event responses.
+mod sse;
+
+/// SS58 account encoding.
+mod ss58;
+
+/// API server router state.
+mod state;
+
+/// Contract event webhook delivery.
+mod webhook;
+
 #[cfg(test)]
 mod testing;
 
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use aide::{
     axum::ApiRouter,
-    openapi::{OpenApi, SecurityScheme, Tag},
+    openapi::{self, OpenApi, SecurityScheme, Tag},
     transform::TransformOpenApi,
 };
-use axum::{middleware::from_fn_with_state, Extension, Server};
-use common::{config::Config, logging};
+use axum::{
+    extract::FromRef,
+    middleware::{from_fn, from_fn_with_state},
+    routing::{get, post},
+    Extension, Router, Server,
+};
+use broadcast::EventBroadcast;
+use common::{
+    config::{Config, ListenAddress, RouteGroup},
+    logging,
+};
 use db::{Database, DatabaseConnection};
-use tracing::info;
+use metrics::Metrics;
+use rate_limit::RateLimiter;
+use request_id::RequestSpan;
+use state::{AppState, ReadReplica};
+use tokio::sync::oneshot;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
+use tracing::{error, info};
+use unix_socket::UnixAccept;
 
 /// API server entrypoint.
 #[tokio::main]
@@ -55,28 +134,146 @@ async fn main() -> Result<(), anyhow::Error> {
         return Err(anyhow::Error::msg("unable to load server config"));
     };
 
+    let listen_address = server_config.listen_address()?;
+
     info!("connecting to database");
-    let database = Arc::new(Database::connect(&config.database.url).await?);
+    let database = Arc::new(Database::connect(config.database.connect_options()).await?);
     info!("database connection established");
-    let server = Server::bind(&server_config.address);
+
+    let read_replica = match config.database.read_replica_connect_options() {
+        Some(options) => {
+            info!("connecting to read replica database");
+            let read_replica = Arc::new(Database::connect(options).await?);
+            info!("read replica database connection established");
+            Some(read_replica)
+        }
+        None => None,
+    };
+
+    handlers::admin::migrations::ensure_applied(&database, server_config.run_migrations).await?;
+
+    let metrics_address = server_config.metrics_address;
+    let webhook_private_addresses = server_config.webhook_private_addresses;
+    let embedded_event_client = server_config.embedded_event_client;
     let config = Arc::new(config);
 
-    let mut api = OpenApi::default();
+    let event_client = embedded_event_client.then(|| {
+        let (shutdown_sender, shutdown) = oneshot::channel::<()>();
+        let database = (*database).clone();
+        let config = config.clone();
 
-    server
-        .serve(
-            app_router(database, config)
-                .finish_api_with(&mut api, api_docs)
-                .layer(Extension(Arc::new(api)))
-                .into_make_service(),
-        )
-        .await?;
+        let handle = tokio::spawn(async move {
+            let shutdown = async move {
+                let _ = shutdown.await;
+            };
+
+            if let Err(err) = event_client::run_event_client(database, config, shutdown).await {
+                error!(%err, "embedded event client stopped");
+            }
+        });
+
+        (shutdown_sender, handle)
+    });
+
+    let event_broadcast = EventBroadcast::default();
+    tokio::spawn({
+        let event_broadcast = event_broadcast.clone();
+        let database = database.clone();
+
+        async move {
+            if let Err(err) = event_broadcast.watch(database).await {
+                error!(%err, "contract event broadcast stopped");
+            }
+        }
+    });
+
+    tokio::spawn(webhook::deliver(
+        database.clone(),
+        webhook_private_addresses,
+    ));
+
+    tokio::spawn(auth::cleanup_expired_tokens(
+        database.clone(),
+        config.clone(),
+    ));
+
+    tokio::spawn(retention::prune_events(database.clone(), config.clone()));
+
+    let metrics = Metrics::default();
+
+    if let Some(metrics_address) = metrics_address {
+        let router = metrics::router(metrics.clone(), database.clone());
+
+        tokio::spawn(async move {
+            info!(%metrics_address, "serving metrics on a separate address");
+
+            if let Err(err) = Server::bind(&metrics_address)
+                .serve(router.into_make_service())
+                .await
+            {
+                error!(%err, "metrics server stopped");
+            }
+        });
+    }
+
+    let router = documented_router(AppState::new(database, read_replica), config)
+        .layer(Extension(event_broadcast))
+        .layer(Extension(metrics));
+
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("shutting down");
+    };
+
+    match listen_address {
+        ListenAddress::Tcp(address) => {
+            info!(%address, "serving API");
+
+            Server::try_bind(&address)?
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                .with_graceful_shutdown(shutdown)
+                .await?;
+        }
+        ListenAddress::Unix(path) => {
+            info!(path = %path.display(), "serving API on a unix socket");
+
+            // Peer addresses are not available, thus client IP addresses
+            // are always taken from forwarded headers.
+            Server::builder(UnixAccept::bind(&path)?)
+                .serve(router.into_make_service())
+                .with_graceful_shutdown(shutdown)
+                .await?;
+        }
+    }
+
+    if let Some((shutdown_sender, handle)) = event_client {
+        let _ = shutdown_sender.send(());
+        handle.await?;
+    }
 
     Ok(())
 }
 
 /// Construct a [`ApiRouter`] with API server endpoints.
-fn app_router(database: Arc<DatabaseConnection>, config: Arc<Config>) -> ApiRouter {
+///
+/// Read-only routes are served using the primary database
+/// if the provided state doesn't contain a read replica connection.
+fn app_router(state: impl Into<AppState>, config: Arc<Config>) -> ApiRouter {
+    let state = state.into();
+    let database = state.database().clone();
+
+    let serve_metrics = config
+        .server
+        .as_ref()
+        .and_then(|server| server.metrics_address)
+        .is_none();
+    let serve_graphql = matches!(config.server.as_ref(), Some(server) if server.graphql);
+    let serve_docs = matches!(config.server.as_ref(), Some(server) if server.docs);
+    let rate_limiter = RateLimiter::new(&config.rate_limit);
+    let cors = cors::layer(&config.cors);
+    let compression = compression::layer(&config.compression);
+    let request_id_header = request_id::header_name(&config);
+
     let mixed_routes = ApiRouter::new()
         .nest(
             "/sourceCode",
@@ -84,11 +281,26 @@ fn app_router(database: Arc<DatabaseConnection>, config: Arc<Config>) -> ApiRout
         )
         .nest(
             "/buildSessions",
-            handlers::build_sessions::routes(database.clone(), config.clone()),
+            handlers::build_sessions::routes(
+                database.clone(),
+                config.clone(),
+                rate_limiter.clone(),
+            ),
+        )
+        .nest(
+            "/contracts",
+            protect_route_group(
+                handlers::contracts::routes(database.clone(), config.clone()),
+                RouteGroup::Contracts,
+                &database,
+                &config,
+            ),
         );
 
     let protected_routes = ApiRouter::new()
-        .nest("/keys", handlers::keys::routes())
+        .nest("/keys", handlers::keys::routes(rate_limiter.clone()))
+        .nest("/nodes", handlers::nodes::routes())
+        .nest("/webhooks", handlers::webhooks::routes())
         .route_layer(from_fn_with_state(
             (database.clone(), config.clone()),
             auth::require_authentication::<false, false, _>,
@@ -103,22 +315,161 @@ fn app_router(database: Arc<DatabaseConnection>, config: Arc<Config>) -> ApiRout
         ))
         .with_path_items(|op| op.security_requirement("Authentication token"));
 
-    ApiRouter::new()
+    let admin_routes = ApiRouter::new()
+        .nest("/admin", handlers::admin::routes())
+        .route_layer(from_fn_with_state(
+            (database.clone(), config.clone()),
+            auth::require_authentication::<false, false, _>,
+        ))
+        .with_path_items(|op| op.security_requirement("Authentication token"));
+
+    let mut router = ApiRouter::new()
         .merge(mixed_routes)
         .merge(protected_routes)
         .merge(payment_routes)
-        .nest("/auth", handlers::auth::routes())
-        .nest("/contracts", handlers::contracts::routes())
-        .nest("/files", handlers::files::routes())
-        .nest("/docs", handlers::docs::routes())
+        .merge(admin_routes)
+        .nest(
+            "/auth",
+            handlers::auth::routes(database.clone(), config.clone(), rate_limiter),
+        )
+        .merge(handlers::health::routes())
+        .merge(protect_route_group(
+            handlers::subscriptions::routes(),
+            RouteGroup::Subscriptions,
+            &database,
+            &config,
+        ))
+        .nest(
+            "/codes",
+            protect_route_group(
+                handlers::codes::routes(),
+                RouteGroup::Codes,
+                &database,
+                &config,
+            ),
+        )
+        .nest(
+            "/events",
+            protect_route_group(
+                handlers::events::routes(),
+                RouteGroup::Events,
+                &database,
+                &config,
+            ),
+        )
+        .nest(
+            "/files",
+            protect_route_group(
+                handlers::files::routes(),
+                RouteGroup::Files,
+                &database,
+                &config,
+            ),
+        );
+
+    if serve_docs {
+        router = router.merge(handlers::docs::routes());
+    }
+
+    // Metrics route is intentionally left undocumented.
+    if serve_metrics {
+        router = router.route("/metrics", get(metrics::export));
+    }
+
+    // GraphQL schema is documented via introspection instead.
+    if serve_graphql {
+        let mut graphql_route = post(graphql::handler).layer(Extension(graphql::schema(
+            ReadReplica::from_ref(&state).0,
+            config.clone(),
+        )));
+
+        if is_protected(&config, RouteGroup::Graphql) {
+            graphql_route = graphql_route.route_layer(from_fn_with_state(
+                (database.clone(), config.clone()),
+                auth::require_authentication::<false, false, _>,
+            ));
+        }
+
+        router = router.route("/graphql", graphql_route);
+    }
+
+    // CORS layer is applied to all routes, thus preflight requests
+    // are handled before reaching authentication middleware.
+    router
+        .route_layer(from_fn(metrics::track))
+        .route_layer(from_fn(access_log::matched_path))
+        .layer(compression)
+        .layer(cors)
+        .layer(PropagateRequestIdLayer::new(request_id_header.clone()))
+        .layer(TraceLayer::new_for_http().make_span_with(RequestSpan(request_id_header.clone())))
+        .layer(from_fn_with_state(
+            request_id_header.clone(),
+            access_log::log,
+        ))
+        .layer(SetRequestIdLayer::new(request_id_header, MakeRequestUuid))
+        // Access log middleware requires config to determine client IP addresses.
         .layer(Extension(config))
-        .with_state(database)
+        .with_state(state)
+}
+
+/// Check if the provided route group was configured to require authentication.
+fn is_protected(config: &Config, group: RouteGroup) -> bool {
+    config.authentication.protected_routes.contains(&group)
+}
+
+/// Require authentication to access routes of the provided group,
+/// if the group was configured to be protected.
+///
+/// Routes of the group that already require authentication are left intact.
+fn protect_route_group(
+    router: ApiRouter<AppState>,
+    group: RouteGroup,
+    database: &Arc<DatabaseConnection>,
+    config: &Arc<Config>,
+) -> ApiRouter<AppState> {
+    if !is_protected(config, group) {
+        return router;
+    }
+
+    router
+        .route_layer(from_fn_with_state(
+            (database.clone(), config.clone()),
+            auth::require_authentication::<false, false, _>,
+        ))
+        .with_path_items(|op| op.security_requirement("Authentication token"))
+}
+
+/// Construct a [`Router`] with API server endpoints and generated OpenAPI documentation.
+fn documented_router(state: impl Into<AppState>, config: Arc<Config>) -> Router {
+    let mut api = OpenApi::default();
+
+    app_router(state, config.clone())
+        .finish_api_with(&mut api, |api| api_docs(api, &config))
+        .layer(Extension(Arc::new(api)))
 }
 
 /// Document public API using [`aide`] crate.
-fn api_docs(api: TransformOpenApi) -> TransformOpenApi {
-    api.title("Patron")
+fn api_docs<'a>(api: TransformOpenApi<'a>, config: &Config) -> TransformOpenApi<'a> {
+    let servers = config
+        .server
+        .as_ref()
+        .map(|server| server.openapi_servers.clone())
+        .unwrap_or_default();
+
+    servers
+        .into_iter()
+        .fold(api, |api, url| {
+            api.server(openapi::Server {
+                url,
+                ..Default::default()
+            })
+        })
+        .title("Patron")
         .description("API server public routes")
+        .tag(Tag {
+            name: "Administration".into(),
+            ..Default::default()
+        })
         .tag(Tag {
             name: "Authentication".into(),
             ..Default::default()
@@ -131,10 +482,22 @@ fn api_docs(api: TransformOpenApi) -> TransformOpenApi {
             name: "Contract management".into(),
             ..Default::default()
         })
+        .tag(Tag {
+            name: "Event subscriptions".into(),
+            ..Default::default()
+        })
         .tag(Tag {
             name: "File uploads".into(),
             ..Default::default()
         })
+        .tag(Tag {
+            name: "Health checks".into(),
+            ..Default::default()
+        })
+        .tag(Tag {
+            name: "Node management".into(),
+            ..Default::default()
+        })
         .tag(Tag {
             name: "Public key verification".into(),
             ..Default::default()
@@ -147,12 +510,18 @@ fn api_docs(api: TransformOpenApi) -> TransformOpenApi {
             name: "Source code management".into(),
             ..Default::default()
         })
+        .tag(Tag {
+            name: "Webhook management".into(),
+            ..Default::default()
+        })
         .security_scheme(
             "Authentication token",
             SecurityScheme::Http {
                 scheme: String::from("bearer"),
                 bearer_format: None,
-                description: None,
+                description: Some(String::from(
+                    "Authentication token issued by the authentication routes.",
+                )),
                 extensions: Default::default(),
             },
         )
@@ -0,0 +1,276 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::{MatchedPath, State},
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Router,
+};
+use db::{node, DatabaseConnection, DbErr, EntityTrait, QueryOrder};
+use derive_more::{Display, Error, From};
+use error_response::ErrorResponse;
+
+/// Upper bounds of request latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Request latency histogram.
+#[derive(Default)]
+struct Histogram {
+    /// Cumulative request counts for each of the [`LATENCY_BUCKETS`].
+    buckets: [u64; LATENCY_BUCKETS.len()],
+
+    /// Sum of all observed latencies, in seconds.
+    sum: f64,
+
+    /// Count of all observed requests.
+    count: u64,
+}
+
+/// Collected HTTP request metrics.
+#[derive(Default)]
+struct Registry {
+    /// Request counts, keyed by method, route and response status code.
+    requests: BTreeMap<(String, String, u16), u64>,
+
+    /// Request latencies, keyed by method and route.
+    latencies: BTreeMap<(String, String), Histogram>,
+}
+
+/// Shared in-memory HTTP request metrics storage.
+#[derive(Clone, Default)]
+pub(crate) struct Metrics(Arc<Mutex<Registry>>);
+
+impl Metrics {
+    /// Record a single handled request.
+    fn record(&self, method: &str, route: &str, status: u16, elapsed: f64) {
+        let mut registry = self.0.lock().unwrap();
+
+        *registry
+            .requests
+            .entry((method.to_owned(), route.to_owned(), status))
+            .or_default() += 1;
+
+        let histogram = registry
+            .latencies
+            .entry((method.to_owned(), route.to_owned()))
+            .or_default();
+
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if elapsed <= bound {
+                *bucket += 1;
+            }
+        }
+
+        histogram.sum += elapsed;
+        histogram.count += 1;
+    }
+
+    /// Render collected request metrics in a Prometheus text format.
+    fn render(&self, out: &mut String) {
+        let registry = self.0.lock().unwrap();
+
+        out.push_str("# HELP patron_http_requests_total Total count of handled HTTP requests.\n");
+        out.push_str("# TYPE patron_http_requests_total counter\n");
+
+        for ((method, route, status), count) in &registry.requests {
+            let _ = writeln!(
+                out,
+                r#"patron_http_requests_total{{method="{method}",route="{}",status="{status}"}} {count}"#,
+                escape(route)
+            );
+        }
+
+        out.push_str("# HELP patron_http_request_duration_seconds HTTP request latencies.\n");
+        out.push_str("# TYPE patron_http_request_duration_seconds histogram\n");
+
+        for ((method, route), histogram) in &registry.latencies {
+            let route = escape(route);
+
+            for (count, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    r#"patron_http_request_duration_seconds_bucket{{method="{method}",route="{route}",le="{bound}"}} {count}"#,
+                );
+            }
+
+            let _ = writeln!(
+                out,
+                r#"patron_http_request_duration_seconds_bucket{{method="{method}",route="{route}",le="+Inf"}} {}"#,
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                r#"patron_http_request_duration_seconds_sum{{method="{method}",route="{route}"}} {}"#,
+                histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                r#"patron_http_request_duration_seconds_count{{method="{method}",route="{route}"}} {}"#,
+                histogram.count
+            );
+        }
+    }
+}
+
+/// Escape a Prometheus label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+/// Errors that may occur during the metrics export.
+#[derive(ErrorResponse, Display, From, Error)]
+pub(crate) enum MetricsError {
+    /// Database-related error.
+    DatabaseError(DbErr),
+}
+
+/// Request metrics recording middleware.
+///
+/// Requests are recorded only if the [`Metrics`] extension is present.
+pub(crate) async fn track<B>(request: Request<B>, next: Next<B>) -> Response {
+    let (Some(metrics), Some(route)) = (
+        request.extensions().get::<Metrics>().cloned(),
+        request.extensions().get::<MatchedPath>().cloned(),
+    ) else {
+        return next.run(request).await;
+    };
+
+    let method = request.method().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics.record(
+        &method,
+        route.as_str(),
+        response.status().as_u16(),
+        start.elapsed().as_secs_f64(),
+    );
+
+    response
+}
+
+/// Prometheus metrics export handler.
+pub(crate) async fn export(
+    Extension(metrics): Extension<Metrics>,
+    State(db): State<Arc<DatabaseConnection>>,
+) -> Result<impl IntoResponse, MetricsError> {
+    let mut out = String::new();
+
+    metrics.render(&mut out);
+
+    if let DatabaseConnection::SqlxPostgresPoolConnection(_) = &*db {
+        let pool = db.get_postgres_connection_pool();
+        let idle = pool.num_idle() as u64;
+
+        out.push_str("# HELP patron_db_connections Database connection pool usage.\n");
+        out.push_str("# TYPE patron_db_connections gauge\n");
+        let _ = writeln!(out, r#"patron_db_connections{{state="idle"}} {idle}"#);
+        let _ = writeln!(
+            out,
+            r#"patron_db_connections{{state="active"}} {}"#,
+            u64::from(pool.size()).saturating_sub(idle)
+        );
+    }
+
+    let nodes = node::Entity::find()
+        .order_by_asc(node::Column::Id)
+        .all(&*db)
+        .await?;
+
+    out.push_str(
+        "# HELP patron_node_confirmed_block Latest block processed by the event client.\n",
+    );
+    out.push_str("# TYPE patron_node_confirmed_block gauge\n");
+
+    for node in nodes {
+        let _ = writeln!(
+            out,
+            r#"patron_node_confirmed_block{{node="{}"}} {}"#,
+            escape(&node.name),
+            node.confirmed_block
+        );
+    }
+
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], out))
+}
+
+/// Create a standalone [`Router`] that serves metrics on a separate address.
+pub(crate) fn router(metrics: Metrics, database: Arc<DatabaseConnection>) -> Router {
+    Router::new()
+        .route("/metrics", get(export))
+        .layer(Extension(metrics))
+        .with_state(database)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, ResponseBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Extension,
+    };
+    use common::config::Config;
+    use tower::Service;
+
+    use super::Metrics;
+
+    #[tokio::test]
+    async fn counts_requests() {
+        let db = create_database().await;
+
+        let mut service = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .layer(Extension(Metrics::default()));
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri(format!("/codes/{}/metadata", "00".repeat(32)))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = service
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/metrics")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.text().await;
+
+        assert!(body
+            .contains(r#"patron_http_requests_total{method="GET",route="/codes/:code_hash/metadata",status="404"} 1"#));
+        assert!(body.contains("# TYPE patron_node_confirmed_block gauge"));
+    }
+}
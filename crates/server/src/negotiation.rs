@@ -0,0 +1,139 @@
+use std::convert::Infallible;
+
+use aide::{
+    gen::GenContext,
+    openapi::{Operation, Response},
+    OperationInput, OperationOutput,
+};
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// MessagePack media type.
+const MSGPACK: &str = "application/msgpack";
+
+/// Response body encoding requested by a client using the `Accept` header.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ResponseFormat {
+    /// JSON encoding, used unless MessagePack is explicitly accepted.
+    #[default]
+    Json,
+
+    /// MessagePack encoding.
+    MessagePack,
+}
+
+impl ResponseFormat {
+    /// Determine the response format from the `Accept` header value.
+    fn from_accept(accept: &str) -> Self {
+        let msgpack_accepted = accept.split(',').any(|range| {
+            let mut params = range.split(';').map(str::trim);
+
+            params
+                .next()
+                .is_some_and(|media_type| media_type.eq_ignore_ascii_case(MSGPACK))
+                && !params.any(|param| matches!(param, "q=0" | "q=0.0" | "q=0.00" | "q=0.000"))
+        });
+
+        if msgpack_accepted {
+            Self::MessagePack
+        } else {
+            Self::Json
+        }
+    }
+}
+
+/// `Accept` header is ignored by OpenAPI when described as a parameter,
+/// thus MessagePack support is mentioned in route descriptions instead.
+impl OperationInput for ResponseFormat {}
+
+#[async_trait]
+impl<S: Sync> FromRequestParts<S> for ResponseFormat {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(Self::from_accept)
+            .find(|format| *format == Self::MessagePack)
+            .unwrap_or_default())
+    }
+}
+
+/// Response body serialized according to the requested [`ResponseFormat`].
+///
+/// OpenAPI documentation describes the JSON representation only.
+pub(crate) struct Negotiated<T>(pub ResponseFormat, pub T);
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> axum::response::Response {
+        let vary = [(header::VARY, HeaderValue::from_static("accept"))];
+
+        match self.0 {
+            ResponseFormat::Json => (vary, Json(self.1)).into_response(),
+            ResponseFormat::MessagePack => match rmp_serde::to_vec_named(&self.1) {
+                Ok(body) => (
+                    vary,
+                    [(header::CONTENT_TYPE, HeaderValue::from_static(MSGPACK))],
+                    body,
+                )
+                    .into_response(),
+                Err(err) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    [(
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("text/plain; charset=utf-8"),
+                    )],
+                    err.to_string(),
+                )
+                    .into_response(),
+            },
+        }
+    }
+}
+
+impl<T: JsonSchema> OperationOutput for Negotiated<T> {
+    type Inner = T;
+
+    fn operation_response(ctx: &mut GenContext, operation: &mut Operation) -> Option<Response> {
+        Json::<T>::operation_response(ctx, operation)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Json::<T>::inferred_responses(ctx, operation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseFormat;
+
+    #[test]
+    fn accept_header() {
+        for (accept, expected) in [
+            ("application/msgpack", ResponseFormat::MessagePack),
+            (
+                "application/json;q=0.5, application/msgpack",
+                ResponseFormat::MessagePack,
+            ),
+            ("Application/MsgPack; q=0.8", ResponseFormat::MessagePack),
+            ("application/msgpack;q=0", ResponseFormat::Json),
+            ("application/json", ResponseFormat::Json),
+            ("*/*", ResponseFormat::Json),
+        ] {
+            assert_eq!(ResponseFormat::from_accept(accept), expected, "{accept}");
+        }
+    }
+}
@@ -1,14 +1,27 @@
-use std::num::NonZeroU64;
+use std::{fmt::Display, num::NonZeroU64};
 
+use aide::{
+    gen::GenContext,
+    openapi::{
+        Header, HeaderStyle, Operation, ParameterSchemaOrContent, ReferenceOr, Response,
+        SchemaObject,
+    },
+    OperationOutput,
+};
+use axum::{
+    http::{header, HeaderName, HeaderValue, Uri},
+    response::IntoResponse,
+};
+use common::config;
 use schemars::JsonSchema;
 use serde::Deserialize;
 
-/// Count of items per page.
-pub const PER_PAGE: u64 = 25;
-
 /// Total page limit.
 pub const MAX_PAGES: u64 = 10000;
 
+/// Name of a header that contains the total count of list items.
+static X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
 /// Pagination helper for the [`Query`] extractor.
 ///
 /// [`Query`]: axum::extract::Query
@@ -27,12 +40,199 @@ fn default_page() -> NonZeroU64 {
 
 impl Pagination {
     /// Get `LIMIT` value for a SQL query.
-    pub fn limit(&self) -> u64 {
-        PER_PAGE
+    pub fn limit(&self, config: &config::Pagination) -> u64 {
+        config.page_size()
+    }
+
+    /// Get `OFFSET` value for a SQL query.
+    pub fn offset(&self, config: &config::Pagination) -> u64 {
+        (self.page.get().min(MAX_PAGES) - 1) * config.page_size()
+    }
+}
+
+/// Offset-based pagination helper for the [`Query`] extractor.
+///
+/// [`Query`]: axum::extract::Query
+#[derive(Deserialize, JsonSchema)]
+pub struct OffsetPagination {
+    /// Count of items to return.
+    ///
+    /// Defaults to 25, values larger than 100 are capped,
+    /// unless configured otherwise by the server.
+    #[serde(default)]
+    #[schemars(example = "crate::schema::example_limit")]
+    pub(crate) limit: Option<u64>,
+
+    /// Count of items to skip.
+    #[serde(default)]
+    pub(crate) offset: u64,
+}
+
+impl OffsetPagination {
+    /// Get `LIMIT` value for a SQL query.
+    pub fn limit(&self, config: &config::Pagination) -> u64 {
+        config.limit(self.limit)
     }
 
     /// Get `OFFSET` value for a SQL query.
     pub fn offset(&self) -> u64 {
-        (self.page.get().min(MAX_PAGES) - 1) * PER_PAGE
+        self.offset
+    }
+}
+
+/// List response with pagination metadata exposed
+/// using the `Link` and `X-Total-Count` headers.
+///
+/// Links are relative to the request URI and keep
+/// other query string parameters intact.
+pub(crate) struct Paginated<T> {
+    /// Response body.
+    body: T,
+
+    /// Total count of items matching the request filters.
+    total: u64,
+
+    /// Link relation types and their target URIs.
+    links: Vec<(&'static str, String)>,
+}
+
+impl<T> Paginated<T> {
+    /// Create new [`Paginated`] response of a list requested with [`OffsetPagination`].
+    pub(crate) fn offset(
+        body: T,
+        uri: &Uri,
+        pagination: &OffsetPagination,
+        config: &config::Pagination,
+        total: u64,
+    ) -> Self {
+        let (limit, offset) = (pagination.limit(config), pagination.offset());
+
+        let mut links = Vec::new();
+
+        if limit > 0 && offset.saturating_add(limit) < total {
+            links.push(("next", with_query_param(uri, "offset", offset + limit)));
+        }
+
+        if offset > 0 {
+            links.push((
+                "prev",
+                with_query_param(uri, "offset", offset.saturating_sub(limit)),
+            ));
+        }
+
+        Self { body, total, links }
+    }
+
+    /// Create new [`Paginated`] response of a cursor-based list.
+    ///
+    /// Cursors only point to older items, thus only the `next` link is provided.
+    pub(crate) fn cursor<C: Display>(
+        body: T,
+        uri: &Uri,
+        param: &str,
+        next: Option<C>,
+        total: u64,
+    ) -> Self {
+        Self {
+            body,
+            total,
+            links: next
+                .map(|cursor| ("next", with_query_param(uri, param, cursor)))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Replace the value of a query string parameter of the provided URI.
+fn with_query_param<V: Display>(uri: &Uri, name: &str, value: V) -> String {
+    let param = format!("{name}={value}");
+
+    let params: Vec<_> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| !param.is_empty() && param.split('=').next() != Some(name))
+        .chain([param.as_str()])
+        .collect();
+
+    format!("{}?{}", uri.path(), params.join("&"))
+}
+
+impl<T: IntoResponse> IntoResponse for Paginated<T> {
+    fn into_response(self) -> axum::response::Response {
+        let mut response = self.body.into_response();
+        let headers = response.headers_mut();
+
+        headers.insert(X_TOTAL_COUNT.clone(), HeaderValue::from(self.total));
+
+        if !self.links.is_empty() {
+            let link = self
+                .links
+                .iter()
+                .map(|(rel, uri)| format!("<{uri}>; rel=\"{rel}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if let Ok(value) = HeaderValue::from_str(&link) {
+                headers.insert(header::LINK, value);
+            }
+        }
+
+        response
+    }
+}
+
+impl<T: OperationOutput> OperationOutput for Paginated<T> {
+    type Inner = T::Inner;
+
+    fn operation_response(ctx: &mut GenContext, operation: &mut Operation) -> Option<Response> {
+        let mut response = T::operation_response(ctx, operation)?;
+
+        let headers = [
+            (
+                X_TOTAL_COUNT.as_str(),
+                "Total count of items matching the request filters.",
+                ctx.schema.subschema_for::<u64>(),
+                true,
+            ),
+            (
+                header::LINK.as_str(),
+                "Links to the adjacent pages, if there are any.",
+                ctx.schema.subschema_for::<String>(),
+                false,
+            ),
+        ];
+
+        for (name, description, schema, required) in headers {
+            response.headers.insert(
+                name.to_string(),
+                ReferenceOr::Item(Header {
+                    description: Some(String::from(description)),
+                    style: HeaderStyle::Simple,
+                    required,
+                    deprecated: None,
+                    format: ParameterSchemaOrContent::Schema(SchemaObject {
+                        json_schema: schema,
+                        example: None,
+                        external_docs: None,
+                    }),
+                    example: None,
+                    examples: Default::default(),
+                    extensions: Default::default(),
+                }),
+            );
+        }
+
+        Some(response)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| Vec::from([(Some(200), response)]))
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use common::config::RateLimit;
+use derive_more::{Display, Error};
+use error_response::ErrorResponse;
+
+use crate::{auth::AuthenticatedUserId, client_ip::ClientIp};
+
+/// Count of tracked keys, after which fully refilled buckets are discarded.
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// Errors that may occur during the rate limit check.
+#[derive(ErrorResponse, Display, Error)]
+pub(crate) enum RateLimitError {
+    /// Request limit was exceeded.
+    #[status(StatusCode::TOO_MANY_REQUESTS)]
+    #[display(fmt = "too many requests")]
+    TooManyRequests,
+}
+
+/// Rate limiting key, which identifies a client.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    /// Authenticated user identifier.
+    User(i64),
+
+    /// Client IP address, used for anonymous requests.
+    Ip(IpAddr),
+
+    /// Shared key of anonymous requests without a known client IP address,
+    /// such as requests received on a unix socket without forwarding headers.
+    Unknown,
+}
+
+/// Token bucket state of a single client.
+struct Bucket {
+    /// Count of currently available tokens.
+    tokens: f64,
+
+    /// Last time the token count was updated.
+    updated: Instant,
+}
+
+/// Shared rate limiter state.
+struct Inner {
+    /// Maximum count of tokens in a single bucket.
+    capacity: f64,
+
+    /// Count of tokens refilled each second.
+    refill_rate: f64,
+
+    /// Token buckets of all tracked clients.
+    buckets: Mutex<HashMap<Key, Bucket>>,
+}
+
+/// In-memory token bucket rate limiter.
+#[derive(Clone)]
+pub(crate) struct RateLimiter(Arc<Inner>);
+
+impl RateLimiter {
+    /// Create new rate limiter using the provided configuration.
+    pub(crate) fn new(config: &RateLimit) -> Self {
+        let capacity = f64::from(config.requests.max(1));
+
+        Self(Arc::new(Inner {
+            capacity,
+            refill_rate: capacity / config.window.max(1) as f64,
+            buckets: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// Take a single token from the bucket of the provided key.
+    ///
+    /// If no tokens are available, the count of seconds until
+    /// the next token is available is returned instead.
+    fn acquire(&self, key: Key) -> Result<(), u64> {
+        let Inner {
+            capacity,
+            refill_rate,
+            buckets,
+        } = &*self.0;
+
+        let now = Instant::now();
+        let refill = |bucket: &mut Bucket| {
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+
+            bucket.tokens = (bucket.tokens + elapsed * refill_rate).min(*capacity);
+            bucket.updated = now;
+        };
+
+        let mut buckets = buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_KEYS {
+            buckets.retain(|_, bucket| {
+                refill(bucket);
+                bucket.tokens < *capacity
+            });
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: *capacity,
+            updated: now,
+        });
+
+        refill(bucket);
+
+        if bucket.tokens >= 1. {
+            bucket.tokens -= 1.;
+            Ok(())
+        } else {
+            Err(((1. - bucket.tokens) / refill_rate).ceil().max(1.) as u64)
+        }
+    }
+}
+
+/// Rate limiting middleware for [`axum`].
+///
+/// Authenticated requests are limited per user, while anonymous requests
+/// are limited per client IP address. Anonymous requests without a known
+/// client IP address share a single limit.
+pub(crate) async fn limit<B>(
+    State(limiter): State<RateLimiter>,
+    ClientIp(client_ip): ClientIp,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let key = match request.extensions().get::<AuthenticatedUserId>() {
+        Some(user) => Key::User(user.id()),
+        None => client_ip.map_or(Key::Unknown, Key::Ip),
+    };
+
+    match limiter.acquire(key) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => (
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            RateLimitError::TooManyRequests,
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::{create_database, RequestBodyExt};
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+    };
+    use common::config::{Config, RateLimit};
+    use db::{token, user, DatabaseConnection, EntityTrait};
+    use serde_json::json;
+    use tower::Service;
+
+    fn config() -> Arc<Config> {
+        let mut config = Config::for_tests();
+
+        config.rate_limit = RateLimit {
+            requests: 2,
+            window: 60,
+        };
+
+        Arc::new(config)
+    }
+
+    async fn create_user(db: &DatabaseConnection) -> String {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        let (model, token) = token::generate_token(user.id, token::TOKEN_LIFESPAN);
+
+        token::Entity::insert(model)
+            .exec_without_returning(db)
+            .await
+            .expect("unable to insert token");
+
+        token
+    }
+
+    fn register(ip: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder().method("POST").uri("/auth/register");
+
+        if let Some(ip) = ip {
+            request = request.header("X-Forwarded-For", ip);
+        }
+
+        request.body(Body::empty()).unwrap()
+    }
+
+    fn delete_key(token: &str) -> Request<Body> {
+        Request::builder()
+            .method("DELETE")
+            .uri("/keys")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("X-Forwarded-For", "10.0.0.1")
+            .header("Content-Type", "application/json")
+            .body(Body::from_json(json!({
+                "account": "5FeLhJAs4CUHqpWmPDBLeL7NLAoHsB2ZuFZ5Mk62EgYemtFj",
+            })))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn limited_by_ip() {
+        let db = create_database().await;
+
+        let mut service = crate::app_router(Arc::new(db), config());
+
+        for _ in 0..2 {
+            let response = service.call(register(Some("10.0.0.1"))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = service.call(register(Some("10.0.0.1"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+
+        let response = service.call(register(Some("10.0.0.2"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn unknown_ip() {
+        let db = create_database().await;
+
+        let mut service = crate::app_router(Arc::new(db), config());
+
+        for _ in 0..2 {
+            let response = service.call(register(None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = service.call(register(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = service.call(register(Some("10.0.0.1"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn limited_by_user() {
+        let db = create_database().await;
+
+        let first_token = create_user(&db).await;
+        let second_token = create_user(&db).await;
+
+        let mut service = crate::app_router(Arc::new(db), config());
+
+        for _ in 0..2 {
+            let response = service.call(delete_key(&first_token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = service.call(delete_key(&first_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let response = service.call(delete_key(&second_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,104 @@
+use axum::http::{HeaderName, Request};
+use common::config::Config;
+use tower_http::trace::MakeSpan;
+use tracing::{info_span, warn, Span};
+
+/// Request identifier header used if no valid header name was configured.
+const DEFAULT_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Get the configured request identifier header name.
+pub(crate) fn header_name(config: &Config) -> HeaderName {
+    let Some(server) = config.server.as_ref() else {
+        return DEFAULT_HEADER;
+    };
+
+    match HeaderName::from_bytes(server.request_id_header.as_bytes()) {
+        Ok(name) => name,
+        Err(_) => {
+            warn!(
+                header = %server.request_id_header,
+                "invalid request id header name, using the default one"
+            );
+            DEFAULT_HEADER
+        }
+    }
+}
+
+/// [`MakeSpan`] implementation that attaches request identifiers to spans.
+///
+/// All events emitted while handling a request, including database queries
+/// and RPC calls, inherit the created span.
+#[derive(Clone)]
+pub(crate) struct RequestSpan(pub HeaderName);
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(&self.0)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        info_span!(
+            "request",
+            request_id,
+            method = %request.method(),
+            uri = %request.uri(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::create_database;
+
+    use axum::{body::Body, http::Request};
+    use common::config::Config;
+    use tower::Service;
+
+    #[tokio::test]
+    async fn generated() {
+        let db = create_database().await;
+
+        let response = crate::app_router(Arc::new(db), Arc::new(Config::for_tests()))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let request_id = response.headers()["x-request-id"].to_str().unwrap();
+
+        // UUID string length.
+        assert_eq!(request_id.len(), 36);
+    }
+
+    #[tokio::test]
+    async fn propagated() {
+        let db = create_database().await;
+
+        let mut config = Config::for_tests();
+        config.server.as_mut().unwrap().request_id_header = String::from("x-correlation-id");
+
+        let response = crate::app_router(Arc::new(db), Arc::new(config))
+            .call(
+                Request::builder()
+                    .method("GET")
+                    .uri("/keys")
+                    .header("x-correlation-id", "test-id")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()["x-correlation-id"], "test-id");
+        assert!(!response.headers().contains_key("x-request-id"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::{sync::Arc, time::Duration};
+
+use common::config::Config;
+use db::{event, DatabaseConnection, PrimitiveDateTime};
+use tracing::{error, info};
+
+/// Periodically delete events older than the configured maximum age.
+///
+/// Does nothing if the maximum event age is not configured.
+pub(crate) async fn prune_events(db: Arc<DatabaseConnection>, config: Arc<Config>) {
+    let Some(max_age) = config.event_retention.max_age else {
+        return;
+    };
+
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.event_retention.prune_interval.get(),
+    ));
+
+    loop {
+        interval.tick().await;
+
+        // Cutoff that can not be represented is older than any stored event.
+        let Some(before) = prune_cutoff(db::now(), max_age) else {
+            continue;
+        };
+
+        match event::prune(&*db, before, config.event_retention.batch_size).await {
+            Ok(deleted) => info!(deleted, "pruned expired events"),
+            Err(err) => error!(%err, "unable to prune expired events"),
+        }
+    }
+}
+
+/// Get the timestamp before which events older than `max_age` days were discovered.
+///
+/// [`None`] if the timestamp can not be represented.
+fn prune_cutoff(now: PrimitiveDateTime, max_age: u64) -> Option<PrimitiveDateTime> {
+    let seconds = i64::try_from(max_age).ok()?.checked_mul(86_400)?;
+
+    now.checked_sub(db::Duration::seconds(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{OffsetDateTime, PrimitiveDateTime};
+
+    use super::prune_cutoff;
+
+    #[test]
+    fn cutoff() {
+        let now = OffsetDateTime::from_unix_timestamp(1_000_000_000).unwrap();
+        let now = PrimitiveDateTime::new(now.date(), now.time());
+
+        assert_eq!(
+            prune_cutoff(now, 90).map(|cutoff| cutoff.assume_utc().unix_timestamp()),
+            Some(1_000_000_000 - 90 * 86_400)
+        );
+        assert_eq!(prune_cutoff(now, 10_000_000), None);
+        assert_eq!(prune_cutoff(now, u64::MAX), None);
+    }
+}
@@ -1,15 +1,18 @@
-use std::fmt::Display;
-
 use axum::response::IntoResponse;
 use common::rpc::sp_core::{
     crypto::{AccountId32, Ss58Codec},
     sr25519::{Pair, Public, Signature},
     Pair as _,
 };
-use db::{build_session, event::EventBody};
-use serde_json::{json, Value};
+use db::{
+    backfill_job, build_session,
+    event::{self, EventBody},
+};
+use futures_util::FutureExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::hex_hash::HexHash;
+use crate::{hex_hash::HexHash, validation::FieldError};
 
 /// Generate example values for OAPI documentation.
 macro_rules! generate_examples {
@@ -28,26 +31,73 @@ macro_rules! generate_examples {
     }
 }
 
-/// Convert an error into a JSON value suitable for OAPI documentation.
-pub(crate) fn example_error<E: Display + IntoResponse>(err: E) -> Value {
-    let error = err.to_string();
+/// JSON body of an error response.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ErrorBody {
+    /// Error details.
+    error: ErrorDetails,
+
+    /// Errors related to individual request body fields.
+    ///
+    /// Present only if a request body could not be deserialized or validated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<FieldError>>,
+}
+
+/// Details of an error that occured during request handling.
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct ErrorDetails {
+    /// Stable machine-readable error code.
+    #[schemars(example = "example_error_code")]
+    code: String,
+
+    /// Human-readable error message.
+    ///
+    /// Messages of server errors are not exposed.
+    #[schemars(example = "example_error_message")]
+    message: String,
+}
+
+/// Convert an error into a response body suitable for OAPI documentation.
+pub(crate) fn example_error<E: IntoResponse>(err: E) -> ErrorBody {
+    let body = err.into_response().into_body();
+
+    // Error response bodies are available immediately.
+    let bytes = hyper::body::to_bytes(body)
+        .now_or_never()
+        .expect("error response body is not ready")
+        .expect("unable to read error response body");
 
-    json! {{
-        "code": err.into_response().status().as_u16(),
-        "error": error,
-    }}
+    serde_json::from_slice(&bytes).expect("invalid error response body")
 }
 
 generate_examples!(
     database_identifier, i64, 1;
     hex_hash, HexHash, HexHash([200; 32]);
     cargo_contract_version, String, String::from("4.0.0-alpha");
+    cargo_contract_versions, Vec<String>, vec![String::from("4.0.0-alpha")];
+    cargo_contract_version_filter, Option<String>, Some(String::from("4.0.0-alpha"));
     build_session_status, build_session::Status, build_session::Status::Completed;
+    build_count, u64, 3;
+    worker_count, Option<u64>, Some(4);
     log_position, Option<i64>, Some(40);
     log_entry, String, String::from("Compiling futures-util v0.3.28");
     timestamp, i64, 1672531200;
+    block_number, i64, 4242;
+    event_type, event::EventType, event::EventType::Instantiation;
+    event_cursor, String, String::from("1672531200_1");
+    event_count, u64, 42;
+    limit, Option<u64>, Some(25);
+    search_term, String, String::from("CodeHashUpdate");
+    raw_event_body, String, hex::encode([1; 32]);
+    event_fields, Option<String>, Some(String::from("timestamp,block_number"));
+    deleted_count, u64, 1;
+    public_key_name, Option<String>, Some(String::from("Hardware wallet"));
+    public_key_version, i64, 3;
+    nonce, String, String::from("x4VpkDX2rzYfMhdrI5HH8wg0knBZkHT1");
     account, AccountId32, AccountId32::from_ss58check("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").unwrap();
     public_key, Public, Public(example_account().into());
+    public_keys, Vec<Public>, vec![example_public_key()];
     signature, Signature, Pair::from_seed(&[0; 32]).sign(b"test message");
     token, String, String::from("UYEIngStyH6Bxu1hLFIIwBxLgyMBhMQv4SVR1KzzbvzIDCSMcwwF8ApXagqyuWbh");
     event_body, EventBody, EventBody::CodeHashUpdate {
@@ -59,5 +109,18 @@ generate_examples!(
         String::from("Cargo.toml"),
         String::from("Cargo.lock"),
     ];
-    node, String, String::from("alephzero")
+    node, String, String::from("alephzero");
+    node_url, String, String::from("wss://ws.test.azero.dev");
+    spec_name, Option<String>, Some(String::from("aleph-node"));
+    spec_version, Option<i64>, Some(67);
+    backfill_job_status, backfill_job::Status, backfill_job::Status::Completed;
+    webhook_url, String, String::from("https://example.com/webhook");
+    webhook_secret, String, String::from("MZbDyq1Hf0mYwRYg3UxNnW7BrHJ4ojkf");
+    dead_letter_count, i64, 0;
+    uptime, u64, 3600;
+    migration_name, String, String::from("m20220101_000001_create_users_table");
+    field, String, String::from("account");
+    field_message, String, String::from("invalid ss58 address");
+    error_code, String, String::from("source_code_not_found");
+    error_message, String, String::from("source code not found")
 );
@@ -0,0 +1,9 @@
+use common::{
+    config::Config,
+    rpc::sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec},
+};
+
+/// Encode the provided account using the SS58 address prefix from the server configuration.
+pub(crate) fn encode(account: &AccountId32, config: &Config) -> String {
+    account.to_ss58check_with_version(Ss58AddressFormat::custom(config.ss58_prefix))
+}
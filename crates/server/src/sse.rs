@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use aide::{
+    gen::GenContext,
+    openapi::{MediaType, Operation, Response},
+    OperationOutput,
+};
+use axum::{
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Sse,
+    },
+    BoxError,
+};
+use futures_util::Stream;
+
+/// Interval between heartbeat comments sent to idle connections.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Server-sent event stream response.
+///
+/// Heartbeat comments are sent periodically to ensure that
+/// proxies do not drop idle connections.
+pub(crate) struct EventStream<S>(Sse<S>);
+
+impl<S, E> EventStream<S>
+where
+    S: Stream<Item = Result<Event, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    /// Create new [`EventStream`] from the provided [`Stream`].
+    pub(crate) fn new(stream: S) -> Self {
+        Self(Sse::new(stream).keep_alive(KeepAlive::new().interval(HEARTBEAT_INTERVAL)))
+    }
+}
+
+impl<S, E> IntoResponse for EventStream<S>
+where
+    S: Stream<Item = Result<Event, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    fn into_response(self) -> axum::response::Response {
+        self.0.into_response()
+    }
+}
+
+impl<S> OperationOutput for EventStream<S> {
+    type Inner = ();
+
+    fn operation_response(_ctx: &mut GenContext, _operation: &mut Operation) -> Option<Response> {
+        let mut response = Response {
+            description: "Server-sent event stream".into(),
+            ..Default::default()
+        };
+
+        response
+            .content
+            .insert("text/event-stream".into(), MediaType::default());
+
+        Some(response)
+    }
+
+    fn inferred_responses(
+        ctx: &mut GenContext,
+        operation: &mut Operation,
+    ) -> Vec<(Option<u16>, Response)> {
+        Self::operation_response(ctx, operation)
+            .map(|response| Vec::from([(Some(200), response)]))
+            .unwrap_or_default()
+    }
+}
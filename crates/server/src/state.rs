@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use axum::extract::FromRef;
+use db::DatabaseConnection;
+
+/// API server router state.
+///
+/// Handlers that modify data or have to observe their own writes use the primary
+/// database connection, extracted using `State<Arc<DatabaseConnection>>`,
+/// while read-only handlers may use a [`ReadReplica`] connection instead.
+#[derive(Clone)]
+pub(crate) struct AppState {
+    /// Primary database connection.
+    database: Arc<DatabaseConnection>,
+
+    /// Read replica database connection.
+    ///
+    /// Same as the primary database connection if no read replica is configured.
+    read_replica: Arc<DatabaseConnection>,
+}
+
+impl AppState {
+    /// Create new router state, using the primary database
+    /// for read-only routes if no read replica is provided.
+    pub(crate) fn new(
+        database: Arc<DatabaseConnection>,
+        read_replica: Option<Arc<DatabaseConnection>>,
+    ) -> Self {
+        Self {
+            read_replica: read_replica.unwrap_or_else(|| database.clone()),
+            database,
+        }
+    }
+
+    /// Get the primary database connection.
+    pub(crate) fn database(&self) -> &Arc<DatabaseConnection> {
+        &self.database
+    }
+}
+
+impl From<Arc<DatabaseConnection>> for AppState {
+    fn from(database: Arc<DatabaseConnection>) -> Self {
+        Self::new(database, None)
+    }
+}
+
+impl FromRef<AppState> for Arc<DatabaseConnection> {
+    fn from_ref(state: &AppState) -> Self {
+        state.database.clone()
+    }
+}
+
+/// Read replica database connection, which should be used only by read-only handlers.
+///
+/// Written data may become visible to read replica connections with a delay.
+#[derive(Clone)]
+pub(crate) struct ReadReplica(pub Arc<DatabaseConnection>);
+
+impl FromRef<AppState> for ReadReplica {
+    fn from_ref(state: &AppState) -> Self {
+        Self(state.read_replica.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::extract::FromRef;
+    use db::DatabaseConnection;
+
+    use crate::testing::create_database;
+
+    use super::{AppState, ReadReplica};
+
+    #[tokio::test]
+    async fn dual_pool() {
+        let database = Arc::new(create_database().await);
+        let read_replica = Arc::new(create_database().await);
+
+        let state = AppState::new(database.clone(), Some(read_replica.clone()));
+
+        let ReadReplica(replica) = ReadReplica::from_ref(&state);
+
+        assert!(Arc::ptr_eq(
+            &Arc::<DatabaseConnection>::from_ref(&state),
+            &database
+        ));
+        assert!(Arc::ptr_eq(&replica, &read_replica));
+    }
+
+    #[tokio::test]
+    async fn primary_fallback() {
+        let database = Arc::new(create_database().await);
+
+        let state = AppState::from(database.clone());
+
+        let ReadReplica(replica) = ReadReplica::from_ref(&state);
+
+        assert!(Arc::ptr_eq(&replica, &database));
+    }
+}
@@ -1,7 +1,10 @@
 use std::error::Error;
 
 use axum::async_trait;
-use db::{Database, DatabaseConnection};
+use db::{
+    sea_orm::{DbBackend, Statement},
+    ConnectionTrait, Database, DatabaseConnection,
+};
 use hyper::body::{self, Bytes, HttpBody};
 use migration::MigratorTrait;
 use serde::Serialize;
@@ -18,6 +21,39 @@ pub(crate) async fn create_database() -> DatabaseConnection {
     db
 }
 
+/// Create a new Postgres database with all migrations applied.
+///
+/// Postgres server is provided with the `TEST_DATABASE_URL` variable,
+/// and a separate database is created for each call.
+pub(crate) async fn create_postgres_database() -> DatabaseConnection {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL is not set");
+    let (server_url, _) = url.rsplit_once('/').expect("invalid TEST_DATABASE_URL");
+
+    let server = Database::connect(&url)
+        .await
+        .expect("unable to connect to test database");
+
+    let name = format!("patron_test_{}", rand::random::<u32>());
+
+    server
+        .execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("CREATE DATABASE {name}"),
+        ))
+        .await
+        .expect("unable to create test database");
+
+    let db = Database::connect(format!("{server_url}/{name}"))
+        .await
+        .expect("unable to connect to test database");
+
+    migration::Migrator::up(&db, None)
+        .await
+        .expect("unable to run migrations");
+
+    db
+}
+
 pub(crate) trait RequestBodyExt: Sized {
     fn from_json<B: Serialize>(val: B) -> Self;
 }
@@ -0,0 +1,82 @@
+use std::{
+    io,
+    os::unix::fs::FileTypeExt,
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use hyper::server::accept::Accept;
+use tokio::net::{UnixListener, UnixStream};
+
+/// [`Accept`] implementation that accepts connections from a unix socket.
+pub(crate) struct UnixAccept(UnixListener);
+
+impl UnixAccept {
+    /// Bind to a unix socket at the provided path.
+    ///
+    /// Stale socket files left after previous runs are removed, while
+    /// any other file located at the provided path is left intact.
+    pub(crate) fn bind(path: &Path) -> io::Result<Self> {
+        match path.metadata() {
+            Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+            Ok(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "a file that is not a socket exists at the socket path",
+                ))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+
+        UnixListener::bind(path).map(Self)
+    }
+}
+
+impl Accept for UnixAccept {
+    type Conn = UnixStream;
+    type Error = io::Error;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        self.0
+            .poll_accept(cx)
+            .map(|result| Some(result.map(|(stream, _)| stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::UnixAccept;
+
+    #[tokio::test]
+    async fn stale_socket() {
+        let path = std::env::temp_dir().join(format!("patron-{}.sock", std::process::id()));
+
+        drop(UnixAccept::bind(&path).expect("unable to bind socket"));
+
+        // Socket file is left after the listener is dropped.
+        assert!(path.exists());
+
+        drop(UnixAccept::bind(&path).expect("unable to rebind socket"));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn regular_file() {
+        let path = std::env::temp_dir().join(format!("patron-{}.file", std::process::id()));
+
+        fs::write(&path, "").unwrap();
+
+        assert!(UnixAccept::bind(&path).is_err());
+        assert!(path.exists());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -3,30 +3,184 @@ use axum::{
     async_trait,
     extract::{rejection::JsonRejection, FromRequest},
     http::{Request, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
-use axum_derive_error::ErrorResponse;
 use derive_more::{Display, Error};
-use validator::{Validate, ValidationErrors};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
 
 /// Errors related to JSON validation.
-#[derive(ErrorResponse, Display, Error)]
+#[derive(Debug, Display, Error)]
 pub enum ValidatedJsonRejection {
     /// Unable to parse a JSON value.
-    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
     JsonParsingError(JsonRejection),
 
+    /// Unable to deserialize a parsed JSON value into a request body.
+    InvalidRequestBody(serde_path_to_error::Error<serde_json::Error>),
+
     /// Unable to validate a JSON value.
-    #[status(StatusCode::UNPROCESSABLE_ENTITY)]
     ValidationError(ValidationErrors),
 }
 
-/// Wrapper for [`axum`] JSON value validation.
+/// Error related to a single request body field.
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub(crate) struct FieldError {
+    /// Path to the field, with nested fields separated by dots.
+    ///
+    /// Not present if the error is related to the request body as a whole.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(example = "crate::schema::example_field")]
+    pub field: Option<String>,
+
+    /// Human-readable error message.
+    #[schemars(example = "crate::schema::example_field_message")]
+    pub message: String,
+}
+
+impl ValidatedJsonRejection {
+    /// Get a stable machine-readable code of the current error.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            Self::JsonParsingError(_) => "json_parsing_error",
+            Self::InvalidRequestBody(_) => "invalid_request_body",
+            Self::ValidationError(_) => "validation_error",
+        }
+    }
+
+    /// Get errors related to individual request body fields.
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            Self::JsonParsingError(_) => Vec::new(),
+            Self::InvalidRequestBody(err) => {
+                let mut field = Some(err.path().to_string()).filter(|path| path != ".");
+                let mut message = err.inner().to_string();
+
+                // Missing fields are reported on the containing struct,
+                // thus the field name has to be recovered from the error message.
+                if let Some(missing) = message
+                    .strip_prefix("missing field `")
+                    .and_then(|rest| rest.strip_suffix('`'))
+                {
+                    field = Some(match field {
+                        Some(path) => format!("{path}.{missing}"),
+                        None => missing.to_owned(),
+                    });
+                    message = String::from("missing field");
+                }
+
+                vec![FieldError { field, message }]
+            }
+            Self::ValidationError(errors) => {
+                let mut result = Vec::new();
+                collect_validation_errors(None, errors, &mut result);
+                result.sort_by(|a, b| a.field.cmp(&b.field));
+                result
+            }
+        }
+    }
+}
+
+/// Flatten nested [`ValidationErrors`] into a list of [`FieldError`] values.
+fn collect_validation_errors(
+    prefix: Option<&str>,
+    errors: &ValidationErrors,
+    result: &mut Vec<FieldError>,
+) {
+    for (name, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}.{name}"),
+            None => String::from(*name),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(errors) => result.extend(errors.iter().map(|err| {
+                FieldError {
+                    field: Some(path.clone()),
+                    message: err
+                        .message
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| err.code.to_string()),
+                }
+            })),
+            ValidationErrorsKind::Struct(errors) => {
+                collect_validation_errors(Some(&path), errors, result)
+            }
+            ValidationErrorsKind::List(list) => {
+                for (index, errors) in list {
+                    collect_validation_errors(Some(&format!("{path}[{index}]")), errors, result)
+                }
+            }
+        }
+    }
+}
+
+impl IntoResponse for ValidatedJsonRejection {
+    fn into_response(self) -> Response {
+        let body = Json(serde_json::json!({
+            "error": {
+                "code": self.error_code(),
+                "message": self.to_string(),
+            },
+            "errors": self.field_errors(),
+        }));
+
+        (StatusCode::UNPROCESSABLE_ENTITY, body).into_response()
+    }
+}
+
+/// Wrapper for [`axum`] JSON value deserialization.
 ///
-/// Equivalent to the [`axum`]'s [`Json`] struct
-/// with [`validator`] crate support.
+/// Equivalent to the [`axum`]'s [`Json`] struct,
+/// with deserialization errors reported for individual fields.
 ///
 /// [`JSON`]: axum::extract::Json
+pub struct JsonBody<T>(pub T);
+
+impl<T> OperationInput for JsonBody<T>
+where
+    Json<T>: OperationInput,
+{
+    fn operation_input(ctx: &mut aide::gen::GenContext, operation: &mut aide::openapi::Operation) {
+        <Json<T> as OperationInput>::operation_input(ctx, operation)
+    }
+
+    fn inferred_early_responses(
+        ctx: &mut aide::gen::GenContext,
+        operation: &mut aide::openapi::Operation,
+    ) -> Vec<(Option<u16>, aide::openapi::Response)> {
+        <Json<T> as OperationInput>::inferred_early_responses(ctx, operation)
+    }
+}
+
+#[async_trait]
+impl<T, S, B> FromRequest<S, B> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    B: Send + 'static,
+    S: Sync,
+    Json<Value>: FromRequest<S, B, Rejection = JsonRejection>,
+{
+    type Rejection = ValidatedJsonRejection;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<Value>::from_request(req, state)
+            .await
+            .map_err(ValidatedJsonRejection::JsonParsingError)?;
+
+        serde_path_to_error::deserialize(value)
+            .map(JsonBody)
+            .map_err(ValidatedJsonRejection::InvalidRequestBody)
+    }
+}
+
+/// Wrapper for [`axum`] JSON value validation.
+///
+/// Equivalent to the [`JsonBody`] struct
+/// with [`validator`] crate support.
 pub struct ValidatedJson<T>(pub T);
 
 impl<T> OperationInput for ValidatedJson<T>
@@ -51,14 +205,12 @@ where
     T: Validate,
     B: Send + 'static,
     S: Sync,
-    Json<T>: FromRequest<S, B, Rejection = JsonRejection>,
+    JsonBody<T>: FromRequest<S, B, Rejection = ValidatedJsonRejection>,
 {
     type Rejection = ValidatedJsonRejection;
 
     async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
-        let Json(value) = Json::from_request(req, state)
-            .await
-            .map_err(ValidatedJsonRejection::JsonParsingError)?;
+        let JsonBody(value) = JsonBody::from_request(req, state).await?;
 
         match value.validate() {
             Ok(_) => Ok(ValidatedJson(value)),
@@ -0,0 +1,690 @@
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::http::header::CONTENT_TYPE;
+use db::{
+    event, now,
+    sea_query::Expr,
+    webhook::{self, Model as Webhook},
+    webhook_delivery::{self, Model as WebhookDelivery},
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use derive_more::{Display, Error, From};
+use hmac::{Hmac, Mac};
+use hyper::client::connect::dns::Name;
+use reqwest::{
+    dns::{Addrs, Resolve, Resolving},
+    redirect::Policy,
+    Url,
+};
+use sha2::Sha256;
+use tokio::net::lookup_host;
+use tracing::{error, warn};
+
+use crate::handlers::contracts::events::ContractEvent;
+
+/// Header that contains a signature of a webhook request body.
+pub(crate) const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Maximum count of attempts to deliver a single event notification.
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry attempt, doubled after each failed attempt.
+const RETRY_DELAY: db::Duration = db::Duration::seconds(30);
+
+/// Maximum count of deliveries processed during a single database check.
+const BATCH_SIZE: u64 = 64;
+
+/// Interval between database checks for pending deliveries.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Maximum duration of a single delivery attempt.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Duration for which a delivery attempt is claimed by a single API server.
+///
+/// Must exceed [`REQUEST_TIMEOUT`], otherwise deliveries that are still
+/// in progress may be claimed by other API server instances.
+const DELIVERY_LEASE: db::Duration = db::Duration::minutes(1);
+
+/// Errors that may occur while resolving webhook URL hosts.
+#[derive(Debug, Display, Error)]
+pub(crate) enum WebhookAddressError {
+    /// Webhook URL is invalid or does not contain a host.
+    #[display(fmt = "invalid webhook url")]
+    InvalidUrl,
+
+    /// Webhook URL host could not be resolved.
+    #[display(fmt = "unable to resolve webhook host: {}", _0)]
+    Resolve(io::Error),
+
+    /// Webhook URL host resolves to a loopback, private or link-local address.
+    #[display(fmt = "webhook host resolves to a non-public address {}", _0)]
+    NonPublic(#[error(not(source))] IpAddr),
+}
+
+/// Errors that may occur during a single delivery attempt.
+#[derive(Debug, Display, Error, From)]
+enum DeliveryError {
+    /// Webhook URL is invalid or targets a non-public address.
+    Address(WebhookAddressError),
+
+    /// HTTP request failed.
+    Request(reqwest::Error),
+
+    /// Webhook responded with a non-successful status.
+    #[display(fmt = "webhook responded with status {}", _0)]
+    #[from(ignore)]
+    Status(#[error(not(source))] reqwest::StatusCode),
+}
+
+/// Determine if the provided IP address is publicly routable.
+///
+/// Loopback, private, link-local, shared, documentation and multicast
+/// addresses are not considered public, including their IPv4-mapped IPv6 forms.
+pub(crate) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            let shared = first == 100 && second & 0xc0 == 64;
+
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || shared)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(ip.into()),
+            None => {
+                let first = ip.segments()[0];
+                let unique_local = first & 0xfe00 == 0xfc00;
+                let link_local = first & 0xffc0 == 0xfe80;
+
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || unique_local
+                    || link_local)
+            }
+        },
+    }
+}
+
+/// Resolve the provided host, ensuring that all of its addresses are public.
+async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, WebhookAddressError> {
+    let addresses: Vec<_> = lookup_host((host, port))
+        .await
+        .map_err(WebhookAddressError::Resolve)?
+        .collect();
+
+    match addresses.iter().find(|address| !is_public(address.ip())) {
+        Some(address) => Err(WebhookAddressError::NonPublic(address.ip())),
+        None => Ok(addresses),
+    }
+}
+
+/// Get host of the provided URL, with IPv6 address brackets removed.
+fn url_host(url: &Url) -> Option<&str> {
+    url.host_str().map(|host| {
+        host.strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host)
+    })
+}
+
+/// Ensure that the provided webhook URL host resolves to public addresses only.
+pub(crate) async fn check_url(url: &str) -> Result<(), WebhookAddressError> {
+    let url = Url::parse(url).map_err(|_| WebhookAddressError::InvalidUrl)?;
+    let host = url_host(&url).ok_or(WebhookAddressError::InvalidUrl)?;
+
+    resolve_public(host, url.port_or_known_default().unwrap_or_default()).await?;
+
+    Ok(())
+}
+
+/// DNS resolver that rejects hosts resolving to any non-public address.
+///
+/// Webhook hosts are resolved once again during each delivery attempt,
+/// thus the check performed during webhook registration is not sufficient.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses = resolve_public(name.as_str(), 0).await?;
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// HTTP client used to deliver event notifications.
+pub(crate) struct WebhookClient {
+    /// Underlying HTTP client, which does not follow redirects.
+    client: reqwest::Client,
+
+    /// Allow deliveries to loopback, private and link-local addresses.
+    private_addresses: bool,
+}
+
+impl WebhookClient {
+    /// Create a new webhook client.
+    pub(crate) fn new(private_addresses: bool) -> Self {
+        let mut builder = reqwest::Client::builder().redirect(Policy::none());
+
+        if !private_addresses {
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+
+        Self {
+            client: builder.build().expect("unable to create webhook client"),
+            private_addresses,
+        }
+    }
+}
+
+/// Sign the provided request body with a webhook secret.
+///
+/// Returns a hex-encoded HMAC-SHA256 value.
+pub(crate) fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Periodically deliver pending event notifications to webhooks.
+///
+/// Deliveries are enqueued by an event client, thus the API server
+/// watches the database for deliveries that are ready to be attempted.
+pub(crate) async fn deliver(db: Arc<DatabaseConnection>, private_addresses: bool) {
+    let client = WebhookClient::new(private_addresses);
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = process_pending(&db, &client).await {
+            error!(%err, "unable to process pending webhook deliveries");
+        }
+    }
+}
+
+/// Attempt to deliver all event notifications that are ready to be sent.
+///
+/// Failed attempts are retried with an exponential backoff. As soon as
+/// [`MAX_ATTEMPTS`] is reached, the delivery is marked as failed and
+/// the webhook's dead letter counter is incremented.
+///
+/// Each delivery is claimed before it is sent, thus multiple API server
+/// instances can process pending deliveries concurrently.
+pub(crate) async fn process_pending(
+    db: &DatabaseConnection,
+    client: &WebhookClient,
+) -> Result<(), DbErr> {
+    let deliveries = webhook_delivery::Entity::find()
+        .find_also_related(webhook::Entity)
+        .filter(webhook_delivery::Column::Status.eq(webhook_delivery::Status::New))
+        .filter(webhook_delivery::Column::NextAttemptAt.lte(now()))
+        .order_by_asc(webhook_delivery::Column::Id)
+        .limit(BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    for (delivery, webhook) in deliveries {
+        let Some(webhook) = webhook else {
+            continue;
+        };
+
+        if !claim(db, &delivery).await? {
+            continue;
+        }
+
+        let Some(event) = event::Entity::find_by_id(delivery.event_id).one(db).await? else {
+            continue;
+        };
+
+        let body = serde_json::to_vec(&ContractEvent::from(event))
+            .expect("contract event is always serializable");
+
+        match send(client, &webhook, body).await {
+            Ok(()) => {
+                webhook_delivery::Entity::update_many()
+                    .filter(webhook_delivery::Column::Id.eq(delivery.id))
+                    .col_expr(
+                        webhook_delivery::Column::Status,
+                        webhook_delivery::Status::Completed.into(),
+                    )
+                    .exec(db)
+                    .await?;
+            }
+            Err(err) => {
+                warn!(id = %delivery.id, %err, "webhook delivery attempt failed");
+                retry(db, &delivery, &webhook).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Claim a pending delivery by moving its next attempt time forward.
+///
+/// Returns `false` if the delivery was already claimed by another API server.
+/// Claimed deliveries that were not finished, for example due to a crash,
+/// are attempted again as soon as [`DELIVERY_LEASE`] expires.
+async fn claim(db: &DatabaseConnection, delivery: &WebhookDelivery) -> Result<bool, DbErr> {
+    let result = webhook_delivery::Entity::update_many()
+        .filter(webhook_delivery::Column::Id.eq(delivery.id))
+        .filter(webhook_delivery::Column::Status.eq(webhook_delivery::Status::New))
+        .filter(webhook_delivery::Column::NextAttemptAt.eq(delivery.next_attempt_at))
+        .col_expr(
+            webhook_delivery::Column::NextAttemptAt,
+            (now() + DELIVERY_LEASE).into(),
+        )
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected == 1)
+}
+
+/// Send a single signed event notification to a webhook.
+async fn send(
+    client: &WebhookClient,
+    webhook: &Webhook,
+    body: Vec<u8>,
+) -> Result<(), DeliveryError> {
+    let url = Url::parse(&webhook.url).map_err(|_| WebhookAddressError::InvalidUrl)?;
+
+    // Hosts provided as IP addresses are not passed to the DNS resolver.
+    if !client.private_addresses {
+        if let Some(ip) = url_host(&url).and_then(|host| host.parse().ok()) {
+            if !is_public(ip) {
+                return Err(WebhookAddressError::NonPublic(ip).into());
+            }
+        }
+    }
+
+    let signature = sign(&webhook.secret, &body);
+
+    let status = client
+        .client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, signature)
+        .body(body)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .status();
+
+    // Redirects are not followed, thus they are not considered to be successful deliveries.
+    if !status.is_success() {
+        return Err(DeliveryError::Status(status));
+    }
+
+    Ok(())
+}
+
+/// Schedule the next delivery attempt or mark the delivery as failed.
+async fn retry(
+    db: &DatabaseConnection,
+    delivery: &WebhookDelivery,
+    webhook: &Webhook,
+) -> Result<(), DbErr> {
+    let attempts = delivery.attempts + 1;
+
+    if attempts >= MAX_ATTEMPTS {
+        webhook_delivery::Entity::update_many()
+            .filter(webhook_delivery::Column::Id.eq(delivery.id))
+            .col_expr(webhook_delivery::Column::Attempts, attempts.into())
+            .col_expr(
+                webhook_delivery::Column::Status,
+                webhook_delivery::Status::Failed.into(),
+            )
+            .exec(db)
+            .await?;
+
+        webhook::Entity::update_many()
+            .filter(webhook::Column::Id.eq(webhook.id))
+            .col_expr(
+                webhook::Column::DeadLetterCount,
+                Expr::col(webhook::Column::DeadLetterCount).add(1),
+            )
+            .exec(db)
+            .await?;
+    } else {
+        let delay = RETRY_DELAY * 2i32.pow(delivery.attempts as u32);
+
+        webhook_delivery::Entity::update_many()
+            .filter(webhook_delivery::Column::Id.eq(delivery.id))
+            .col_expr(webhook_delivery::Column::Attempts, attempts.into())
+            .col_expr(
+                webhook_delivery::Column::NextAttemptAt,
+                (now() + delay).into(),
+            )
+            .exec(db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{SocketAddr, TcpListener};
+
+    use crate::testing::create_database;
+
+    use axum::{
+        body::Bytes,
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router, Server,
+    };
+    use db::{
+        event, node, user, webhook, webhook_delivery, ActiveValue, DatabaseConnection, EntityTrait,
+        OffsetDateTime, PrimitiveDateTime,
+    };
+    use serde_json::{json, Value};
+    use tokio::sync::mpsc;
+
+    /// Request received by a mock webhook receiver.
+    type Received = (HeaderMap, Bytes);
+
+    /// Start a mock webhook receiver that responds with the provided status.
+    fn receiver(status: StatusCode) -> (SocketAddr, mpsc::UnboundedReceiver<Received>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let router = Router::new()
+            .route(
+                "/",
+                post(
+                    move |State(tx): State<mpsc::UnboundedSender<Received>>,
+                          headers: HeaderMap,
+                          body: Bytes| async move {
+                        tx.send((headers, body)).unwrap();
+                        status
+                    },
+                ),
+            )
+            .with_state(tx);
+
+        tokio::spawn(
+            Server::from_tcp(listener)
+                .unwrap()
+                .serve(router.into_make_service()),
+        );
+
+        (address, rx)
+    }
+
+    async fn create_test_env(db: &DatabaseConnection, address: SocketAddr) {
+        let user = user::Entity::insert(user::ActiveModel::default())
+            .exec_with_returning(db)
+            .await
+            .expect("unable to create user");
+
+        webhook::Entity::insert_many([
+            webhook::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                url: ActiveValue::Set(format!("http://{address}/")),
+                account: ActiveValue::Set(vec![1; 32]),
+                secret: ActiveValue::Set(String::from("secret")),
+                ..Default::default()
+            },
+            webhook::ActiveModel {
+                user_id: ActiveValue::Set(user.id),
+                url: ActiveValue::Set(format!("http://{address}/")),
+                account: ActiveValue::Set(vec![2; 32]),
+                secret: ActiveValue::Set(String::from("secret")),
+                ..Default::default()
+            },
+        ])
+        .exec_without_returning(db)
+        .await
+        .expect("unable to create webhooks");
+
+        let node = node::Entity::insert(node::ActiveModel {
+            name: ActiveValue::Set(String::from("test")),
+            url: ActiveValue::Set(String::from("ws://localhost:9944")),
+            confirmed_block: ActiveValue::Set(0),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to insert node");
+
+        let datetime = OffsetDateTime::from_unix_timestamp(0).expect("invalid date");
+
+        let event = event::Entity::insert(event::ActiveModel {
+            node_id: ActiveValue::Set(node.id),
+            account: ActiveValue::Set(vec![1; 32]),
+            event_type: ActiveValue::Set(event::EventType::Instantiation),
+            body: ActiveValue::Set(
+                serde_json::to_string(&event::EventBody::Instantiation).unwrap(),
+            ),
+            block_timestamp: ActiveValue::Set(PrimitiveDateTime::new(
+                datetime.date(),
+                datetime.time(),
+            )),
+            block_number: ActiveValue::Set(1),
+            ..Default::default()
+        })
+        .exec_with_returning(db)
+        .await
+        .expect("unable to create event");
+
+        webhook_delivery::enqueue(db, &[(event.id, &event.account[..])])
+            .await
+            .expect("unable to enqueue webhook deliveries");
+    }
+
+    #[tokio::test]
+    async fn successful() {
+        let db = create_database().await;
+
+        let (address, mut rx) = receiver(StatusCode::OK);
+
+        create_test_env(&db, address).await;
+
+        super::process_pending(&db, &super::WebhookClient::new(true))
+            .await
+            .unwrap();
+
+        let (headers, body) = rx.recv().await.unwrap();
+
+        assert_eq!(
+            headers[super::SIGNATURE_HEADER],
+            super::sign("secret", &body)
+        );
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({
+                "id": 1,
+                "event_type": "instantiation",
+                "body": "Instantiation",
+                "timestamp": 0,
+                "block_number": 1,
+            })
+        );
+
+        let deliveries = webhook_delivery::Entity::find().all(&db).await.unwrap();
+
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, webhook_delivery::Status::Completed);
+    }
+
+    #[tokio::test]
+    async fn retries() {
+        let db = create_database().await;
+
+        let (address, mut rx) = receiver(StatusCode::INTERNAL_SERVER_ERROR);
+
+        create_test_env(&db, address).await;
+
+        let client = super::WebhookClient::new(true);
+
+        super::process_pending(&db, &client).await.unwrap();
+        assert!(rx.recv().await.is_some());
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, webhook_delivery::Status::New);
+        assert_eq!(delivery.attempts, 1);
+        assert!(delivery.next_attempt_at > db::now());
+
+        // Next attempt is delayed.
+        super::process_pending(&db, &client).await.unwrap();
+        assert!(rx.try_recv().is_err());
+
+        webhook_delivery::Entity::update(webhook_delivery::ActiveModel {
+            id: ActiveValue::Unchanged(delivery.id),
+            attempts: ActiveValue::Set(super::MAX_ATTEMPTS - 1),
+            next_attempt_at: ActiveValue::Set(PrimitiveDateTime::MIN),
+            ..Default::default()
+        })
+        .exec(&db)
+        .await
+        .unwrap();
+
+        super::process_pending(&db, &client).await.unwrap();
+        assert!(rx.recv().await.is_some());
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, webhook_delivery::Status::Failed);
+        assert_eq!(delivery.attempts, super::MAX_ATTEMPTS);
+
+        let webhook = webhook::Entity::find_by_id(delivery.webhook_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(webhook.dead_letter_count, 1);
+    }
+
+    #[tokio::test]
+    async fn claimed() {
+        let db = create_database().await;
+
+        let (address, mut rx) = receiver(StatusCode::OK);
+
+        create_test_env(&db, address).await;
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Delivery is being sent by another API server.
+        assert!(super::claim(&db, &delivery).await.unwrap());
+        assert!(!super::claim(&db, &delivery).await.unwrap());
+
+        super::process_pending(&db, &super::WebhookClient::new(true))
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, webhook_delivery::Status::New);
+        assert_eq!(delivery.attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn private_address() {
+        let db = create_database().await;
+
+        let (address, mut rx) = receiver(StatusCode::OK);
+
+        create_test_env(&db, address).await;
+
+        super::process_pending(&db, &super::WebhookClient::new(false))
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, webhook_delivery::Status::New);
+        assert_eq!(delivery.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn redirect() {
+        let db = create_database().await;
+
+        let (address, mut rx) = receiver(StatusCode::TEMPORARY_REDIRECT);
+
+        create_test_env(&db, address).await;
+
+        super::process_pending(&db, &super::WebhookClient::new(true))
+            .await
+            .unwrap();
+        assert!(rx.recv().await.is_some());
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, webhook_delivery::Status::New);
+        assert_eq!(delivery.attempts, 1);
+    }
+
+    #[test]
+    fn public_addresses() {
+        for address in ["1.1.1.1", "2606:4700:4700::1111", "::ffff:1.1.1.1"] {
+            assert!(super::is_public(address.parse().unwrap()), "{address}");
+        }
+
+        for address in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fc00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!super::is_public(address.parse().unwrap()), "{address}");
+        }
+    }
+}